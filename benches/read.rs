@@ -0,0 +1,37 @@
+extern crate criterion;
+extern crate atcoder_snippets;
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use atcoder_snippets::read::*;
+
+fn bench_read_integers(c: &mut Criterion) {
+    // 10^3 lines of 10^3 integers
+    let mut input = String::new();
+    for i in 0..1_000 {
+        for j in 0..1_000 {
+            input.push_str(&(i * j).to_string());
+            input.push(if j == 999 { '\n' } else { ' ' });
+        }
+    }
+
+    let input1 = input.clone();
+    c.bench_function(
+        "read: Tokens with 10^6 integers",
+        move |b| b.iter(|| {
+            let mut tokens = Tokens::new(input1.clone());
+            black_box(tokens.next_vec::<u64>(1_000_000));
+        })
+    );
+
+    c.bench_function(
+        "read: read_line with 10^6 integers",
+        move |b| b.iter(|| {
+            for line in input.lines() {
+                black_box(Vec::<u64>::read_line(line).unwrap());
+            }
+        })
+    );
+}
+
+criterion_group!(benches, bench_read_integers);
+criterion_main!(benches);
@@ -43,10 +43,10 @@ fn bench_z_match_indices(c: &mut Criterion) {
     );
 
     let mut text2 = Vec::new();
-        for _ in 0..10 {
-            text2.extend_from_slice(&[0u8; 9_999]);
-            text2.push(1u8)
-        }
+    for _ in 0..10 {
+        text2.extend_from_slice(&[0u8; 9_999]);
+        text2.push(1u8)
+    }
     let pattern2 = vec![0u8; 1_000];
     c.bench_function(
         "z: z_match_indices with 10^5 text and 10^3 pattern",
@@ -54,6 +54,19 @@ fn bench_z_match_indices(c: &mut Criterion) {
             text2.z_match_indices(&pattern2).for_each(|_| { black_box(0); })
         })
     );
+
+    let mut text3 = Vec::new();
+    for _ in 0..10 {
+        text3.extend_from_slice(&[0u8; 99_999]);
+        text3.push(1u8)
+    }
+    let pattern3 = vec![0u8; 1_000];
+    c.bench_function(
+        "z: z_match_indices with 10^6 text and 10^3 pattern",
+        move |b| b.iter(|| {
+            text3.z_match_indices(&pattern3).for_each(|_| { black_box(0); })
+        })
+    );
 }
 
 criterion_group!(benches, bench_longest_prefix_lengths, bench_z_match_indices);
@@ -23,6 +23,26 @@
 //! assert_eq!(count_key(&[1,1,2,2,3,3], 2), 2);
 //! assert_eq!(count_key(&[2,2,2,2,2,2], 2), 6);
 //! ```
+//!
+//! For the common case of searching over integers,
+//! `partition_point_i64` and its variants are handier.
+//! The following finds the minimum time in which `machines` produce `n` items,
+//! where the `i`-th machine produces an item every `machines[i]` seconds.
+//!
+//! ```
+//! # use atcoder_snippets::bsearch::*;
+//! fn min_time(machines: &[i64], n: i64) -> i64 {
+//!     let fastest = *machines.iter().min().unwrap();
+//!     partition_point_i64(0..fastest * n + 1, |t| {
+//!         machines.iter().map(|&a| t / a).sum::<i64>() >= n
+//!     })
+//! }
+//!
+//! assert_eq!(min_time(&[1], 5), 5);
+//! assert_eq!(min_time(&[2, 3], 5), 6);
+//! assert_eq!(min_time(&[3, 3, 3], 0), 0);
+//! assert_eq!(min_time(&[1_000_000_000], 1_000_000_000), 1_000_000_000_000_000_000);
+//! ```
 
 use crate::num::Integer;
 
@@ -114,9 +134,7 @@ pub trait BSearch: Sized {
     where
         F: FnMut(&Self::Item) -> bool
     {
-        if self.is_empty() {
-            None
-        } else if !is_left(&self.leftmost_item()) {
+        if self.is_empty() || !is_left(&self.leftmost_item()) {
             None
         } else {
             let rightmost = self.rightmost_item();
@@ -144,9 +162,7 @@ pub trait BSearch: Sized {
     where
         F: FnMut(&Self::Item) -> bool
     {
-        if self.is_empty() {
-            None
-        } else if !is_right(&self.rightmost_item()) {
+        if self.is_empty() || !is_right(&self.rightmost_item()) {
             None
         } else {
             let leftmost = self.leftmost_item();
@@ -251,7 +267,7 @@ impl<T: Integer + Clone> BSearch for std::ops::RangeInclusive<T> {
     }
 
     fn is_bsearch_converged(&self) -> bool {
-        BSearch::is_empty(self) || self.end().clone() - &self.start() <= T::one()
+        BSearch::is_empty(self) || self.end().clone() - self.start() <= T::one()
     }
 }
 
@@ -305,6 +321,43 @@ pub trait SliceBSearch {
     fn bsearch_index_right_min<F>(&self, is_right: F) -> Option<usize>
     where
         F: FnMut(&Self::Item) -> bool;
+
+    /// Returns the first index whose item has a key not less than `key`.
+    ///
+    /// Keys of items extracted by `f` must be non-decreasing.
+    /// This is checked only if `cfg(local)` is enabled.
+    /// If all the keys are less than `key`, returns the length of the slice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::bsearch::*;
+    /// let events = [(1, 'a'), (3, 'b'), (3, 'c'), (7, 'd')];
+    /// assert_eq!(events.partition_point_by_key(3, |&(time, _)| time), 1);
+    /// assert_eq!(events.partition_point_by_key(4, |&(time, _)| time), 3);
+    /// assert_eq!(events.partition_point_by_key(8, |&(time, _)| time), 4);
+    /// ```
+    fn partition_point_by_key<K: Ord, F>(&self, key: K, f: F) -> usize
+    where
+        F: FnMut(&Self::Item) -> K;
+
+    /// Returns the index range of items whose keys are in `range`.
+    ///
+    /// Keys of items extracted by `f` must be non-decreasing.
+    /// This is checked only if `cfg(local)` is enabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::bsearch::*;
+    /// let events = [(1, 'a'), (3, 'b'), (3, 'c'), (7, 'd')];
+    /// // Events happening in time [2, 7)
+    /// assert_eq!(events.range_by_key(2..7, |&(time, _)| time), 1..3);
+    /// assert_eq!(events.range_by_key(4..7, |&(time, _)| time), 3..3);
+    /// ```
+    fn range_by_key<K: Ord, F>(&self, range: std::ops::Range<K>, f: F) -> std::ops::Range<usize>
+    where
+        F: FnMut(&Self::Item) -> K;
 }
 
 impl<T> SliceBSearch for [T] {
@@ -343,6 +396,348 @@ impl<T> SliceBSearch for [T] {
             is_right(x)
         })
     }
+
+    fn partition_point_by_key<K: Ord, F>(&self, key: K, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> K
+    {
+        assert_sorted_by_key(self, &mut f);
+        partition_point_usize(0..self.len(), |i| f(&self[i]) >= key)
+    }
+
+    fn range_by_key<K: Ord, F>(&self, range: std::ops::Range<K>, mut f: F) -> std::ops::Range<usize>
+    where
+        F: FnMut(&T) -> K
+    {
+        assert_sorted_by_key(self, &mut f);
+        let start = partition_point_usize(0..self.len(), |i| f(&self[i]) >= range.start);
+        let end = partition_point_usize(start..self.len(), |i| f(&self[i]) >= range.end);
+        start..end
+    }
+}
+
+#[cfg(local)]
+fn assert_sorted_by_key<T, K: Ord>(slice: &[T], mut f: impl FnMut(&T) -> K) {
+    assert!(slice.windows(2).all(|pair| f(&pair[0]) <= f(&pair[1])),
+            "keys of the slice are not sorted");
+}
+
+#[cfg(not(local))]
+fn assert_sorted_by_key<T, K: Ord>(_slice: &[T], _f: impl FnMut(&T) -> K) {}
+
+macro_rules! impl_partition_point {
+    ($name:ident, $t:ty, $unsigned:ty) => {
+        /// Returns the first value in `range` satisfying `pred`.
+        ///
+        /// `pred` must be monotone in `range`, that is, once `pred` becomes `true`,
+        /// it must be `true` for all the larger values in `range`.
+        /// If no value satisfies `pred`, returns `range.end`.
+        /// If `range` is empty, returns `range.start`.
+        ///
+        /// The midpoint is calculated without overflow,
+        /// so `range` can span nearly all the values of the type.
+        pub fn $name<F: FnMut($t) -> bool>(range: std::ops::Range<$t>, mut pred: F) -> $t {
+            let (mut lo, mut hi) = (range.start, range.end);
+            while lo < hi {
+                let mid = lo + ((hi.wrapping_sub(lo) as $unsigned) / 2) as $t;
+                if pred(mid) {
+                    hi = mid;
+                } else {
+                    lo = mid + 1;
+                }
+            }
+            lo
+        }
+    }
+}
+
+impl_partition_point!(partition_point_i64, i64, u64);
+impl_partition_point!(partition_point_u64, u64, u64);
+impl_partition_point!(partition_point_usize, usize, usize);
+
+/// Returns the first value not less than `start` satisfying `pred`,
+/// when no upper bound of the answer is known.
+///
+/// `pred` must be monotone, as in [`partition_point_i64`](fn.partition_point_i64.html).
+/// The upper bound is doubled from `start` until `pred` holds, capped at `i64::MAX`,
+/// and then the answer is binary-searched.
+/// It takes `O(log(answer - start))` calls of `pred`.
+///
+/// If `pred` never holds until `i64::MAX`, returns `None`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::bsearch::*;
+/// // The smallest n such that n^2 >= 10^18
+/// let n = exponential_search(0, |n| n * n >= 1_000_000_000_000_000_000);
+/// assert_eq!(n, Some(1_000_000_000));
+///
+/// assert_eq!(exponential_search(0, |n| n < 0), None);
+/// ```
+pub fn exponential_search<F: FnMut(i64) -> bool>(start: i64, mut pred: F) -> Option<i64> {
+    if pred(start) {
+        return Some(start);
+    }
+
+    // `pred(lo)` is false, and `pred(hi)` is checked in each iteration.
+    let mut lo = start;
+    let mut step: i64 = 1;
+    loop {
+        if lo == std::i64::MAX {
+            return None;
+        }
+        let hi = lo.saturating_add(step);
+        if pred(hi) {
+            return Some(partition_point_i64(lo+1..hi, &mut pred));
+        }
+        lo = hi;
+        step = step.saturating_mul(2);
+    }
+}
+
+/// Finds the minimum point of a unimodal function `f` in `[lo, hi]` by golden-section search.
+///
+/// Returns `(x, f(x))` where `x` is the approximated minimum point.
+/// Each iteration shrinks the interval by a factor of about 0.618,
+/// and calls `f` only once except the first iteration,
+/// so it needs fewer calls than ternary search for the same precision.
+///
+/// If `f` has the same value at the two inner points, the left part is kept.
+/// Thus, if the minimum is attained on a plateau, `x` approaches a point in the plateau.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::bsearch::*;
+/// // The meeting point minimizing the sum of squared distances is the average.
+/// let people = [1.0, 2.0, 6.0];
+/// let (x, cost) = golden_section_min(0.0, 10.0, 100, |p| {
+///     people.iter().map(|&q| (q - p) * (q - p)).sum()
+/// });
+/// assert!((x - 3.0).abs() < 1e-6);
+/// assert!((cost - 14.0).abs() < 1e-9);
+/// ```
+pub fn golden_section_min<F: FnMut(f64) -> f64>(lo: f64, hi: f64, iterations: usize, mut f: F)
+                                                -> (f64, f64) {
+    // 1 / golden ratio
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut lo, mut hi) = (lo, hi);
+    let mut c = hi - (hi - lo) * ratio;
+    let mut d = lo + (hi - lo) * ratio;
+    let mut fc = f(c);
+    let mut fd = f(d);
+    for _ in 0..iterations {
+        if fc <= fd {
+            hi = d;
+            d = c;
+            fd = fc;
+            c = hi - (hi - lo) * ratio;
+            fc = f(c);
+        } else {
+            lo = c;
+            c = d;
+            fc = fd;
+            d = lo + (hi - lo) * ratio;
+            fd = f(d);
+        }
+    }
+    if fc <= fd { (c, fc) } else { (d, fd) }
+}
+
+/// Finds the minimum of a convex sequence `f(range.start), ..., f(range.end - 1)`.
+///
+/// Returns `(x, f(x))` where `f(x)` is the minimum.
+/// If the minimum is attained at two or more points (a plateau), `x` is the leftmost one.
+///
+/// Binary search on the sign of `f(m + 1) - f(m)` is used.
+/// This needs 2 calls of `f` per halving, while ternary search needs 4 calls per 3/4 shrinking.
+///
+/// # Panics
+///
+/// Panics if `range` is empty.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::bsearch::*;
+/// // The meeting point minimizing the total walking distance is a median.
+/// let people = [1, 2, 6, 10];
+/// let (x, cost) = min_of_convex(0..11, |p| people.iter().map(|&q| (q - p as i64).abs()).sum::<i64>());
+/// assert_eq!((x, cost), (2, 13));
+/// ```
+pub fn min_of_convex<V: PartialOrd, F: FnMut(i64) -> V>(range: std::ops::Range<i64>, mut f: F)
+                                                         -> (i64, V) {
+    assert!(range.start < range.end, "range must not be empty");
+    let x = partition_point_i64(range.start..range.end-1, |m| f(m) <= f(m+1));
+    (x, f(x))
+}
+
+/// Solves binary search problems for a batch of queries offline, by parallel binary search.
+///
+/// Consider a state changed by a sequence of `num_steps` steps,
+/// and a monotone condition for each query, which never becomes false once it becomes true.
+/// For each query `q`, this function finds the minimum `k` in `0..=num_steps`
+/// such that `check(q)` holds after the first `k` steps are applied.
+/// If `check(q)` does not hold even after all the steps, the answer is `None`.
+///
+/// The callbacks are called as follows:
+///
+/// - The search is split into `O(log num_steps)` rounds.
+/// - Each round begins with a call of `reset`, which must restore the initial state.
+/// - Then, `apply_step(0)`, `apply_step(1)`, ... are called in this order,
+///   but the round may end before all the steps are applied.
+/// - `check(q)` is called for queries scheduled in the round
+///   when the number of steps applied since the last `reset` is the midpoint of their candidates.
+///   The calls for queries sharing the midpoint are made in the order of `queries`.
+///
+/// `check` is called `O(queries.len() log num_steps)` times
+/// and `apply_step` is called `O(num_steps log num_steps)` times in total.
+///
+/// Since the three callbacks usually share the state,
+/// wrap the state in `RefCell` to borrow it from all of them.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::bsearch::*;
+/// use std::cell::RefCell;
+///
+/// // Add `adds[i]` at step `i`. When does the total reach each threshold?
+/// let adds = [3, 1, 4, 1, 5];
+/// let thresholds = [0, 4, 8, 14, 15];
+/// let total = RefCell::new(0);
+/// let answers = parallel_bsearch(
+///     adds.len(), &thresholds,
+///     |i| *total.borrow_mut() += adds[i],
+///     |&threshold| *total.borrow() >= threshold,
+///     || *total.borrow_mut() = 0
+/// );
+/// assert_eq!(answers, vec![Some(0), Some(2), Some(3), Some(5), None]);
+/// ```
+pub fn parallel_bsearch<Q, A, C, R>(num_steps: usize, queries: &[Q],
+                                    mut apply_step: A, mut check: C, mut reset: R)
+                                    -> Vec<Option<usize>>
+where
+    A: FnMut(usize),
+    C: FnMut(&Q) -> bool,
+    R: FnMut()
+{
+    // The answer of the `i`-th query is in `lo[i]..hi[i]`,
+    // where `num_steps + 1` stands for `None`.
+    let mut lo = vec![0; queries.len()];
+    let mut hi = vec![num_steps + 1; queries.len()];
+    let mut scheduled: Vec<Vec<usize>> = vec![Vec::new(); num_steps + 1];
+
+    loop {
+        let mut last_mid = None;
+        for i in 0..queries.len() {
+            if lo[i] < hi[i] {
+                let mid = lo[i] + (hi[i] - lo[i]) / 2;
+                scheduled[mid].push(i);
+                last_mid = Some(std::cmp::max(last_mid.unwrap_or(0), mid));
+            }
+        }
+        let last_mid = match last_mid {
+            Some(mid) => mid,
+            None => break
+        };
+
+        reset();
+        for (mid, scheduled_at_mid) in scheduled.iter_mut().enumerate().take(last_mid + 1) {
+            if mid > 0 {
+                apply_step(mid - 1);
+            }
+            for i in scheduled_at_mid.drain(..) {
+                if check(&queries[i]) {
+                    hi[i] = mid;
+                } else {
+                    lo[i] = mid + 1;
+                }
+            }
+        }
+    }
+
+    lo.into_iter().map(|k| if k <= num_steps { Some(k) } else { None }).collect()
+}
+
+/// Returns the boundary of a monotone predicate `pred` in `[lo, hi]` (both inclusive)
+/// as `(last value where pred is false, first value where pred is true)`.
+///
+/// `pred` must be monotone, that is, once it becomes `true`,
+/// it must be `true` for all the larger values.
+/// If `pred` is `true` for all the values, the first element is `None`.
+/// If `pred` is `false` for all the values, the second element is `None`.
+/// If the range is empty, returns `(None, None)`.
+///
+/// # Example
+///
+/// Both "maximize feasible k" and "minimize feasible k" are solved
+/// by taking the appropriate side of the boundary.
+///
+/// ```
+/// # use atcoder_snippets::bsearch::*;
+/// // Maximize k such that k^2 <= 50. Feasible values are on the false side.
+/// let (max_k, _) = boundary(0, 100, |k| k * k > 50);
+/// assert_eq!(max_k, Some(7));
+///
+/// // Minimize k such that k^2 >= 50. Feasible values are on the true side.
+/// let (_, min_k) = boundary(0, 100, |k| k * k >= 50);
+/// assert_eq!(min_k, Some(8));
+///
+/// // No feasible value
+/// assert_eq!(boundary(0, 100, |k| k * k > 10000), (Some(100), None));
+/// assert_eq!(boundary(0, 100, |k| k * k >= 0), (None, Some(0)));
+/// ```
+pub fn boundary<F: FnMut(i64) -> bool>(lo: i64, hi: i64, mut pred: F) -> (Option<i64>, Option<i64>) {
+    if lo > hi {
+        return (None, None);
+    }
+
+    let first_true = partition_point_i64(lo..hi, &mut pred);
+    let first_true = if first_true < hi || pred(hi) { Some(first_true) } else { None };
+    let last_false = match first_true {
+        Some(x) => if x > lo { Some(x - 1) } else { None },
+        None => Some(hi)
+    };
+    (last_false, first_true)
+}
+
+/// Floating point version of [`boundary`](fn.boundary.html).
+///
+/// The boundary in `[lo, hi]` is bisected `iterations` times,
+/// and `(a, b)` is returned such that `pred(a)` is `false`, `pred(b)` is `true`
+/// and `b - a` is `(hi - lo) / 2^iterations`.
+/// If `pred(lo)` is `true`, returns `(None, Some(lo))`.
+/// If `pred(hi)` is `false`, returns `(Some(hi), None)`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::bsearch::*;
+/// let (a, b) = boundary_f64(0.0, 2.0, 100, |x| x * x >= 2.0);
+/// assert!((a.unwrap() - 2f64.sqrt()).abs() < 1e-9);
+/// assert!((b.unwrap() - 2f64.sqrt()).abs() < 1e-9);
+/// ```
+pub fn boundary_f64<F: FnMut(f64) -> bool>(lo: f64, hi: f64, iterations: usize, mut pred: F)
+                                           -> (Option<f64>, Option<f64>) {
+    if pred(lo) {
+        return (None, Some(lo));
+    }
+    if !pred(hi) {
+        return (Some(hi), None);
+    }
+
+    let (mut lo, mut hi) = (lo, hi);
+    for _ in 0..iterations {
+        let mid = lo + (hi - lo) / 2.0;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    (Some(lo), Some(hi))
 }
 
 // END SNIPPET
@@ -412,6 +807,295 @@ mod tests {
         assert_eq!(seq.bsearch_index_right_min(|&x| x >= 20), None);
     }
 
+    #[test]
+    fn test_partition_point_i64() {
+        use super::partition_point_i64;
+
+        assert_eq!(partition_point_i64(0..10, |x| x >= 3), 3);
+        assert_eq!(partition_point_i64(-10..10, |x| x >= -5), -5);
+        assert_eq!(partition_point_i64(0..10, |_| true), 0);
+        assert_eq!(partition_point_i64(0..10, |_| false), 10);
+        assert_eq!(partition_point_i64(0..10, |x| x >= 9), 9);
+        assert_eq!(partition_point_i64(5..5, |_| true), 5);
+        assert_eq!(partition_point_i64(5..5, |_| false), 5);
+
+        let (min, max) = (std::i64::MIN, std::i64::MAX);
+        assert_eq!(partition_point_i64(min..max, |_| true), min);
+        assert_eq!(partition_point_i64(min..max, |_| false), max);
+        assert_eq!(partition_point_i64(min..max, |x| x >= 0), 0);
+        assert_eq!(partition_point_i64(min..max, |x| x >= max - 1), max - 1);
+        assert_eq!(partition_point_i64(min..max, |x| x > min), min + 1);
+    }
+
+    #[test]
+    fn test_partition_point_unsigned() {
+        use super::{partition_point_u64, partition_point_usize};
+
+        assert_eq!(partition_point_u64(0..10, |x| x >= 3), 3);
+        assert_eq!(partition_point_u64(0..10, |_| true), 0);
+        assert_eq!(partition_point_u64(0..10, |_| false), 10);
+        let max = std::u64::MAX;
+        assert_eq!(partition_point_u64(0..max, |x| x >= max / 3), max / 3);
+        assert_eq!(partition_point_u64(0..max, |x| x >= max - 1), max - 1);
+        assert_eq!(partition_point_u64(0..max, |_| false), max);
+
+        let seq = [3, 6, 9, 12, 15];
+        assert_eq!(partition_point_usize(0..seq.len(), |i| seq[i] >= 10), 3);
+        assert_eq!(partition_point_usize(0..seq.len(), |i| seq[i] >= 0), 0);
+        assert_eq!(partition_point_usize(0..seq.len(), |i| seq[i] >= 20), 5);
+        assert_eq!(partition_point_usize(0..0, |_| true), 0);
+    }
+
+    #[test]
+    fn test_exponential_search() {
+        use super::exponential_search;
+
+        assert_eq!(exponential_search(0, |_| true), Some(0));
+        assert_eq!(exponential_search(-100, |x| x >= -100), Some(-100));
+        assert_eq!(exponential_search(0, |x| x >= 1), Some(1));
+        for answer in 0..100 {
+            assert_eq!(exponential_search(0, |x| x >= answer), Some(answer));
+            assert_eq!(exponential_search(-50, |x| x >= answer - 50), Some(answer - 50));
+        }
+
+        let max = std::i64::MAX;
+        assert_eq!(exponential_search(0, |x| x >= 1_000_000_000_000_000_000),
+                   Some(1_000_000_000_000_000_000));
+        assert_eq!(exponential_search(0, |x| x >= max - 1), Some(max - 1));
+        assert_eq!(exponential_search(0, |x| x == max), Some(max));
+        assert_eq!(exponential_search(max, |_| true), Some(max));
+        assert_eq!(exponential_search(std::i64::MIN, |x| x >= max), Some(max));
+
+        assert_eq!(exponential_search(0, |_| false), None);
+        assert_eq!(exponential_search(max, |_| false), None);
+        assert_eq!(exponential_search(0, |x| x < 0), None);
+
+        let mut calls = 0;
+        exponential_search(0, |x| { calls += 1; x >= 1_000_000_000 });
+        assert!(calls <= 2 * 64);
+    }
+
+    #[derive(Debug)]
+    struct Event {
+        time: u32,
+        id: usize
+    }
+
+    fn events(times: &[u32]) -> Vec<Event> {
+        times.iter().enumerate().map(|(id, &time)| Event { time, id }).collect()
+    }
+
+    #[test]
+    fn test_slice_partition_point_by_key() {
+        use super::SliceBSearch;
+
+        let empty = events(&[]);
+        assert_eq!(empty.partition_point_by_key(0, |e| e.time), 0);
+
+        let seq = events(&[1, 3, 3, 3, 5, 8, 8]);
+        assert_eq!(seq.partition_point_by_key(0, |e| e.time), 0);
+        assert_eq!(seq.partition_point_by_key(1, |e| e.time), 0);
+        assert_eq!(seq.partition_point_by_key(3, |e| e.time), 1);
+        assert_eq!(seq.partition_point_by_key(4, |e| e.time), 4);
+        assert_eq!(seq.partition_point_by_key(8, |e| e.time), 5);
+        assert_eq!(seq.partition_point_by_key(9, |e| e.time), 7);
+        assert_eq!(seq[seq.partition_point_by_key(5, |e| e.time)].id, 4);
+    }
+
+    #[test]
+    fn test_slice_range_by_key() {
+        use super::SliceBSearch;
+
+        let empty = events(&[]);
+        assert_eq!(empty.range_by_key(0..10, |e| e.time), 0..0);
+
+        let times = [1, 3, 3, 3, 5, 8, 8];
+        let seq = events(&times);
+        for l in 0..10 {
+            for r in 0..10 {
+                let range = seq.range_by_key(l..r, |e| e.time);
+                let expected: Vec<usize> = seq.iter()
+                    .filter(|e| l <= e.time && e.time < r)
+                    .map(|e| e.id)
+                    .collect();
+                let actual: Vec<usize> = seq[range].iter().map(|e| e.id).collect();
+                assert_eq!(actual, expected);
+            }
+        }
+        assert_eq!(seq.range_by_key(3..4, |e| e.time), 1..4);
+        assert_eq!(seq.range_by_key(8..100, |e| e.time), 5..7);
+        assert_eq!(seq.range_by_key(5..3, |e| e.time), 4..4);
+    }
+
+    #[test]
+    #[cfg(local)]
+    #[should_panic]
+    fn test_slice_range_by_key_unsorted() {
+        use super::SliceBSearch;
+
+        let seq = events(&[1, 3, 2]);
+        seq.range_by_key(0..10, |e| e.time);
+    }
+
+    #[test]
+    fn test_golden_section_min() {
+        use super::golden_section_min;
+
+        let (x, y) = golden_section_min(-10.0, 10.0, 100, |x| (x - 1.5) * (x - 1.5) + 2.0);
+        assert!((x - 1.5).abs() < 1e-6);
+        assert!((y - 2.0).abs() < 1e-9);
+
+        // Minimum at the ends
+        let (x, _) = golden_section_min(0.0, 1.0, 100, |x| x);
+        assert!(x.abs() < 1e-9);
+        let (x, _) = golden_section_min(0.0, 1.0, 100, |x| -x);
+        assert!((x - 1.0).abs() < 1e-9);
+
+        // Plateau of minimum in [2, 4]
+        let (x, y) = golden_section_min(0.0, 10.0, 100, |x| {
+            if x < 2.0 { 2.0 - x } else if x > 4.0 { x - 4.0 } else { 0.0 }
+        });
+        assert!(2.0 - 1e-9 <= x && x <= 4.0 + 1e-9);
+        assert_eq!(y, 0.0);
+
+        let mut calls = 0;
+        golden_section_min(0.0, 1.0, 50, |x| { calls += 1; x * x });
+        assert_eq!(calls, 52);
+    }
+
+    #[test]
+    fn test_min_of_convex() {
+        use super::min_of_convex;
+
+        assert_eq!(min_of_convex(-10..10, |x| (x - 3) * (x - 3)), (3, 0));
+        assert_eq!(min_of_convex(5..6, |x| x), (5, 5));
+        // Minimum at the ends
+        assert_eq!(min_of_convex(0..10, |x| x), (0, 0));
+        assert_eq!(min_of_convex(0..10, |x| -x), (9, -9));
+        // Plateau
+        assert_eq!(min_of_convex(0..10, |x| if x < 3 { 3 - x } else if x > 6 { x - 6 } else { 0 }), (3, 0));
+        assert_eq!(min_of_convex(0..10, |_| 7), (0, 7));
+        // Floating point values
+        let (x, y) = min_of_convex(-100..100, |x| (x as f64 - 2.4).abs());
+        assert_eq!(x, 2);
+        assert!((y - 0.4).abs() < 1e-9);
+
+        let (min, max) = (std::i64::MIN, std::i64::MAX);
+        assert_eq!(min_of_convex(min+1..max, |x| x.abs() as u64), (0, 0));
+        assert_eq!(min_of_convex(min..max, |x| (x as i128 - 12345).abs()), (12345, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_min_of_convex_empty() {
+        super::min_of_convex(3..3, |x| x);
+    }
+
+    #[test]
+    fn test_parallel_bsearch_connectivity() {
+        use super::parallel_bsearch;
+        use crate::collections::vec_union_find_sets::VecUnionFindSets;
+        use rand::prelude::*;
+        use std::cell::RefCell;
+
+        let mut rng = StdRng::seed_from_u64(925);
+        for _ in 0..20 {
+            let n = rng.gen_range(2, 30);
+            let edges: Vec<(usize, usize)> = (0..rng.gen_range(0, 40))
+                .map(|_| (rng.gen_range(0, n), rng.gen_range(0, n)))
+                .collect();
+            let queries: Vec<(usize, usize)> = (0..50)
+                .map(|_| (rng.gen_range(0, n), rng.gen_range(0, n)))
+                .collect();
+
+            let sets = RefCell::new(VecUnionFindSets::new(n));
+            let steps_applied = RefCell::new(Vec::new());
+            let answers = parallel_bsearch(
+                edges.len(), &queries,
+                |i| {
+                    steps_applied.borrow_mut().push(i);
+                    sets.borrow_mut().unite(edges[i].0, edges[i].1);
+                },
+                |&(u, v)| sets.borrow().set_eq(u, v),
+                || {
+                    steps_applied.borrow_mut().clear();
+                    *sets.borrow_mut() = VecUnionFindSets::new(n);
+                }
+            );
+            assert!(steps_applied.borrow().iter().enumerate().all(|(i, &step)| i == step));
+
+            for (&(u, v), &answer) in queries.iter().zip(&answers) {
+                let mut sets = VecUnionFindSets::new(n);
+                let mut expected = None;
+                for k in 0..=edges.len() {
+                    if k > 0 {
+                        sets.unite(edges[k-1].0, edges[k-1].1);
+                    }
+                    if sets.set_eq(u, v) {
+                        expected = Some(k);
+                        break;
+                    }
+                }
+                assert_eq!(answer, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parallel_bsearch_edge_cases() {
+        use super::parallel_bsearch;
+
+        let queries: [bool; 0] = [];
+        assert_eq!(parallel_bsearch(10, &queries, |_| {}, |&q| q, || {}), vec![]);
+        assert_eq!(parallel_bsearch(0, &[true, false], |_| panic!(), |&q| q, || {}),
+                   vec![Some(0), None]);
+
+        let mut check_calls = 0;
+        let answers = parallel_bsearch(1_000_000, &[true], |_| {}, |&q| { check_calls += 1; q }, || {});
+        assert_eq!(answers, vec![Some(0)]);
+        assert!(check_calls <= 21);
+    }
+
+    #[test]
+    fn test_boundary() {
+        use super::boundary;
+
+        // Flips inside
+        assert_eq!(boundary(0, 10, |x| x >= 4), (Some(3), Some(4)));
+        assert_eq!(boundary(0, 10, |x| x >= 1), (Some(0), Some(1)));
+        assert_eq!(boundary(0, 10, |x| x >= 10), (Some(9), Some(10)));
+        // Always true
+        assert_eq!(boundary(0, 10, |_| true), (None, Some(0)));
+        // Always false
+        assert_eq!(boundary(0, 10, |_| false), (Some(10), None));
+        // Empty range
+        assert_eq!(boundary(10, 0, |_| true), (None, None));
+        // Single value
+        assert_eq!(boundary(5, 5, |_| true), (None, Some(5)));
+        assert_eq!(boundary(5, 5, |_| false), (Some(5), None));
+
+        let (min, max) = (std::i64::MIN, std::i64::MAX);
+        assert_eq!(boundary(min, max, |_| true), (None, Some(min)));
+        assert_eq!(boundary(min, max, |_| false), (Some(max), None));
+        assert_eq!(boundary(min, max, |x| x >= 0), (Some(-1), Some(0)));
+        assert_eq!(boundary(min, max, |x| x == max), (Some(max - 1), Some(max)));
+    }
+
+    #[test]
+    fn test_boundary_f64() {
+        use super::boundary_f64;
+
+        let (a, b) = boundary_f64(-10.0, 10.0, 100, |x| x >= 1.25);
+        let (a, b) = (a.unwrap(), b.unwrap());
+        assert!(a < 1.25 && 1.25 <= b);
+        assert!(b - a < 1e-9);
+
+        assert_eq!(boundary_f64(0.0, 1.0, 100, |_| true), (None, Some(0.0)));
+        assert_eq!(boundary_f64(0.0, 1.0, 100, |_| false), (Some(1.0), None));
+        assert_eq!(boundary_f64(0.0, 1.0, 0, |x| x >= 0.5), (Some(0.0), Some(1.0)));
+        assert_eq!(boundary_f64(0.0, 1.0, 1, |x| x >= 0.7), (Some(0.5), Some(1.0)));
+    }
+
     #[test]
     fn test_range_inclusive_middle_item() {
         assert_eq!((10..=10).middle_item(), 10);
@@ -32,6 +32,35 @@ const BITSET_TRUE: &'static bool = &true;
 const BITSET_FALSE: &'static bool = &false;
 
 /// Efficient boolean vector
+///
+/// Bitwise operations and shifts are performed 64 bits at a time.
+///
+/// # Example
+///
+/// Whether items can be partitioned into two groups of the same total weight,
+/// in O(*n*·*S*/64) time as *S* is the total weight.
+///
+/// ```
+/// # use atcoder_snippets::collections::bitset::*;
+/// fn can_partition(weights: &[usize]) -> bool {
+///     let total: usize = weights.iter().sum();
+///     if total % 2 != 0 {
+///         return false;
+///     }
+///
+///     // dp[s]: whether some of items weigh s in total
+///     let mut dp = BitSet::new(total + 1);
+///     dp.set(0, true);
+///     for &w in weights {
+///         dp |= &(dp.clone() << w);
+///     }
+///     dp[total / 2]
+/// }
+///
+/// assert!(can_partition(&[3, 1, 4, 1, 5, 9, 7]));
+/// assert!(!can_partition(&[3, 1, 4, 1, 5, 9, 2]));
+/// assert!(!can_partition(&[2, 10, 4]));
+/// ```
 #[derive(Clone)]
 pub struct BitSet {
     // 64-bit chunks ordered lowest chunk first and high chunk last
@@ -57,7 +86,7 @@ impl BitSet {
     ///
     /// If `i` is out of range, returns `None`.
     pub fn get(&self, i: usize) -> Option<bool> {
-        if i <= self.len() {
+        if i < self.len() {
             Some(unsafe { self.get_unchecked(i) })
         } else {
             None
@@ -95,6 +124,20 @@ impl BitSet {
         })
     }
 
+    /// Sets `i`-th bit to `value`.
+    ///
+    /// # Panic
+    ///
+    /// If `i` is out of range, it panics.
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.len(), "index out of bounds: the bit-length is {} but index is {}", self.len(), i);
+        if value {
+            self.buf[i >> 6] |= 1 << (i & 63);
+        } else {
+            self.buf[i >> 6] &= !(1 << (i & 63));
+        }
+    }
+
     /// Counts how many bits are sets.
     ///
     /// # Example
@@ -133,6 +176,26 @@ impl BitSet {
         }
     }
 
+    /// Gets an iterator yielding indices of set bits in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::bitset::*;
+    /// let mut set = BitSet::new(200);
+    /// set.set(3, true);
+    /// set.set(64, true);
+    /// set.set(199, true);
+    /// assert_eq!(set.ones().collect::<Vec<_>>(), vec![3, 64, 199]);
+    /// ```
+    pub fn ones(&self) -> BitSetOnes<'_> {
+        BitSetOnes {
+            buf: &self.buf,
+            block_index: 0,
+            block: self.buf.first().cloned().unwrap_or(0)
+        }
+    }
+
     fn chomp(&mut self) {
         let r = self.len() % 64;
         if r != 0 {
@@ -241,6 +304,30 @@ impl ExactSizeIterator for BitSetBits<'_> {}
 
 impl std::iter::FusedIterator for BitSetBits<'_> {}
 
+/// An iterator created by [`ones`](struct.BitSet.html#method.ones) method on bitsets.
+pub struct BitSetOnes<'a> {
+    buf: &'a [u64],
+    block_index: usize,
+    // Bits of the current block not yielded yet.
+    block: u64
+}
+
+impl Iterator for BitSetOnes<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.block == 0 {
+            self.block_index += 1;
+            self.block = *self.buf.get(self.block_index)?;
+        }
+        let bit = self.block.trailing_zeros() as usize;
+        self.block &= self.block - 1;
+        Some(self.block_index * 64 + bit)
+    }
+}
+
+impl std::iter::FusedIterator for BitSetOnes<'_> {}
+
 impl std::ops::ShlAssign<usize> for BitSet {
     fn shl_assign(&mut self, x: usize) {
         let q = x >> 6;
@@ -321,6 +408,18 @@ impl std::ops::Shr<usize> for BitSet {
     }
 }
 
+impl std::ops::Not for BitSet {
+    type Output = Self;
+
+    fn not(mut self) -> Self {
+        for x in &mut self.buf {
+            *x = !*x;
+        }
+        self.chomp();
+        self
+    }
+}
+
 impl<'a> std::ops::BitAndAssign<&'a BitSet> for BitSet {
     fn bitand_assign(&mut self, rhs: &'a Self) {
         for (a, b) in self.buf.iter_mut().zip(rhs.buf.iter()) {
@@ -496,6 +595,55 @@ mod tests {
         assert_eq!((set1.clone() ^ &set2).count_ones(), 2);
     }
 
+    #[test]
+    fn test_against_vec() {
+        let mut rng = StdRng::seed_from_u64(1017);
+        for &size in &[0, 1, 63, 64, 65, 128, 300] {
+            let random_pair = |rng: &mut StdRng| {
+                let mut set = BitSet::new(size);
+                let mut v = vec![false; size];
+                for i in 0..size {
+                    let b = rng.gen_range(0, 2) == 0;
+                    set.set(i, b);
+                    v[i] = b;
+                }
+                (set, v)
+            };
+            let (set1, v1) = random_pair(&mut rng);
+            let (set2, v2) = random_pair(&mut rng);
+
+            let assert_same = |set: &BitSet, v: &[bool]| {
+                assert_eq!(set.bits().collect::<Vec<_>>(), v);
+                assert_eq!(set.count_ones() as usize, v.iter().filter(|&&b| b).count());
+                assert_eq!(
+                    set.ones().collect::<Vec<_>>(),
+                    (0..size).filter(|&i| v[i]).collect::<Vec<_>>()
+                );
+            };
+
+            assert_same(&set1, &v1);
+            assert_same(&!set1.clone(), &v1.iter().map(|&b| !b).collect::<Vec<_>>());
+            assert_same(&(set1.clone() & &set2), &(0..size).map(|i| v1[i] & v2[i]).collect::<Vec<_>>());
+            assert_same(&(set1.clone() | &set2), &(0..size).map(|i| v1[i] | v2[i]).collect::<Vec<_>>());
+            assert_same(&(set1.clone() ^ &set2), &(0..size).map(|i| v1[i] ^ v2[i]).collect::<Vec<_>>());
+
+            for &shift in &[0, 1, 5, 63, 64, 65, 130, 1000] {
+                let shl: Vec<bool> = (0..size).map(|i| i >= shift && v1[i - shift]).collect();
+                assert_same(&(set1.clone() << shift), &shl);
+                let shr: Vec<bool> = (0..size).map(|i| i + shift < size && v1[i + shift]).collect();
+                assert_same(&(set1.clone() >> shift), &shr);
+            }
+
+            assert_eq!(set1.get(size), None);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the bit-length is 64 but index is 64")]
+    fn test_set_out_of_range() {
+        BitSet::new(64).set(64, true);
+    }
+
     #[test]
     fn test_bits_len() {
         let set = BitSet::new(0);
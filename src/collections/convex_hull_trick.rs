@@ -0,0 +1,329 @@
+//! Convex hull trick, getting the minimum of linear functions.
+
+// BEGIN SNIPPET convex_hull_trick
+
+// The smallest integer `x` such that `q(x) <= p(x)`, as the slope of `p` is greater than that of `q`.
+fn cht_cross((ap, bp): (i64, i64), (aq, bq): (i64, i64)) -> i128 {
+    let num = bq as i128 - bp as i128;
+    let den = ap as i128 - aq as i128;
+    -((-num).div_euclid(den))
+}
+
+// The value of the line `(a, b)` at `x`, which may not fit in `i64`.
+fn cht_eval((a, b): (i64, i64), x: i64) -> i128 {
+    a as i128 * x as i128 + b as i128
+}
+
+/// Set of lines `a*x + b` added in non-increasing order of slope,
+/// answering the minimum at `x` in non-decreasing order.
+///
+/// Each of `add_line` and `query` is performed in amortized constant time.
+/// Intersections are compared in `i128`, so any `i64` coefficients are allowed,
+/// as far as the values at queried `x` fit in `i64`.
+///
+/// If lines or queries are not in the order, use [`ConvexHullTrick`](struct.ConvexHullTrick.html).
+///
+/// # Example
+///
+/// Frog 3 (EDPC Z): the cost to jump from stone `i` to stone `j` is
+/// `(h[j] - h[i])^2 + c`, with `h` strictly increasing.
+///
+/// ```
+/// # use atcoder_snippets::collections::convex_hull_trick::*;
+/// let h: Vec<i64> = vec![1, 2, 3, 4, 5];
+/// let c = 6;
+///
+/// // dp[j] = min_i (-2*h[i]*h[j] + dp[i] + h[i]^2) + h[j]^2 + c
+/// let mut dp = vec![0; h.len()];
+/// let mut cht = MonotoneConvexHullTrick::new();
+/// cht.add_line(-2 * h[0], h[0] * h[0]);
+/// for j in 1..h.len() {
+///     dp[j] = cht.query(h[j]).unwrap() + h[j] * h[j] + c;
+///     cht.add_line(-2 * h[j], dp[j] + h[j] * h[j]);
+/// }
+/// assert_eq!(dp[h.len() - 1], 20);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MonotoneConvexHullTrick {
+    lines: std::collections::VecDeque<(i64, i64)>,
+    last_query: Option<i64>
+}
+
+impl MonotoneConvexHullTrick {
+    /// Creates an empty set of lines.
+    pub fn new() -> MonotoneConvexHullTrick {
+        MonotoneConvexHullTrick {
+            lines: std::collections::VecDeque::new(),
+            last_query: None
+        }
+    }
+
+    /// Adds a line `a*x + b`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` is greater than the slope of a line added before.
+    pub fn add_line(&mut self, a: i64, b: i64) {
+        if let Some(&(last_a, last_b)) = self.lines.back() {
+            assert!(a <= last_a, "slope {} is greater than the last slope {}", a, last_a);
+            if a == last_a {
+                if last_b <= b {
+                    return;
+                }
+                self.lines.pop_back();
+            }
+        }
+
+        while self.lines.len() >= 2 {
+            let len = self.lines.len();
+            let (l1, l2) = (self.lines[len - 2], self.lines[len - 1]);
+            if cht_cross(l1, l2) < cht_cross(l2, (a, b)) {
+                break;
+            }
+            self.lines.pop_back();
+        }
+        self.lines.push_back((a, b));
+    }
+
+    /// Gets the minimum of `a*x + b` over all the lines, or `None` if no lines are added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is less than `x` of a query before.
+    pub fn query(&mut self, x: i64) -> Option<i64> {
+        if let Some(last) = self.last_query {
+            assert!(last <= x, "x = {} is less than the last query {}", x, last);
+        }
+        self.last_query = Some(x);
+
+        while self.lines.len() >= 2 {
+            if cht_eval(self.lines[0], x) < cht_eval(self.lines[1], x) {
+                break;
+            }
+            self.lines.pop_front();
+        }
+        self.lines.front().map(|&line| cht_eval(line, x) as i64)
+    }
+}
+
+impl Default for MonotoneConvexHullTrick {
+    fn default() -> MonotoneConvexHullTrick {
+        MonotoneConvexHullTrick::new()
+    }
+}
+
+/// Set of lines `a*x + b` answering the minimum at `x`,
+/// where lines and queries can be in any order.
+///
+/// Each of `add_line` and `query` is performed in amortized O(log(*n*)) time,
+/// as *n* is the number of lines.
+/// Intersections are compared in `i128`, so any `i64` coefficients are allowed,
+/// as far as the values at queried `x` fit in `i64`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::convex_hull_trick::*;
+/// let mut cht = ConvexHullTrick::new();
+/// assert_eq!(cht.query(0), None);
+/// cht.add_line(1, 0);
+/// cht.add_line(-1, 0);
+/// cht.add_line(0, -2);
+/// assert_eq!(cht.query(-5), Some(-5));
+/// assert_eq!(cht.query(1), Some(-2));
+/// assert_eq!(cht.query(3), Some(-3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConvexHullTrick {
+    // Map from the slope to the intercept and the smallest `x` at which the line is the minimum.
+    lines: std::collections::BTreeMap<i64, (i64, i128)>,
+    // Map from the smallest `x` at which a line is the minimum to its slope.
+    starts: std::collections::BTreeMap<i128, i64>
+}
+
+impl ConvexHullTrick {
+    /// Creates an empty set of lines.
+    pub fn new() -> ConvexHullTrick {
+        ConvexHullTrick {
+            lines: std::collections::BTreeMap::new(),
+            starts: std::collections::BTreeMap::new()
+        }
+    }
+
+    // The line with the smallest slope greater than `a`.
+    fn greater(&self, a: i64) -> Option<(i64, i64)> {
+        use std::ops::Bound::*;
+        self.lines.range((Excluded(a), Unbounded)).next().map(|(&a, &(b, _))| (a, b))
+    }
+
+    // The line with the greatest slope less than `a`.
+    fn less(&self, a: i64) -> Option<(i64, i64)> {
+        self.lines.range(..a).next_back().map(|(&a, &(b, _))| (a, b))
+    }
+
+    fn remove(&mut self, a: i64) {
+        let (_, start) = self.lines.remove(&a).unwrap();
+        self.starts.remove(&start);
+    }
+
+    fn set_start(&mut self, a: i64, start: i128) {
+        let entry = self.lines.get_mut(&a).unwrap();
+        self.starts.remove(&entry.1);
+        entry.1 = start;
+        self.starts.insert(start, a);
+    }
+
+    /// Adds a line `a*x + b`.
+    pub fn add_line(&mut self, a: i64, b: i64) {
+        if let Some(&(old_b, _)) = self.lines.get(&a) {
+            if old_b <= b {
+                return;
+            }
+            self.remove(a);
+        }
+
+        let line = (a, b);
+        if let (Some(prev), Some(next)) = (self.greater(a), self.less(a)) {
+            if cht_cross(prev, line) >= cht_cross(line, next) {
+                return;
+            }
+        }
+
+        while let Some(next) = self.less(a) {
+            match self.less(next.0) {
+                Some(next_next) if cht_cross(line, next) >= cht_cross(next, next_next) => {
+                    self.remove(next.0);
+                }
+                _ => break
+            }
+        }
+        while let Some(prev) = self.greater(a) {
+            match self.greater(prev.0) {
+                Some(prev_prev) if cht_cross(prev_prev, prev) >= cht_cross(prev, line) => {
+                    self.remove(prev.0);
+                }
+                _ => break
+            }
+        }
+
+        let start = match self.greater(a) {
+            Some(prev) => cht_cross(prev, line),
+            None => std::i128::MIN
+        };
+        if let Some(next) = self.less(a) {
+            self.set_start(next.0, cht_cross(line, next));
+        }
+        self.lines.insert(a, (b, start));
+        self.starts.insert(start, a);
+    }
+
+    /// Gets the minimum of `a*x + b` over all the lines, or `None` if no lines are added.
+    pub fn query(&self, x: i64) -> Option<i64> {
+        let (_, &a) = self.starts.range(..=x as i128).next_back()?;
+        let (b, _) = self.lines[&a];
+        Some(a * x + b)
+    }
+}
+
+impl Default for ConvexHullTrick {
+    fn default() -> ConvexHullTrick {
+        ConvexHullTrick::new()
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+
+    fn naive_min(lines: &[(i64, i64)], x: i64) -> Option<i64> {
+        lines.iter().map(|&(a, b)| a * x + b).min()
+    }
+
+    #[test]
+    fn test_monotone() {
+        let mut rng = Xorshift::with_seed(1009);
+        for _ in 0..100 {
+            let mut slopes: Vec<i64> = (0..rng.gen_range(1..30)).map(|_| rng.gen_range_i64(-20..20)).collect();
+            slopes.sort_by(|a, b| b.cmp(a));
+            let mut xs: Vec<i64> = (0..30).map(|_| rng.gen_range_i64(-50..50)).collect();
+            xs.sort();
+
+            let mut cht = MonotoneConvexHullTrick::new();
+            assert_eq!(cht.query(xs[0]), None);
+            let mut lines = Vec::new();
+            let (mut i, mut j) = (0, 0);
+            while i < slopes.len() || j < xs.len() {
+                if j == xs.len() || i < slopes.len() && rng.gen_range(0..2) == 0 {
+                    let b = rng.gen_range_i64(-100..100);
+                    cht.add_line(slopes[i], b);
+                    lines.push((slopes[i], b));
+                    i += 1;
+                } else {
+                    assert_eq!(cht.query(xs[j]), naive_min(&lines, xs[j]));
+                    j += 1;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_general() {
+        let mut rng = Xorshift::with_seed(1009);
+        for _ in 0..100 {
+            let mut cht = ConvexHullTrick::new();
+            let mut lines = Vec::new();
+            for _ in 0..60 {
+                if rng.gen_range(0..2) == 0 {
+                    let a = rng.gen_range_i64(-20..20);
+                    let b = rng.gen_range_i64(-100..100);
+                    cht.add_line(a, b);
+                    lines.push((a, b));
+                } else {
+                    let x = rng.gen_range_i64(-50..50);
+                    assert_eq!(cht.query(x), naive_min(&lines, x));
+                }
+            }
+            for x in -50..50 {
+                assert_eq!(cht.query(x), naive_min(&lines, x));
+            }
+        }
+    }
+
+    #[test]
+    fn test_large_coefficients() {
+        let big = 1_000_000_000_000_000_000;
+        let mut cht = ConvexHullTrick::new();
+        cht.add_line(big, -big);
+        cht.add_line(-big, -big);
+        cht.add_line(0, -big - 1);
+        assert_eq!(cht.query(0), Some(-big - 1));
+        assert_eq!(cht.query(1), Some(-2 * big));
+        assert_eq!(cht.query(2), Some(-3 * big));
+
+        let mut cht = MonotoneConvexHullTrick::new();
+        cht.add_line(big, -big);
+        cht.add_line(0, -big - 1);
+        cht.add_line(-big, -big);
+        assert_eq!(cht.query(-2), Some(-3 * big));
+        assert_eq!(cht.query(0), Some(-big - 1));
+        assert_eq!(cht.query(2), Some(-3 * big));
+
+        // Non-optimal lines overflow `i64` at the queried `x`.
+        let mut cht = MonotoneConvexHullTrick::new();
+        cht.add_line(big, -big);
+        cht.add_line(0, 0);
+        assert_eq!(cht.query(100), Some(0));
+        assert_eq!(cht.query(200), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "slope 1 is greater than the last slope 0")]
+    fn test_monotone_unsorted_slopes() {
+        let mut cht = MonotoneConvexHullTrick::new();
+        cht.add_line(0, 0);
+        cht.add_line(1, 0);
+    }
+}
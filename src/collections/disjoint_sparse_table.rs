@@ -0,0 +1,188 @@
+//! Disjoint sparse table.
+
+use crate::range::UsizeRangeBoundsExt;
+
+// BEGIN SNIPPET disjoint_sparse_table DEPENDS ON range
+
+/// Static sequence aggregatable by interval in constant time.
+///
+/// Unlike [`SparseTable`](../sparse_table/struct.SparseTable.html),
+/// the items only have to be semigroup. That is, aggregation must be associative,
+/// but need not be idempotent nor commutative.
+///
+/// Construction takes Θ(*n* log(*n*)) time, as *n* is the number of items.
+pub struct DisjointSparseTable<T: Clone, F: Fn(&T, &T) -> T> {
+    // `table[0]` is the items.
+    // For `k >= 1`, the items are splitted into blocks of length 2^k,
+    // and each block is splitted at the middle.
+    // `table[k][i]` is the aggregation from `i` to the middle (exclusive)
+    // if `i` is in the left half, and from the middle to `i` (inclusive) otherwise.
+    table: Vec<Vec<T>>,
+    aggregate: F
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> DisjointSparseTable<T, F> {
+    /// Creates a new disjoint sparse table from items in a vector.
+    ///
+    /// `T` must be semigroup under `aggregate`.
+    pub fn from_vec(items: Vec<T>, aggregate: F) -> DisjointSparseTable<T, F> {
+        let len = items.len();
+        let mut table = vec![items];
+        let mut half = 1;
+        while half < len {
+            let items = &table[0];
+            let mut row = items.clone();
+            let mut mid = half;
+            while mid < len {
+                for i in (mid - half..mid - 1).rev() {
+                    row[i] = aggregate(&items[i], &row[i + 1]);
+                }
+                for i in mid + 1..std::cmp::min(mid + half, len) {
+                    row[i] = aggregate(&row[i - 1], &items[i]);
+                }
+                mid += 2 * half;
+            }
+            table.push(row);
+            half *= 2;
+        }
+
+        DisjointSparseTable {
+            table,
+            aggregate
+        }
+    }
+
+    /// The number of items.
+    pub fn len(&self) -> usize {
+        self.table[0].len()
+    }
+
+    /// Returns whether the table has no items.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets all items as a slice.
+    pub fn items(&self) -> &[T] {
+        &self.table[0]
+    }
+
+    /// Aggregate items in the range of `index`.
+    ///
+    /// If the index is out of bound or empty, returns `None`.
+    ///
+    /// This method takes constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::disjoint_sparse_table::*;
+    /// # use atcoder_snippets::modulo::modp::*;
+    /// unsafe {
+    ///     ModP::set_mod(7).unwrap();
+    /// }
+    /// let table = vec![3, 1, 4, 1, 5, 9, 2, 6].into_iter()
+    ///     .map(ModP::new)
+    ///     .disjoint_sparse_table(|&x, &y| x * y);
+    ///
+    /// // 1 * 4 * 1 * 5 = 20 = 6 mod 7
+    /// assert_eq!(table.query(1..5), Some(ModP::new(6)));
+    /// assert_eq!(table.query(5..), Some(ModP::new(3)));
+    /// assert_eq!(table.query(3..3), None);
+    /// assert_eq!(table.query(3..=8), None);
+    /// ```
+    pub fn query<R: std::ops::RangeBounds<usize>>(&self, range: R) -> Option<T> {
+        range.to_range(self.len()).and_then(|range| {
+            if range.start == range.end {
+                return None;
+            }
+            let (l, r) = (range.start, range.end - 1);
+            if l == r {
+                return Some(self.table[0][l].clone());
+            }
+            // The level whose blocks split `l` and `r` at the middle.
+            let k = (0usize.leading_zeros() - (l ^ r).leading_zeros()) as usize;
+            let row = &self.table[k];
+            Some((self.aggregate)(&row[l], &row[r]))
+        })
+    }
+}
+
+pub trait IntoDisjointSparseTable: Sized + IntoIterator
+where
+    Self::Item: Clone
+{
+    fn disjoint_sparse_table<F>(self, aggregate: F) -> DisjointSparseTable<Self::Item, F>
+    where
+        F: Fn(&Self::Item, &Self::Item) -> Self::Item
+    {
+        DisjointSparseTable::from_vec(self.into_iter().collect(), aggregate)
+    }
+}
+
+impl<I: IntoIterator> IntoDisjointSparseTable for I where Self::Item: Clone {}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+
+    const MOD: u64 = 1_000_000_007;
+
+    type Matrix = [[u64; 2]; 2];
+
+    fn mul(a: &Matrix, b: &Matrix) -> Matrix {
+        let mut c = [[0; 2]; 2];
+        for i in 0..2 {
+            for j in 0..2 {
+                c[i][j] = (a[i][0] * b[0][j] + a[i][1] * b[1][j]) % MOD;
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn test_query_brute_force() {
+        let mut rng = Xorshift::with_seed(1028);
+
+        for len in 0..40 {
+            let items: Vec<Matrix> = (0..len)
+                .map(|_| {
+                    let mut m = [[0; 2]; 2];
+                    for row in m.iter_mut() {
+                        for x in row.iter_mut() {
+                            *x = rng.gen_range(0..MOD);
+                        }
+                    }
+                    m
+                })
+                .collect();
+            let table = items.clone().disjoint_sparse_table(mul);
+            assert_eq!(table.len(), len);
+            assert_eq!(table.items(), &items[..]);
+
+            for l in 0..=len {
+                for r in l..=len {
+                    let expected = items[l..r].iter().skip(1).fold(items.get(l).cloned(), |acc, m| {
+                        acc.map(|acc| mul(&acc, m))
+                    });
+                    assert_eq!(table.query(l..r), if l < r { expected } else { None });
+                }
+            }
+            assert_eq!(table.query(..=len), None);
+        }
+    }
+
+    #[test]
+    fn test_string_concat() {
+        let words = vec!["a", "b", "c", "d", "e"];
+        let table = words.into_iter()
+            .map(String::from)
+            .disjoint_sparse_table(|x, y| format!("{}{}", x, y));
+        assert_eq!(table.query(..), Some("abcde".to_string()));
+        assert_eq!(table.query(1..4), Some("bcd".to_string()));
+        assert_eq!(table.query(4..), Some("e".to_string()));
+    }
+}
@@ -0,0 +1,193 @@
+//! Maximum heap supporting erasure of arbitrary values.
+
+// BEGIN SNIPPET erasable_heap
+
+/// Maximum heap supporting erasure of arbitrary values by lazy deletion.
+///
+/// Erased values are kept in another heap until they reach the top of the heap,
+/// so each operation takes amortized O(log(*n*)) time.
+///
+/// Erasing a value not in the heap is a logic error.
+/// It panics if `cfg(local)` is enabled, checking the heap in O(*n*) time per erasure.
+/// Otherwise the heap is left in an unspecified state.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::erasable_heap::*;
+/// let mut heap = ErasableHeap::new();
+/// heap.push(3);
+/// heap.push(1);
+/// heap.push(4);
+/// heap.push(1);
+/// heap.erase(4);
+/// heap.erase(1);
+/// assert_eq!(heap.len(), 2);
+/// assert_eq!(heap.pop(), Some(3));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ErasableHeap<T: Ord> {
+    live: std::collections::BinaryHeap<T>,
+    // Values erased but not removed from `live` yet.
+    pending: std::collections::BinaryHeap<T>
+}
+
+impl<T: Ord> ErasableHeap<T> {
+    /// Creates an empty heap.
+    pub fn new() -> ErasableHeap<T> {
+        ErasableHeap {
+            live: std::collections::BinaryHeap::new(),
+            pending: std::collections::BinaryHeap::new()
+        }
+    }
+
+    /// The number of values in the heap.
+    pub fn len(&self) -> usize {
+        self.live.len().saturating_sub(self.pending.len())
+    }
+
+    /// Returns whether the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Removes erased values from the top of the heap.
+    fn clean(&mut self) {
+        loop {
+            match (self.live.peek(), self.pending.peek()) {
+                (_, None) => break,
+                (Some(x), Some(y)) if x > y => break,
+                (Some(x), Some(y)) if x == y => {
+                    self.live.pop();
+                }
+                // The erased value was not in the heap.
+                _ => {}
+            }
+            self.pending.pop();
+        }
+    }
+
+    /// Pushes a value.
+    pub fn push(&mut self, value: T) {
+        self.live.push(value);
+        self.clean();
+    }
+
+    /// Erases one value equal to `value`.
+    ///
+    /// The removal is deferred until the value reaches the top of the heap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cfg(local)` is enabled and `value` is not in the heap.
+    pub fn erase(&mut self, value: T) {
+        assert_erased_value_in_heap(self, &value);
+        self.pending.push(value);
+        self.clean();
+    }
+
+    /// Gets the maximum value, or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.live.peek()
+    }
+
+    /// Removes the maximum value and returns it, or `None` if the heap is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.live.pop();
+        self.clean();
+        value
+    }
+}
+
+impl<T: Ord> Default for ErasableHeap<T> {
+    fn default() -> ErasableHeap<T> {
+        ErasableHeap::new()
+    }
+}
+
+#[cfg(local)]
+fn assert_erased_value_in_heap<T: Ord>(heap: &ErasableHeap<T>, value: &T) {
+    let live = heap.live.iter().filter(|&x| x == value).count();
+    let pending = heap.pending.iter().filter(|&x| x == value).count();
+    assert!(live > pending, "erased a value not in the heap");
+}
+
+#[cfg(not(local))]
+fn assert_erased_value_in_heap<T: Ord>(_heap: &ErasableHeap<T>, _value: &T) {}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::multiset::MultiSet;
+    use crate::xorshift::{Xorshift, Rng};
+
+    #[test]
+    fn test_random_operations() {
+        let mut rng = Xorshift::with_seed(1029);
+
+        for _ in 0..20 {
+            let mut heap = ErasableHeap::new();
+            let mut reference = MultiSet::new();
+
+            for _ in 0..1000 {
+                match rng.gen_range(0..3) {
+                    0 => {
+                        let x = rng.gen_range(0..30);
+                        heap.push(x);
+                        reference.insert(x);
+                    },
+                    1 => {
+                        let x = rng.gen_range(0..30);
+                        if reference.remove_one(&x) {
+                            heap.erase(x);
+                        }
+                    },
+                    _ => {
+                        let expected = reference.last().cloned();
+                        if let Some(x) = expected {
+                            reference.remove_one(&x);
+                        }
+                        assert_eq!(heap.pop(), expected);
+                    }
+                }
+                assert_eq!(heap.peek(), reference.last());
+                assert_eq!(heap.len(), reference.len());
+                assert_eq!(heap.is_empty(), reference.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(local)]
+    #[should_panic(expected = "erased a value not in the heap")]
+    fn test_erase_absent_value_panics() {
+        let mut heap = ErasableHeap::new();
+        heap.push(2);
+        heap.erase(5);
+    }
+
+    #[test]
+    #[cfg(local)]
+    #[should_panic(expected = "erased a value not in the heap")]
+    fn test_erase_absent_value_below_top_panics() {
+        let mut heap = ErasableHeap::new();
+        heap.push(5);
+        heap.push(2);
+        heap.erase(3);
+    }
+
+    #[test]
+    #[cfg(local)]
+    #[should_panic(expected = "erased a value not in the heap")]
+    fn test_erase_twice_panics() {
+        let mut heap = ErasableHeap::new();
+        heap.push(5);
+        heap.push(2);
+        heap.erase(2);
+        heap.erase(2);
+    }
+}
@@ -19,6 +19,22 @@ use crate::read::{Readable, ReadableFromLine, read_words_into_vec, split_into_wo
 /// // stdin: "5 a a b b b"
 /// read!(len = usize, counter = HashCounter<char>);
 /// ```
+///
+/// # Example
+///
+/// Finding the mode of input numbers.
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::collections::hash_counter::*;
+/// # use atcoder_snippets::read::*;
+/// // stdin: "6\n3 1 4 1 5 1"
+/// read!(n = usize);
+/// read!(a = Vec<u32>);
+/// let counter: HashCounter<u32> = a.into_iter().collect();
+/// let (mode, count) = counter.most_common(1)[0];
+/// assert_eq!((mode, count), (1, 3));
+/// ```
 #[derive(Clone)]
 pub struct HashCounter<T> {
     counter: std::collections::HashMap<T, usize>
@@ -263,6 +279,26 @@ impl<T: Eq + std::hash::Hash> HashCounter<T> {
         HashCounterValues { iter: self.counter.values() }
     }
 
+    /// Gets at most `k` pairs of a key and its counter value, in descending order of the values.
+    ///
+    /// The order of keys with the same value is unspecified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[macro_use] extern crate atcoder_snippets;
+    /// # use atcoder_snippets::collections::hash_counter::*;
+    /// let counter = hash_counter!['a', 'b', 'b', 'c', 'c', 'c'];
+    /// assert_eq!(counter.most_common(2), vec![('c', 3), ('b', 2)]);
+    /// assert_eq!(counter.most_common(5).len(), 3);
+    /// ```
+    pub fn most_common(&self, k: usize) -> Vec<(T, usize)> where T: Clone {
+        let mut pairs: Vec<(T, usize)> = self.iter().map(|(key, count)| (key.clone(), count)).collect();
+        pairs.sort_by(|(_, count1), (_, count2)| count2.cmp(count1));
+        pairs.truncate(k);
+        pairs
+    }
+
     /// Creates an iterator yielding (&key, count) pairs.
     ///
     /// # Example
@@ -297,6 +333,34 @@ impl<T: std::hash::Hash + Eq> std::iter::FromIterator<T> for HashCounter<T> {
     }
 }
 
+impl<T: std::hash::Hash + Eq> Extend<T> for HashCounter<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+/// Merges two counters by adding counter values for each key.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::collections::hash_counter::*;
+/// let mut counter = hash_counter![1, 2, 2];
+/// counter += hash_counter![2, 3];
+/// assert_eq!(counter.count(&2), 3);
+/// assert_eq!(counter.count(&3), 1);
+/// ```
+impl<T: std::hash::Hash + Eq> std::ops::AddAssign for HashCounter<T> {
+    fn add_assign(&mut self, other: HashCounter<T>) {
+        for (key, count) in other {
+            *self.counter.entry(key).or_insert(0) += count;
+        }
+    }
+}
+
 impl<T: std::hash::Hash + Eq> std::iter::IntoIterator for HashCounter<T> {
     type Item = (T, usize);
     type IntoIter = HashCounterIntoIter<T>;
@@ -368,4 +432,38 @@ mod tests {
         assert_eq!(counter.count(&'b'), 0);
         assert_eq!(counter.keys_len(), 2);
     }
+
+    #[test]
+    fn test_remove_saturating() {
+        let mut counter = hash_counter!['a', 'a'];
+        assert!(counter.remove(&'a'));
+        assert!(counter.remove(&'a'));
+        assert!(!counter.remove(&'a'));
+        assert_eq!(counter.count(&'a'), 0);
+        assert_eq!(counter.keys_len(), 0);
+        counter.insert('a');
+        assert_eq!(counter.count(&'a'), 1);
+    }
+
+    #[test]
+    fn test_extend_and_merge() {
+        let mut counter: HashCounter<u32> = vec![1, 2, 2].into_iter().collect();
+        counter.extend(vec![2, 3]);
+        assert_eq!(counter.count(&2), 3);
+
+        let mut other = hash_counter![3, 4, 4, 4];
+        other.remove(&3);
+        counter += other;
+        assert_eq!(counter.count(&1), 1);
+        assert_eq!(counter.count(&3), 1);
+        assert_eq!(counter.count(&4), 3);
+        assert_eq!(counter.keys_len(), 4);
+
+        counter += HashCounter::new();
+        assert_eq!(counter.keys_len(), 4);
+        let mut most_common = counter.most_common(2);
+        most_common.sort();
+        assert_eq!(most_common, vec![(2, 3), (4, 3)]);
+        assert_eq!(counter.most_common(0), vec![]);
+    }
 }
@@ -16,13 +16,18 @@ pub struct HashUnionFindSets<T: Eq + std::hash::Hash + std::fmt::Debug> {
     // but that frees users from maintaining it
     // by checking the returned values for all `add` and `unite` operations.
     set_count: usize,
-    items: std::collections::HashMap<T, UnionFindNode>
+    // Items are shared with `members`.
+    items: std::collections::HashMap<std::rc::Rc<T>, UnionFindNode>,
+    // Map from each root to the items in its set.
+    members: std::collections::HashMap<UnionFindNode, Vec<std::rc::Rc<T>>>
 }
 
 #[derive(Clone)]
 enum UnionFindNodeInner {
     Root {
         len: usize,
+        // Counted by `unite_counting`.
+        edges: usize
     },
     Child {
         parent: UnionFindNode
@@ -35,9 +40,17 @@ struct UnionFindNode(std::rc::Rc<std::cell::RefCell<UnionFindNodeInner>>);
 impl UnionFindNode {
     fn new() -> UnionFindNode {
         UnionFindNode(std::rc::Rc::new(std::cell::RefCell::new(
-            UnionFindNodeInner::Root { len: 1 }
+            UnionFindNodeInner::Root { len: 1, edges: 0 }
         )))
     }
+
+    // Valid only for roots.
+    fn edges(&self) -> usize {
+        match *self.0.borrow() {
+            UnionFindNodeInner::Root { edges, .. } => edges,
+            UnionFindNodeInner::Child { .. } => unreachable!()
+        }
+    }
 }
 
 impl std::cmp::PartialEq for UnionFindNode {
@@ -62,7 +75,8 @@ impl<T: Eq + std::hash::Hash + std::fmt::Debug> HashUnionFindSets<T> {
     pub fn new() -> HashUnionFindSets<T> {
         HashUnionFindSets {
             set_count: 0,
-            items: std::collections::HashMap::new()
+            items: std::collections::HashMap::new(),
+            members: std::collections::HashMap::new()
         }
     }
 
@@ -94,7 +108,10 @@ impl<T: Eq + std::hash::Hash + std::fmt::Debug> HashUnionFindSets<T> {
             false
         } else {
             self.set_count += 1;
-            self.items.insert(item, UnionFindNode::new());
+            let item = std::rc::Rc::new(item);
+            let node = UnionFindNode::new();
+            self.items.insert(item.clone(), node.clone());
+            self.members.insert(node, vec![item]);
             true
         }
     }
@@ -118,7 +135,7 @@ impl<T: Eq + std::hash::Hash + std::fmt::Debug> HashUnionFindSets<T> {
         fn go(node: UnionFindNode) -> (UnionFindNode, usize) {
             let inner = node.0.as_ref().clone().into_inner();
             match inner {
-                UnionFindNodeInner::Root { len } => (node, len),
+                UnionFindNodeInner::Root { len, .. } => (node, len),
                 UnionFindNodeInner::Child { parent } => {
                     let (root, len) = go(parent);
                     let mut borrowed = node.0.borrow_mut();
@@ -206,13 +223,18 @@ impl<T: Eq + std::hash::Hash + std::fmt::Debug> HashUnionFindSets<T> {
                     Ok(false)
                 } else {
                     self.set_count -= 1;
-                    let (mut root, mut child, root_node) = if len1 < len2 {
-                        (root2.0.borrow_mut(), root1.0.borrow_mut(), &root2)
+                    let edges = root1.edges() + root2.edges();
+                    let (root_node, child_node) = if len1 < len2 {
+                        (root2, root1)
                     } else {
-                        (root1.0.borrow_mut(), root2.0.borrow_mut(), &root1)
+                        (root1, root2)
                     };
-                    *root = UnionFindNodeInner::Root { len: len1 + len2 };
-                    *child = UnionFindNodeInner::Child { parent: root_node.clone() };
+                    *root_node.0.borrow_mut() = UnionFindNodeInner::Root { len: len1 + len2, edges };
+                    *child_node.0.borrow_mut() = UnionFindNodeInner::Child { parent: root_node.clone() };
+
+                    // Moves members of the smaller set into the larger one.
+                    let child_members = self.members.remove(&child_node).unwrap();
+                    self.members.get_mut(&root_node).unwrap().extend(child_members);
                     Ok(true)
                 }
             },
@@ -221,20 +243,117 @@ impl<T: Eq + std::hash::Hash + std::fmt::Debug> HashUnionFindSets<T> {
             (None, None) => Err(HashUnionFindSets::error_msg(&[item1, item2]))
         }
     }
+
+    /// Merges two sets like [`unite`](#method.unite), and counts an edge between `item1` and `item2`
+    /// for the merged set.
+    ///
+    /// The edge is counted even if the two sets are the same,
+    /// including the case `item1` and `item2` are the same.
+    /// Each set has the number of edges counted for the items in the set,
+    /// which is returned by [`edges_of`](#method.edges_of).
+    ///
+    /// If no set contains `item1` or `item2`, returns `Err` with an error message,
+    /// and no edge is counted.
+    ///
+    /// # Example
+    ///
+    /// Whether each connected component of a graph is a tree.
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::hash_union_find_sets::*;
+    /// let mut sets: HashUnionFindSets<char> = "abcde".chars().collect();
+    /// for &(u, v) in &[('a', 'b'), ('b', 'c'), ('d', 'e')] {
+    ///     sets.unite_counting(&u, &v).unwrap();
+    /// }
+    /// let is_forest = |sets: &HashUnionFindSets<char>| {
+    ///     "abcde".chars().all(|x| sets.edges_of(&x).unwrap() + 1 == sets.len_of(&x).unwrap())
+    /// };
+    /// assert!(is_forest(&sets));
+    ///
+    /// sets.unite_counting(&'c', &'a').unwrap();
+    /// assert!(!is_forest(&sets));
+    /// ```
+    pub fn unite_counting(&mut self, item1: &T, item2: &T) -> Result<bool, String> {
+        let united = self.unite(item1, item2)?;
+        let (root, _) = self.find(item1).unwrap();
+        let mut inner = root.0.borrow_mut();
+        if let UnionFindNodeInner::Root { ref mut edges, .. } = *inner {
+            *edges += 1;
+        }
+        Ok(united)
+    }
+
+    /// Returns how many edges are counted by [`unite_counting`](#method.unite_counting)
+    /// for the set which has `item`.
+    ///
+    /// If no set contains `item`, returns `Err` with an error message.
+    pub fn edges_of(&self, item: &T) -> Result<usize, String> {
+        self.find(item).map(|(root, _)| root.edges()).ok_or_else(|| {
+            HashUnionFindSets::error_msg(&[item])
+        })
+    }
+
+    /// Returns all the items of the set containing `item`, in unspecified order.
+    ///
+    /// It takes time proportional to the size of the set, not to the number of all the items.
+    ///
+    /// If no set contains `item`, returns `Err` with an error message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::hash_union_find_sets::*;
+    /// let mut sets: HashUnionFindSets<char> = "abcd".chars().collect();
+    /// sets.unite(&'a', &'c').unwrap();
+    ///
+    /// let mut members = sets.members_of(&'c').unwrap();
+    /// members.sort();
+    /// assert_eq!(members, vec![&'a', &'c']);
+    /// assert!(sets.members_of(&'e').is_err());
+    /// ```
+    pub fn members_of(&self, item: &T) -> Result<Vec<&T>, String> {
+        let (root, _) = self.find(item).ok_or_else(|| HashUnionFindSets::error_msg(&[item]))?;
+        Ok(self.members[&root].iter().map(|member| &**member).collect())
+    }
+
+    /// Returns all the sets, each of which is a vector of items.
+    ///
+    /// The order of sets and the order of items in each set are unspecified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::hash_union_find_sets::*;
+    /// let mut sets: HashUnionFindSets<i32> = (0..5).collect();
+    /// sets.unite(&0, &3).unwrap();
+    /// sets.unite(&1, &4).unwrap();
+    ///
+    /// let mut groups: Vec<Vec<i32>> = sets.groups()
+    ///     .into_iter()
+    ///     .map(|group| {
+    ///         let mut group: Vec<i32> = group.into_iter().cloned().collect();
+    ///         group.sort();
+    ///         group
+    ///     })
+    ///     .collect();
+    /// groups.sort();
+    /// assert_eq!(groups, vec![vec![0, 3], vec![1, 4], vec![2]]);
+    /// ```
+    pub fn groups(&self) -> Vec<Vec<&T>> {
+        self.members.values()
+            .map(|members| members.iter().map(|member| &**member).collect())
+            .collect()
+    }
 }
 
 impl<T: Eq + std::hash::Hash + std::fmt::Debug> std::fmt::Debug for HashUnionFindSets<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        use std::collections::{HashMap, HashSet};
-
-        let mut root_to_set = HashMap::new();
-        for item in self.items.keys() {
-            let root = self.find(item);
-            let set = root_to_set.entry(root).or_insert(HashSet::new());
-            set.insert(item);
-        }
+        use std::collections::HashSet;
 
-        let sets: Vec<HashSet<&T>> = root_to_set.into_iter().map(|(_, v)| v).collect();
+        let sets: Vec<HashSet<&T>> = self.groups()
+            .into_iter()
+            .map(|group| group.into_iter().collect())
+            .collect();
         if sets.len() == 0 {
             write!(f, "{{}}")
         } else {
@@ -262,13 +381,11 @@ impl<T: Eq + std::hash::Hash + std::fmt::Debug> std::iter::FromIterator<T>
     /// assert_eq!(sets.items_len(), 3);
     /// ```
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> HashUnionFindSets<T> {
-        let items = iter.into_iter()
-            .map(|x| (x, UnionFindNode::new()))
-            .collect::<std::collections::HashMap<_, _>>();
-        HashUnionFindSets {
-            set_count: items.len(),
-            items
+        let mut sets = HashUnionFindSets::new();
+        for item in iter {
+            sets.add(item);
         }
+        sets
     }
 }
 
@@ -370,6 +487,62 @@ mod tests {
         assert_eq!(sets.count(), 3);
     }
 
+    #[test]
+    fn test_members() {
+        use std::collections::HashSet;
+
+        let mut sets: HashUnionFindSets<i32> = (0..10).collect();
+        sets.unite(&0, &1).unwrap();
+        sets.unite(&2, &3).unwrap();
+        sets.unite(&1, &3).unwrap();
+        sets.unite(&5, &6).unwrap();
+        sets.unite(&6, &5).unwrap();
+        sets.unite(&7, &5).unwrap();
+
+        let members = |item: i32| -> HashSet<i32> {
+            sets.members_of(&item).unwrap().into_iter().cloned().collect()
+        };
+        let expected: HashSet<i32> = vec![0, 1, 2, 3].into_iter().collect();
+        for i in 0..4 {
+            assert_eq!(members(i), expected);
+        }
+        assert_eq!(members(6), vec![5, 6, 7].into_iter().collect());
+        assert_eq!(members(9), vec![9].into_iter().collect());
+
+        let groups = sets.groups();
+        assert_eq!(groups.len(), sets.count());
+        let mut all: Vec<i32> = groups.iter().flatten().map(|&&item| item).collect();
+        all.sort();
+        assert_eq!(all, (0..10).collect::<Vec<_>>());
+        for group in &groups {
+            for &item in group {
+                assert_eq!(sets.len_of(item).unwrap(), group.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_edges() {
+        let mut sets: HashUnionFindSets<i32> = (0..5).collect();
+        assert_eq!(sets.edges_of(&0), Ok(0));
+        assert!(sets.unite_counting(&0, &1).unwrap());
+        assert!(!sets.unite_counting(&1, &0).unwrap());
+        assert!(!sets.unite_counting(&1, &1).unwrap());
+        assert_eq!(sets.edges_of(&0), Ok(3));
+        assert_eq!(sets.len_of(&0), Ok(2));
+
+        sets.unite_counting(&2, &3).unwrap();
+        sets.unite(&3, &0).unwrap();
+        assert_eq!(sets.edges_of(&2), Ok(4));
+        assert_eq!(sets.edges_of(&1), Ok(4));
+        assert_eq!(sets.len_of(&1), Ok(4));
+        assert_eq!(sets.edges_of(&4), Ok(0));
+
+        assert!(sets.unite_counting(&4, &5).is_err());
+        assert_eq!(sets.edges_of(&4), Ok(0));
+        assert!(sets.edges_of(&5).is_err());
+    }
+
     #[test]
     fn test_count_from_iterator() {
         let sets: HashUnionFindSets<i32> = (0..20).collect();
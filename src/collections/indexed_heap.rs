@@ -0,0 +1,241 @@
+//! Minimum heap of keys indexed by ids, supporting decrease-key.
+
+// BEGIN SNIPPET indexed_heap
+
+/// Minimum heap of keys indexed by ids `0..n`, supporting decrease-key.
+///
+/// Each id is contained at most once.
+/// It is a binary heap with the map from ids to positions in the heap,
+/// so each of `push_or_decrease` and `pop` is performed in O(log(*n*)) time.
+///
+/// # Example
+///
+/// Dijkstra's algorithm without duplicate entries in the heap.
+///
+/// ```
+/// # use atcoder_snippets::collections::indexed_heap::*;
+/// // gets shortest distances for each node from node 0
+/// fn dijkstra(adjacency_list: &[Vec<(usize, u32)>]) -> Vec<Option<u32>> {
+///     let mut distances = vec![None; adjacency_list.len()];
+///     let mut queue = IndexedHeap::new(adjacency_list.len());
+///     queue.push_or_decrease(0, 0);
+///
+///     while let Some((node, distance)) = queue.pop() {
+///         distances[node] = Some(distance);
+///         for &(next, cost) in &adjacency_list[node] {
+///             if distances[next].is_none() {
+///                 queue.push_or_decrease(next, distance + cost);
+///             }
+///         }
+///     }
+///     distances
+/// }
+///
+/// let adjacency_list = vec![
+///     vec![(1, 4), (2, 1)],
+///     vec![(3, 1)],
+///     vec![(1, 2), (3, 5)],
+///     vec![],
+///     vec![(0, 1)]
+/// ];
+/// assert_eq!(dijkstra(&adjacency_list), vec![Some(0), Some(3), Some(1), Some(4), None]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IndexedHeap<K> {
+    heap: Vec<(K, usize)>,
+    positions: Vec<Option<usize>>
+}
+
+impl<K: Ord> IndexedHeap<K> {
+    /// Creates an empty heap for ids `0..n`.
+    pub fn new(n: usize) -> IndexedHeap<K> {
+        IndexedHeap {
+            heap: Vec::new(),
+            positions: vec![None; n]
+        }
+    }
+
+    /// The number of ids in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns whether the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns whether the heap contains `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is out of range.
+    pub fn contains(&self, id: usize) -> bool {
+        self.positions[id].is_some()
+    }
+
+    /// Gets the key of `id`, or `None` if the heap doesn't contain `id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is out of range.
+    pub fn key_of(&self, id: usize) -> Option<&K> {
+        self.positions[id].map(|pos| &self.heap[pos].0)
+    }
+
+    /// Gets the id with the minimum key and the key, without removing them.
+    pub fn peek(&self) -> Option<(usize, &K)> {
+        self.heap.first().map(|&(ref key, id)| (id, key))
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.heap.swap(i, j);
+        self.positions[self.heap[i].1] = Some(i);
+        self.positions[self.heap[j].1] = Some(j);
+    }
+
+    fn sift_up(&mut self, mut pos: usize) {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if self.heap[parent].0 <= self.heap[pos].0 {
+                break;
+            }
+            self.swap(parent, pos);
+            pos = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut pos: usize) {
+        loop {
+            let mut min = pos;
+            for child in 2 * pos + 1..std::cmp::min(2 * pos + 3, self.heap.len()) {
+                if self.heap[child].0 < self.heap[min].0 {
+                    min = child;
+                }
+            }
+            if min == pos {
+                break;
+            }
+            self.swap(pos, min);
+            pos = min;
+        }
+    }
+
+    /// Pushes `id` with `key` if the heap doesn't contain `id`,
+    /// or decreases the key of `id` to `key` if `key` is less than the current key.
+    ///
+    /// Returns whether the heap is changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::indexed_heap::*;
+    /// let mut heap = IndexedHeap::new(3);
+    /// assert!(heap.push_or_decrease(1, 10));
+    /// assert!(!heap.push_or_decrease(1, 20));
+    /// assert!(heap.push_or_decrease(1, 5));
+    /// assert_eq!(heap.key_of(1), Some(&5));
+    /// assert_eq!(heap.len(), 1);
+    /// ```
+    pub fn push_or_decrease(&mut self, id: usize, key: K) -> bool {
+        match self.positions[id] {
+            Some(pos) => {
+                if key >= self.heap[pos].0 {
+                    return false;
+                }
+                self.heap[pos].0 = key;
+                self.sift_up(pos);
+            }
+            None => {
+                let pos = self.heap.len();
+                self.heap.push((key, id));
+                self.positions[id] = Some(pos);
+                self.sift_up(pos);
+            }
+        }
+        true
+    }
+
+    /// Removes the id with the minimum key, and returns the id and the key.
+    ///
+    /// If two or more ids have the minimum key, which one is removed is unspecified.
+    pub fn pop(&mut self) -> Option<(usize, K)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let (key, id) = self.heap.pop().unwrap();
+        self.positions[id] = None;
+        self.sift_down(0);
+        Some((id, key))
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_random_operations() {
+        let mut rng = Xorshift::with_seed(1013);
+        let n = 30;
+        let mut heap = IndexedHeap::new(n);
+        let mut keys: Vec<Option<i64>> = vec![None; n];
+        let mut naive: BTreeSet<(i64, usize)> = BTreeSet::new();
+
+        for _ in 0..10000 {
+            if rng.gen_range(0..3) < 2 {
+                let id = rng.gen_range(0..n as u64) as usize;
+                let key = rng.gen_range_i64(-100..100);
+                let changes = keys[id].map_or(true, |old| key < old);
+                assert_eq!(heap.push_or_decrease(id, key), changes);
+                if changes {
+                    if let Some(old) = keys[id] {
+                        naive.remove(&(old, id));
+                    }
+                    keys[id] = Some(key);
+                    naive.insert((key, id));
+                }
+            } else {
+                let expected_key = naive.iter().next().map(|&(key, _)| key);
+                match heap.pop() {
+                    Some((id, key)) => {
+                        assert_eq!(Some(key), expected_key);
+                        assert!(naive.remove(&(key, id)));
+                        keys[id] = None;
+                    }
+                    None => assert!(naive.is_empty())
+                }
+            }
+
+            assert_eq!(heap.len(), naive.len());
+            assert_eq!(heap.peek().map(|(_, &key)| key), naive.iter().next().map(|&(key, _)| key));
+            for id in 0..n {
+                assert_eq!(heap.contains(id), keys[id].is_some());
+                assert_eq!(heap.key_of(id), keys[id].as_ref());
+            }
+        }
+    }
+
+    #[test]
+    fn test_pop_all_sorted() {
+        let mut heap = IndexedHeap::new(6);
+        for (id, &key) in [5, 3, 8, 1, 9, 2].iter().enumerate() {
+            heap.push_or_decrease(id, key);
+        }
+        heap.push_or_decrease(4, 0);
+        let popped: Vec<(usize, i32)> = std::iter::from_fn(|| heap.pop()).collect();
+        assert_eq!(popped, vec![(4, 0), (3, 1), (5, 2), (1, 3), (0, 5), (2, 8)]);
+        assert!(heap.is_empty());
+    }
+}
@@ -0,0 +1,429 @@
+//! Segment tree with range update by lazy propagation.
+//!
+//! The design follows [AtCoder Library](https://atcoder.github.io/ac-library/production/document_en/lazysegtree.html).
+
+use crate::range::UsizeRangeBoundsExt;
+
+// BEGIN SNIPPET lazy_segment_tree DEPENDS ON range
+
+/// Sequence updatable by interval and aggregatable by interval.
+///
+/// The tree is defined by the following:
+///
+/// - Items of type `T`, which are monoidal under `aggregate` with the identity `identity`,
+///   as items of [`SegmentTree`](../segment_tree/struct.SegmentTree.html).
+/// - Operators of type `U` applied to items by `mapping(&op, &item)`.
+/// - `composition(&new_op, &old_op)`, the operator equivalent to applying `old_op` and then `new_op`.
+///   Operators are monoidal under `composition` with the identity `op_identity`,
+///   which changes no items.
+///
+/// In addition, applying an operator to an aggregation must be the same as
+/// aggregating the items each of which the operator is applied to.
+///
+/// [`range_add_range_sum`](fn.range_add_range_sum.html) and
+/// [`range_assign_range_min`](fn.range_assign_range_min.html) create trees
+/// for common combinations.
+///
+/// Any update and aggregation is performed in Θ(log(*n*)) time,
+/// as *n* is the number of items in the sequence.
+///
+/// # Example
+///
+/// Range-add and range-max query.
+///
+/// ```
+/// # use atcoder_snippets::collections::lazy_segment_tree::*;
+/// let mut tree = LazySegmentTree::from_vec(
+///     vec![3, 1, 4, 1, 5],
+///     std::i64::MIN, |&a: &i64, &b: &i64| a.max(b),
+///     0, |&op: &i64, &x: &i64| if x == std::i64::MIN { x } else { x + op }, |&f, &g| f + g
+/// );
+/// assert_eq!(tree.query(..), Some(5));
+/// tree.apply(1..4, 3);
+/// assert_eq!(tree.query(..), Some(7));
+/// assert_eq!(tree.query(..3), Some(7));
+/// assert_eq!(tree.query(0..1), Some(3));
+/// assert_eq!(tree.query(2..2), Some(std::i64::MIN));
+/// assert_eq!(tree.get(3), Some(4));
+/// ```
+pub struct LazySegmentTree<T, U, Agg, Map, Comp> {
+    len: usize,
+    log: usize,
+    // `data[size..size+len]` are the items, and `data[k]` is the aggregation of
+    // `data[2*k]` and `data[2*k+1]` for `1 <= k < size`.
+    data: Vec<T>,
+    // Operators to be applied to the children of each node.
+    lazy: Vec<U>,
+    identity: T,
+    op_identity: U,
+    aggregate: Agg,
+    mapping: Map,
+    composition: Comp
+}
+
+impl<T, U, Agg, Map, Comp> LazySegmentTree<T, U, Agg, Map, Comp>
+where
+    T: Clone,
+    U: Clone,
+    Agg: Fn(&T, &T) -> T,
+    Map: Fn(&U, &T) -> T,
+    Comp: Fn(&U, &U) -> U
+{
+    /// Creates a new tree with `len` items, all of which are `identity`.
+    pub fn new(
+        len: usize,
+        identity: T, aggregate: Agg,
+        op_identity: U, mapping: Map, composition: Comp
+    ) -> LazySegmentTree<T, U, Agg, Map, Comp> {
+        let items = vec![identity.clone(); len];
+        LazySegmentTree::from_vec(items, identity, aggregate, op_identity, mapping, composition)
+    }
+
+    /// Creates a new tree from items in a vector.
+    pub fn from_vec(
+        items: Vec<T>,
+        identity: T, aggregate: Agg,
+        op_identity: U, mapping: Map, composition: Comp
+    ) -> LazySegmentTree<T, U, Agg, Map, Comp> {
+        let len = items.len();
+        let size = len.next_power_of_two();
+        let mut data = vec![identity.clone(); size];
+        data.extend(items);
+        data.resize(size * 2, identity.clone());
+        let mut tree = LazySegmentTree {
+            len,
+            log: size.trailing_zeros() as usize,
+            data,
+            lazy: vec![op_identity.clone(); size],
+            identity,
+            op_identity,
+            aggregate,
+            mapping,
+            composition
+        };
+        for k in (1..size).rev() {
+            tree.update(k);
+        }
+        tree
+    }
+
+    fn size(&self) -> usize {
+        self.lazy.len()
+    }
+
+    /// The number of items.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the tree has no items.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn update(&mut self, k: usize) {
+        self.data[k] = (self.aggregate)(&self.data[2 * k], &self.data[2 * k + 1]);
+    }
+
+    fn apply_to_node(&mut self, k: usize, op: &U) {
+        self.data[k] = (self.mapping)(op, &self.data[k]);
+        if k < self.size() {
+            self.lazy[k] = (self.composition)(op, &self.lazy[k]);
+        }
+    }
+
+    fn push(&mut self, k: usize) {
+        let op = std::mem::replace(&mut self.lazy[k], self.op_identity.clone());
+        self.apply_to_node(2 * k, &op);
+        self.apply_to_node(2 * k + 1, &op);
+    }
+
+    // Pushes the operators of the ancestors of the leaf `index`, from the root.
+    fn push_ancestors(&mut self, index: usize) {
+        let leaf = index + self.size();
+        for i in (1..=self.log).rev() {
+            self.push(leaf >> i);
+        }
+    }
+
+    /// Gets the item at `index`, or `None` if the index is out of bound.
+    pub fn get(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        self.push_ancestors(index);
+        Some(self.data[index + self.size()].clone())
+    }
+
+    /// Sets the item at `index` to `item`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bound.
+    pub fn set(&mut self, index: usize, item: T) {
+        assert!(index < self.len, "index {} is out of bound: the length is {}", index, self.len);
+
+        self.push_ancestors(index);
+        let leaf = index + self.size();
+        self.data[leaf] = item;
+        for i in 1..=self.log {
+            self.update(leaf >> i);
+        }
+    }
+
+    /// Aggregates items in `range`.
+    ///
+    /// If the range is out of bound, returns `None`.
+    pub fn query<R: std::ops::RangeBounds<usize>>(&mut self, range: R) -> Option<T> {
+        let range = range.to_range(self.len)?;
+        if range.start == range.end {
+            return Some(self.identity.clone());
+        }
+
+        let mut l = range.start + self.size();
+        let mut r = range.end + self.size();
+        for i in (1..=self.log).rev() {
+            if (l >> i) << i != l {
+                self.push(l >> i);
+            }
+            if (r >> i) << i != r {
+                self.push((r - 1) >> i);
+            }
+        }
+
+        let mut acc_left = self.identity.clone();
+        let mut acc_right = self.identity.clone();
+        while l < r {
+            if l & 1 == 1 {
+                acc_left = (self.aggregate)(&acc_left, &self.data[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                acc_right = (self.aggregate)(&self.data[r], &acc_right);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        Some((self.aggregate)(&acc_left, &acc_right))
+    }
+
+    /// Applies `op` to each item in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bound.
+    pub fn apply<R: std::ops::RangeBounds<usize>>(&mut self, range: R, op: U) {
+        let range = range.to_range(self.len).expect("range is out of bound");
+        if range.start == range.end {
+            return;
+        }
+
+        let l = range.start + self.size();
+        let r = range.end + self.size();
+        for i in (1..=self.log).rev() {
+            if (l >> i) << i != l {
+                self.push(l >> i);
+            }
+            if (r >> i) << i != r {
+                self.push((r - 1) >> i);
+            }
+        }
+
+        {
+            let mut l = l;
+            let mut r = r;
+            while l < r {
+                if l & 1 == 1 {
+                    self.apply_to_node(l, &op);
+                    l += 1;
+                }
+                if r & 1 == 1 {
+                    r -= 1;
+                    self.apply_to_node(r, &op);
+                }
+                l >>= 1;
+                r >>= 1;
+            }
+        }
+
+        for i in 1..=self.log {
+            if (l >> i) << i != l {
+                self.update(l >> i);
+            }
+            if (r >> i) << i != r {
+                self.update((r - 1) >> i);
+            }
+        }
+    }
+}
+
+/// Tree created by [`range_add_range_sum`](fn.range_add_range_sum.html).
+///
+/// Each item is a pair of the sum and the length of an interval.
+pub type RangeAddRangeSum = LazySegmentTree<
+    (i64, usize), i64,
+    fn(&(i64, usize), &(i64, usize)) -> (i64, usize),
+    fn(&i64, &(i64, usize)) -> (i64, usize),
+    fn(&i64, &i64) -> i64
+>;
+
+/// Creates a tree adding a value to items in a range, and getting the sum of items in a range.
+///
+/// Each item of the tree is a pair of the sum and the length of an interval,
+/// so the sum is the first element of the result of `query`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::lazy_segment_tree::*;
+/// let mut tree = range_add_range_sum(vec![1, 2, 3, 4, 5]);
+/// tree.apply(1..=3, 10);
+/// assert_eq!(tree.query(..).unwrap().0, 45);
+/// assert_eq!(tree.query(3..).unwrap().0, 19);
+/// ```
+pub fn range_add_range_sum(items: Vec<i64>) -> RangeAddRangeSum {
+    LazySegmentTree::from_vec(
+        items.into_iter().map(|x| (x, 1)).collect(),
+        (0, 0), |&(sum1, len1), &(sum2, len2)| (sum1 + sum2, len1 + len2),
+        0, |&add, &(sum, len)| (sum + add * len as i64, len), |&add1, &add2| add1 + add2
+    )
+}
+
+/// Tree created by [`range_assign_range_min`](fn.range_assign_range_min.html).
+pub type RangeAssignRangeMin = LazySegmentTree<
+    i64, Option<i64>,
+    fn(&i64, &i64) -> i64,
+    fn(&Option<i64>, &i64) -> i64,
+    fn(&Option<i64>, &Option<i64>) -> Option<i64>
+>;
+
+/// Creates a tree assigning a value to items in a range, and getting the minimum in a range.
+///
+/// The minimum of an empty range is `std::i64::MAX`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::lazy_segment_tree::*;
+/// let mut tree = range_assign_range_min(vec![5, 3, 8, 6]);
+/// assert_eq!(tree.query(..), Some(3));
+/// tree.apply(..2, Some(7));
+/// assert_eq!(tree.query(..), Some(6));
+/// assert_eq!(tree.query(..2), Some(7));
+/// ```
+pub fn range_assign_range_min(items: Vec<i64>) -> RangeAssignRangeMin {
+    LazySegmentTree::from_vec(
+        items,
+        std::i64::MAX, |&a, &b| std::cmp::min(a, b),
+        None, |&assign, &x| assign.unwrap_or(x), |&new, &old| new.or(old)
+    )
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+
+    fn random_range(rng: &mut Xorshift, len: usize) -> std::ops::Range<usize> {
+        let a = rng.gen_range(0..=len as u64) as usize;
+        let b = rng.gen_range(0..=len as u64) as usize;
+        std::cmp::min(a, b)..std::cmp::max(a, b)
+    }
+
+    #[test]
+    fn test_range_add_range_sum() {
+        let mut rng = Xorshift::with_seed(1004);
+        for _ in 0..50 {
+            let len = rng.gen_range(0..40) as usize;
+            let mut naive: Vec<i64> = (0..len).map(|_| rng.gen_range_i64(-100..100)).collect();
+            let mut tree = range_add_range_sum(naive.clone());
+            assert_eq!(tree.len(), len);
+
+            for _ in 0..100 {
+                let range = random_range(&mut rng, len);
+                match rng.gen_range(0..4) {
+                    0 => {
+                        let add = rng.gen_range_i64(-100..100);
+                        tree.apply(range.clone(), add);
+                        for x in &mut naive[range] {
+                            *x += add;
+                        }
+                    }
+                    1 if len > 0 => {
+                        let i = rng.gen_range(0..len as u64) as usize;
+                        let x = rng.gen_range_i64(-100..100);
+                        tree.set(i, (x, 1));
+                        naive[i] = x;
+                    }
+                    2 if len > 0 => {
+                        let i = rng.gen_range(0..len as u64) as usize;
+                        assert_eq!(tree.get(i), Some((naive[i], 1)));
+                    }
+                    _ => {
+                        let expected: i64 = naive[range.clone()].iter().sum();
+                        assert_eq!(tree.query(range.clone()), Some((expected, range.len())));
+                    }
+                }
+            }
+            assert_eq!(tree.get(len), None);
+            assert_eq!(tree.query(..=len), None);
+        }
+    }
+
+    #[test]
+    fn test_range_assign_range_min() {
+        let mut rng = Xorshift::with_seed(1004);
+        for _ in 0..50 {
+            let len = rng.gen_range(0..40) as usize;
+            let mut naive: Vec<i64> = (0..len).map(|_| rng.gen_range_i64(-100..100)).collect();
+            let mut tree = range_assign_range_min(naive.clone());
+
+            for _ in 0..100 {
+                let range = random_range(&mut rng, len);
+                match rng.gen_range(0..4) {
+                    0 => {
+                        let x = rng.gen_range_i64(-100..100);
+                        tree.apply(range.clone(), Some(x));
+                        for y in &mut naive[range] {
+                            *y = x;
+                        }
+                    }
+                    1 if len > 0 => {
+                        let i = rng.gen_range(0..len as u64) as usize;
+                        let x = rng.gen_range_i64(-100..100);
+                        tree.set(i, x);
+                        naive[i] = x;
+                    }
+                    2 if len > 0 => {
+                        let i = rng.gen_range(0..len as u64) as usize;
+                        assert_eq!(tree.get(i), Some(naive[i]));
+                    }
+                    _ => {
+                        let expected = naive[range.clone()].iter().cloned().min().unwrap_or(std::i64::MAX);
+                        assert_eq!(tree.query(range), Some(expected));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_identity() {
+        let mut tree = range_assign_range_min(vec![3, 1, 2]);
+        tree.apply(.., None);
+        tree.apply(1..1, Some(0));
+        assert_eq!(tree.query(..), Some(1));
+        assert_eq!((0..3).map(|i| tree.get(i).unwrap()).collect::<Vec<_>>(), vec![3, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "range is out of bound")]
+    fn test_apply_out_of_bound() {
+        let mut tree = range_add_range_sum(vec![0; 3]);
+        tree.apply(2..4, 1);
+    }
+}
@@ -0,0 +1,309 @@
+//! Li Chao tree, getting the minimum of linear functions.
+
+// BEGIN SNIPPET li_chao_tree
+
+#[derive(Clone, Debug)]
+enum LiChaoCoords {
+    Values(Vec<i64>),
+    Range { start: i64, len: usize }
+}
+
+/// Set of lines and segments `a*x + b` answering the minimum at `x`,
+/// for fixed `x` coordinates known in advance.
+///
+/// Unlike [`ConvexHullTrick`](../convex_hull_trick/struct.ConvexHullTrick.html),
+/// segments defined only on a range of `x` can be added.
+///
+/// Adding a line and a query are performed in O(log(*n*)) time,
+/// and adding a segment is in O(log(*n*)^2) time,
+/// as *n* is the number of coordinates.
+/// Values of lines are compared in `i128`, so any `i64` coefficients are allowed,
+/// as far as the minimums at queried `x` fit in `i64`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::li_chao_tree::*;
+/// let mut tree = LiChaoTree::from_range(-5..5);
+/// assert_eq!(tree.query(0), None);
+/// tree.add_line(1, 0);
+/// tree.add_segment(-1, -3, 0..=2);
+/// assert_eq!(tree.query(-3), Some(-3));
+/// assert_eq!(tree.query(1), Some(-4));
+/// assert_eq!(tree.query(3), Some(3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct LiChaoTree {
+    coords: LiChaoCoords,
+    // 1-indexed heap of nodes, each of which holds the line being the minimum
+    // at the middle of its interval among lines added to the node.
+    lines: Vec<Option<(i64, i64)>>
+}
+
+fn li_chao_eval((a, b): (i64, i64), x: i64) -> i128 {
+    a as i128 * x as i128 + b as i128
+}
+
+impl LiChaoTree {
+    fn with_coords(coords: LiChaoCoords) -> LiChaoTree {
+        let len = match coords {
+            LiChaoCoords::Values(ref xs) => xs.len(),
+            LiChaoCoords::Range { len, .. } => len
+        };
+        LiChaoTree {
+            coords,
+            lines: vec![None; 4 * len]
+        }
+    }
+
+    /// Creates an empty tree whose query coordinates are `xs`.
+    ///
+    /// `xs` needs not to be sorted nor deduplicated.
+    pub fn new(xs: &[i64]) -> LiChaoTree {
+        let mut xs = xs.to_vec();
+        xs.sort();
+        xs.dedup();
+        LiChaoTree::with_coords(LiChaoCoords::Values(xs))
+    }
+
+    /// Creates an empty tree whose query coordinates are all the integers in `range`.
+    ///
+    /// The memory of O(`range.len()`) is used.
+    pub fn from_range(range: std::ops::Range<i64>) -> LiChaoTree {
+        let len = if range.start < range.end { (range.end - range.start) as usize } else { 0 };
+        LiChaoTree::with_coords(LiChaoCoords::Range { start: range.start, len })
+    }
+
+    fn len(&self) -> usize {
+        self.lines.len() / 4
+    }
+
+    fn x(&self, index: usize) -> i64 {
+        match self.coords {
+            LiChaoCoords::Values(ref xs) => xs[index],
+            LiChaoCoords::Range { start, .. } => start + index as i64
+        }
+    }
+
+    // The number of coordinates less than `x`.
+    fn lower_bound(&self, x: i64) -> usize {
+        match self.coords {
+            LiChaoCoords::Values(ref xs) => xs.binary_search(&x).unwrap_or_else(|i| i),
+            LiChaoCoords::Range { start, len } => {
+                if x <= start {
+                    0
+                } else {
+                    std::cmp::min((x as i128 - start as i128) as u128, len as u128) as usize
+                }
+            }
+        }
+    }
+
+    // The number of coordinates less than or equal to `x`.
+    fn upper_bound(&self, x: i64) -> usize {
+        match x.checked_add(1) {
+            Some(x) => self.lower_bound(x),
+            None => self.len()
+        }
+    }
+
+    fn add_line_to_node(&mut self, mut node: usize, mut l: usize, mut r: usize, mut line: (i64, i64)) {
+        loop {
+            let current = match self.lines[node] {
+                Some(current) => current,
+                None => {
+                    self.lines[node] = Some(line);
+                    return;
+                }
+            };
+
+            let m = (l + r) / 2;
+            let (lx, mx) = (self.x(l), self.x(m));
+            let left_better = li_chao_eval(line, lx) < li_chao_eval(current, lx);
+            let mid_better = li_chao_eval(line, mx) < li_chao_eval(current, mx);
+            if mid_better {
+                self.lines[node] = Some(line);
+                line = current;
+            }
+
+            if l == r {
+                return;
+            }
+            if left_better != mid_better {
+                node *= 2;
+                r = m;
+            } else {
+                node = node * 2 + 1;
+                l = m + 1;
+            }
+        }
+    }
+
+    // Adds the line to nodes covering [from, to) in the subtree of `node` covering [l, r].
+    fn add_segment_to_node(&mut self, node: usize, l: usize, r: usize, from: usize, to: usize, line: (i64, i64)) {
+        if to <= l || r < from {
+            return;
+        }
+        if from <= l && r < to {
+            self.add_line_to_node(node, l, r, line);
+            return;
+        }
+        let m = (l + r) / 2;
+        self.add_segment_to_node(node * 2, l, m, from, to, line);
+        self.add_segment_to_node(node * 2 + 1, m + 1, r, from, to, line);
+    }
+
+    /// Adds a line `a*x + b`.
+    pub fn add_line(&mut self, a: i64, b: i64) {
+        if self.len() > 0 {
+            let r = self.len() - 1;
+            self.add_line_to_node(1, 0, r, (a, b));
+        }
+    }
+
+    /// Adds a segment `a*x + b` defined for `x` in `range`.
+    pub fn add_segment<R: std::ops::RangeBounds<i64>>(&mut self, a: i64, b: i64, range: R) {
+        use std::ops::Bound::*;
+
+        let from = match range.start_bound() {
+            Included(&x) => self.lower_bound(x),
+            Excluded(&x) => self.upper_bound(x),
+            Unbounded => 0
+        };
+        let to = match range.end_bound() {
+            Included(&x) => self.upper_bound(x),
+            Excluded(&x) => self.lower_bound(x),
+            Unbounded => self.len()
+        };
+        if from < to {
+            let r = self.len() - 1;
+            self.add_segment_to_node(1, 0, r, from, to, (a, b));
+        }
+    }
+
+    /// Gets the minimum at `x` over all the lines and the segments defined at `x`.
+    ///
+    /// If no lines or segments are defined at `x`, returns `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is not a coordinate given on creation.
+    pub fn query(&self, x: i64) -> Option<i64> {
+        let index = self.lower_bound(x);
+        assert!(index < self.len() && self.x(index) == x, "{} is not a coordinate of the tree", x);
+
+        let mut min: Option<i128> = None;
+        let (mut node, mut l, mut r) = (1, 0, self.len() - 1);
+        loop {
+            if let Some(line) = self.lines[node] {
+                let y = li_chao_eval(line, x);
+                min = Some(min.map_or(y, |min| std::cmp::min(min, y)));
+            }
+            if l == r {
+                return min.map(|min| min as i64);
+            }
+            let m = (l + r) / 2;
+            if index <= m {
+                node *= 2;
+                r = m;
+            } else {
+                node = node * 2 + 1;
+                l = m + 1;
+            }
+        }
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+
+    fn naive_min(segments: &[(i64, i64, i64, i64)], x: i64) -> Option<i64> {
+        segments.iter()
+            .filter(|&&(_, _, from, to)| from <= x && x < to)
+            .map(|&(a, b, _, _)| a * x + b)
+            .min()
+    }
+
+    #[test]
+    fn test_random_segments_on_range() {
+        let mut rng = Xorshift::with_seed(1010);
+        let mut tree = LiChaoTree::from_range(-100..100);
+        let mut segments = Vec::new();
+        for i in 0..10000 {
+            let a = rng.gen_range_i64(-1000..1000);
+            let b = rng.gen_range_i64(-1_000_000..1_000_000);
+            let from = rng.gen_range_i64(-120..120);
+            let to = rng.gen_range_i64(-120..120);
+            tree.add_segment(a, b, from..to);
+            segments.push((a, b, from, to));
+
+            if i % 100 == 0 {
+                for x in -100..100 {
+                    assert_eq!(tree.query(x), naive_min(&segments, x));
+                }
+            }
+        }
+        for x in -100..100 {
+            assert_eq!(tree.query(x), naive_min(&segments, x));
+        }
+    }
+
+    #[test]
+    fn test_random_on_compressed() {
+        let mut rng = Xorshift::with_seed(1010);
+        let xs: Vec<i64> = (0..300).map(|_| rng.gen_range_i64(-1_000_000_000..1_000_000_000)).collect();
+        let mut tree = LiChaoTree::new(&xs);
+        let mut segments = Vec::new();
+        for _ in 0..2000 {
+            let a = rng.gen_range_i64(-1_000_000_000..1_000_000_000);
+            let b = rng.gen_range_i64(-1_000_000_000..1_000_000_000);
+            if rng.gen_range(0..2) == 0 {
+                tree.add_line(a, b);
+                segments.push((a, b, std::i64::MIN, std::i64::MAX));
+            } else {
+                let from = rng.gen_range_i64(-1_000_000_000..1_000_000_000);
+                let last = rng.gen_range_i64(-1_000_000_000..1_000_000_000);
+                tree.add_segment(a, b, from..=last);
+                segments.push((a, b, from, last + 1));
+            }
+        }
+        for &x in &xs {
+            assert_eq!(tree.query(x), naive_min(&segments, x));
+        }
+    }
+
+    #[test]
+    fn test_large_coefficients() {
+        let big = 1_000_000_000_000_000_000;
+        // Values at 10^12 overflow `i64`, but are never the minimums.
+        let mut tree = LiChaoTree::new(&[-1, 0, 1, 1_000_000_000_000]);
+        tree.add_line(big, -big);
+        tree.add_line(-big, -big);
+        tree.add_segment(0, 5, ..=1);
+        assert_eq!(tree.query(-1), Some(-2 * big));
+        assert_eq!(tree.query(0), Some(-big));
+        assert_eq!(tree.query(1), Some(-2 * big));
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut tree = LiChaoTree::from_range(0..0);
+        tree.add_line(1, 1);
+        tree.add_segment(1, 1, ..);
+        let mut tree = LiChaoTree::new(&[1, 3]);
+        tree.add_segment(1, 1, 2..3);
+        assert_eq!(tree.query(1), None);
+        assert_eq!(tree.query(3), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "2 is not a coordinate of the tree")]
+    fn test_query_non_coordinate() {
+        let tree = LiChaoTree::new(&[1, 3]);
+        tree.query(2);
+    }
+}
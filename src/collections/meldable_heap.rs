@@ -0,0 +1,278 @@
+//! Maximum heap which can be melded with another one efficiently.
+
+// BEGIN SNIPPET meldable_heap
+
+const MELDABLE_HEAP_NIL: usize = std::usize::MAX;
+
+/// Maximum heap which can be melded with another one, implemented as a skew heap.
+///
+/// Nodes are kept in an arena of the heap.
+/// `push` and `pop` are performed in amortized O(log(*n*)) time.
+/// `meld` links the two trees in amortized O(log(*n*)) time,
+/// and moves nodes of the smaller arena into the larger one.
+/// When heaps are melded repeatedly, such as merging heaps of groups on union-find,
+/// each node is moved O(log(*n*)) times in total.
+///
+/// # Example
+///
+/// Processing "merge two groups" and "the maximum score in a group" operations.
+///
+/// ```
+/// # use atcoder_snippets::collections::meldable_heap::*;
+/// # use atcoder_snippets::collections::vec_union_find_sets::*;
+/// let scores = [3, 1, 4, 1, 5];
+/// let mut sets = VecUnionFindSets::new(scores.len());
+/// let mut heaps: Vec<MeldableHeap<i32>> = scores.iter()
+///     .map(|&score| {
+///         let mut heap = MeldableHeap::new();
+///         heap.push(score);
+///         heap
+///     })
+///     .collect();
+///
+/// let mut unite = |sets: &mut VecUnionFindSets, heaps: &mut Vec<MeldableHeap<i32>>, a, b| {
+///     let (root_a, root_b) = (sets.root(a), sets.root(b));
+///     if sets.unite(a, b) {
+///         let new_root = sets.root(a);
+///         let other = std::mem::take(&mut heaps[root_a ^ root_b ^ new_root]);
+///         heaps[new_root].meld(other);
+///     }
+/// };
+///
+/// unite(&mut sets, &mut heaps, 0, 1);
+/// unite(&mut sets, &mut heaps, 3, 4);
+/// assert_eq!(heaps[sets.root(1)].peek(), Some(&3));
+/// unite(&mut sets, &mut heaps, 1, 3);
+/// assert_eq!(heaps[sets.root(0)].peek(), Some(&5));
+/// assert_eq!(heaps[sets.root(0)].len(), 4);
+/// ```
+#[derive(Clone)]
+pub struct MeldableHeap<T> {
+    root: usize,
+    len: usize,
+    // Arena of nodes. Values of free nodes are `None`.
+    values: Vec<Option<T>>,
+    left: Vec<usize>,
+    right: Vec<usize>,
+    free: Vec<usize>
+}
+
+impl<T: Ord> MeldableHeap<T> {
+    /// Creates an empty heap.
+    pub fn new() -> MeldableHeap<T> {
+        MeldableHeap {
+            root: MELDABLE_HEAP_NIL,
+            len: 0,
+            values: Vec::new(),
+            left: Vec::new(),
+            right: Vec::new(),
+            free: Vec::new()
+        }
+    }
+
+    /// The number of items in the heap.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets the reference to the maximum item, or `None` if the heap is empty.
+    pub fn peek(&self) -> Option<&T> {
+        if self.root == MELDABLE_HEAP_NIL {
+            None
+        } else {
+            self.values[self.root].as_ref()
+        }
+    }
+
+    fn greater_eq(&self, a: usize, b: usize) -> bool {
+        self.values[a] >= self.values[b]
+    }
+
+    // Merges two trees of the arena without recursion, and returns the new root.
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == MELDABLE_HEAP_NIL {
+            return b;
+        }
+        if b == MELDABLE_HEAP_NIL {
+            return a;
+        }
+
+        let (mut a, mut b) = if self.greater_eq(a, b) { (a, b) } else { (b, a) };
+        let root = a;
+        loop {
+            // The new left child of `a` is the merged tree of the right child and `b`,
+            // and the new right child is the left child.
+            let right = self.right[a];
+            self.right[a] = self.left[a];
+            if right == MELDABLE_HEAP_NIL {
+                self.left[a] = b;
+                return root;
+            }
+            let (x, y) = if self.greater_eq(right, b) { (right, b) } else { (b, right) };
+            self.left[a] = x;
+            a = x;
+            b = y;
+        }
+    }
+
+    /// Pushes an item.
+    pub fn push(&mut self, item: T) {
+        let node = match self.free.pop() {
+            Some(node) => {
+                self.values[node] = Some(item);
+                self.left[node] = MELDABLE_HEAP_NIL;
+                self.right[node] = MELDABLE_HEAP_NIL;
+                node
+            }
+            None => {
+                self.values.push(Some(item));
+                self.left.push(MELDABLE_HEAP_NIL);
+                self.right.push(MELDABLE_HEAP_NIL);
+                self.values.len() - 1
+            }
+        };
+        self.root = self.merge(self.root, node);
+        self.len += 1;
+    }
+
+    /// Removes the maximum item and returns it, or `None` if the heap is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.root == MELDABLE_HEAP_NIL {
+            return None;
+        }
+
+        let node = self.root;
+        self.root = self.merge(self.left[node], self.right[node]);
+        self.free.push(node);
+        self.len -= 1;
+        self.values[node].take()
+    }
+
+    /// Moves all the items of `other` into `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::meldable_heap::*;
+    /// let mut heap1: MeldableHeap<i32> = vec![3, 1, 4].into_iter().collect();
+    /// let heap2: MeldableHeap<i32> = vec![1, 5].into_iter().collect();
+    /// heap1.meld(heap2);
+    /// assert_eq!(heap1.into_sorted_vec(), vec![1, 1, 3, 4, 5]);
+    /// ```
+    pub fn meld(&mut self, mut other: MeldableHeap<T>) {
+        if self.values.len() < other.values.len() {
+            std::mem::swap(self, &mut other);
+        }
+
+        let offset = self.values.len();
+        let shift = |node: usize| if node == MELDABLE_HEAP_NIL { node } else { node + offset };
+        self.values.extend(other.values);
+        self.left.extend(other.left.into_iter().map(shift));
+        self.right.extend(other.right.into_iter().map(shift));
+        self.free.extend(other.free.into_iter().map(shift));
+        self.root = self.merge(self.root, shift(other.root));
+        self.len += other.len;
+    }
+
+    /// Consumes the heap and returns a vector sorted in ascending order.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut vec = Vec::with_capacity(self.len);
+        while let Some(item) = self.pop() {
+            vec.push(item);
+        }
+        vec.reverse();
+        vec
+    }
+}
+
+impl<T: Ord> Default for MeldableHeap<T> {
+    fn default() -> MeldableHeap<T> {
+        MeldableHeap::new()
+    }
+}
+
+impl<T: Ord> std::iter::FromIterator<T> for MeldableHeap<T> {
+    fn from_iter<I>(iter: I) -> MeldableHeap<T> where I: IntoIterator<Item=T> {
+        let mut heap = MeldableHeap::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord> Extend<T> for MeldableHeap<T> {
+    fn extend<I: IntoIterator<Item=T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T: Ord + std::fmt::Debug> std::fmt::Debug for MeldableHeap<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut items: Vec<&T> = self.values.iter().filter_map(|value| value.as_ref()).collect();
+        items.sort_by(|a, b| b.cmp(a));
+        f.debug_list().entries(items).finish()
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+
+    #[test]
+    fn test_meld_many_small_heaps() {
+        let mut rng = Xorshift::with_seed(1019);
+        let mut heaps: Vec<(MeldableHeap<i64>, Vec<i64>)> = (0..300)
+            .map(|_| {
+                let items: Vec<i64> = (0..rng.gen_range(0..5)).map(|_| rng.gen_range_i64(-50..50)).collect();
+                (items.iter().cloned().collect(), items)
+            })
+            .collect();
+
+        while heaps.len() > 1 {
+            let i = rng.gen_range(0..heaps.len() as u64) as usize;
+            let (heap, items) = heaps.swap_remove(i);
+            let j = rng.gen_range(0..heaps.len() as u64) as usize;
+            heaps[j].0.meld(heap);
+            heaps[j].1.extend(items);
+
+            // Pop some items, and push some items
+            let (ref mut heap, ref mut items) = heaps[j];
+            items.sort();
+            for _ in 0..rng.gen_range(0..3) {
+                assert_eq!(heap.pop(), items.pop());
+            }
+            for _ in 0..rng.gen_range(0..3) {
+                let x = rng.gen_range_i64(-50..50);
+                heap.push(x);
+                items.push(x);
+            }
+            items.sort();
+            assert_eq!(heap.len(), items.len());
+            assert_eq!(heap.peek(), items.last());
+        }
+
+        let (heap, items) = heaps.pop().unwrap();
+        assert_eq!(heap.into_sorted_vec(), items);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut heap: MeldableHeap<i32> = MeldableHeap::new();
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+        heap.meld(MeldableHeap::new());
+        assert!(heap.is_empty());
+        heap.meld(vec![2, 1].into_iter().collect());
+        assert_eq!(format!("{:?}", heap), "[2, 1]");
+        assert_eq!(heap.pop(), Some(2));
+    }
+}
@@ -0,0 +1,155 @@
+//! Segment tree of sorted segments, counting items by their values in an interval.
+
+use crate::range::UsizeRangeBoundsExt;
+use crate::bsearch::partition_point_usize;
+
+// BEGIN SNIPPET merge_sort_tree DEPENDS ON range bsearch
+
+/// Static sequence of `i64` counting items in an interval by their values.
+///
+/// Each node of the segment tree keeps its segment sorted,
+/// which is built by merging the children in O(*n* log(*n*)) time in total.
+/// Each query takes O(log(*n*)^2) time.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::merge_sort_tree::*;
+/// let tree = MergeSortTree::new(&[3, 1, 4, 1, 5, 9, 2, 6]);
+/// // How many items are at most 3 in each query interval?
+/// let queries = [0..4, 2..6, 4..8];
+/// let counts: Vec<usize> = queries.iter()
+///     .map(|range| tree.count_leq(range.clone(), 3).unwrap())
+///     .collect();
+/// assert_eq!(counts, vec![3, 1, 1]);
+/// assert_eq!(tree.count_in(.., 2..5), Some(3));
+/// ```
+#[derive(Clone, Debug)]
+pub struct MergeSortTree {
+    len: usize,
+    // Leaves are `nodes[len..2*len]`, and `nodes[k]` is the merged `nodes[2*k]` and `nodes[2*k+1]`.
+    nodes: Vec<Vec<i64>>
+}
+
+impl MergeSortTree {
+    /// Creates a tree from items in a slice.
+    pub fn new(seq: &[i64]) -> MergeSortTree {
+        let len = seq.len();
+        let mut nodes = vec![Vec::new(); len];
+        nodes.extend(seq.iter().map(|&x| vec![x]));
+        for k in (1..len).rev() {
+            let (left, right) = (&nodes[2 * k], &nodes[2 * k + 1]);
+            let mut merged = Vec::with_capacity(left.len() + right.len());
+            let (mut i, mut j) = (0, 0);
+            while i < left.len() || j < right.len() {
+                if j == right.len() || i < left.len() && left[i] <= right[j] {
+                    merged.push(left[i]);
+                    i += 1;
+                } else {
+                    merged.push(right[j]);
+                    j += 1;
+                }
+            }
+            nodes[k] = merged;
+        }
+        MergeSortTree { len, nodes }
+    }
+
+    /// The length of the sequence.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Sums `f` over nodes composing `range`.
+    fn sum_over_nodes<R, F>(&self, range: R, mut f: F) -> Option<usize>
+    where
+        R: std::ops::RangeBounds<usize>,
+        F: FnMut(&[i64]) -> usize
+    {
+        let range = range.to_range(self.len)?;
+        let mut l = range.start + self.len;
+        let mut r = range.end + self.len;
+        let mut sum = 0;
+        while l < r {
+            if l & 1 == 1 {
+                sum += f(&self.nodes[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                sum += f(&self.nodes[r]);
+            }
+            l >>= 1;
+            r >>= 1;
+        }
+        Some(sum)
+    }
+
+    /// Counts items at most `x` in `range`.
+    ///
+    /// If the range is out of bound, returns `None`.
+    pub fn count_leq<R: std::ops::RangeBounds<usize>>(&self, range: R, x: i64) -> Option<usize> {
+        self.sum_over_nodes(range, |sorted| upper_bound(sorted, x))
+    }
+
+    /// Counts items in `range` whose values are in `values`.
+    ///
+    /// If the range is out of bound, returns `None`.
+    pub fn count_in<R: std::ops::RangeBounds<usize>>(&self, range: R, values: std::ops::Range<i64>) -> Option<usize> {
+        self.sum_over_nodes(range, |sorted| {
+            lower_bound(sorted, values.end).saturating_sub(lower_bound(sorted, values.start))
+        })
+    }
+}
+
+// The number of items less than `x` in `sorted`.
+fn lower_bound(sorted: &[i64], x: i64) -> usize {
+    partition_point_usize(0..sorted.len(), |i| sorted[i] >= x)
+}
+
+// The number of items less than or equal to `x` in `sorted`.
+fn upper_bound(sorted: &[i64], x: i64) -> usize {
+    partition_point_usize(0..sorted.len(), |i| sorted[i] > x)
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+
+    #[test]
+    fn test_random() {
+        let mut rng = Xorshift::with_seed(1020);
+        for _ in 0..30 {
+            let len = rng.gen_range(0..100) as usize;
+            let seq: Vec<i64> = (0..len).map(|_| rng.gen_range_i64(-20..20)).collect();
+            let tree = MergeSortTree::new(&seq);
+            assert_eq!(tree.len(), len);
+
+            for _ in 0..100 {
+                let l = rng.gen_range(0..=len as u64) as usize;
+                let r = rng.gen_range(l as u64..=len as u64) as usize;
+                let x = rng.gen_range_i64(-25..25);
+                let y = rng.gen_range_i64(-25..25);
+                assert_eq!(tree.count_leq(l..r, x), Some(seq[l..r].iter().filter(|&&v| v <= x).count()));
+                assert_eq!(tree.count_in(l..r, x..y), Some(seq[l..r].iter().filter(|&&v| x <= v && v < y).count()));
+            }
+            assert_eq!(tree.count_leq(..=len, 0), None);
+        }
+    }
+
+    #[test]
+    fn test_extreme_values() {
+        let tree = MergeSortTree::new(&[std::i64::MAX, std::i64::MIN, 0]);
+        assert_eq!(tree.count_leq(.., std::i64::MAX), Some(3));
+        assert_eq!(tree.count_leq(.., std::i64::MIN), Some(1));
+        assert_eq!(tree.count_in(.., std::i64::MIN..std::i64::MAX), Some(2));
+    }
+}
@@ -3,8 +3,25 @@
 pub mod list;
 pub mod vec_union_find_sets;
 pub mod hash_union_find_sets;
+pub mod weighted_union_find;
+pub mod rollback_union_find;
 pub mod hash_counter;
+pub mod multiset;
 pub mod segment_tree;
+pub mod lazy_segment_tree;
+pub mod persistent_segment_tree;
+pub mod merge_sort_tree;
+pub mod wavelet_matrix;
+pub mod sparse_table;
+pub mod disjoint_sparse_table;
+pub mod sliding_window_aggregator;
+pub mod sliding_min_queue;
+pub mod convex_hull_trick;
+pub mod li_chao_tree;
+pub mod trie;
 pub mod heap;
+pub mod indexed_heap;
+pub mod meldable_heap;
+pub mod erasable_heap;
 pub mod bitset;
 pub mod once_queue;
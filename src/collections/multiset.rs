@@ -0,0 +1,326 @@
+//! Ordered multiset by a B-tree map from items to their counts.
+
+// BEGIN SNIPPET multiset
+
+/// Ordered multiset by a B-tree map from items to their counts.
+///
+/// Similar to C++'s `std::multiset`.
+///
+/// # Example
+///
+/// Minimum of each sliding window of length 3.
+///
+/// ```
+/// # use atcoder_snippets::collections::multiset::*;
+/// let a = [5, 3, 3, 4, 6, 2];
+/// let mut window: MultiSet<i32> = a[..2].iter().cloned().collect();
+/// let mut mins = Vec::new();
+/// for i in 2..a.len() {
+///     window.insert(a[i]);
+///     mins.push(*window.first().unwrap());
+///     window.remove_one(&a[i - 2]);
+/// }
+/// assert_eq!(mins, vec![3, 3, 3, 2]);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct MultiSet<T> {
+    // Every count is positive.
+    counts: std::collections::BTreeMap<T, usize>,
+    len: usize
+}
+
+/// An iterator created by [`iter`](struct.MultiSet.html#method.iter) and
+/// [`range`](struct.MultiSet.html#method.range) methods on multisets.
+///
+/// Each item is yielded as many times as it is contained, in ascending order.
+pub struct MultiSetIter<'a, T: 'a> {
+    iter: std::collections::btree_map::Range<'a, T, usize>,
+    current: Option<(&'a T, usize)>
+}
+
+impl<'a, T> Iterator for MultiSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match self.current {
+            Some((item, rest)) if rest > 0 => {
+                self.current = Some((item, rest - 1));
+                Some(item)
+            }
+            _ => {
+                let (item, &count) = self.iter.next()?;
+                self.current = Some((item, count - 1));
+                Some(item)
+            }
+        }
+    }
+}
+
+impl<T: Ord> MultiSet<T> {
+    /// Creates an empty multiset.
+    pub fn new() -> MultiSet<T> {
+        MultiSet {
+            counts: std::collections::BTreeMap::new(),
+            len: 0
+        }
+    }
+
+    /// Gets how many items the multiset contains, counting duplicates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::multiset::*;
+    /// let set: MultiSet<i32> = vec![1, 2, 2, 3, 3, 3].into_iter().collect();
+    /// assert_eq!(set.len(), 6);
+    /// assert_eq!(set.keys_len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Gets how many distinct items the multiset contains.
+    pub fn keys_len(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Returns whether the multiset contains no items.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets how many `item`s the multiset contains.
+    pub fn count<Q>(&self, item: &Q) -> usize
+    where
+        T: std::borrow::Borrow<Q>,
+        Q: ?Sized + Ord
+    {
+        self.counts.get(item).cloned().unwrap_or(0)
+    }
+
+    /// Returns whether the multiset contains `item` at least once.
+    pub fn contains<Q>(&self, item: &Q) -> bool
+    where
+        T: std::borrow::Borrow<Q>,
+        Q: ?Sized + Ord
+    {
+        self.counts.contains_key(item)
+    }
+
+    /// Adds an `item`.
+    pub fn insert(&mut self, item: T) {
+        *self.counts.entry(item).or_insert(0) += 1;
+        self.len += 1;
+    }
+
+    /// Removes one `item`.
+    ///
+    /// If the multiset doesn't contain `item`, does nothing and returns false.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::multiset::*;
+    /// let mut set: MultiSet<i32> = vec![1, 1].into_iter().collect();
+    /// assert!(set.remove_one(&1));
+    /// assert_eq!(set.count(&1), 1);
+    /// assert!(set.remove_one(&1));
+    /// assert!(!set.remove_one(&1));
+    /// assert!(set.is_empty());
+    /// ```
+    pub fn remove_one<Q>(&mut self, item: &Q) -> bool
+    where
+        T: std::borrow::Borrow<Q>,
+        Q: ?Sized + Ord
+    {
+        let removes = match self.counts.get_mut(item) {
+            None => return false,
+            Some(count) => {
+                *count -= 1;
+                *count == 0
+            }
+        };
+        if removes {
+            self.counts.remove(item);
+        }
+        self.len -= 1;
+        true
+    }
+
+    /// Removes all `item`s, and returns how many items are removed.
+    pub fn remove_all<Q>(&mut self, item: &Q) -> usize
+    where
+        T: std::borrow::Borrow<Q>,
+        Q: ?Sized + Ord
+    {
+        let count = self.counts.remove(item).unwrap_or(0);
+        self.len -= count;
+        count
+    }
+
+    /// Gets the minimum item, or `None` if the multiset is empty.
+    pub fn first(&self) -> Option<&T> {
+        self.counts.keys().next()
+    }
+
+    /// Gets the maximum item, or `None` if the multiset is empty.
+    pub fn last(&self) -> Option<&T> {
+        self.counts.keys().next_back()
+    }
+
+    /// Creates an iterator yielding all the items in ascending order, including duplicates.
+    ///
+    /// The `k`-th smallest item is `iter().nth(k)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::multiset::*;
+    /// let set: MultiSet<i32> = vec![3, 1, 2, 1].into_iter().collect();
+    /// assert_eq!(set.iter().cloned().collect::<Vec<_>>(), vec![1, 1, 2, 3]);
+    /// assert_eq!(set.iter().nth(2), Some(&2));
+    /// ```
+    pub fn iter(&self) -> MultiSetIter<'_, T> {
+        MultiSetIter {
+            iter: self.counts.range::<T, _>(..),
+            current: None
+        }
+    }
+
+    /// Creates an iterator yielding the items in `range` in ascending order, including duplicates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than the end,
+    /// as `BTreeMap::range` does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::multiset::*;
+    /// let set: MultiSet<i32> = vec![1, 3, 3, 5, 7].into_iter().collect();
+    /// assert_eq!(set.range(2..6).cloned().collect::<Vec<_>>(), vec![3, 3, 5]);
+    /// // The smallest item not less than 4
+    /// assert_eq!(set.range(4..).next(), Some(&5));
+    /// ```
+    pub fn range<Q, R>(&self, range: R) -> MultiSetIter<'_, T>
+    where
+        T: std::borrow::Borrow<Q>,
+        Q: ?Sized + Ord,
+        R: std::ops::RangeBounds<Q>
+    {
+        MultiSetIter {
+            iter: self.counts.range(range),
+            current: None
+        }
+    }
+}
+
+impl<T: Ord> Default for MultiSet<T> {
+    fn default() -> MultiSet<T> {
+        MultiSet::new()
+    }
+}
+
+impl<T: Ord + std::fmt::Debug> std::fmt::Debug for MultiSet<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord> std::iter::FromIterator<T> for MultiSet<T> {
+    fn from_iter<I>(iter: I) -> MultiSet<T> where I: IntoIterator<Item=T> {
+        let mut set = MultiSet::new();
+        for item in iter {
+            set.insert(item);
+        }
+        set
+    }
+}
+
+impl<'a, T: Ord> std::iter::IntoIterator for &'a MultiSet<T> {
+    type Item = &'a T;
+    type IntoIter = MultiSetIter<'a, T>;
+
+    fn into_iter(self) -> MultiSetIter<'a, T> {
+        self.iter()
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+
+    #[test]
+    fn test_duplicates() {
+        let mut set: MultiSet<u32> = std::iter::repeat(7).take(100).collect();
+        set.insert(3);
+        assert_eq!(set.len(), 101);
+        assert_eq!(set.keys_len(), 2);
+        assert_eq!(set.count(&7), 100);
+        assert_eq!(set.first(), Some(&3));
+        assert_eq!(set.last(), Some(&7));
+
+        for _ in 0..99 {
+            assert!(set.remove_one(&7));
+        }
+        assert_eq!(set.count(&7), 1);
+        assert_eq!(set.remove_all(&7), 1);
+        assert_eq!(set.remove_all(&7), 0);
+        assert!(!set.remove_one(&7));
+        assert!(!set.contains(&7));
+        assert_eq!(set.keys_len(), 1);
+        assert_eq!(set.last(), Some(&3));
+        assert_eq!(format!("{:?}", set), "{3}");
+
+        assert!(set.remove_one(&3));
+        assert!(set.is_empty());
+        assert_eq!(set.first(), None);
+        assert_eq!(set.last(), None);
+        assert!(!set.remove_one(&3));
+        assert_eq!(set.len(), 0);
+        assert_eq!(set, MultiSet::new());
+    }
+
+    #[test]
+    fn test_random_operations() {
+        let mut rng = Xorshift::with_seed(1007);
+        let mut set = MultiSet::new();
+        let mut naive: Vec<u64> = Vec::new();
+        for _ in 0..2000 {
+            let x = rng.gen_range(0..10);
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    set.insert(x);
+                    naive.push(x);
+                    naive.sort();
+                }
+                2 => {
+                    let pos = naive.iter().position(|&y| y == x);
+                    assert_eq!(set.remove_one(&x), pos.is_some());
+                    if let Some(pos) = pos {
+                        naive.remove(pos);
+                    }
+                }
+                _ => {
+                    let count = naive.iter().filter(|&&y| y == x).count();
+                    assert_eq!(set.remove_all(&x), count);
+                    naive.retain(|&y| y != x);
+                }
+            }
+
+            assert_eq!(set.len(), naive.len());
+            assert_eq!(set.first(), naive.first());
+            assert_eq!(set.last(), naive.last());
+            assert_eq!(set.count(&x), naive.iter().filter(|&&y| y == x).count());
+            assert_eq!(set.iter().cloned().collect::<Vec<_>>(), naive);
+            assert_eq!(
+                set.range(3..x.max(3)).cloned().collect::<Vec<_>>(),
+                naive.iter().cloned().filter(|&y| 3 <= y && y < x).collect::<Vec<_>>()
+            );
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Persistent segment tree of sums, and k-th smallest queries on it.
+
+use crate::range::UsizeRangeBoundsExt;
+
+// BEGIN SNIPPET persistent_segment_tree DEPENDS ON range
+
+#[derive(Clone, Copy, Debug)]
+struct PersistentSegmentTreeNode {
+    left: usize,
+    right: usize,
+    sum: i64
+}
+
+/// Persistent sequence of `i64` aggregatable by sum over an interval.
+///
+/// Each version of the sequence is identified by a handle of `usize`.
+/// The handle `0` is the version where all the items are 0.
+/// Updating a version creates a new version, keeping the old one intact.
+///
+/// Nodes are kept in an arena, and each update adds O(log(*n*)) nodes,
+/// as *n* is the length of the sequence.
+/// Any update and aggregation is performed in O(log(*n*)) time.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::persistent_segment_tree::*;
+/// let mut tree = PersistentSegmentTree::new(5);
+/// let v1 = tree.add(0, 1, 10);
+/// let v2 = tree.add(v1, 3, 5);
+/// assert_eq!(tree.sum(v2, ..), Some(15));
+/// assert_eq!(tree.sum(v1, ..), Some(10));
+/// assert_eq!(tree.sum(0, ..), Some(0));
+/// // Difference between versions
+/// assert_eq!(tree.query(v1, v2, 2..), Some(5));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PersistentSegmentTree {
+    len: usize,
+    // `nodes[0]` is the node of all 0, whose children are itself.
+    nodes: Vec<PersistentSegmentTreeNode>
+}
+
+impl PersistentSegmentTree {
+    /// Creates a tree of length `len`, which has only the version `0` of all 0.
+    pub fn new(len: usize) -> PersistentSegmentTree {
+        PersistentSegmentTree {
+            len,
+            nodes: vec![PersistentSegmentTreeNode { left: 0, right: 0, sum: 0 }]
+        }
+    }
+
+    /// The length of the sequence.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn add_to_node(&mut self, node: usize, l: usize, r: usize, index: usize, delta: i64) -> usize {
+        let mut new_node = self.nodes[node];
+        new_node.sum += delta;
+        if r - l > 1 {
+            let m = (l + r) / 2;
+            if index < m {
+                new_node.left = self.add_to_node(new_node.left, l, m, index, delta);
+            } else {
+                new_node.right = self.add_to_node(new_node.right, m, r, index, delta);
+            }
+        }
+        self.nodes.push(new_node);
+        self.nodes.len() - 1
+    }
+
+    /// Creates a new version by adding `delta` to the item at `index` of `version`,
+    /// and returns the new version.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bound.
+    pub fn add(&mut self, version: usize, index: usize, delta: i64) -> usize {
+        assert!(index < self.len, "index {} is out of bound: the length is {}", index, self.len);
+        self.add_to_node(version, 0, self.len, index, delta)
+    }
+
+    fn sum_of_node(&self, node: usize, l: usize, r: usize, range: &std::ops::Range<usize>) -> i64 {
+        if node == 0 || range.end <= l || r <= range.start {
+            0
+        } else if range.start <= l && r <= range.end {
+            self.nodes[node].sum
+        } else {
+            let m = (l + r) / 2;
+            self.sum_of_node(self.nodes[node].left, l, m, range)
+                + self.sum_of_node(self.nodes[node].right, m, r, range)
+        }
+    }
+
+    /// Sums items in `range` of `version`.
+    ///
+    /// If the range is out of bound, returns `None`.
+    pub fn sum<R: std::ops::RangeBounds<usize>>(&self, version: usize, range: R) -> Option<i64> {
+        let range = range.to_range(self.len)?;
+        Some(self.sum_of_node(version, 0, self.len, &range))
+    }
+
+    /// Sums items in `range` of `version_b` minus those of `version_a`.
+    ///
+    /// If the range is out of bound, returns `None`.
+    pub fn query<R: std::ops::RangeBounds<usize>>(&self, version_a: usize, version_b: usize, range: R) -> Option<i64> {
+        let range = range.to_range(self.len)?;
+        Some(self.sum_of_node(version_b, 0, self.len, &range) - self.sum_of_node(version_a, 0, self.len, &range))
+    }
+}
+
+/// Sequence answering the k-th smallest item in an interval.
+///
+/// Built on [`PersistentSegmentTree`](struct.PersistentSegmentTree.html)
+/// counting items by their compressed values, with a version for each prefix of the sequence.
+/// Creation takes O(*n* log(*n*)) time, and each query takes O(log(*n*)) time.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::persistent_segment_tree::*;
+/// let seq = KthSmallest::new(&[5, 1, 4, 1, 3]);
+/// assert_eq!(seq.kth(1..4, 0), Some(1));
+/// assert_eq!(seq.kth(1..4, 1), Some(1));
+/// assert_eq!(seq.kth(1..4, 2), Some(4));
+/// assert_eq!(seq.kth(1..4, 3), None);
+/// assert_eq!(seq.kth(.., 4), Some(5));
+/// ```
+#[derive(Clone, Debug)]
+pub struct KthSmallest {
+    // Sorted distinct values.
+    values: Vec<i64>,
+    tree: PersistentSegmentTree,
+    // `versions[i]` counts the first `i` items.
+    versions: Vec<usize>
+}
+
+impl KthSmallest {
+    /// Creates a sequence from a slice.
+    pub fn new(seq: &[i64]) -> KthSmallest {
+        let mut values = seq.to_vec();
+        values.sort();
+        values.dedup();
+
+        let mut tree = PersistentSegmentTree::new(values.len());
+        let mut versions = vec![0];
+        for x in seq {
+            let index = values.binary_search(x).unwrap();
+            let version = tree.add(*versions.last().unwrap(), index, 1);
+            versions.push(version);
+        }
+        KthSmallest { values, tree, versions }
+    }
+
+    /// The length of the sequence.
+    pub fn len(&self) -> usize {
+        self.versions.len() - 1
+    }
+
+    /// Returns whether the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the `k`-th smallest item in `range`, where `k` is 0-origin.
+    ///
+    /// If the range is out of bound, or the range has `k` or less items, returns `None`.
+    pub fn kth<R: std::ops::RangeBounds<usize>>(&self, range: R, k: usize) -> Option<i64> {
+        let range = range.to_range(self.len())?;
+        if k >= range.len() {
+            return None;
+        }
+
+        let nodes = &self.tree.nodes;
+        let mut a = self.versions[range.start];
+        let mut b = self.versions[range.end];
+        let (mut l, mut r) = (0, self.values.len());
+        let mut k = k as i64;
+        while r - l > 1 {
+            let m = (l + r) / 2;
+            let left_count = nodes[nodes[b].left].sum - nodes[nodes[a].left].sum;
+            if k < left_count {
+                a = nodes[a].left;
+                b = nodes[b].left;
+                r = m;
+            } else {
+                k -= left_count;
+                a = nodes[a].right;
+                b = nodes[b].right;
+                l = m;
+            }
+        }
+        Some(self.values[l])
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+
+    #[test]
+    fn test_versions() {
+        let mut rng = Xorshift::with_seed(1015);
+        let len = 50;
+        let mut tree = PersistentSegmentTree::new(len);
+        let mut versions = vec![(0, vec![0; len])];
+        for _ in 0..500 {
+            let (version, ref items) = versions[rng.gen_range(0..versions.len() as u64) as usize];
+            let mut items = items.clone();
+            let index = rng.gen_range(0..len as u64) as usize;
+            let delta = rng.gen_range_i64(-100..100);
+            items[index] += delta;
+            versions.push((tree.add(version, index, delta), items));
+        }
+
+        for _ in 0..500 {
+            let (version_a, ref items_a) = versions[rng.gen_range(0..versions.len() as u64) as usize];
+            let (version_b, ref items_b) = versions[rng.gen_range(0..versions.len() as u64) as usize];
+            let l = rng.gen_range(0..=len as u64) as usize;
+            let r = rng.gen_range(l as u64..=len as u64) as usize;
+            let sum_a: i64 = items_a[l..r].iter().sum();
+            let sum_b: i64 = items_b[l..r].iter().sum();
+            assert_eq!(tree.sum(version_a, l..r), Some(sum_a));
+            assert_eq!(tree.query(version_a, version_b, l..r), Some(sum_b - sum_a));
+        }
+        assert_eq!(tree.sum(0, ..=len), None);
+    }
+
+    #[test]
+    fn test_kth_smallest() {
+        let mut rng = Xorshift::with_seed(1015);
+        let len = 10000;
+        let seq: Vec<i64> = (0..len).map(|_| rng.gen_range_i64(-1000..1000)).collect();
+        let kth = KthSmallest::new(&seq);
+        assert_eq!(kth.len(), len);
+
+        for _ in 0..300 {
+            let l = rng.gen_range(0..=len as u64) as usize;
+            let r = rng.gen_range(l as u64..=len as u64) as usize;
+            let mut sorted = seq[l..r].to_vec();
+            sorted.sort();
+            for _ in 0..5 {
+                let k = rng.gen_range(0..=(r - l) as u64) as usize;
+                assert_eq!(kth.kth(l..r, k), sorted.get(k).cloned());
+            }
+        }
+        assert_eq!(kth.kth(..=len, 0), None);
+    }
+
+    #[test]
+    fn test_kth_smallest_empty() {
+        let kth = KthSmallest::new(&[]);
+        assert!(kth.is_empty());
+        assert_eq!(kth.kth(.., 0), None);
+    }
+}
@@ -0,0 +1,244 @@
+//! Union-find whose merges can be undone.
+
+// BEGIN SNIPPET rollback_union_find
+
+/// Disjoint-set data structure for integers `0..n`, whose merges can be undone.
+///
+/// Unlike [`VecUnionFindSets`](../vec_union_find_sets/struct.VecUnionFindSets.html),
+/// it uses union-by-size strategy without path compression,
+/// so that each `unite` changes a constant number of entries recorded in the history.
+/// Each operation is performed in O(log(*n*)) time.
+///
+/// Methods panic if an item is out of range.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::rollback_union_find::*;
+/// let mut sets = RollbackUnionFind::new(4);
+/// sets.unite(0, 1);
+/// let snapshot = sets.snapshot();
+/// sets.unite(1, 2);
+/// sets.unite(2, 3);
+/// assert_eq!(sets.count(), 1);
+///
+/// sets.undo();
+/// assert!(!sets.set_eq(0, 3));
+/// sets.rollback_to(snapshot);
+/// assert!(sets.set_eq(0, 1));
+/// assert!(!sets.set_eq(0, 2));
+/// assert_eq!(sets.count(), 3);
+/// ```
+#[derive(Clone)]
+pub struct RollbackUnionFind {
+    set_count: usize,
+    parent: Vec<usize>,
+    // Valid only for roots.
+    size: Vec<usize>,
+    // For each call of `unite`, the pair of the new child and the new root,
+    // or `None` if the sets were not changed.
+    history: Vec<Option<(usize, usize)>>
+}
+
+impl RollbackUnionFind {
+    /// Creates `n` singleton sets of items `0..n`.
+    pub fn new(n: usize) -> RollbackUnionFind {
+        RollbackUnionFind {
+            set_count: n,
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            history: Vec::new()
+        }
+    }
+
+    /// Returns how many items are contained by all the sets.
+    pub fn items_len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Returns the representative item of the set containing `item`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item` is out of range.
+    pub fn root(&self, item: usize) -> usize {
+        if item >= self.items_len() {
+            panic!("item {} is out of range: the sets have {} items", item, self.items_len());
+        }
+
+        let mut root = item;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        root
+    }
+
+    /// Returns how many sets `self` contains.
+    pub fn count(&self) -> usize {
+        self.set_count
+    }
+
+    /// Returns how many items `self` contains by the set which has `item`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item` is out of range.
+    pub fn len_of(&self, item: usize) -> usize {
+        self.size[self.root(item)]
+    }
+
+    /// Returns if two sets containing `item1` and `item2` are the same one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item1` or `item2` is out of range.
+    pub fn set_eq(&self, item1: usize, item2: usize) -> bool {
+        self.root(item1) == self.root(item2)
+    }
+
+    /// Merges two sets, set containing `item1` and set containing `item2`.
+    ///
+    /// If the two sets are same (already merged ones), do nothing and returns `false`.
+    /// Even in that case, the call is recorded and can be undone by [`undo`](#method.undo).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item1` or `item2` is out of range.
+    pub fn unite(&mut self, item1: usize, item2: usize) -> bool {
+        let root1 = self.root(item1);
+        let root2 = self.root(item2);
+        if root1 == root2 {
+            self.history.push(None);
+            return false;
+        }
+
+        self.set_count -= 1;
+        let (new_root, new_child) = if self.size[root1] < self.size[root2] {
+            (root2, root1)
+        } else {
+            (root1, root2)
+        };
+        self.parent[new_child] = new_root;
+        self.size[new_root] += self.size[new_child];
+        self.history.push(Some((new_child, new_root)));
+        true
+    }
+
+    /// Returns the current state, to which the sets can be restored by
+    /// [`rollback_to`](#method.rollback_to).
+    ///
+    /// The state is the number of calls of `unite` not undone.
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Reverts the last call of `unite`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no call of `unite` to revert.
+    pub fn undo(&mut self) {
+        if let Some((child, root)) = self.history.pop().expect("no unite to undo") {
+            self.parent[child] = child;
+            self.size[root] -= self.size[child];
+            self.set_count += 1;
+        }
+    }
+
+    /// Reverts calls of `unite` until the sets are in the state of `snapshot`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `snapshot` is a state already reverted.
+    pub fn rollback_to(&mut self, snapshot: usize) {
+        assert!(
+            snapshot <= self.history.len(),
+            "snapshot {} is already reverted: the current state is {}", snapshot, self.history.len()
+        );
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+}
+
+impl std::fmt::Debug for RollbackUnionFind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let mut sets = vec![Vec::new(); self.items_len()];
+        for i in 0..self.items_len() {
+            sets[self.root(i)].push(i);
+        }
+        f.debug_set().entries(sets.iter().filter(|set| !set.is_empty())).finish()
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::vec_union_find_sets::VecUnionFindSets;
+    use crate::xorshift::{Xorshift, Rng};
+
+    fn assert_same(sets: &RollbackUnionFind, expected: &VecUnionFindSets) {
+        assert_eq!(sets.count(), expected.count());
+        for i in 0..sets.items_len() {
+            assert_eq!(sets.len_of(i), expected.len_of(i));
+            for j in 0..sets.items_len() {
+                assert_eq!(sets.set_eq(i, j), expected.set_eq(i, j));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rollback_to_midpoint() {
+        let mut rng = Xorshift::with_seed(1014);
+        let n = 30;
+        let edges: Vec<(usize, usize)> = (0..40)
+            .map(|_| (rng.gen_range(0..n as u64) as usize, rng.gen_range(0..n as u64) as usize))
+            .collect();
+
+        let mut sets = RollbackUnionFind::new(n);
+        let mut snapshots = Vec::new();
+        for &(a, b) in &edges {
+            snapshots.push(sets.snapshot());
+            sets.unite(a, b);
+        }
+
+        for &mid in &[35, 20, 20, 7, 0] {
+            sets.rollback_to(snapshots[mid]);
+            let mut expected = VecUnionFindSets::new(n);
+            for &(a, b) in &edges[..mid] {
+                expected.unite(a, b);
+            }
+            assert_same(&sets, &expected);
+        }
+
+        for &(a, b) in &edges[..10] {
+            sets.unite(a, b);
+        }
+        sets.undo();
+        let mut expected = VecUnionFindSets::new(n);
+        for &(a, b) in &edges[..9] {
+            expected.unite(a, b);
+        }
+        assert_same(&sets, &expected);
+    }
+
+    #[test]
+    fn test_undo_noop_unite() {
+        let mut sets = RollbackUnionFind::new(3);
+        assert!(sets.unite(0, 1));
+        assert!(!sets.unite(1, 0));
+        sets.undo();
+        assert!(sets.set_eq(0, 1));
+        sets.undo();
+        assert!(!sets.set_eq(0, 1));
+        assert_eq!(format!("{:?}", sets), "{[0], [1], [2]}");
+    }
+
+    #[test]
+    #[should_panic(expected = "no unite to undo")]
+    fn test_undo_empty() {
+        RollbackUnionFind::new(1).undo();
+    }
+}
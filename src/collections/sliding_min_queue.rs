@@ -0,0 +1,214 @@
+//! Queue answering its minimum by a monotone deque.
+
+// BEGIN SNIPPET sliding_min_queue
+
+/// Queue answering its minimum, or its maximum, by a monotone deque.
+///
+/// Only items which can be the minimum in the future are kept,
+/// so `min` returns a reference to an item of the queue,
+/// but `pop_front` cannot return the removed item.
+/// Each of `push`, `pop_front` and `min` is performed in amortized constant time.
+///
+/// For any associative operation other than minimum,
+/// use [`SlidingWindowAggregator`](../sliding_window_aggregator/struct.SlidingWindowAggregator.html).
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::sliding_min_queue::*;
+/// let mut queue = SlidingMinQueue::new();
+/// queue.push(3);
+/// queue.push(1);
+/// queue.push(4);
+/// assert_eq!(queue.min(), Some(&1));
+/// queue.pop_front();
+/// queue.pop_front();
+/// assert_eq!(queue.min(), Some(&4));
+///
+/// let mut queue = SlidingMinQueue::new_max();
+/// queue.push(3);
+/// queue.push(1);
+/// assert_eq!(queue.min(), Some(&3));
+/// ```
+#[derive(Clone)]
+pub struct SlidingMinQueue<T, F = fn(&T, &T) -> std::cmp::Ordering> {
+    // Pairs of the index in the queue and the item, strictly increasing by `cmp`.
+    deque: std::collections::VecDeque<(usize, T)>,
+    pushed: usize,
+    popped: usize,
+    cmp: F
+}
+
+impl<T: Ord> SlidingMinQueue<T> {
+    /// Creates an empty queue answering the minimum.
+    pub fn new() -> SlidingMinQueue<T> {
+        SlidingMinQueue::by(T::cmp)
+    }
+
+    /// Creates an empty queue answering the maximum by `min`.
+    pub fn new_max() -> SlidingMinQueue<T> {
+        SlidingMinQueue::by(|a: &T, b: &T| b.cmp(a))
+    }
+}
+
+impl<T: Ord> Default for SlidingMinQueue<T> {
+    fn default() -> SlidingMinQueue<T> {
+        SlidingMinQueue::new()
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> std::cmp::Ordering> SlidingMinQueue<T, F> {
+    /// Creates an empty queue answering the minimum by `cmp`.
+    pub fn by(cmp: F) -> SlidingMinQueue<T, F> {
+        SlidingMinQueue {
+            deque: std::collections::VecDeque::new(),
+            pushed: 0,
+            popped: 0,
+            cmp
+        }
+    }
+
+    /// The number of items in the queue.
+    pub fn len(&self) -> usize {
+        self.pushed - self.popped
+    }
+
+    /// Returns whether the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.pushed == self.popped
+    }
+
+    /// Enqueues an item at the back.
+    pub fn push(&mut self, item: T) {
+        while let Some((_, last)) = self.deque.back() {
+            if (self.cmp)(last, &item) == std::cmp::Ordering::Less {
+                break;
+            }
+            self.deque.pop_back();
+        }
+        self.deque.push_back((self.pushed, item));
+        self.pushed += 1;
+    }
+
+    /// Dequeues the frontmost item.
+    ///
+    /// If the queue is empty, does nothing and returns `false`.
+    pub fn pop_front(&mut self) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        if let Some(&(index, _)) = self.deque.front() {
+            if index == self.popped {
+                self.deque.pop_front();
+            }
+        }
+        self.popped += 1;
+        true
+    }
+
+    /// Gets a reference to the minimum item, or `None` if the queue is empty.
+    pub fn min(&self) -> Option<&T> {
+        self.deque.front().map(|(_, item)| item)
+    }
+}
+
+/// Gets the minimum of every window of width `k` in `seq`.
+///
+/// If `k` is greater than the length of `seq`, returns an empty vector.
+///
+/// # Panics
+///
+/// Panics if `k` is 0.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::sliding_min_queue::*;
+/// assert_eq!(sliding_min(&[4, 2, 5, 1, 3], 2), vec![2, 2, 1, 1]);
+/// assert_eq!(sliding_max(&[4, 2, 5, 1, 3], 3), vec![5, 5, 5]);
+/// ```
+pub fn sliding_min<T: Ord + Clone>(seq: &[T], k: usize) -> Vec<T> {
+    sliding_min_by(seq, k, SlidingMinQueue::new())
+}
+
+/// Gets the maximum of every window of width `k` in `seq`.
+///
+/// If `k` is greater than the length of `seq`, returns an empty vector.
+///
+/// # Panics
+///
+/// Panics if `k` is 0.
+pub fn sliding_max<T: Ord + Clone>(seq: &[T], k: usize) -> Vec<T> {
+    sliding_min_by(seq, k, SlidingMinQueue::new_max())
+}
+
+fn sliding_min_by<T: Clone, F>(seq: &[T], k: usize, mut queue: SlidingMinQueue<T, F>) -> Vec<T>
+where
+    F: Fn(&T, &T) -> std::cmp::Ordering
+{
+    assert!(k > 0, "window width must be positive");
+    let mut result = Vec::with_capacity((seq.len() + 1).saturating_sub(k));
+    for (i, item) in seq.iter().enumerate() {
+        queue.push(item.clone());
+        if i >= k {
+            queue.pop_front();
+        }
+        if i + 1 >= k {
+            result.push(queue.min().unwrap().clone());
+        }
+    }
+    result
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+
+    #[test]
+    fn test_sliding_min() {
+        let mut rng = Xorshift::with_seed(1021);
+        for _ in 0..100 {
+            let len = rng.gen_range(0..30) as usize;
+            // Small values for many ties
+            let seq: Vec<u64> = (0..len).map(|_| rng.gen_range(0..4)).collect();
+            for k in 1..len + 3 {
+                let naive_min: Vec<u64> = seq.windows(k).map(|w| *w.iter().min().unwrap()).collect();
+                let naive_max: Vec<u64> = seq.windows(k).map(|w| *w.iter().max().unwrap()).collect();
+                assert_eq!(sliding_min(&seq, k), naive_min);
+                assert_eq!(sliding_max(&seq, k), naive_max);
+            }
+        }
+        assert_eq!(sliding_min(&[3, 1, 2], 1), vec![3, 1, 2]);
+        assert_eq!(sliding_min(&[3, 1, 2], 4), vec![]);
+    }
+
+    #[test]
+    fn test_queue_operations() {
+        let mut rng = Xorshift::with_seed(1021);
+        let mut queue = SlidingMinQueue::by(|a: &(u64, u64), b: &(u64, u64)| a.0.cmp(&b.0));
+        let mut naive = std::collections::VecDeque::new();
+        for i in 0..2000 {
+            if rng.gen_range(0..2) == 0 {
+                let item = (rng.gen_range(0..5), i);
+                queue.push(item);
+                naive.push_back(item);
+            } else {
+                assert_eq!(queue.pop_front(), naive.pop_front().is_some());
+            }
+            assert_eq!(queue.len(), naive.len());
+            // The last of the minimum items is kept on ties
+            let expected = naive.iter().rev().min_by_key(|item| item.0);
+            assert_eq!(queue.min(), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "window width must be positive")]
+    fn test_zero_width() {
+        sliding_min(&[1], 0);
+    }
+}
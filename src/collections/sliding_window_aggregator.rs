@@ -0,0 +1,169 @@
+//! Queue aggregating its items, known as SWAG.
+
+// BEGIN SNIPPET sliding_window_aggregator
+
+/// Queue aggregating its items by an associative operation, known as SWAG.
+///
+/// The operation `aggregate` must be associative, but needs not to be commutative
+/// nor to have the identity.
+///
+/// The queue is composed of two stacks.
+/// Each of `push`, `pop` and `fold` is performed in amortized constant time.
+///
+/// # Example
+///
+/// Minimum of each sliding window of length 3 in O(*n*) time.
+///
+/// ```
+/// # use atcoder_snippets::collections::sliding_window_aggregator::*;
+/// let a = vec![5, 3, 3, 4, 6, 2, 7];
+/// let mut window = SlidingWindowAggregator::new(|&x: &i32, &y: &i32| x.min(y));
+/// let mut mins = Vec::new();
+/// for &x in &a {
+///     window.push(x);
+///     if window.len() > 3 {
+///         window.pop();
+///     }
+///     if window.len() == 3 {
+///         mins.push(window.fold().unwrap());
+///     }
+/// }
+/// assert_eq!(mins, vec![3, 3, 3, 2, 2]);
+/// ```
+pub struct SlidingWindowAggregator<T, F> {
+    // Items to be popped first are at the tail, each with the aggregation
+    // from itself to the bottom of the stack.
+    front: Vec<(T, T)>,
+    back: Vec<T>,
+    // Aggregation of all the items in `back`.
+    back_fold: Option<T>,
+    aggregate: F
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> SlidingWindowAggregator<T, F> {
+    /// Creates an empty queue.
+    pub fn new(aggregate: F) -> SlidingWindowAggregator<T, F> {
+        SlidingWindowAggregator {
+            front: Vec::new(),
+            back: Vec::new(),
+            back_fold: None,
+            aggregate
+        }
+    }
+
+    /// The number of items in the queue.
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    /// Returns whether the queue has no items.
+    pub fn is_empty(&self) -> bool {
+        self.front.is_empty() && self.back.is_empty()
+    }
+
+    /// Enqueues an item at the back.
+    pub fn push(&mut self, item: T) {
+        self.back_fold = Some(match self.back_fold {
+            Some(ref fold) => (self.aggregate)(fold, &item),
+            None => item.clone()
+        });
+        self.back.push(item);
+    }
+
+    /// Dequeues the frontmost item if the queue is not empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            self.back_fold = None;
+            while let Some(item) = self.back.pop() {
+                let fold = match self.front.last() {
+                    Some((_, fold)) => (self.aggregate)(&item, fold),
+                    None => item.clone()
+                };
+                self.front.push((item, fold));
+            }
+        }
+        self.front.pop().map(|(item, _)| item)
+    }
+
+    /// Aggregates all the items in the queue from the front to the back.
+    ///
+    /// If the queue is empty, returns `None`.
+    pub fn fold(&self) -> Option<T> {
+        match (self.front.last(), &self.back_fold) {
+            (Some((_, front)), Some(back)) => Some((self.aggregate)(front, back)),
+            (Some((_, front)), None) => Some(front.clone()),
+            (None, back) => back.clone()
+        }
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+    use std::collections::VecDeque;
+
+    const MOD: u64 = 1_000_000_007;
+
+    type Matrix = [[u64; 2]; 2];
+
+    fn mul(a: &Matrix, b: &Matrix) -> Matrix {
+        let mut c = [[0; 2]; 2];
+        for i in 0..2 {
+            for j in 0..2 {
+                for k in 0..2 {
+                    c[i][j] = (c[i][j] + a[i][k] * b[k][j]) % MOD;
+                }
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn test_matrix_product() {
+        let mut rng = Xorshift::with_seed(1008);
+        let mut swag = SlidingWindowAggregator::new(mul);
+        let mut naive: VecDeque<Matrix> = VecDeque::new();
+        for _ in 0..2000 {
+            if rng.gen_range(0..3) < 2 {
+                let mut m = [[0; 2]; 2];
+                for row in &mut m {
+                    for x in row.iter_mut() {
+                        *x = rng.gen_range(0..MOD);
+                    }
+                }
+                swag.push(m);
+                naive.push_back(m);
+            } else {
+                assert_eq!(swag.pop(), naive.pop_front());
+            }
+
+            assert_eq!(swag.len(), naive.len());
+            assert_eq!(swag.is_empty(), naive.is_empty());
+            let mut iter = naive.iter();
+            let expected = iter.next().map(|&first| iter.fold(first, |acc, m| mul(&acc, m)));
+            assert_eq!(swag.fold(), expected);
+        }
+    }
+
+    #[test]
+    fn test_string_concat() {
+        let mut swag = SlidingWindowAggregator::new(|a: &String, b: &String| format!("{}{}", a, b));
+        assert_eq!(swag.fold(), None);
+        assert_eq!(swag.pop(), None);
+        for s in &["a", "b", "c"] {
+            swag.push(s.to_string());
+        }
+        assert_eq!(swag.fold().unwrap(), "abc");
+        assert_eq!(swag.pop().unwrap(), "a");
+        swag.push("d".to_string());
+        assert_eq!(swag.fold().unwrap(), "bcd");
+        assert_eq!(swag.pop().unwrap(), "b");
+        assert_eq!(swag.pop().unwrap(), "c");
+        assert_eq!(swag.fold().unwrap(), "d");
+        assert_eq!(swag.pop().unwrap(), "d");
+        assert_eq!(swag.fold(), None);
+    }
+}
@@ -0,0 +1,133 @@
+//! Sparse table.
+
+use crate::range::UsizeRangeBoundsExt;
+
+// BEGIN SNIPPET sparse_table DEPENDS ON range
+
+/// Static sequence aggregatable by interval in constant time.
+///
+/// The items of the sequence must be idempotent semigroup. That is:
+///
+/// - Aggregation of a subsequence is performed by repeating aggregation of
+///   adjacent two item. In any order of two-item aggregations,
+///   the final result must be the same.
+/// - For any item *x*, aggregation of *x* and *x* must be *x*.
+///
+/// For example, `i32` is idempotent under `min` and `max`,
+/// and is not under addition.
+///
+/// Construction takes Θ(*n* log(*n*)) time, as *n* is the number of items.
+pub struct SparseTable<T: Clone, F: Fn(&T, &T) -> T> {
+    // `table[k][i]` is the aggregation of `items[i..i + 2^k]`.
+    table: Vec<Vec<T>>,
+    aggregate: F
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> SparseTable<T, F> {
+    /// Create a new sparse table from items in a vector.
+    ///
+    /// `T` must be idempotent semigroup under `aggregate`.
+    pub fn from_vec(items: Vec<T>, aggregate: F) -> SparseTable<T, F> {
+        let mut table = vec![items];
+        let mut width = 1;
+        while 2 * width <= table[0].len() {
+            let next = {
+                let prev = table.last().unwrap();
+                (0..prev.len() - width)
+                    .map(|i| aggregate(&prev[i], &prev[i + width]))
+                    .collect()
+            };
+            table.push(next);
+            width *= 2;
+        }
+
+        SparseTable {
+            table,
+            aggregate
+        }
+    }
+
+    /// The number of items.
+    pub fn len(&self) -> usize {
+        self.table[0].len()
+    }
+
+    /// Returns whether the table has no items.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets all items as a slice.
+    pub fn items(&self) -> &[T] {
+        &self.table[0]
+    }
+
+    /// Aggregate items in the range of `index`.
+    ///
+    /// If the index is out of bound or empty, returns `None`.
+    ///
+    /// This method takes constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::sparse_table::*;
+    /// let table = vec![3, 1, 4, 1, 5, 9, 2, 6].sparse_table(|&x, &y| std::cmp::max(x, y));
+    ///
+    /// assert_eq!(table.query(1..4), Some(4));
+    /// assert_eq!(table.query(4..), Some(9));
+    /// assert_eq!(table.query(3..3), None);
+    /// assert_eq!(table.query(3..=8), None);
+    /// ```
+    pub fn query<R: std::ops::RangeBounds<usize>>(&self, range: R) -> Option<T> {
+        range.to_range(self.len()).and_then(|range| {
+            if range.start == range.end {
+                return None;
+            }
+            // The largest `k` such that 2^k <= `range.len()`.
+            let k = (0usize.leading_zeros() - range.len().leading_zeros() - 1) as usize;
+            let row = &self.table[k];
+            Some((self.aggregate)(&row[range.start], &row[range.end - (1 << k)]))
+        })
+    }
+}
+
+pub trait IntoSparseTable: Sized + IntoIterator
+where
+    Self::Item: Clone
+{
+    fn sparse_table<F>(self, aggregate: F) -> SparseTable<Self::Item, F>
+    where
+        F: Fn(&Self::Item, &Self::Item) -> Self::Item
+    {
+        SparseTable::from_vec(self.into_iter().collect(), aggregate)
+    }
+}
+
+impl<I: IntoIterator> IntoSparseTable for I where Self::Item: Clone {}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(945);
+
+        for len in 0..40 {
+            let items: Vec<i32> = (0..len).map(|_| rng.gen_range(-100, 100)).collect();
+            let table = items.clone().sparse_table(|&x, &y| std::cmp::min(x, y));
+            assert_eq!(table.len(), len);
+            assert_eq!(table.items(), &items[..]);
+            for l in 0..len+1 {
+                for r in l..len+1 {
+                    assert_eq!(table.query(l..r), items[l..r].iter().cloned().min());
+                }
+            }
+            assert_eq!(table.query(0..len+1), None);
+        }
+    }
+}
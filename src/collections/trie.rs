@@ -0,0 +1,275 @@
+//! Trie counting strings by their prefixes.
+
+// BEGIN SNIPPET trie
+
+/// Trie of byte strings, counting inserted strings by their prefixes.
+///
+/// The alphabet is a range of bytes, `b'a'..=b'z'` by default.
+/// Nodes are kept in an arena, where the children of each node are
+/// a fixed-length block of indices to the arena.
+/// Inserting a string of length *m* takes O(*m*) time,
+/// and the memory of O(*m*·*k*) is used, as *k* is the size of the alphabet.
+///
+/// # Example
+///
+/// The sum of the lengths of the longest common prefixes over all pairs of strings
+/// is the sum of the number of pairs sharing each non-empty prefix.
+///
+/// ```
+/// # use atcoder_snippets::collections::trie::*;
+/// let mut trie = Trie::new();
+/// for s in &["abc", "abd", "ab", "b"] {
+///     trie.insert(s.as_bytes());
+/// }
+/// assert_eq!(trie.count_prefix(b"ab"), 3);
+/// assert_eq!(trie.count_exact(b"ab"), 1);
+///
+/// let sum: usize = trie.nodes()
+///     .filter(|node| node.depth > 0)
+///     .map(|node| node.count_prefix * (node.count_prefix - 1) / 2)
+///     .sum();
+/// // "abc" and "abd": 2, "abc" and "ab": 2, "abd" and "ab": 2
+/// assert_eq!(sum, 6);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Trie {
+    min_byte: u8,
+    alphabet_len: usize,
+    // Children of node `i` are `children[i * alphabet_len..(i + 1) * alphabet_len]`.
+    children: Vec<Option<usize>>,
+    count_prefix: Vec<usize>,
+    count_exact: Vec<usize>
+}
+
+/// A node of a trie, yielded by [`nodes`](struct.Trie.html#method.nodes) method.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TrieNode {
+    /// Index of the node, which is 0 for the root.
+    pub id: usize,
+    /// The length of the prefix corresponding to the node.
+    pub depth: usize,
+    /// The last byte of the prefix, or `None` for the root.
+    pub byte: Option<u8>,
+    /// How many inserted strings have the prefix.
+    pub count_prefix: usize,
+    /// How many inserted strings are equal to the prefix.
+    pub count_exact: usize
+}
+
+/// An iterator created by [`nodes`](struct.Trie.html#method.nodes) method on tries.
+pub struct TrieNodes<'a> {
+    trie: &'a Trie,
+    stack: Vec<(usize, usize, Option<u8>)>
+}
+
+impl<'a> Iterator for TrieNodes<'a> {
+    type Item = TrieNode;
+
+    fn next(&mut self) -> Option<TrieNode> {
+        let (id, depth, byte) = self.stack.pop()?;
+        for c in (0..self.trie.alphabet_len).rev() {
+            if let Some(child) = self.trie.children[id * self.trie.alphabet_len + c] {
+                self.stack.push((child, depth + 1, Some(self.trie.min_byte + c as u8)));
+            }
+        }
+        Some(TrieNode {
+            id,
+            depth,
+            byte,
+            count_prefix: self.trie.count_prefix[id],
+            count_exact: self.trie.count_exact[id]
+        })
+    }
+}
+
+impl Trie {
+    /// Creates an empty trie for lowercase alphabets.
+    pub fn new() -> Trie {
+        Trie::with_alphabet(b'a'..=b'z')
+    }
+
+    /// Creates an empty trie for strings composed of bytes in `alphabet`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alphabet` is empty.
+    pub fn with_alphabet(alphabet: std::ops::RangeInclusive<u8>) -> Trie {
+        assert!(alphabet.start() <= alphabet.end(), "alphabet is empty");
+        let alphabet_len = (*alphabet.end() - *alphabet.start()) as usize + 1;
+        Trie {
+            min_byte: *alphabet.start(),
+            alphabet_len,
+            children: vec![None; alphabet_len],
+            count_prefix: vec![0],
+            count_exact: vec![0]
+        }
+    }
+
+    fn index_of(&self, byte: u8) -> usize {
+        let c = byte.wrapping_sub(self.min_byte) as usize;
+        assert!(c < self.alphabet_len, "{:?} is out of the alphabet", byte as char);
+        c
+    }
+
+    /// Gets how many strings are inserted.
+    pub fn len(&self) -> usize {
+        self.count_prefix[0]
+    }
+
+    /// Returns whether no strings are inserted.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the child of `node` by `byte`, if it exists.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte` is out of the alphabet.
+    pub fn child(&self, node: usize, byte: u8) -> Option<usize> {
+        self.children[node * self.alphabet_len + self.index_of(byte)]
+    }
+
+    /// Gets the node corresponding to `prefix`, if some inserted string has the prefix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix` contains a byte out of the alphabet.
+    pub fn find(&self, prefix: &[u8]) -> Option<usize> {
+        prefix.iter().try_fold(0, |node, &byte| self.child(node, byte))
+    }
+
+    /// Inserts `s`, and returns the node corresponding to `s`.
+    ///
+    /// The same string can be inserted multiple times.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` contains a byte out of the alphabet.
+    pub fn insert(&mut self, s: &[u8]) -> usize {
+        let mut node = 0;
+        self.count_prefix[0] += 1;
+        for &byte in s {
+            let i = node * self.alphabet_len + self.index_of(byte);
+            node = match self.children[i] {
+                Some(child) => child,
+                None => {
+                    let child = self.count_prefix.len();
+                    self.children[i] = Some(child);
+                    let len = self.children.len() + self.alphabet_len;
+                    self.children.resize(len, None);
+                    self.count_prefix.push(0);
+                    self.count_exact.push(0);
+                    child
+                }
+            };
+            self.count_prefix[node] += 1;
+        }
+        self.count_exact[node] += 1;
+        node
+    }
+
+    /// Gets how many inserted strings have `prefix` as a prefix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix` contains a byte out of the alphabet.
+    pub fn count_prefix(&self, prefix: &[u8]) -> usize {
+        self.find(prefix).map_or(0, |node| self.count_prefix[node])
+    }
+
+    /// Gets how many inserted strings are equal to `s`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` contains a byte out of the alphabet.
+    pub fn count_exact(&self, s: &[u8]) -> usize {
+        self.find(s).map_or(0, |node| self.count_exact[node])
+    }
+
+    /// Creates an iterator yielding all the nodes in preorder,
+    /// where children are visited in the order of bytes.
+    pub fn nodes(&self) -> TrieNodes<'_> {
+        TrieNodes {
+            trie: self,
+            stack: vec![(0, 0, None)]
+        }
+    }
+}
+
+impl Default for Trie {
+    fn default() -> Trie {
+        Trie::new()
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_words() {
+        let mut trie = Trie::new();
+        assert!(trie.is_empty());
+        for s in &["a", "ab", "abc", "abc", "abd", "b", ""] {
+            trie.insert(s.as_bytes());
+        }
+        assert_eq!(trie.len(), 7);
+
+        assert_eq!(trie.count_prefix(b""), 7);
+        assert_eq!(trie.count_prefix(b"a"), 5);
+        assert_eq!(trie.count_prefix(b"ab"), 4);
+        assert_eq!(trie.count_prefix(b"abc"), 2);
+        assert_eq!(trie.count_prefix(b"abcd"), 0);
+        assert_eq!(trie.count_prefix(b"b"), 1);
+        assert_eq!(trie.count_prefix(b"c"), 0);
+
+        assert_eq!(trie.count_exact(b""), 1);
+        assert_eq!(trie.count_exact(b"ab"), 1);
+        assert_eq!(trie.count_exact(b"abc"), 2);
+        assert_eq!(trie.count_exact(b"abcd"), 0);
+        assert_eq!(trie.count_exact(b"ba"), 0);
+    }
+
+    #[test]
+    fn test_nodes() {
+        let mut trie = Trie::new();
+        for s in &["ba", "ab", "abc", "ab"] {
+            trie.insert(s.as_bytes());
+        }
+        let nodes: Vec<(usize, Option<u8>, usize, usize)> = trie.nodes()
+            .map(|node| (node.depth, node.byte, node.count_prefix, node.count_exact))
+            .collect();
+        assert_eq!(nodes, vec![
+            (0, None, 4, 0),
+            (1, Some(b'a'), 3, 0),
+            (2, Some(b'b'), 3, 2),
+            (3, Some(b'c'), 1, 1),
+            (1, Some(b'b'), 1, 0),
+            (2, Some(b'a'), 1, 1)
+        ]);
+
+        let ab = trie.find(b"ab").unwrap();
+        assert_eq!(trie.child(ab, b'c'), trie.find(b"abc"));
+        assert_eq!(trie.child(ab, b'd'), None);
+    }
+
+    #[test]
+    fn test_custom_alphabet() {
+        let mut trie = Trie::with_alphabet(b'0'..=b'1');
+        trie.insert(b"0110");
+        trie.insert(b"01");
+        assert_eq!(trie.count_prefix(b"01"), 2);
+        assert_eq!(trie.count_prefix(b"011"), 1);
+        assert_eq!(trie.nodes().count(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "'2' is out of the alphabet")]
+    fn test_out_of_alphabet() {
+        let mut trie = Trie::with_alphabet(b'0'..=b'1');
+        trie.insert(b"012");
+    }
+}
@@ -8,68 +8,56 @@
 /// Thanks to union-by-size and path-compression strategy,
 /// average cost of each operation is so much low that
 /// it can be regarded as constant time, although theoretically it is not constant.
+///
+/// Unlike [`HashUnionFindSets`](../hash_union_find_sets/struct.HashUnionFindSets.html),
+/// items are indices of plain `Vec`s, and methods panic if an item is out of range,
+/// since items are known to be valid in most cases.
 pub struct VecUnionFindSets {
     // Maintaining `set_count` can be an unnecessary cost,
     // but that frees users from maintaining it
     // by checking the returned values for all `add` and `unite` operations.
-    set_count: usize,  items: Vec<VecUnionFindNode>
-}
-
-#[derive(Clone)]
-struct VecUnionFindNode {
-    parent: std::cell::Cell<usize>,
-    len: usize
-}
-
-impl VecUnionFindNode {
-    fn new(item: usize) -> VecUnionFindNode {
-        VecUnionFindNode {
-            parent: std::cell::Cell::new(item),
-            len: 1
-        }
-    }
+    set_count: usize,
+    // `Cell` for path compression in methods taking `&self`.
+    parent: Vec<std::cell::Cell<usize>>,
+    // Valid only for roots.
+    size: Vec<usize>,
+    // Items of the set for each root, and empty for non-roots.
+    members: Vec<Vec<usize>>,
+    // Counted by `unite_counting`. Valid only for roots.
+    edges: Vec<usize>
 }
 
 impl VecUnionFindSets {
-    /// Creates empty sets.
-    pub fn new() -> VecUnionFindSets {
-        VecUnionFindSets {
+    /// Creates `n` singleton sets of items `0..n`.
+    pub fn new(n: usize) -> VecUnionFindSets {
+        let mut sets = VecUnionFindSets {
             set_count: 0,
-            items: Vec::new()
-        }
-    }
-
-    /// Creates `count` singleton sets.
-    pub fn with_items(items_count: usize) -> VecUnionFindSets {
-        let mut sets = VecUnionFindSets::new();
-        sets.add_items(items_count);
+            parent: Vec::new(),
+            size: Vec::new(),
+            members: Vec::new(),
+            edges: Vec::new()
+        };
+        sets.add_items(n);
         sets
     }
 
-    fn error_msg(items: &[usize]) -> String {
-        assert!(items.len() == 1 || items.len() == 2);
-        if items.len() == 1 {
-            format!("no set contains {}", items[0])
-        } else {
-            format!("no set contains {} and no set contains {}", items[0], items[1])
-        }
-    }
-
     /// Adds `count` items labeled `n..n+count`, where `n` is how many items the sets currently have.
     ///
     /// # Example
     ///
     /// ```
     /// # use atcoder_snippets::collections::vec_union_find_sets::*;
-    /// let mut sets = VecUnionFindSets::new();
+    /// let mut sets = VecUnionFindSets::new(0);
     /// sets.add_items(3);
     /// assert_eq!(sets.count(), 3);
     /// ```
     pub fn add_items(&mut self, count: usize) {
         self.set_count += count;
-        let new_items = (self.items_len()..self.items_len()+count)
-            .map(|i| VecUnionFindNode::new(i));
-        self.items.extend(new_items);
+        let n = self.items_len();
+        self.parent.extend((n..n+count).map(std::cell::Cell::new));
+        self.size.resize(n + count, 1);
+        self.members.extend((n..n+count).map(|item| vec![item]));
+        self.edges.resize(n + count, 0);
     }
 
     /// Returns how many items are contained by all the sets.
@@ -78,31 +66,52 @@ impl VecUnionFindSets {
     ///
     /// ```
     /// # use atcoder_snippets::collections::vec_union_find_sets::*;
-    /// let mut sets = VecUnionFindSets::with_items(2);
+    /// let mut sets = VecUnionFindSets::new(2);
     /// assert_eq!(sets.items_len(), 2);
-    /// sets.unite(0, 1).unwrap();
+    /// sets.unite(0, 1);
     /// assert_eq!(sets.items_len(), 2);
     /// ```
     pub fn items_len(&self) -> usize {
-        self.items.len()
+        self.parent.len()
     }
 
-    fn find(&self, item: usize) -> Option<usize> {
+    /// Returns the representative item of the set containing `item`.
+    ///
+    /// Two items are in the same set if and only if their roots are the same.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item` is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::vec_union_find_sets::*;
+    /// let mut sets = VecUnionFindSets::new(3);
+    /// assert_eq!(sets.root(1), 1);
+    /// sets.unite(0, 1);
+    /// assert_eq!(sets.root(0), sets.root(1));
+    /// assert_ne!(sets.root(0), sets.root(2));
+    /// ```
+    pub fn root(&self, item: usize) -> usize {
         if item >= self.items_len() {
-            return None;
+            panic!("item {} is out of range: the sets have {} items", item, self.items_len());
         }
 
-        fn go(sets: &VecUnionFindSets, item: usize) -> usize {
-            let node = &sets.items[item];
-            if node.parent.get() == item {
-                return item;
-            }
+        let mut root = item;
+        while self.parent[root].get() != root {
+            root = self.parent[root].get();
+        }
 
-            let root = go(sets, node.parent.get());
-            sets.items[root].parent.set(root);
-            root
+        // Path compression without recursion, not to overflow the stack.
+        let mut current = item;
+        while current != root {
+            let next = self.parent[current].get();
+            self.parent[current].set(root);
+            current = next;
         }
-        Some(go(self, item))
+
+        root
     }
 
     /// Returns how many sets `self` contains.
@@ -111,9 +120,9 @@ impl VecUnionFindSets {
     ///
     /// ```
     /// # use atcoder_snippets::collections::vec_union_find_sets::*;
-    /// let mut sets = VecUnionFindSets::with_items(2);
+    /// let mut sets = VecUnionFindSets::new(2);
     /// assert_eq!(sets.count(), 2);
-    /// sets.unite(0, 1).unwrap();
+    /// sets.unite(0, 1);
     /// assert_eq!(sets.count(), 1);
     /// ```
     pub fn count(&self) -> usize {
@@ -122,85 +131,151 @@ impl VecUnionFindSets {
 
     /// Returns how many items `self` contains by the set which has `item`.
     ///
-    /// If no set contains `item`, returns `Err` with an error message.
+    /// # Panics
+    ///
+    /// Panics if `item` is out of range.
     ///
     /// # Example
     ///
     /// ```
     /// # use atcoder_snippets::collections::vec_union_find_sets::*;
-    /// let mut sets = VecUnionFindSets::with_items(2);
-    ///
-    /// assert_eq!(sets.len_of(0), Ok(1));
-    /// sets.unite(0, 1).unwrap();
-    /// assert_eq!(sets.len_of(0), Ok(2));
+    /// let mut sets = VecUnionFindSets::new(2);
     ///
-    /// assert!(sets.len_of(2).is_err());
+    /// assert_eq!(sets.len_of(0), 1);
+    /// sets.unite(0, 1);
+    /// assert_eq!(sets.len_of(0), 2);
     /// ```
-    pub fn len_of(&self, item: usize) -> Result<usize, String> {
-        self.find(item).map(|root| self.items[root].len).ok_or_else(|| {
-            VecUnionFindSets::error_msg(&[item])
-        })
+    pub fn len_of(&self, item: usize) -> usize {
+        self.size[self.root(item)]
     }
 
     /// Returns if two sets containing `item1` and `item2` are the same one.
     ///
-    /// If no set contains `item1` or `item2`, returns `Err` with an error message.
+    /// # Panics
+    ///
+    /// Panics if `item1` or `item2` is out of range.
     ///
     /// # Example
     ///
     /// ```
     /// # use atcoder_snippets::collections::vec_union_find_sets::*;
-    /// let mut sets = VecUnionFindSets::with_items(2);
+    /// let mut sets = VecUnionFindSets::new(2);
     ///
-    /// assert_eq!(sets.set_eq(0, 1), Ok(false));
+    /// assert!(!sets.set_eq(0, 1));
     /// sets.unite(0, 1);
-    /// assert_eq!(sets.set_eq(0, 1), Ok(true));
-    ///
-    /// assert!(sets.set_eq(0, 2).is_err());
-    /// assert!(sets.set_eq(2, 3).is_err());
+    /// assert!(sets.set_eq(0, 1));
     /// ```
-    pub fn set_eq(&self, item1: usize, item2: usize) -> Result<bool, String> {
-        match (self.find(item1), self.find(item2)) {
-            (Some(root1), Some(root2)) => Ok(root1 == root2),
-            (Some(_), None) => Err(VecUnionFindSets::error_msg(&[item2])),
-            (None, Some(_)) => Err(VecUnionFindSets::error_msg(&[item1])),
-            (None, None) => Err(VecUnionFindSets::error_msg(&[item1, item2])),
-        }
+    pub fn set_eq(&self, item1: usize, item2: usize) -> bool {
+        self.root(item1) == self.root(item2)
     }
 
     /// Merges two sets, set containing `item1` and set containing `item2`.
     ///
-    /// If the two sets are same (already merged ones), do nothing and returns `Ok(false)`.
-    ///
-    /// If no set contains `item1` or `item2`, returns `Err` with an error message.
-    pub fn unite(&mut self, item1: usize, item2: usize) -> Result<bool, String> {
-        match (self.find(item1), self.find(item2)) {
-            (Some(root1), Some(root2)) => {
-                if root1 == root2 {
-                    return Ok(false);
-                }
-
-                self.set_count -= 1;
-                let (new_root, new_child) = if self.items[root1].len < self.items[root2].len {
-                    (root2, root1)
-                } else {
-                    (root1, root2)
-                };
-                let new_len = self.items[root1].len + self.items[root2].len;
-                self.items[new_root] = VecUnionFindNode {
-                    parent: std::cell::Cell::new(new_root),
-                    len: new_len
-                };
-                self.items[new_child] = VecUnionFindNode {
-                    parent: std::cell::Cell::new(new_root),
-                    len: 0
-                };
-                Ok(true)
-            },
-            (Some(_), None) => Err(VecUnionFindSets::error_msg(&[item2])),
-            (None, Some(_)) => Err(VecUnionFindSets::error_msg(&[item1])),
-            (None, None) => Err(VecUnionFindSets::error_msg(&[item1, item2]))
+    /// If the two sets are same (already merged ones), do nothing and returns `false`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item1` or `item2` is out of range.
+    pub fn unite(&mut self, item1: usize, item2: usize) -> bool {
+        let root1 = self.root(item1);
+        let root2 = self.root(item2);
+        if root1 == root2 {
+            return false;
         }
+
+        self.set_count -= 1;
+        let (new_root, new_child) = if self.size[root1] < self.size[root2] {
+            (root2, root1)
+        } else {
+            (root1, root2)
+        };
+        self.parent[new_child].set(new_root);
+        self.size[new_root] += self.size[new_child];
+        self.edges[new_root] += self.edges[new_child];
+        // Moves members of the smaller set into the larger one.
+        let child_members = std::mem::take(&mut self.members[new_child]);
+        self.members[new_root].extend(child_members);
+        true
+    }
+
+    /// Merges two sets like [`unite`](#method.unite), and counts an edge between `item1` and `item2`
+    /// for the merged set.
+    ///
+    /// The edge is counted even if the two sets are the same,
+    /// including the case `item1` and `item2` are the same.
+    /// Each set has the number of edges counted for the items in the set,
+    /// which is returned by [`edges_of`](#method.edges_of).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item1` or `item2` is out of range.
+    ///
+    /// # Example
+    ///
+    /// Whether each connected component of a graph is a tree.
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::vec_union_find_sets::*;
+    /// let mut sets = VecUnionFindSets::new(5);
+    /// for &(u, v) in &[(0, 1), (1, 2), (3, 4)] {
+    ///     sets.unite_counting(u, v);
+    /// }
+    /// let is_forest = |sets: &VecUnionFindSets| {
+    ///     (0..5).all(|x| sets.edges_of(x) + 1 == sets.len_of(x))
+    /// };
+    /// assert!(is_forest(&sets));
+    ///
+    /// sets.unite_counting(2, 0);
+    /// assert!(!is_forest(&sets));
+    /// ```
+    pub fn unite_counting(&mut self, item1: usize, item2: usize) -> bool {
+        let united = self.unite(item1, item2);
+        let root = self.root(item1);
+        self.edges[root] += 1;
+        united
+    }
+
+    /// Returns how many edges are counted by [`unite_counting`](#method.unite_counting)
+    /// for the set which has `item`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item` is out of range.
+    pub fn edges_of(&self, item: usize) -> usize {
+        self.edges[self.root(item)]
+    }
+
+    /// Returns all the items of the set containing `item`, in unspecified order.
+    ///
+    /// It takes time proportional to the size of the set, not to the number of all the items.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item` is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::vec_union_find_sets::*;
+    /// let mut sets = VecUnionFindSets::new(4);
+    /// sets.unite(0, 2);
+    ///
+    /// let mut members = sets.members_of(2).to_vec();
+    /// members.sort();
+    /// assert_eq!(members, vec![0, 2]);
+    /// ```
+    pub fn members_of(&self, item: usize) -> &[usize] {
+        &self.members[self.root(item)]
+    }
+
+    /// Returns all the sets, each of which is a slice of items.
+    ///
+    /// Sets are ordered by their roots, and the order of items in each set is unspecified.
+    pub fn groups(&self) -> Vec<&[usize]> {
+        self.members.iter()
+            .filter(|members| !members.is_empty())
+            .map(|members| members.as_slice())
+            .collect()
     }
 
     /// All sets as an iterator yielding `Vec<usize>`.
@@ -213,10 +288,10 @@ impl VecUnionFindSets {
     /// # use atcoder_snippets::collections::vec_union_find_sets::*;
     /// use std::collections::HashSet;
     ///
-    /// let mut sets = VecUnionFindSets::with_items(6);
-    /// sets.unite(0, 1).unwrap();
-    /// sets.unite(2, 3).unwrap();
-    /// sets.unite(3, 4).unwrap();
+    /// let mut sets = VecUnionFindSets::new(6);
+    /// sets.unite(0, 1);
+    /// sets.unite(2, 3);
+    /// sets.unite(3, 4);
     ///
     /// let sets: HashSet<Vec<usize>> = sets.iter_cloned().collect();
     /// assert_eq!(sets.len(), 3);
@@ -225,12 +300,15 @@ impl VecUnionFindSets {
     /// assert!(sets.contains(&vec![5]));
     /// ```
     pub fn iter_cloned(&self) -> impl Iterator<Item=Vec<usize>> {
-        let mut sets = vec![Vec::new(); self.items_len()];
-        for i in 0..self.items_len() {
-            let repr = self.find(i).unwrap();
-            sets[repr].push(i);
-        }
-        sets.into_iter().filter(|v| !v.is_empty())
+        let sets: Vec<Vec<usize>> = self.groups()
+            .into_iter()
+            .map(|members| {
+                let mut set = members.to_vec();
+                set.sort();
+                set
+            })
+            .collect();
+        sets.into_iter()
     }
 }
 
@@ -267,67 +345,67 @@ mod tests {
 
     #[test]
     fn test_set_eq() {
-        let mut sets = VecUnionFindSets::with_items(20);
+        let mut sets = VecUnionFindSets::new(20);
 
         // unite in sequential order
         for i in 0..9 {
-            sets.unite(i, i+1).unwrap();
+            sets.unite(i, i+1);
         }
 
         for i in 0..10 {
             for j in 0..10 {
-                assert!(sets.set_eq(i, j).unwrap());
+                assert!(sets.set_eq(i, j));
             }
         }
         for i in 0..10 {
             for j in 10..20 {
-                assert!(!sets.set_eq(i, j).unwrap());
+                assert!(!sets.set_eq(i, j));
             }
         }
 
         // unite in random order
-        sets.unite(10, 11).unwrap();
-        sets.unite(12, 13).unwrap();
-        sets.unite(10, 12).unwrap();
+        sets.unite(10, 11);
+        sets.unite(12, 13);
+        sets.unite(10, 12);
 
-        sets.unite(14, 15).unwrap();
-        sets.unite(16, 17).unwrap();
-        sets.unite(17, 18).unwrap();
-        sets.unite(14, 17).unwrap();
+        sets.unite(14, 15);
+        sets.unite(16, 17);
+        sets.unite(17, 18);
+        sets.unite(14, 17);
 
-        sets.unite(10, 14).unwrap();
-        sets.unite(10, 19).unwrap();
+        sets.unite(10, 14);
+        sets.unite(10, 19);
 
         for i in 10..20 {
             for j in 10..20 {
-                assert!(sets.set_eq(i, j).unwrap());
+                assert!(sets.set_eq(i, j));
             }
         }
         for i in 0..10 {
             for j in 10..20 {
-                assert!(!sets.set_eq(i, j).unwrap());
+                assert!(!sets.set_eq(i, j));
             }
         }
     }
 
     #[test]
     fn test_count() {
-        let mut sets = VecUnionFindSets::new();
+        let mut sets = VecUnionFindSets::new(0);
         assert_eq!(sets.count(), 0);
 
         sets.add_items(6);
         assert_eq!(sets.count(), 6);
 
-        sets.unite(0, 1).unwrap();
+        sets.unite(0, 1);
         assert_eq!(sets.count(), 5);
-        sets.unite(2, 3).unwrap();
+        sets.unite(2, 3);
         assert_eq!(sets.count(), 4);
-        sets.unite(3, 4).unwrap();
+        sets.unite(3, 4);
         assert_eq!(sets.count(), 3);
-        sets.unite(0, 2).unwrap();
+        sets.unite(0, 2);
         assert_eq!(sets.count(), 2);
 
-        sets.unite(1, 3).unwrap();
+        sets.unite(1, 3);
         assert_eq!(sets.count(), 2);
 
         sets.add_items(3);
@@ -336,19 +414,53 @@ mod tests {
 
     #[test]
     fn test_len_of() {
-        let mut sets = VecUnionFindSets::with_items(6);
-        assert_eq!(sets.len_of(0).unwrap(), 1);
+        let mut sets = VecUnionFindSets::new(6);
+        assert_eq!(sets.len_of(0), 1);
+
+        sets.unite(0, 1);
+        assert_eq!(sets.len_of(0), 2);
+
+        sets.unite(2, 5);
+        sets.unite(3, 4);
+        sets.unite(2, 4);
+        assert_eq!(sets.len_of(3), 4);
 
-        sets.unite(0, 1).unwrap();
-        assert_eq!(sets.len_of(0).unwrap(), 2);
+        sets.unite(0, 5);
+        assert_eq!(sets.len_of(4), 6);
+    }
 
-        sets.unite(2, 5).unwrap();
-        sets.unite(3, 4).unwrap();
-        sets.unite(2, 4).unwrap();
-        assert_eq!(sets.len_of(3).unwrap(), 4);
+    #[test]
+    fn test_root() {
+        let mut sets = VecUnionFindSets::new(5);
+        assert!((0..5).all(|i| sets.root(i) == i));
+
+        sets.unite(0, 1);
+        sets.unite(1, 2);
+        sets.unite(3, 4);
+        let root = sets.root(0);
+        assert!(root < 3);
+        assert!((0..3).all(|i| sets.root(i) == root));
+        assert_eq!(sets.root(3), sets.root(4));
+        assert_ne!(sets.root(3), root);
+    }
 
-        sets.unite(0, 5).unwrap();
-        assert_eq!(sets.len_of(4).unwrap(), 6);
+    #[test]
+    fn test_many_items() {
+        let n = 1_000_000;
+        let mut sets = VecUnionFindSets::new(n);
+        for i in (0..n-1).rev() {
+            sets.unite(i, i+1);
+        }
+        assert_eq!(sets.count(), 1);
+        assert_eq!(sets.len_of(0), n);
+        assert!(sets.set_eq(0, n-1));
+    }
+
+    #[test]
+    #[should_panic(expected = "item 3 is out of range: the sets have 3 items")]
+    fn test_out_of_range() {
+        let sets = VecUnionFindSets::new(3);
+        sets.set_eq(0, 3);
     }
 
     #[test]
@@ -358,13 +470,13 @@ mod tests {
             sets.iter_cloned().collect()
         }
 
-        let mut sets = VecUnionFindSets::with_items(10);
-        sets.unite(0, 1).unwrap();
-        sets.unite(3, 2).unwrap();
-        sets.unite(3, 4).unwrap();
-        sets.unite(5, 6).unwrap();
-        sets.unite(6, 7).unwrap();
-        sets.unite(7, 8).unwrap();
+        let mut sets = VecUnionFindSets::new(10);
+        sets.unite(0, 1);
+        sets.unite(3, 2);
+        sets.unite(3, 4);
+        sets.unite(5, 6);
+        sets.unite(6, 7);
+        sets.unite(7, 8);
         let expected: HashSet<Vec<usize>> = vec![
             vec![0, 1],
             vec![2, 3, 4],
@@ -373,4 +485,56 @@ mod tests {
         ].into_iter().collect();
         assert_eq!(get(&sets), expected);
     }
+
+    #[test]
+    fn test_members() {
+        let mut sets = VecUnionFindSets::new(10);
+        sets.unite(0, 1);
+        sets.unite(3, 2);
+        sets.unite(1, 3);
+        sets.unite(2, 0);
+        sets.unite(8, 9);
+        sets.add_items(2);
+
+        let sorted_members = |item| {
+            let mut members = sets.members_of(item).to_vec();
+            members.sort();
+            members
+        };
+        for i in 0..4 {
+            assert_eq!(sorted_members(i), vec![0, 1, 2, 3]);
+        }
+        assert_eq!(sorted_members(9), vec![8, 9]);
+        assert_eq!(sorted_members(11), vec![11]);
+
+        let groups = sets.groups();
+        assert_eq!(groups.len(), sets.count());
+        let mut all: Vec<usize> = groups.iter().flat_map(|group| group.iter().cloned()).collect();
+        all.sort();
+        assert_eq!(all, (0..12).collect::<Vec<_>>());
+        for group in &groups {
+            for &item in group.iter() {
+                assert_eq!(sets.len_of(item), group.len());
+                assert_eq!(sets.root(item), sets.root(group[0]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_edges() {
+        let mut sets = VecUnionFindSets::new(5);
+        assert_eq!(sets.edges_of(0), 0);
+        assert!(sets.unite_counting(0, 1));
+        assert!(!sets.unite_counting(1, 0));
+        assert!(!sets.unite_counting(1, 1));
+        assert_eq!(sets.edges_of(0), 3);
+        assert_eq!(sets.len_of(0), 2);
+
+        sets.unite_counting(2, 3);
+        sets.unite(3, 0);
+        assert_eq!(sets.edges_of(2), 4);
+        assert_eq!(sets.edges_of(1), 4);
+        assert_eq!(sets.len_of(1), 4);
+        assert_eq!(sets.edges_of(4), 0);
+    }
 }
@@ -0,0 +1,268 @@
+//! Wavelet matrix answering rank and quantile queries on a static sequence.
+
+use crate::range::UsizeRangeBoundsExt;
+
+// BEGIN SNIPPET wavelet_matrix DEPENDS ON range
+
+// Bit vector answering the number of 1s in a prefix in constant time.
+#[derive(Clone, Debug)]
+struct WaveletBitVector {
+    blocks: Vec<u64>,
+    // `ranks[i]` is the number of 1s in `blocks[..i]`.
+    ranks: Vec<u32>
+}
+
+impl WaveletBitVector {
+    fn new(bits: &[bool]) -> WaveletBitVector {
+        let mut blocks = vec![0u64; bits.len() / 64 + 1];
+        for (i, &bit) in bits.iter().enumerate() {
+            if bit {
+                blocks[i / 64] |= 1 << (i % 64);
+            }
+        }
+        let mut ranks = Vec::with_capacity(blocks.len());
+        let mut rank = 0;
+        for block in &blocks {
+            ranks.push(rank);
+            rank += block.count_ones();
+        }
+        WaveletBitVector { blocks, ranks }
+    }
+
+    // The number of 1s in the first `i` bits.
+    fn rank1(&self, i: usize) -> usize {
+        let mask = (1u64 << (i % 64)).wrapping_sub(1);
+        self.ranks[i / 64] as usize + (self.blocks[i / 64] & mask).count_ones() as usize
+    }
+
+    // The number of 0s in the first `i` bits.
+    fn rank0(&self, i: usize) -> usize {
+        i - self.rank1(i)
+    }
+}
+
+/// Static sequence of `u64` answering rank, quantile and frequency queries on an interval.
+///
+/// The matrix has a bit vector for each bit of values, from the highest one.
+/// Creation takes O(*n* log(*σ*)) time, and each query takes O(log(*σ*)) time,
+/// as *σ* is the maximum value in the sequence.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::collections::wavelet_matrix::*;
+/// let matrix = WaveletMatrix::new(&[3, 1, 4, 1, 5, 9, 2, 6]);
+/// assert_eq!(matrix.rank(1, ..), Some(2));
+/// assert_eq!(matrix.rank(1, 2..), Some(1));
+/// // 0-origin k-th smallest
+/// assert_eq!(matrix.quantile(2..6, 0), Some(1));
+/// assert_eq!(matrix.quantile(2..6, 3), Some(9));
+/// assert_eq!(matrix.quantile(2..6, 4), None);
+/// // Values in [2, 6)
+/// assert_eq!(matrix.range_freq(.., 2..6), Some(4));
+/// ```
+#[derive(Clone, Debug)]
+pub struct WaveletMatrix {
+    len: usize,
+    // Bit vectors from the highest bit.
+    levels: Vec<WaveletBitVector>,
+    // The number of 0s in each level.
+    zeros: Vec<usize>
+}
+
+impl WaveletMatrix {
+    /// Creates a matrix from a slice.
+    pub fn new(seq: &[u64]) -> WaveletMatrix {
+        let max = seq.iter().cloned().max().unwrap_or(0);
+        let bit_len = std::cmp::max(1, 64 - max.leading_zeros() as usize);
+
+        let mut current = seq.to_vec();
+        let mut levels = Vec::with_capacity(bit_len);
+        let mut zeros = Vec::with_capacity(bit_len);
+        for b in (0..bit_len).rev() {
+            let bits: Vec<bool> = current.iter().map(|&x| x >> b & 1 == 1).collect();
+            levels.push(WaveletBitVector::new(&bits));
+            let (mut next, ones): (Vec<u64>, Vec<u64>) = current.iter().partition(|&&x| x >> b & 1 == 0);
+            zeros.push(next.len());
+            next.extend(ones);
+            current = next;
+        }
+
+        WaveletMatrix { len: seq.len(), levels, zeros }
+    }
+
+    /// The length of the sequence.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn bit_len(&self) -> usize {
+        self.levels.len()
+    }
+
+    // Whether `value` is representable by the bits of the matrix.
+    fn fits(&self, value: u64) -> bool {
+        self.bit_len() >= 64 || value >> self.bit_len() == 0
+    }
+
+    /// Counts `value`s in `range`.
+    ///
+    /// If the range is out of bound, returns `None`.
+    pub fn rank<R: std::ops::RangeBounds<usize>>(&self, value: u64, range: R) -> Option<usize> {
+        let range = range.to_range(self.len)?;
+        if !self.fits(value) {
+            return Some(0);
+        }
+
+        let (mut l, mut r) = (range.start, range.end);
+        for (d, level) in self.levels.iter().enumerate() {
+            let b = self.bit_len() - 1 - d;
+            if value >> b & 1 == 0 {
+                l = level.rank0(l);
+                r = level.rank0(r);
+            } else {
+                l = self.zeros[d] + level.rank1(l);
+                r = self.zeros[d] + level.rank1(r);
+            }
+        }
+        Some(r - l)
+    }
+
+    /// Gets the `k`-th smallest value in `range`, where `k` is 0-origin.
+    ///
+    /// If the range is out of bound, or the range has `k` or less items, returns `None`.
+    pub fn quantile<R: std::ops::RangeBounds<usize>>(&self, range: R, k: usize) -> Option<u64> {
+        let range = range.to_range(self.len)?;
+        if k >= range.len() {
+            return None;
+        }
+
+        let (mut l, mut r, mut k) = (range.start, range.end, k);
+        let mut value = 0;
+        for (d, level) in self.levels.iter().enumerate() {
+            let (l0, r0) = (level.rank0(l), level.rank0(r));
+            value <<= 1;
+            if k < r0 - l0 {
+                l = l0;
+                r = r0;
+            } else {
+                k -= r0 - l0;
+                value |= 1;
+                l = self.zeros[d] + (l - l0);
+                r = self.zeros[d] + (r - r0);
+            }
+        }
+        Some(value)
+    }
+
+    // Counts values less than `upper` in `range`.
+    fn count_less(&self, range: &std::ops::Range<usize>, upper: u64) -> usize {
+        if !self.fits(upper) {
+            return range.len();
+        }
+
+        let (mut l, mut r) = (range.start, range.end);
+        let mut count = 0;
+        for (d, level) in self.levels.iter().enumerate() {
+            let b = self.bit_len() - 1 - d;
+            let (l0, r0) = (level.rank0(l), level.rank0(r));
+            if upper >> b & 1 == 0 {
+                l = l0;
+                r = r0;
+            } else {
+                count += r0 - l0;
+                l = self.zeros[d] + (l - l0);
+                r = self.zeros[d] + (r - r0);
+            }
+        }
+        count
+    }
+
+    /// Counts items in `range` whose values are in `values`.
+    ///
+    /// If the range is out of bound, returns `None`.
+    pub fn range_freq<R: std::ops::RangeBounds<usize>>(&self, range: R, values: std::ops::Range<u64>) -> Option<usize> {
+        let range = range.to_range(self.len)?;
+        if values.start >= values.end {
+            return Some(0);
+        }
+        Some(self.count_less(&range, values.end) - self.count_less(&range, values.start))
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+
+    fn check(rng: &mut Xorshift, seq: &[u64], max: u64) {
+        let matrix = WaveletMatrix::new(seq);
+        let len = seq.len();
+        assert_eq!(matrix.len(), len);
+
+        for _ in 0..200 {
+            let l = rng.gen_range(0..=len as u64) as usize;
+            let r = rng.gen_range(l as u64..=len as u64) as usize;
+            let slice = &seq[l..r];
+
+            let value = rng.gen_range(0..max);
+            assert_eq!(matrix.rank(value, l..r), Some(slice.iter().filter(|&&x| x == value).count()));
+
+            let mut sorted = slice.to_vec();
+            sorted.sort();
+            let k = rng.gen_range(0..=sorted.len() as u64) as usize;
+            assert_eq!(matrix.quantile(l..r, k), sorted.get(k).cloned());
+
+            let lo = rng.gen_range(0..max);
+            let hi = rng.gen_range(0..max);
+            let expected = slice.iter().filter(|&&x| lo <= x && x < hi).count();
+            assert_eq!(matrix.range_freq(l..r, lo..hi), Some(expected));
+        }
+
+        assert_eq!(matrix.rank(0, ..=len), None);
+        assert_eq!(matrix.quantile(..=len, 0), None);
+        assert_eq!(matrix.range_freq(..=len, 0..1), None);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = Xorshift::with_seed(1025);
+        let seq: Vec<u64> = (0..10000).map(|_| rng.gen_range(0..1000)).collect();
+        check(&mut rng, &seq, 1100);
+        let seq: Vec<u64> = (0..10000).map(|_| rng.gen_range(0..4)).collect();
+        check(&mut rng, &seq, 6);
+    }
+
+    #[test]
+    fn test_large_values() {
+        let mut rng = Xorshift::with_seed(1025);
+        let seq: Vec<u64> = (0..1000).map(|_| rng.gen_range(0..std::u64::MAX)).collect();
+        check(&mut rng, &seq, std::u64::MAX);
+
+        let matrix = WaveletMatrix::new(&[std::u64::MAX, 0, std::u64::MAX]);
+        assert_eq!(matrix.rank(std::u64::MAX, ..), Some(2));
+        assert_eq!(matrix.quantile(.., 2), Some(std::u64::MAX));
+        assert_eq!(matrix.range_freq(.., 1..std::u64::MAX), Some(0));
+    }
+
+    #[test]
+    fn test_empty_and_zeros() {
+        let matrix = WaveletMatrix::new(&[]);
+        assert!(matrix.is_empty());
+        assert_eq!(matrix.quantile(.., 0), None);
+        assert_eq!(matrix.rank(5, ..), Some(0));
+
+        let matrix = WaveletMatrix::new(&[0, 0, 0]);
+        assert_eq!(matrix.rank(0, 1..), Some(2));
+        assert_eq!(matrix.rank(2, ..), Some(0));
+        assert_eq!(matrix.quantile(.., 1), Some(0));
+        assert_eq!(matrix.range_freq(.., 0..100), Some(3));
+    }
+}
@@ -0,0 +1,282 @@
+//! Union-find maintaining differences of potentials between items.
+
+use crate::num::WithZero;
+
+// BEGIN SNIPPET weighted_union_find DEPENDS ON int
+
+/// Union-find for integers `0..n`, maintaining the difference of potentials
+/// between items in the same set.
+///
+/// Useful for difference constraints such as "`b` is exactly `w` larger than `a`".
+/// The weight type `W` is expected to be an abelian group such as `i64`.
+///
+/// Like [`VecUnionFindSets`](../vec_union_find_sets/struct.VecUnionFindSets.html),
+/// methods panic if an item is out of range.
+///
+/// # Example
+///
+/// Solves [AtCoder Beginner Contest 087 D: People on a Line](https://atcoder.jp/contests/abc087/tasks/arc090_b).
+///
+/// ```
+/// # use atcoder_snippets::collections::weighted_union_find::*;
+/// fn consistent(n: usize, facts: &[(usize, usize, i64)]) -> bool {
+///     let mut sets = WeightedUnionFind::new(n);
+///     facts.iter().all(|&(l, r, d)| sets.unite(l, r, d).is_ok())
+/// }
+///
+/// assert!(consistent(3, &[(0, 1, 1), (1, 2, 1), (0, 2, 2)]));
+/// assert!(!consistent(3, &[(0, 1, 1), (1, 2, 1), (0, 2, 5)]));
+/// ```
+pub struct WeightedUnionFind<W = i64> {
+    set_count: usize,
+    // `Cell` for path compression in methods taking `&self`.
+    parent: Vec<std::cell::Cell<usize>>,
+    // Potential of each item minus that of its parent.
+    weight: Vec<std::cell::Cell<W>>,
+    // Valid only for roots.
+    size: Vec<usize>
+}
+
+impl<W> WeightedUnionFind<W>
+where
+    W: Copy + PartialEq + std::fmt::Debug + WithZero +
+       std::ops::Add<Output=W> + std::ops::Sub<Output=W>
+{
+    /// Creates `n` singleton sets of items `0..n`.
+    pub fn new(n: usize) -> WeightedUnionFind<W> {
+        WeightedUnionFind {
+            set_count: n,
+            parent: (0..n).map(std::cell::Cell::new).collect(),
+            weight: (0..n).map(|_| std::cell::Cell::new(W::zero())).collect(),
+            size: vec![1; n]
+        }
+    }
+
+    /// Returns how many items are contained by all the sets.
+    pub fn items_len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Returns the root of the set containing `item`, and the potential of `item`
+    /// minus that of the root.
+    fn find(&self, item: usize) -> (usize, W) {
+        if item >= self.items_len() {
+            panic!("item {} is out of range: the sets have {} items", item, self.items_len());
+        }
+
+        let mut root = item;
+        let mut total = W::zero();
+        while self.parent[root].get() != root {
+            total = total + self.weight[root].get();
+            root = self.parent[root].get();
+        }
+
+        // Path compression without recursion, accumulating weights to the root.
+        let mut current = item;
+        let mut rest = total;
+        while current != root {
+            let next = self.parent[current].get();
+            let weight = self.weight[current].get();
+            self.parent[current].set(root);
+            self.weight[current].set(rest);
+            rest = rest - weight;
+            current = next;
+        }
+
+        (root, total)
+    }
+
+    /// Returns the representative item of the set containing `item`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item` is out of range.
+    pub fn root(&self, item: usize) -> usize {
+        self.find(item).0
+    }
+
+    /// Returns how many sets `self` contains.
+    pub fn count(&self) -> usize {
+        self.set_count
+    }
+
+    /// Returns how many items `self` contains by the set which has `item`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item` is out of range.
+    pub fn len_of(&self, item: usize) -> usize {
+        self.size[self.root(item)]
+    }
+
+    /// Returns if two sets containing `item1` and `item2` are the same one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item1` or `item2` is out of range.
+    pub fn set_eq(&self, item1: usize, item2: usize) -> bool {
+        self.root(item1) == self.root(item2)
+    }
+
+    /// Returns the potential of `item2` minus that of `item1`,
+    /// or `None` if they are in different sets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item1` or `item2` is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::collections::weighted_union_find::*;
+    /// let mut sets = WeightedUnionFind::new(3);
+    /// sets.unite(0, 1, 5).unwrap();
+    /// assert_eq!(sets.diff(0, 1), Some(5));
+    /// assert_eq!(sets.diff(1, 0), Some(-5));
+    /// assert_eq!(sets.diff(0, 2), None);
+    /// ```
+    pub fn diff(&self, item1: usize, item2: usize) -> Option<W> {
+        let (root1, pot1) = self.find(item1);
+        let (root2, pot2) = self.find(item2);
+        if root1 == root2 {
+            Some(pot2 - pot1)
+        } else {
+            None
+        }
+    }
+
+    /// Merges two sets containing `item1` and `item2`,
+    /// with the constraint that the potential of `item2` minus that of `item1` is `weight`.
+    ///
+    /// Returns `Ok(true)` if the sets are merged.
+    /// If they are already the same set, returns `Ok(false)` when the constraint
+    /// is consistent with the known difference, and `Err` with an error message otherwise.
+    /// In both cases, the sets don't change.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `item1` or `item2` is out of range.
+    pub fn unite(&mut self, item1: usize, item2: usize, weight: W) -> Result<bool, String> {
+        let (root1, pot1) = self.find(item1);
+        let (root2, pot2) = self.find(item2);
+        if root1 == root2 {
+            let known = pot2 - pot1;
+            return if known == weight {
+                Ok(false)
+            } else {
+                Err(format!(
+                    "potential of {} minus that of {} is {:?}, but {:?} is given",
+                    item2, item1, known, weight
+                ))
+            };
+        }
+
+        self.set_count -= 1;
+        // Potential of `root2` minus that of `root1`.
+        let root_diff = weight + pot1 - pot2;
+        if self.size[root1] < self.size[root2] {
+            self.parent[root1].set(root2);
+            self.weight[root1].set(W::zero() - root_diff);
+            self.size[root2] += self.size[root1];
+        } else {
+            self.parent[root2].set(root1);
+            self.weight[root2].set(root_diff);
+            self.size[root1] += self.size[root2];
+        }
+        Ok(true)
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain() {
+        let n = 100;
+        let mut sets = WeightedUnionFind::new(n);
+        for i in 0..n-1 {
+            assert_eq!(sets.unite(i, i+1, i as i64), Ok(true));
+        }
+        assert_eq!(sets.count(), 1);
+        assert_eq!(sets.len_of(50), n);
+
+        let pot = |i: usize| (0..i).sum::<usize>() as i64;
+        for i in 0..n {
+            for j in 0..n {
+                assert_eq!(sets.diff(i, j), Some(pot(j) - pot(i)));
+            }
+        }
+        assert_eq!(sets.unite(10, 20, pot(20) - pot(10)), Ok(false));
+    }
+
+    #[test]
+    fn test_random_merge_order() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(1002);
+
+        for _ in 0..20 {
+            let n = rng.gen_range(1, 50);
+            let pot: Vec<i64> = (0..n).map(|_| rng.gen_range(-1000, 1000)).collect();
+            let mut sets = WeightedUnionFind::new(n);
+            let mut naive: Vec<usize> = (0..n).collect();
+            for _ in 0..n {
+                let a = rng.gen_range(0, n);
+                let b = rng.gen_range(0, n);
+                let merged = naive[a] != naive[b];
+                assert_eq!(sets.unite(a, b, pot[b] - pot[a]), Ok(merged));
+                let (from, to) = (naive[b], naive[a]);
+                for label in naive.iter_mut().filter(|label| **label == from) {
+                    *label = to;
+                }
+            }
+
+            for a in 0..n {
+                for b in 0..n {
+                    let expected = if naive[a] == naive[b] { Some(pot[b] - pot[a]) } else { None };
+                    assert_eq!(sets.diff(a, b), expected);
+                    assert_eq!(sets.set_eq(a, b), naive[a] == naive[b]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_contradiction() {
+        let mut sets = WeightedUnionFind::new(3);
+        sets.unite(0, 1, 3).unwrap();
+        sets.unite(1, 2, 4).unwrap();
+        assert_eq!(
+            sets.unite(2, 0, 0),
+            Err("potential of 0 minus that of 2 is -7, but 0 is given".to_string())
+        );
+        assert_eq!(sets.unite(2, 0, -7), Ok(false));
+        assert_eq!(sets.diff(0, 2), Some(7));
+        assert_eq!(sets.count(), 1);
+    }
+
+    #[test]
+    fn test_unmerged() {
+        let mut sets: WeightedUnionFind = WeightedUnionFind::new(4);
+        sets.unite(0, 1, 10).unwrap();
+        sets.unite(2, 3, -10).unwrap();
+        assert_eq!(sets.diff(0, 1), Some(10));
+        assert_eq!(sets.diff(3, 2), Some(10));
+        assert_eq!(sets.diff(0, 2), None);
+        assert_eq!(sets.diff(1, 3), None);
+        assert_eq!(sets.count(), 2);
+
+        sets.unite(1, 2, 1).unwrap();
+        assert_eq!(sets.diff(0, 3), Some(1));
+        assert_eq!(sets.count(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "item 4 is out of range: the sets have 4 items")]
+    fn test_out_of_range() {
+        let sets: WeightedUnionFind = WeightedUnionFind::new(4);
+        sets.diff(0, 4);
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::option::BoolExt;
 use crate::vec::Vec2;
-use crate::xorshift::Xorshift;
+use crate::xorshift::{Rng, Xorshift};
 
 // BEGIN SNIPPET geometry DEPENDS ON option vec xorshift
 
@@ -111,13 +111,17 @@ impl Circle {
     /// The given slice of points are shuffled for randomizing input.
     /// The expectad time complexity is Θ(*n*), where *n* is the number of points.
     pub fn smallest_enclosing(points: &mut [Vec2<f64>]) -> Option<Circle> {
+        Circle::smallest_enclosing_with_rng(points, &mut Xorshift::new())
+    }
+
+    /// Same as `smallest_enclosing`, but shuffles the points by `rng`.
+    pub fn smallest_enclosing_with_rng(points: &mut [Vec2<f64>], rng: &mut impl Rng) -> Option<Circle> {
         use geometry_internal::*;
 
         if points.is_empty() {
             return None;
         }
 
-        let mut rng = Xorshift::new();
         rng.shuffle(points);
 
         let (center, square_radius) = (1..points.len())
@@ -166,4 +170,19 @@ mod tests {
         assert!((actual.center.y - expected.center.y).abs() < GEOMETRY_EPSILON);
         assert!((actual.radius - expected.radius).abs() < GEOMETRY_EPSILON);
     }
+
+    #[test]
+    fn test_smallest_enclosing_with_rng() {
+        use crate::xorshift::StepRng;
+
+        let mut points = vec![
+            Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.5, 0.5),
+            Vec2::new(0.0, 1.0), Vec2::new(1.0, 1.0)
+        ];
+        let circle = Circle::smallest_enclosing_with_rng(&mut points, &mut StepRng::new(0, 12345)).unwrap();
+        assert!((circle.center.x - 0.5).abs() < GEOMETRY_EPSILON);
+        assert!((circle.center.y - 0.5).abs() < GEOMETRY_EPSILON);
+        assert!((circle.radius - 0.5f64.sqrt()).abs() < GEOMETRY_EPSILON);
+        assert!(Circle::smallest_enclosing_with_rng(&mut [], &mut StepRng::new(0, 1)).is_none());
+    }
 }
@@ -6,10 +6,17 @@
 //! This module offers useful algorithms such as
 //! [merging intervals](trait.IntervalIterator.html#method.merge)
 //! or tertiary search on an interval (not implemented yet.)
+//!
+//! Free functions in this module, such as [`merge`](fn.merge.html), take intervals
+//! as tuples `(l, r)` of `i64`. Unless otherwise noted, they are half-open
+//! and represent `l <= x < r`.
 
 // TODO: method to check whether an interval is contained, and whether an interval is overwrapped
 
-// BEGIN SNIPPET interval
+use crate::read::Readable;
+use crate::num::WithZero;
+
+// BEGIN SNIPPET interval DEPENDS ON read int
 
 /// Point on a number line.
 pub trait IntervalEndpoint: Ord {}
@@ -24,7 +31,35 @@ impl IntervalEndpoint for i64 {}
 impl IntervalEndpoint for u64 {}
 
 /// Interval including endpoints.
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// Intervals are ordered by the left endpoints, and then by the right endpoints.
+///
+/// `Interval` is readable from two words `l r`.
+/// If `r < l`, reading fails.
+///
+/// # Example
+///
+/// Common free time of two schedules:
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// let free1 = [Interval::new(9, 12).unwrap(), Interval::new(13, 17).unwrap()];
+/// let free2 = [Interval::new(10, 14).unwrap(), Interval::new(16, 18).unwrap()];
+///
+/// let mut common = Vec::new();
+/// for itv1 in &free1 {
+///     for itv2 in &free2 {
+///         common.extend(itv1 & itv2);
+///     }
+/// }
+/// common.sort();
+/// assert_eq!(common, vec![
+///     Interval::new(10, 12).unwrap(),
+///     Interval::new(13, 14).unwrap(),
+///     Interval::new(16, 17).unwrap()
+/// ]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Interval<T> {
     left: T,
     right: T
@@ -97,6 +132,115 @@ impl<T: IntervalEndpoint> Interval<T> {
     {
         self.right().clone() - self.left()
     }
+
+    /// Checks if the interval contains all the points in `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::interval::*;
+    /// let interval = Interval::new(0, 10).unwrap();
+    /// assert!(interval.contains_interval(&Interval::new(3, 10).unwrap()));
+    /// assert!(interval.contains_interval(&Interval::new(0, 0).unwrap()));
+    /// assert!(!interval.contains_interval(&Interval::new(5, 11).unwrap()));
+    /// ```
+    pub fn contains_interval(&self, other: &Interval<T>) -> bool {
+        self.left() <= other.left() && other.right() <= self.right()
+    }
+
+    /// Intersection of two intervals.
+    ///
+    /// If they have no common point, returns `None`.
+    /// The `&` operator does the same.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::interval::*;
+    /// let interval = Interval::new(0, 10).unwrap();
+    /// assert_eq!(interval.intersect(&Interval::new(5, 15).unwrap()), Interval::new(5, 10));
+    /// assert_eq!(interval.intersect(&Interval::new(10, 15).unwrap()), Interval::new(10, 10));
+    /// assert_eq!(interval.intersect(&Interval::new(11, 15).unwrap()), None);
+    /// ```
+    pub fn intersect(&self, other: &Interval<T>) -> Option<Interval<T>> where T: Clone {
+        let left = std::cmp::max(self.left(), other.left()).clone();
+        let right = std::cmp::min(self.right(), other.right()).clone();
+        Interval::new(left, right)
+    }
+
+    /// The smallest interval containing both intervals.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::interval::*;
+    /// let interval = Interval::new(0, 3).unwrap();
+    /// assert_eq!(interval.hull(&Interval::new(5, 8).unwrap()), Interval::new(0, 8).unwrap());
+    /// ```
+    pub fn hull(&self, other: &Interval<T>) -> Interval<T> where T: Clone {
+        Interval {
+            left: std::cmp::min(self.left(), other.left()).clone(),
+            right: std::cmp::max(self.right(), other.right()).clone()
+        }
+    }
+
+    /// Distance between the closest points of two intervals.
+    ///
+    /// If they have a common point, returns 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::interval::*;
+    /// let interval = Interval::new(0, 3).unwrap();
+    /// assert_eq!(interval.distance_to(&Interval::new(5, 8).unwrap()), 2);
+    /// assert_eq!(interval.distance_to(&Interval::new(-5, -2).unwrap()), 2);
+    /// assert_eq!(interval.distance_to(&Interval::new(3, 8).unwrap()), 0);
+    /// ```
+    pub fn distance_to(&self, other: &Interval<T>) -> T
+    where
+        T: Clone + WithZero + for<'a> std::ops::Sub<&'a T, Output=T>
+    {
+        if self.right() < other.left() {
+            other.left().clone() - self.right()
+        } else if other.right() < self.left() {
+            self.left().clone() - other.right()
+        } else {
+            T::zero()
+        }
+    }
+}
+
+impl<T: IntervalEndpoint + Clone> std::ops::BitAnd for Interval<T> {
+    type Output = Option<Interval<T>>;
+
+    fn bitand(self, other: Interval<T>) -> Option<Interval<T>> {
+        self.intersect(&other)
+    }
+}
+
+impl<T: IntervalEndpoint + Clone> std::ops::BitAnd for &Interval<T> {
+    type Output = Option<Interval<T>>;
+
+    fn bitand(self, other: &Interval<T>) -> Option<Interval<T>> {
+        self.intersect(other)
+    }
+}
+
+impl<T: Readable> Readable for Interval<T> where T::Output: IntervalEndpoint + std::fmt::Debug {
+    type Output = Interval<T::Output>;
+    const WORD_COUNT: usize = 2 * T::WORD_COUNT;
+
+    fn read_words(words: &[&str]) -> Result<Interval<T::Output>, String> {
+        assert_eq!(words.len(), Self::WORD_COUNT);
+        let left = T::read_words(&words[..T::WORD_COUNT])?;
+        let right = T::read_words(&words[T::WORD_COUNT..])?;
+        if right < left {
+            Err(format!("{:?} is greater than {:?}", left, right))
+        } else {
+            Ok(Interval { left, right })
+        }
+    }
 }
 
 impl<T: IntervalEndpoint + std::fmt::Debug> std::fmt::Debug for Interval<T> {
@@ -191,6 +335,613 @@ impl<T: IntervalEndpoint> Iterator for IntervalMerge<T> {
     }
 }
 
+/// Static structure answering which intervals contain a given point.
+///
+/// Endpoints are inclusive as [`Interval`](struct.Interval.html),
+/// so `[1, 3]` contains both 1 and 3.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// let intervals: Vec<Interval<i64>> = [(0, 5), (3, 8), (5, 5), (9, 10)].iter()
+///     .map(|&itv| Interval::from_tuple(itv).unwrap())
+///     .collect();
+/// let query = StabQuery::new(&intervals);
+/// assert_eq!(query.count_containing(&5), 3);
+/// assert_eq!(query.collect_containing(&5), vec![0, 1, 2]);
+/// assert_eq!(query.count_containing(&8), 1);
+/// assert_eq!(query.collect_containing(&8), vec![1]);
+/// assert_eq!(query.count_containing(&11), 0);
+/// ```
+pub struct StabQuery<T> {
+    // Sorted endpoints for counting.
+    lefts: Vec<T>,
+    rights: Vec<T>,
+    // Intervals as `(left, right, index)` sorted by `left`.
+    by_left: Vec<(T, T, usize)>,
+    // Segment tree on `by_left` holding the maximum right endpoint in each node.
+    max_rights: Vec<Option<T>>
+}
+
+impl<T: IntervalEndpoint + Clone> StabQuery<T> {
+    /// Builds the structure in O(*n* log *n*) time.
+    pub fn new(intervals: &[Interval<T>]) -> StabQuery<T> {
+        let mut lefts: Vec<T> = intervals.iter().map(|itv| itv.left().clone()).collect();
+        let mut rights: Vec<T> = intervals.iter().map(|itv| itv.right().clone()).collect();
+        lefts.sort();
+        rights.sort();
+
+        let mut by_left: Vec<(T, T, usize)> = intervals.iter().enumerate()
+            .map(|(i, itv)| (itv.left().clone(), itv.right().clone(), i))
+            .collect();
+        by_left.sort();
+
+        let size = by_left.len().next_power_of_two();
+        let mut max_rights = vec![None; 2 * size];
+        for (i, (_, right, _)) in by_left.iter().enumerate() {
+            max_rights[size + i] = Some(right.clone());
+        }
+        for i in (1..size).rev() {
+            max_rights[i] = std::cmp::max(max_rights[2*i].clone(), max_rights[2*i + 1].clone());
+        }
+
+        StabQuery { lefts, rights, by_left, max_rights }
+    }
+
+    /// Counts intervals containing `point` in O(log *n*) time.
+    pub fn count_containing(&self, point: &T) -> usize {
+        // Intervals starting at or before `point`, minus ones ending before `point`.
+        count_less_or_equal(&self.lefts, point) - count_less(&self.rights, point)
+    }
+
+    /// Collects indices of intervals containing `point`, in the ascending order.
+    ///
+    /// It takes O((*k* + 1) log *n*) time, where *k* is the number of the intervals collected.
+    pub fn collect_containing(&self, point: &T) -> Vec<usize> {
+        let prefix_len = count_less_or_equal(&self.lefts, point);
+        let mut result = Vec::new();
+        self.collect_sub(1, 0, self.max_rights.len() / 2, prefix_len, point, &mut result);
+        result.sort();
+        result
+    }
+
+    // Collects intervals in `by_left[lo..min(hi, prefix_len)]`, where node `node` covers `lo..hi`.
+    fn collect_sub(&self, node: usize, lo: usize, hi: usize, prefix_len: usize,
+                   point: &T, result: &mut Vec<usize>) {
+        let reaches = match self.max_rights[node] {
+            Some(ref max) => max >= point,
+            None => false
+        };
+        if prefix_len <= lo || !reaches {
+            return;
+        }
+        if hi - lo == 1 {
+            result.push(self.by_left[lo].2);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.collect_sub(2*node, lo, mid, prefix_len, point, result);
+        self.collect_sub(2*node + 1, mid, hi, prefix_len, point, result);
+    }
+}
+
+fn count_less<T: Ord>(sorted: &[T], x: &T) -> usize {
+    sorted.binary_search_by(|y| if y < x { std::cmp::Ordering::Less } else { std::cmp::Ordering::Greater })
+        .unwrap_err()
+}
+
+fn count_less_or_equal<T: Ord>(sorted: &[T], x: &T) -> usize {
+    sorted.binary_search_by(|y| if y <= x { std::cmp::Ordering::Less } else { std::cmp::Ordering::Greater })
+        .unwrap_err()
+}
+
+/// Merges half-open intervals `[l, r)` into disjoint ones, sorted in the ascending order.
+///
+/// Overlapping intervals and adjacent ones such as `[1, 3)` and `[3, 5)` are merged,
+/// so the result represents the same set as the union of `intervals`.
+/// Empty intervals (`l >= r`) are dropped.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// assert_eq!(merge(&[(5, 7), (1, 3), (2, 4), (4, 5), (9, 9), (10, 12)]), vec![(1, 7), (10, 12)]);
+/// ```
+pub fn merge(intervals: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    merge_impl(intervals, false)
+}
+
+/// Merges closed integer intervals `[l, r]` into disjoint ones, sorted in the ascending order.
+///
+/// Overlapping intervals and adjacent ones such as `[1, 3]` and `[4, 5]` are merged,
+/// since no integer lies between them.
+/// Empty intervals (`l > r`) are dropped.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// assert_eq!(merge_closed(&[(4, 5), (1, 3), (7, 7), (9, 8)]), vec![(1, 5), (7, 7)]);
+/// ```
+pub fn merge_closed(intervals: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    merge_impl(intervals, true)
+}
+
+fn merge_impl(intervals: &[(i64, i64)], closed: bool) -> Vec<(i64, i64)> {
+    let mut sorted: Vec<(i64, i64)> = intervals.iter()
+        .cloned()
+        .filter(|&(l, r)| l < r || closed && l == r)
+        .collect();
+    sorted.sort();
+
+    let mut merged: Vec<(i64, i64)> = Vec::with_capacity(sorted.len());
+    for (l, r) in sorted {
+        match merged.last_mut() {
+            Some(last) if l <= last.1 || closed && l - 1 == last.1 => {
+                last.1 = std::cmp::max(last.1, r);
+            },
+            _ => merged.push((l, r))
+        }
+    }
+    merged
+}
+
+/// Union of two sets of half-open intervals.
+///
+/// Each set may contain overlapping intervals.
+/// The result is disjoint and sorted, as [`merge`](fn.merge.html) returns.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// assert_eq!(union(&[(0, 3), (8, 10)], &[(2, 5), (6, 7)]), vec![(0, 5), (6, 7), (8, 10)]);
+/// ```
+pub fn union(a: &[(i64, i64)], b: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    combine(a, b, |in_a, in_b| in_a || in_b)
+}
+
+/// Intersection of two sets of half-open intervals.
+///
+/// Each set may contain overlapping intervals.
+/// The result is disjoint and sorted, as [`merge`](fn.merge.html) returns.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// assert_eq!(intersection(&[(0, 3), (8, 10)], &[(2, 5), (6, 9)]), vec![(2, 3), (8, 9)]);
+/// ```
+pub fn intersection(a: &[(i64, i64)], b: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    combine(a, b, |in_a, in_b| in_a && in_b)
+}
+
+/// Points covered by `a` but not by `b`, as half-open intervals.
+///
+/// Each set may contain overlapping intervals.
+/// The result is disjoint and sorted, as [`merge`](fn.merge.html) returns.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// // Time covered by the first schedule but not the second one.
+/// assert_eq!(difference(&[(0, 10)], &[(2, 3), (5, 7)]), vec![(0, 2), (3, 5), (7, 10)]);
+/// ```
+pub fn difference(a: &[(i64, i64)], b: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    combine(a, b, |in_a, in_b| in_a && !in_b)
+}
+
+// Sweeps boundaries of normalized `a` and `b` at once,
+// collecting points where `op(in a, in b)` holds.
+fn combine(a: &[(i64, i64)], b: &[(i64, i64)], op: impl Fn(bool, bool) -> bool) -> Vec<(i64, i64)> {
+    // Strictly increasing since merged intervals never touch each other.
+    // Points in `[bounds[k], bounds[k+1])` are covered iff `k` is even.
+    let bounds = |intervals: &[(i64, i64)]| -> Vec<i64> {
+        merge(intervals).into_iter().flat_map(|(l, r)| vec![l, r]).collect()
+    };
+    let (bounds_a, bounds_b) = (bounds(a), bounds(b));
+
+    let mut result = Vec::new();
+    let mut start = None;
+    let (mut i, mut j) = (0, 0);
+    loop {
+        let x = match (bounds_a.get(i), bounds_b.get(j)) {
+            (Some(&xa), Some(&xb)) => std::cmp::min(xa, xb),
+            (Some(&xa), None) => xa,
+            (None, Some(&xb)) => xb,
+            (None, None) => break
+        };
+        if bounds_a.get(i) == Some(&x) {
+            i += 1;
+        }
+        if bounds_b.get(j) == Some(&x) {
+            j += 1;
+        }
+
+        match (op(i % 2 == 1, j % 2 == 1), start) {
+            (true, None) => start = Some(x),
+            (false, Some(l)) => {
+                result.push((l, x));
+                start = None;
+            },
+            _ => {}
+        }
+    }
+    result
+}
+
+/// Total length of disjoint half-open intervals, such as returned by [`merge`](fn.merge.html).
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// let merged = merge(&[(0, 3), (2, 5), (10, 12)]);
+/// assert_eq!(total_length(&merged), 7);
+/// ```
+pub fn total_length(merged: &[(i64, i64)]) -> i64 {
+    merged.iter().map(|&(l, r)| r - l).sum()
+}
+
+/// Selects the maximum number of pairwise disjoint half-open intervals `[l, r)`.
+///
+/// Returns indices of the selected intervals in `intervals`, in the ascending order of `r`.
+/// Since intervals are half-open, touching ones such as `[1, 3)` and `[3, 5)` are disjoint.
+///
+/// Intervals are greedily selected in the ascending order of `r`.
+///
+/// # Panics
+///
+/// Panics if `intervals` contains an empty interval (`l >= r`).
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// let intervals = [(0, 5), (1, 3), (3, 6), (5, 8), (6, 7)];
+/// assert_eq!(max_disjoint(&intervals), vec![1, 2, 4]);
+/// ```
+pub fn max_disjoint(intervals: &[(i64, i64)]) -> Vec<usize> {
+    let mut selected = Vec::new();
+    let mut last_end = None;
+    for i in indices_by_end(intervals) {
+        let (l, r) = intervals[i];
+        let disjoint = match last_end {
+            Some(end) => end <= l,
+            None => true
+        };
+        if disjoint {
+            selected.push(i);
+            last_end = Some(r);
+        }
+    }
+    selected
+}
+
+/// Selects pairwise disjoint half-open intervals `[l, r)` maximizing the total weight,
+/// where each interval is given as `(l, r, weight)`.
+///
+/// Returns the maximum total weight and indices of the selected intervals
+/// in the ascending order of `r`.
+/// Touching intervals such as `[1, 3)` and `[3, 5)` are disjoint.
+/// Intervals with non-positive weights are never selected.
+///
+/// It takes O(*n* log *n*) time by DP on intervals sorted by `r`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// let intervals = [(0, 3, 5), (2, 5, 6), (4, 7, 5), (3, 4, 1)];
+/// assert_eq!(max_weight_disjoint(&intervals), (11, vec![0, 3, 2]));
+/// ```
+pub fn max_weight_disjoint(intervals: &[(i64, i64, i64)]) -> (i64, Vec<usize>) {
+    let mut order: Vec<usize> = (0..intervals.len()).collect();
+    order.sort_by_key(|&i| intervals[i].1);
+    let ends: Vec<i64> = order.iter().map(|&i| intervals[i].1).collect();
+
+    // `best[k]` is the answer for the first `k` intervals in `order`,
+    // and `prev[k]` is how many of them are compatible with the `k`-th one.
+    let mut best = vec![0; order.len() + 1];
+    let mut prev = vec![0; order.len()];
+    for (k, &i) in order.iter().enumerate() {
+        let (l, _, weight) = intervals[i];
+        prev[k] = ends[..k].binary_search_by(|&end| {
+            if end <= l { std::cmp::Ordering::Less } else { std::cmp::Ordering::Greater }
+        }).unwrap_err();
+        best[k+1] = std::cmp::max(best[k], best[prev[k]] + weight);
+    }
+
+    let mut selected = Vec::new();
+    let mut k = order.len();
+    while k > 0 {
+        if best[k] == best[k-1] {
+            k -= 1;
+        } else {
+            selected.push(order[k-1]);
+            k = prev[k-1];
+        }
+    }
+    selected.reverse();
+    (best[order.len()], selected)
+}
+
+/// Finds the minimum number of integer points such that every half-open interval `[l, r)`
+/// contains at least one of them.
+///
+/// Returns the points in the ascending order.
+/// Since intervals are half-open, `[1, 3)` and `[3, 5)` share no point and need two points.
+///
+/// Each point is greedily put at `r - 1` of the interval with the smallest `r` not pierced yet.
+///
+/// # Panics
+///
+/// Panics if `intervals` contains an empty interval (`l >= r`).
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// let intervals = [(0, 5), (1, 3), (3, 6), (5, 8), (6, 7)];
+/// assert_eq!(min_piercing_points(&intervals), vec![2, 5, 6]);
+/// ```
+pub fn min_piercing_points(intervals: &[(i64, i64)]) -> Vec<i64> {
+    let mut points: Vec<i64> = Vec::new();
+    for i in indices_by_end(intervals) {
+        let (l, r) = intervals[i];
+        let stabbed = match points.last() {
+            Some(&point) => l <= point,
+            None => false
+        };
+        if !stabbed {
+            points.push(r - 1);
+        }
+    }
+    points
+}
+
+fn indices_by_end(intervals: &[(i64, i64)]) -> Vec<usize> {
+    assert!(intervals.iter().all(|&(l, r)| l < r), "intervals must not be empty");
+    let mut indices: Vec<usize> = (0..intervals.len()).collect();
+    indices.sort_by_key(|&i| intervals[i].1);
+    indices
+}
+
+/// Finds the maximum number of half-open intervals `[l, r)` containing a common point.
+///
+/// Returns `(count, point)` where `point` is contained by `count` intervals.
+/// Since intervals are half-open, back-to-back intervals such as `[1, 3)` and `[3, 5)`
+/// do not overlap.
+/// Empty intervals (`l >= r`) are ignored.
+/// If no interval is non-empty, returns `(0, 0)`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// let intervals = [(0, 3), (3, 6), (2, 5), (4, 8)];
+/// assert_eq!(max_overlap(&intervals), (3, 4));
+/// ```
+pub fn max_overlap(intervals: &[(i64, i64)]) -> (usize, i64) {
+    let mut max = (0, 0);
+    let mut depth = 0;
+    for (x, d) in sweep_events(intervals) {
+        depth = (depth as isize + d) as usize;
+        if depth > max.0 {
+            max = (depth, x);
+        }
+    }
+    max
+}
+
+/// Splits the union of half-open intervals `[l, r)` into maximal segments
+/// on which the number of intervals covering each point is constant.
+///
+/// Returns `(l, r, depth)` for each segment in the ascending order,
+/// where `depth` intervals cover `[l, r)`.
+/// Segments not covered by any interval are omitted,
+/// so the segments tile the union of the intervals.
+/// Empty intervals (`l >= r`) are ignored.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// // How long are at least 2 intervals active?
+/// let intervals = [(0, 4), (2, 6), (3, 5), (8, 9)];
+/// let histogram = coverage_histogram(&intervals);
+/// assert_eq!(histogram, vec![(0, 2, 1), (2, 3, 2), (3, 4, 3), (4, 5, 2), (5, 6, 1), (8, 9, 1)]);
+///
+/// let length: i64 = histogram.iter()
+///     .filter(|&&(_, _, depth)| depth >= 2)
+///     .map(|&(l, r, _)| r - l)
+///     .sum();
+/// assert_eq!(length, 3);
+/// ```
+pub fn coverage_histogram(intervals: &[(i64, i64)]) -> Vec<(i64, i64, usize)> {
+    let mut histogram: Vec<(i64, i64, usize)> = Vec::new();
+    let mut depth = 0;
+    let mut prev = 0;
+    for (x, d) in sweep_events(intervals) {
+        if depth > 0 && prev < x {
+            match histogram.last_mut() {
+                Some(last) if last.1 == prev && last.2 == depth => last.1 = x,
+                _ => histogram.push((prev, x, depth))
+            }
+        }
+        depth = (depth as isize + d) as usize;
+        prev = x;
+    }
+    histogram
+}
+
+// Events `(x, +1)` for left ends and `(x, -1)` for right ends,
+// sorted so that right ends come first at the same point.
+fn sweep_events(intervals: &[(i64, i64)]) -> Vec<(i64, isize)> {
+    let mut events: Vec<(i64, isize)> = intervals.iter()
+        .filter(|&&(l, r)| l < r)
+        .flat_map(|&(l, r)| vec![(l, 1), (r, -1)])
+        .collect();
+    events.sort();
+    events
+}
+
+/// Map from disjoint half-open intervals `[l, r)` to values,
+/// known as Chtholly tree or ODT.
+///
+/// Each point belongs to at most one interval.
+/// No interval in the map is empty.
+/// Adjacent intervals with the same value are not merged.
+///
+/// Each assignment adds at most three intervals, and each interval is removed at most once,
+/// so each of `assign` and `remove` takes amortized O(log(*n*)) time,
+/// as *n* is the number of intervals.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::interval::*;
+/// let mut map = IntervalMap::new();
+/// map.assign(0..10, 'a');
+/// map.assign(3..5, 'b');
+/// assert_eq!(map.get(2), Some(&'a'));
+/// assert_eq!(map.get(3), Some(&'b'));
+/// assert_eq!(map.get(10), None);
+///
+/// let pieces: Vec<(i64, i64, &char)> = map.iter_range(4..7).collect();
+/// assert_eq!(pieces, vec![(4, 5, &'b'), (5, 7, &'a')]);
+///
+/// // Maintaining the number of points having 'a'
+/// let mut count_a = 8;
+/// map.assign_with(1..4, 'c', |l, r, value| if value == 'a' { count_a -= r - l });
+/// assert_eq!(count_a, 6);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IntervalMap<V> {
+    // Maps `l` to `(r, value)`.
+    map: std::collections::BTreeMap<i64, (i64, V)>
+}
+
+impl<V: Clone> IntervalMap<V> {
+    /// Creates an empty map.
+    pub fn new() -> IntervalMap<V> {
+        IntervalMap { map: std::collections::BTreeMap::new() }
+    }
+
+    /// The number of intervals.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns whether the map has no intervals.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Splits the interval containing `x` into two at `x`, if `x` is strictly inside it.
+    fn split(&mut self, x: i64) {
+        let (l, r) = match self.map.range(..x).next_back() {
+            Some((&l, &(r, _))) if x < r => (l, r),
+            _ => return
+        };
+        let piece = self.map.get_mut(&l).unwrap();
+        piece.0 = x;
+        let value = piece.1.clone();
+        self.map.insert(x, (r, value));
+    }
+
+    /// Assigns `value` to `range`, overwriting values already assigned there.
+    ///
+    /// Intervals partially overlapping `range` are split.
+    /// If `range` is empty, does nothing.
+    pub fn assign(&mut self, range: std::ops::Range<i64>, value: V) {
+        self.assign_with(range, value, |_, _, _| ());
+    }
+
+    /// Assigns `value` to `range` like `assign`,
+    /// calling `on_remove(l, r, value)` for each overwritten piece `[l, r)` in the ascending order.
+    ///
+    /// Each piece is inside `range`.
+    /// Points in `range` not belonging to any interval are not passed.
+    pub fn assign_with<F>(&mut self, range: std::ops::Range<i64>, value: V, on_remove: F)
+    where
+        F: FnMut(i64, i64, V)
+    {
+        if range.start >= range.end {
+            return;
+        }
+        self.remove_with(range.clone(), on_remove);
+        self.map.insert(range.start, (range.end, value));
+    }
+
+    /// Removes values assigned to `range`.
+    ///
+    /// Intervals partially overlapping `range` are split and only the overlapping parts are removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::interval::*;
+    /// let mut map = IntervalMap::new();
+    /// map.assign(0..10, 1);
+    /// map.remove(3..5);
+    /// assert_eq!(map.get(2), Some(&1));
+    /// assert_eq!(map.get(3), None);
+    /// assert_eq!(map.get(5), Some(&1));
+    /// ```
+    pub fn remove(&mut self, range: std::ops::Range<i64>) {
+        self.remove_with(range, |_, _, _| ());
+    }
+
+    fn remove_with<F: FnMut(i64, i64, V)>(&mut self, range: std::ops::Range<i64>, mut on_remove: F) {
+        if range.start >= range.end {
+            return;
+        }
+        self.split(range.start);
+        self.split(range.end);
+        let keys: Vec<i64> = self.map.range(range).map(|(&l, _)| l).collect();
+        for l in keys {
+            let (r, value) = self.map.remove(&l).unwrap();
+            on_remove(l, r, value);
+        }
+    }
+}
+
+impl<V> IntervalMap<V> {
+    /// Gets the value assigned to `point`.
+    pub fn get(&self, point: i64) -> Option<&V> {
+        self.map.range(..=point).next_back()
+            .and_then(|(_, (r, value))| if point < *r { Some(value) } else { None })
+    }
+
+    /// Creates an iterator yielding `(l, r, &value)` for all the intervals in the ascending order.
+    pub fn iter(&self) -> impl Iterator<Item=(i64, i64, &V)> {
+        self.map.iter().map(|(&l, (r, value))| (l, *r, value))
+    }
+
+    /// Creates an iterator yielding `(l, r, &value)` for intervals overlapping `range`
+    /// in the ascending order.
+    ///
+    /// Each interval is clipped to `range`.
+    pub fn iter_range(&self, range: std::ops::Range<i64>) -> impl Iterator<Item=(i64, i64, &V)> {
+        let std::ops::Range { start, end } = range;
+        // The interval containing `start` may begin before `start`.
+        let first = self.map.range(..start).next_back()
+            .filter(|&(_, &(r, _))| start < r && start < end);
+        let rest = if start < end { Some(self.map.range(start..end)) } else { None };
+        first.into_iter().chain(rest.into_iter().flatten())
+            .map(move |(&l, (r, value))| (std::cmp::max(l, start), std::cmp::min(*r, end), value))
+    }
+}
+
+impl<V: Clone> Default for IntervalMap<V> {
+    fn default() -> IntervalMap<V> {
+        IntervalMap::new()
+    }
+}
+
 // END SNIPPET
 
 #[cfg(test)]
@@ -384,4 +1135,522 @@ mod tests {
             vec![(0, 1), (3, 7), (9, 12), (14, 14)]
         );
     }
+
+    #[test]
+    fn test_merge_half_open() {
+        assert_eq!(merge(&[]), vec![]);
+        // Nested
+        assert_eq!(merge(&[(0, 10), (2, 3), (4, 8)]), vec![(0, 10)]);
+        // Overlapping
+        assert_eq!(merge(&[(3, 6), (0, 4)]), vec![(0, 6)]);
+        // Adjacent
+        assert_eq!(merge(&[(3, 5), (0, 3)]), vec![(0, 5)]);
+        // Separated by a gap of length 1
+        assert_eq!(merge(&[(4, 5), (0, 3)]), vec![(0, 3), (4, 5)]);
+        // Empty intervals are dropped even if inside another interval
+        assert_eq!(merge(&[(3, 3), (5, 2)]), vec![]);
+        assert_eq!(merge(&[(0, 2), (2, 2), (5, 5)]), vec![(0, 2)]);
+        // Already disjoint
+        let disjoint = vec![(-5, -1), (0, 3), (4, 8), (10, 11)];
+        assert_eq!(merge(&disjoint), disjoint);
+    }
+
+    #[test]
+    fn test_merge_closed() {
+        assert_eq!(merge_closed(&[]), vec![]);
+        // Nested
+        assert_eq!(merge_closed(&[(0, 10), (2, 3), (4, 8)]), vec![(0, 10)]);
+        // Overlapping at a point
+        assert_eq!(merge_closed(&[(3, 5), (0, 3)]), vec![(0, 5)]);
+        // Adjacent as integers
+        assert_eq!(merge_closed(&[(4, 5), (1, 3)]), vec![(1, 5)]);
+        // Separated by an integer
+        assert_eq!(merge_closed(&[(5, 6), (1, 3)]), vec![(1, 3), (5, 6)]);
+        // Points
+        assert_eq!(merge_closed(&[(2, 2), (1, 1), (3, 3), (5, 5)]), vec![(1, 3), (5, 5)]);
+        // Empty intervals
+        assert_eq!(merge_closed(&[(1, 0), (std::i64::MAX, std::i64::MAX)]),
+                   vec![(std::i64::MAX, std::i64::MAX)]);
+        // Already disjoint
+        let disjoint = vec![(-5, -1), (1, 3), (5, 8), (10, 10)];
+        assert_eq!(merge_closed(&disjoint), disjoint);
+    }
+
+    #[test]
+    fn test_merge_random() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(927);
+
+        for _ in 0..100 {
+            let intervals: Vec<(i64, i64)> = (0..rng.gen_range(0, 10))
+                .map(|_| (rng.gen_range(0, 30), rng.gen_range(0, 30)))
+                .collect();
+            let merged = merge(&intervals);
+            let covered = |x: i64| intervals.iter().any(|&(l, r)| l <= x && x < r);
+            let covered_merged = |x: i64| merged.iter().any(|&(l, r)| l <= x && x < r);
+            assert!((0..30).all(|x| covered(x) == covered_merged(x)));
+            assert!(merged.windows(2).all(|w| w[0].1 < w[1].0));
+            assert_eq!(total_length(&merged), (0..30).filter(|&x| covered(x)).count() as i64);
+        }
+    }
+
+    #[test]
+    fn test_total_length() {
+        assert_eq!(total_length(&[]), 0);
+        assert_eq!(total_length(&[(0, 1)]), 1);
+        assert_eq!(total_length(&merge(&[(0, 5), (3, 8), (10, 20)])), 18);
+    }
+
+    fn random_intervals(rng: &mut impl rand::Rng, max_len: usize) -> Vec<(i64, i64)> {
+        (0..rng.gen_range(0, max_len + 1)).map(|_| {
+            let l = rng.gen_range(0, 20);
+            (l, l + rng.gen_range(1, 8))
+        }).collect()
+    }
+
+    #[test]
+    fn test_max_disjoint() {
+        assert_eq!(max_disjoint(&[]), vec![]);
+        assert_eq!(max_disjoint(&[(0, 1)]), vec![0]);
+        // Touching intervals are disjoint
+        assert_eq!(max_disjoint(&[(3, 5), (1, 3), (5, 6)]), vec![1, 0, 2]);
+        // Overlapping by one point
+        assert_eq!(max_disjoint(&[(2, 5), (1, 3)]).len(), 1);
+        // Nested
+        assert_eq!(max_disjoint(&[(0, 10), (2, 3), (4, 6)]), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_max_disjoint_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(928);
+
+        for _ in 0..300 {
+            let intervals = random_intervals(&mut rng, 15);
+            let n = intervals.len();
+            let disjoint = |i: usize, j: usize| {
+                intervals[i].1 <= intervals[j].0 || intervals[j].1 <= intervals[i].0
+            };
+
+            let selected = max_disjoint(&intervals);
+            for (k, &i) in selected.iter().enumerate() {
+                assert!(selected[k+1..].iter().all(|&j| disjoint(i, j)));
+            }
+
+            let expected = (0..1usize << n).filter(|&set| {
+                (0..n).all(|i| set >> i & 1 == 0 || (i+1..n).all(|j| set >> j & 1 == 0 || disjoint(i, j)))
+            }).map(|set| set.count_ones()).max().unwrap();
+            assert_eq!(selected.len() as u32, expected);
+        }
+    }
+
+    #[test]
+    fn test_min_piercing_points() {
+        assert_eq!(min_piercing_points(&[]), vec![]);
+        assert_eq!(min_piercing_points(&[(0, 1)]), vec![0]);
+        // Touching intervals share no point
+        assert_eq!(min_piercing_points(&[(3, 5), (1, 3)]), vec![2, 4]);
+        // Overlapping by one point
+        assert_eq!(min_piercing_points(&[(2, 5), (1, 3)]), vec![2]);
+        // Nested
+        assert_eq!(min_piercing_points(&[(0, 10), (2, 3), (4, 6)]), vec![2, 5]);
+    }
+
+    #[test]
+    fn test_min_piercing_points_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(928);
+
+        for _ in 0..300 {
+            let intervals = random_intervals(&mut rng, 10);
+            let points = min_piercing_points(&intervals);
+            assert!(intervals.iter().all(|&(l, r)| points.iter().any(|&p| l <= p && p < r)));
+
+            // Some optimal solution consists of only right ends.
+            let candidates: Vec<i64> = intervals.iter().map(|&(_, r)| r - 1).collect();
+            let m = candidates.len();
+            let expected = (0..1usize << m).filter(|&set| {
+                intervals.iter().all(|&(l, r)| {
+                    (0..m).any(|i| set >> i & 1 == 1 && l <= candidates[i] && candidates[i] < r)
+                })
+            }).map(|set| set.count_ones()).min().unwrap();
+            assert_eq!(points.len() as u32, expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_max_disjoint_empty_interval() {
+        max_disjoint(&[(0, 3), (2, 2)]);
+    }
+
+    #[test]
+    fn test_max_overlap() {
+        assert_eq!(max_overlap(&[]), (0, 0));
+        assert_eq!(max_overlap(&[(3, 3)]), (0, 0));
+        assert_eq!(max_overlap(&[(2, 5)]), (1, 2));
+        // Back-to-back intervals don't overlap
+        assert_eq!(max_overlap(&[(1, 3), (3, 5), (5, 7)]).0, 1);
+        assert_eq!(max_overlap(&[(1, 4), (3, 5)]), (2, 3));
+        // Nested
+        assert_eq!(max_overlap(&[(0, 10), (2, 8), (4, 6)]), (3, 4));
+    }
+
+    #[test]
+    fn test_max_overlap_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(929);
+
+        for _ in 0..300 {
+            let intervals = random_intervals(&mut rng, 10);
+            let depth = |x: i64| intervals.iter().filter(|&&(l, r)| l <= x && x < r).count();
+            let (count, point) = max_overlap(&intervals);
+            let expected = (0..30).map(depth).max().unwrap();
+            assert_eq!(count, expected);
+            if count > 0 {
+                assert_eq!(depth(point), count);
+            }
+        }
+    }
+
+    #[test]
+    fn test_coverage_histogram() {
+        assert_eq!(coverage_histogram(&[]), vec![]);
+        assert_eq!(coverage_histogram(&[(2, 2)]), vec![]);
+        // Back-to-back intervals make one segment of depth 1
+        assert_eq!(coverage_histogram(&[(3, 5), (1, 3)]), vec![(1, 5, 1)]);
+        assert_eq!(coverage_histogram(&[(1, 3), (1, 3)]), vec![(1, 3, 2)]);
+        assert_eq!(coverage_histogram(&[(1, 3), (4, 5)]), vec![(1, 3, 1), (4, 5, 1)]);
+    }
+
+    #[test]
+    fn test_coverage_histogram_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(929);
+
+        for _ in 0..300 {
+            let intervals = random_intervals(&mut rng, 10);
+            let depth = |x: i64| intervals.iter().filter(|&&(l, r)| l <= x && x < r).count();
+            let histogram = coverage_histogram(&intervals);
+
+            // Segments tile the union.
+            let segments: Vec<(i64, i64)> = histogram.iter().map(|&(l, r, _)| (l, r)).collect();
+            assert_eq!(merge(&segments), merge(&intervals));
+            assert!(histogram.windows(2).all(|w| w[0].1 <= w[1].0));
+            assert!(histogram.iter().all(|&(l, r, _)| l < r));
+
+            for &(l, r, d) in &histogram {
+                assert!(d > 0);
+                assert!((l..r).all(|x| depth(x) == d));
+            }
+            // Maximality
+            assert!(histogram.windows(2).all(|w| w[0].1 < w[1].0 || w[0].2 != w[1].2));
+        }
+    }
+
+    #[test]
+    fn test_interval_map_boundaries() {
+        let mut map = IntervalMap::new();
+        map.assign(0..10, 1);
+        // Assignments at exactly matching boundaries create no empty interval.
+        map.assign(0..10, 2);
+        map.assign(0..5, 3);
+        map.assign(5..10, 4);
+        map.assign(5..5, 5);
+        map.remove(10..20);
+        map.remove(0..0);
+        let pieces: Vec<(i64, i64, &i32)> = map.iter().collect();
+        assert_eq!(pieces, vec![(0, 5, &3), (5, 10, &4)]);
+
+        map.remove(3..7);
+        let pieces: Vec<(i64, i64, &i32)> = map.iter().collect();
+        assert_eq!(pieces, vec![(0, 3, &3), (7, 10, &4)]);
+
+        assert_eq!(map.iter_range(5..5).count(), 0);
+        assert_eq!(map.iter_range(3..7).count(), 0);
+        let pieces: Vec<(i64, i64, &i32)> = map.iter_range(-5..20).collect();
+        assert_eq!(pieces, vec![(0, 3, &3), (7, 10, &4)]);
+        let pieces: Vec<(i64, i64, &i32)> = map.iter_range(1..8).collect();
+        assert_eq!(pieces, vec![(1, 3, &3), (7, 8, &4)]);
+    }
+
+    #[test]
+    fn test_interval_map_assign_with() {
+        let mut map = IntervalMap::new();
+        map.assign(2..4, 1);
+        // Abutting intervals are not merged even if their values are the same.
+        map.assign(4..6, 1);
+        map.assign(0..2, 3);
+        let pieces: Vec<(i64, i64, &i32)> = map.iter().collect();
+        assert_eq!(pieces, vec![(0, 2, &3), (2, 4, &1), (4, 6, &1)]);
+        assert_eq!(map.len(), 3);
+
+        map.assign(1..2, 4);
+        let mut removed = Vec::new();
+        map.assign_with(1..6, 6, |l, r, value| removed.push((l, r, value)));
+        assert_eq!(removed, vec![(1, 2, 4), (2, 4, 1), (4, 6, 1)]);
+        let pieces: Vec<(i64, i64, &i32)> = map.iter().collect();
+        assert_eq!(pieces, vec![(0, 1, &3), (1, 6, &6)]);
+
+        // Gaps are not passed, and empty ranges remove nothing.
+        let mut removed = Vec::new();
+        map.assign_with(-5..3, 7, |l, r, value| removed.push((l, r, value)));
+        map.assign_with(5..5, 8, |l, r, value| removed.push((l, r, value)));
+        assert_eq!(removed, vec![(0, 1, 3), (1, 3, 6)]);
+        assert!(!map.is_empty());
+    }
+
+    #[test]
+    fn test_interval_map_random() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(930);
+        const N: i64 = 30;
+
+        for _ in 0..30 {
+            let mut map = IntervalMap::new();
+            let mut cells: Vec<Option<u32>> = vec![None; N as usize];
+
+            for _ in 0..50 {
+                let l = rng.gen_range(-2, N + 2);
+                let r = rng.gen_range(-2, N + 2);
+                if rng.gen_bool(0.8) {
+                    let value = rng.gen_range(0, 3);
+                    let mut removed = vec![None; N as usize];
+                    map.assign_with(l..r, value, |rl, rr, v| {
+                        assert!(l <= rl && rl < rr && rr <= r);
+                        for x in std::cmp::max(rl, 0)..std::cmp::min(rr, N) {
+                            assert!(removed[x as usize].is_none());
+                            removed[x as usize] = Some(v);
+                        }
+                    });
+                    for x in 0..N {
+                        let expected = if l <= x && x < r { cells[x as usize] } else { None };
+                        assert_eq!(removed[x as usize], expected);
+                    }
+                    for x in std::cmp::max(l, 0)..std::cmp::min(r, N) {
+                        cells[x as usize] = Some(value);
+                    }
+                } else {
+                    map.remove(l..r);
+                    for x in std::cmp::max(l, 0)..std::cmp::min(r, N) {
+                        cells[x as usize] = None;
+                    }
+                }
+
+                for x in 0..N {
+                    assert_eq!(map.get(x), cells[x as usize].as_ref());
+                }
+                assert!(map.iter().all(|(l, r, _)| l < r));
+                assert!(map.iter().zip(map.iter().skip(1)).all(|((_, r1, _), (l2, _, _))| r1 <= l2));
+
+                let (ql, qr) = (rng.gen_range(0, N), rng.gen_range(0, N));
+                let pieces: Vec<(i64, i64, &u32)> = map.iter_range(ql..qr).collect();
+                let mut covered = vec![None; N as usize];
+                for &(l, r, &value) in &pieces {
+                    assert!(ql <= l && l < r && r <= qr);
+                    for x in l..r {
+                        assert!(covered[x as usize].is_none());
+                        covered[x as usize] = Some(value);
+                    }
+                }
+                for x in 0..N {
+                    let expected = if ql <= x && x < qr { cells[x as usize] } else { None };
+                    assert_eq!(covered[x as usize], expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_interval_intersect() {
+        let itv = |l: i64, r: i64| Interval::new(l, r).unwrap();
+
+        assert_eq!(itv(0, 5).intersect(&itv(3, 8)), Some(itv(3, 5)));
+        assert_eq!(itv(3, 8).intersect(&itv(0, 5)), Some(itv(3, 5)));
+        assert_eq!(itv(0, 10).intersect(&itv(3, 5)), Some(itv(3, 5)));
+        // Touching at a point
+        assert_eq!(itv(0, 5).intersect(&itv(5, 8)), Some(itv(5, 5)));
+        assert_eq!(itv(5, 5).intersect(&itv(5, 5)), Some(itv(5, 5)));
+        // Disjoint
+        assert_eq!(itv(0, 5).intersect(&itv(6, 8)), None);
+        assert_eq!(itv(4, 4).intersect(&itv(5, 5)), None);
+
+        assert_eq!(itv(0, 5) & itv(3, 8), Some(itv(3, 5)));
+        assert_eq!(&itv(0, 5) & &itv(6, 8), None);
+    }
+
+    #[test]
+    fn test_interval_hull_and_distance() {
+        let itv = |l: i64, r: i64| Interval::new(l, r).unwrap();
+
+        assert_eq!(itv(0, 5).hull(&itv(3, 8)), itv(0, 8));
+        assert_eq!(itv(7, 7).hull(&itv(2, 2)), itv(2, 7));
+        assert_eq!(itv(0, 10).hull(&itv(3, 5)), itv(0, 10));
+
+        assert_eq!(itv(0, 5).distance_to(&itv(3, 8)), 0);
+        assert_eq!(itv(0, 5).distance_to(&itv(5, 8)), 0);
+        assert_eq!(itv(0, 5).distance_to(&itv(6, 8)), 1);
+        assert_eq!(itv(6, 8).distance_to(&itv(0, 5)), 1);
+        assert_eq!(itv(3, 3).distance_to(&itv(3, 3)), 0);
+        assert_eq!(itv(3, 3).distance_to(&itv(10, 10)), 7);
+        assert_eq!(Interval::new(2u32, 3).unwrap().distance_to(&Interval::new(0, 1).unwrap()), 1);
+    }
+
+    #[test]
+    fn test_interval_contains_interval() {
+        let itv = |l: i64, r: i64| Interval::new(l, r).unwrap();
+
+        assert!(itv(0, 5).contains_interval(&itv(0, 5)));
+        assert!(itv(0, 5).contains_interval(&itv(5, 5)));
+        assert!(itv(0, 5).contains_interval(&itv(1, 4)));
+        assert!(!itv(0, 5).contains_interval(&itv(-1, 4)));
+        assert!(!itv(3, 3).contains_interval(&itv(3, 4)));
+    }
+
+    #[test]
+    fn test_interval_ord() {
+        let itv = |l: i64, r: i64| Interval::new(l, r).unwrap();
+        let mut intervals = vec![itv(3, 5), itv(1, 8), itv(3, 4), itv(1, 1)];
+        intervals.sort();
+        assert_eq!(intervals, vec![itv(1, 1), itv(1, 8), itv(3, 4), itv(3, 5)]);
+    }
+
+    #[test]
+    fn test_interval_read() {
+        assert_eq!(Interval::<i64>::read_words(&["1", "3"]), Ok(Interval::new(1, 3).unwrap()));
+        assert_eq!(Interval::<i64>::read_words(&["-2", "-2"]), Ok(Interval::new(-2, -2).unwrap()));
+        assert!(Interval::<i64>::read_words(&["3", "1"]).is_err());
+        assert!(Interval::<i64>::read_words(&["a", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_stab_query() {
+        let empty: StabQuery<i64> = StabQuery::new(&[]);
+        assert_eq!(empty.count_containing(&0), 0);
+        assert_eq!(empty.collect_containing(&0), vec![]);
+
+        let query = StabQuery::new(&[Interval::new(3, 3).unwrap()]);
+        assert_eq!(query.count_containing(&2), 0);
+        assert_eq!(query.count_containing(&3), 1);
+        assert_eq!(query.collect_containing(&3), vec![0]);
+        assert_eq!(query.collect_containing(&4), vec![]);
+    }
+
+    #[test]
+    fn test_stab_query_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(932);
+
+        for _ in 0..100 {
+            let mut intervals: Vec<Interval<i64>> = (0..rng.gen_range(0, 30)).map(|_| {
+                let l = rng.gen_range(0, 20);
+                Interval::new(l, l + rng.gen_range(0, 6)).unwrap()
+            }).collect();
+            // Many identical intervals
+            if let Some(&itv) = intervals.first() {
+                for _ in 0..rng.gen_range(0, 10) {
+                    intervals.push(itv);
+                }
+            }
+
+            let query = StabQuery::new(&intervals);
+            for x in -2..28 {
+                let expected: Vec<usize> = (0..intervals.len())
+                    .filter(|&i| intervals[i].contains(&x))
+                    .collect();
+                assert_eq!(query.count_containing(&x), expected.len());
+                assert_eq!(query.collect_containing(&x), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_operations() {
+        assert_eq!(union(&[], &[]), vec![]);
+        assert_eq!(intersection(&[(0, 3)], &[]), vec![]);
+        assert_eq!(difference(&[(0, 3)], &[]), vec![(0, 3)]);
+        assert_eq!(difference(&[], &[(0, 3)]), vec![]);
+
+        // Touching intervals
+        assert_eq!(union(&[(0, 3)], &[(3, 5)]), vec![(0, 5)]);
+        assert_eq!(intersection(&[(0, 3)], &[(3, 5)]), vec![]);
+        assert_eq!(difference(&[(0, 5)], &[(3, 5)]), vec![(0, 3)]);
+        // Overlapping inside one input
+        assert_eq!(intersection(&[(0, 4), (2, 6)], &[(5, 8), (1, 2)]), vec![(1, 2), (5, 6)]);
+        assert_eq!(difference(&[(0, 4), (2, 6), (6, 6)], &[(3, 3)]), vec![(0, 6)]);
+    }
+
+    #[test]
+    fn test_set_operations_random() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(933);
+        const N: i64 = 20;
+
+        let materialize = |intervals: &[(i64, i64)]| -> Vec<bool> {
+            (0..N).map(|x| intervals.iter().any(|&(l, r)| l <= x && x < r)).collect()
+        };
+
+        for _ in 0..300 {
+            let mut random = || -> Vec<(i64, i64)> {
+                (0..rng.gen_range(0, 6)).map(|_| (rng.gen_range(0, N), rng.gen_range(0, N))).collect()
+            };
+            let (a, b) = (random(), random());
+            let (bits_a, bits_b) = (materialize(&a), materialize(&b));
+
+            let results = [
+                (union(&a, &b), (0..N as usize).map(|x| bits_a[x] || bits_b[x]).collect::<Vec<_>>()),
+                (intersection(&a, &b), (0..N as usize).map(|x| bits_a[x] && bits_b[x]).collect()),
+                (difference(&a, &b), (0..N as usize).map(|x| bits_a[x] && !bits_b[x]).collect())
+            ];
+            for (result, expected) in results.iter() {
+                assert_eq!(&materialize(result), expected);
+                assert_eq!(&merge(result), result);
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_weight_disjoint() {
+        assert_eq!(max_weight_disjoint(&[]), (0, vec![]));
+        assert_eq!(max_weight_disjoint(&[(0, 1, 0)]), (0, vec![]));
+        assert_eq!(max_weight_disjoint(&[(0, 1, -3)]), (0, vec![]));
+        // Touching intervals are compatible
+        assert_eq!(max_weight_disjoint(&[(3, 5, 2), (0, 3, 2), (2, 4, 3)]), (4, vec![1, 0]));
+        // Heavy long interval beats short ones
+        assert_eq!(max_weight_disjoint(&[(0, 10, 10), (0, 2, 3), (3, 5, 3), (6, 9, 3)]), (10, vec![0]));
+        // Ties with zero weights
+        let (weight, selected) = max_weight_disjoint(&[(0, 2, 0), (1, 3, 4), (3, 5, 0), (0, 3, 4)]);
+        assert_eq!(weight, 4);
+        assert_eq!(selected.len(), 1);
+    }
+
+    #[test]
+    fn test_max_weight_disjoint_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(934);
+
+        for _ in 0..200 {
+            let n = rng.gen_range(0, 19);
+            let intervals: Vec<(i64, i64, i64)> = (0..n).map(|_| {
+                let l = rng.gen_range(0, 20);
+                (l, l + rng.gen_range(1, 6), rng.gen_range(-1, 4))
+            }).collect();
+            let disjoint = |i: usize, j: usize| {
+                intervals[i].1 <= intervals[j].0 || intervals[j].1 <= intervals[i].0
+            };
+
+            let (weight, selected) = max_weight_disjoint(&intervals);
+            for (k, &i) in selected.iter().enumerate() {
+                assert!(selected[k+1..].iter().all(|&j| i != j && disjoint(i, j)));
+            }
+            assert_eq!(selected.iter().map(|&i| intervals[i].2).sum::<i64>(), weight);
+
+            let expected = (0..1usize << n).filter(|&set| {
+                (0..n).all(|i| set >> i & 1 == 0 || (i+1..n).all(|j| set >> j & 1 == 0 || disjoint(i, j)))
+            }).map(|set| {
+                (0..n).filter(|&i| set >> i & 1 == 1).map(|i| intervals[i].2).sum::<i64>()
+            }).max().unwrap();
+            assert_eq!(weight, expected);
+        }
+    }
 }
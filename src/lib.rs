@@ -1,6 +1,6 @@
 //! Utility snippets for fighting AtCoder.
 
-// internal macros
+// macros implementing operators
 #[macro_use] mod op_macros;
 
 #[macro_use] pub mod read;
@@ -1,2 +1,4 @@
 pub mod modp;
+pub mod static_modp;
+pub mod ntt;
 pub use modp::ModP;
@@ -1,12 +1,15 @@
 //! Arithmetics modulo a prime number.
 //!
 //! Never use this module in multi-threaded programs.
+//! If the modulus is known at compile time, use [`static_modp`](../static_modp/index.html) instead.
 // 動的なmod設定が必要な問題: ABC137 F
 // 複数のmodを使い分けなければならない問題には対応できない
 
 use crate::read::{Readable, Words};
+use crate::write::Writable;
+use crate::num::{WithZero, WithOne};
 
-// BEGIN SNIPPET modp DEPENDS ON read op_macros
+// BEGIN SNIPPET modp DEPENDS ON read write op_macros num_types
 
 pub type ModPBase = u64;
 pub type ModPModulus = u32;
@@ -78,6 +81,12 @@ impl ModP {
         Ok(())
     }
 
+    /// Gets the modulus set by `set_mod`.
+    pub fn modulus() -> ModPBase {
+        ModP::assert_mod_already_set();
+        unsafe { MODULUS }
+    }
+
     /// Create a number.
     pub fn new(n: ModPBase) -> ModP {
         ModP::assert_mod_already_set();
@@ -185,6 +194,12 @@ impl std::fmt::Display for ModP {
     }
 }
 
+impl Writable for ModP {
+    fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write!(writer, "{}", self.base())
+    }
+}
+
 impl std::fmt::Debug for ModP {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{} mod P", self.base())
@@ -459,6 +474,18 @@ impl<'a> std::iter::Product<&'a ModP> for ModP {
     }
 }
 
+impl WithZero for ModP {
+    fn zero() -> ModP {
+        ModP::new(0)
+    }
+}
+
+impl WithOne for ModP {
+    fn one() -> ModP {
+        ModP::new(1)
+    }
+}
+
 readable!(ModP, 1, |ws| ModP::new(ws[0].read::<ModPBase>()));
 
 pub struct FactCache {
@@ -671,6 +698,14 @@ mod tests {
         assert_eq!(modp(3), modp(10));
     }
 
+    #[test]
+    fn test_write() {
+        unsafe { ModP::set_mod(7).unwrap(); }
+        let mut out = Vec::new();
+        vec![modp(10), modp(6)].write_to(&mut out).unwrap();
+        assert_eq!(out, b"3 6");
+    }
+
     #[test]
     fn test_pow() {
         unsafe { ModP::set_mod(7).unwrap(); }
@@ -0,0 +1,382 @@
+//! Convolution by number theoretic transform.
+
+use crate::modulo::modp::{ModP, ModPBase};
+
+// BEGIN SNIPPET ntt DEPENDS ON modp
+
+// `base^exp mod modulus`.
+fn ntt_pow(base: u64, exp: u64, modulus: u64) -> u64 {
+    let mut ans = 1;
+    let mut base = base % modulus;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp % 2 == 1 {
+            ans = ans * base % modulus;
+        }
+        base = base * base % modulus;
+        exp /= 2;
+    }
+    ans
+}
+
+// The smallest primitive root of a prime `modulus`.
+fn ntt_primitive_root(modulus: u64) -> u64 {
+    let mut factors = Vec::new();
+    let mut rest = modulus - 1;
+    let mut d = 2;
+    while d * d <= rest {
+        if rest % d == 0 {
+            factors.push(d);
+            while rest % d == 0 {
+                rest /= d;
+            }
+        }
+        d += 1;
+    }
+    if rest > 1 {
+        factors.push(rest);
+    }
+
+    (2..).find(|&g| factors.iter().all(|&q| ntt_pow(g, (modulus - 1) / q, modulus) != 1)).unwrap()
+}
+
+// In-place NTT of `a`, whose length is a power of 2.
+// `roots[k]` must be a primitive 2^k-th root of unity,
+// or its inverse for the inverse transform.
+fn ntt_transform(a: &mut [u64], roots: &[u64], modulus: u64) {
+    let n = a.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let mut k = 1;
+    while 1 << k <= n {
+        let half = 1 << (k - 1);
+        let mut powers = Vec::with_capacity(half);
+        let mut w = 1;
+        for _ in 0..half {
+            powers.push(w);
+            w = w * roots[k] % modulus;
+        }
+
+        for block in a.chunks_mut(2 * half) {
+            let (left, right) = block.split_at_mut(half);
+            for ((x, y), &w) in left.iter_mut().zip(right.iter_mut()).zip(&powers) {
+                let u = *x;
+                let v = *y * w % modulus;
+                *x = if u + v >= modulus { u + v - modulus } else { u + v };
+                *y = if u >= v { u - v } else { u + modulus - v };
+            }
+        }
+        k += 1;
+    }
+}
+
+/// Convolution of `a` and `b` modulo a prime `modulus`.
+///
+/// The `k`-th item of the result is Σ `a[i] * b[k - i]`,
+/// and the length of the result is `a.len() + b.len() - 1`,
+/// or 0 if either of `a` and `b` is empty.
+///
+/// It takes O(*n* log(*n*)) time by number theoretic transform,
+/// where *n* is the length of the result.
+/// `modulus` must be less than 2^32.
+///
+/// # Panics
+///
+/// Panics if 2^*k* doesn't divide `modulus - 1`, where 2^*k* is the length of NTT,
+/// the smallest power of 2 not less than the length of the result.
+/// This is checked even if the inputs are so short that they are convolved naively.
+/// For example, `998_244_353 - 1` is divisible by 2^23,
+/// so the length of the result can be up to 2^23.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::modulo::ntt::*;
+/// assert_eq!(convolution_mod(&[1, 2, 3], &[4, 5], 998_244_353), vec![4, 13, 22, 15]);
+/// ```
+pub fn convolution_mod(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let len = a.len() + b.len() - 1;
+    let size = len.next_power_of_two();
+    let log = size.trailing_zeros() as usize;
+    let valuation = (modulus - 1).trailing_zeros() as usize;
+    assert!(log <= valuation,
+            "the modulus {} doesn't support NTT of length 2^{}, as {} - 1 is divisible by 2^{} at most",
+            modulus, log, modulus, valuation);
+
+    if std::cmp::min(a.len(), b.len()) <= 32 {
+        let mut c = vec![0; len];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                c[i + j] = (c[i + j] + x % modulus * (y % modulus)) % modulus;
+            }
+        }
+        return c;
+    }
+
+    let g = ntt_primitive_root(modulus);
+    let roots: Vec<u64> = (0..=log).map(|k| ntt_pow(g, (modulus - 1) >> k, modulus)).collect();
+    let inv_roots: Vec<u64> = roots.iter().map(|&w| ntt_pow(w, modulus - 2, modulus)).collect();
+
+    let mut fa: Vec<u64> = a.iter().map(|&x| x % modulus).collect();
+    fa.resize(size, 0);
+    let mut fb: Vec<u64> = b.iter().map(|&x| x % modulus).collect();
+    fb.resize(size, 0);
+    ntt_transform(&mut fa, &roots, modulus);
+    ntt_transform(&mut fb, &roots, modulus);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x = *x * y % modulus;
+    }
+    ntt_transform(&mut fa, &inv_roots, modulus);
+
+    let size_inv = ntt_pow(size as u64, modulus - 2, modulus);
+    fa.truncate(len);
+    for x in &mut fa {
+        *x = *x * size_inv % modulus;
+    }
+    fa
+}
+
+/// Convolution of `a` and `b` modulo the modulus of `ModP`.
+///
+/// See [`convolution_mod`](fn.convolution_mod.html) for details.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::modulo::modp::*;
+/// # use atcoder_snippets::modulo::ntt::*;
+/// unsafe {
+///     ModP::set_mod(998_244_353).unwrap();
+/// }
+/// let a: Vec<ModP> = vec![1, 2, 3].into_iter().map(ModP::new).collect();
+/// let b: Vec<ModP> = vec![998_244_352, 1].into_iter().map(ModP::new).collect();
+/// // (1 + 2x + 3x^2)(-1 + x) = -1 - x - x^2 + 3x^3
+/// assert_eq!(convolution(&a, &b), vec![998_244_352, 998_244_352, 998_244_352, 3]);
+/// ```
+pub fn convolution(a: &[ModP], b: &[ModP]) -> Vec<ModP> {
+    let a: Vec<ModPBase> = a.iter().map(|x| x.base()).collect();
+    let b: Vec<ModPBase> = b.iter().map(|x| x.base()).collect();
+    convolution_mod(&a, &b, ModP::modulus())
+        .into_iter()
+        .map(|x| unsafe { ModP::new_unchecked(x) })
+        .collect()
+}
+
+/// Convolution of `a` and `b` modulo any `modulus`, which need not be prime.
+///
+/// The convolution is computed modulo three primes
+/// 754_974_721, 167_772_161 and 469_762_049, whose product is about 5.8 * 10^25,
+/// and is reconstructed by Garner's algorithm.
+/// So the true values of the convolution must be less than the product,
+/// which holds if `modulus` is up to 1_100_000_000 and the length of the result is up to 2^24.
+///
+/// # Panics
+///
+/// Panics if the length of the result is greater than 2^24.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::modulo::ntt::*;
+/// let m = 1_000_000_007;
+/// assert_eq!(convolution_arbitrary_mod(&[m - 1, 2], &[m - 1, 3], m), vec![1, m - 5, 6]);
+/// assert_eq!(convolution_arbitrary_mod(&[5, 7], &[3, 4], 12), vec![3, 5, 4]);
+/// ```
+pub fn convolution_arbitrary_mod(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+    const P1: u64 = 754_974_721;
+    const P2: u64 = 167_772_161;
+    const P3: u64 = 469_762_049;
+
+    let a: Vec<u64> = a.iter().map(|&x| x % modulus).collect();
+    let b: Vec<u64> = b.iter().map(|&x| x % modulus).collect();
+    let c1 = convolution_mod(&a, &b, P1);
+    let c2 = convolution_mod(&a, &b, P2);
+    let c3 = convolution_mod(&a, &b, P3);
+
+    let p1_inv = ntt_pow(P1, P2 - 2, P2);
+    let p1p2_inv = ntt_pow(P1 * P2 % P3, P3 - 2, P3);
+    c1.into_iter().zip(c2).zip(c3).map(|((r1, r2), r3)| {
+        // x = r1 + P1 * t1 + P1 * P2 * t2
+        let t1 = (r2 + P2 - r1 % P2) % P2 * p1_inv % P2;
+        let x12 = r1 as u128 + P1 as u128 * t1 as u128;
+        let t2 = (r3 + P3 - (x12 % P3 as u128) as u64) % P3 * p1p2_inv % P3;
+        let x = x12 + (P1 * P2) as u128 * t2 as u128;
+        (x % modulus as u128) as u64
+    }).collect()
+}
+
+/// Convolution of `a` and `b` modulo the modulus of `ModP`,
+/// which need not be NTT-friendly.
+///
+/// See [`convolution_arbitrary_mod`](fn.convolution_arbitrary_mod.html) for details.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::modulo::modp::*;
+/// # use atcoder_snippets::modulo::ntt::*;
+/// unsafe {
+///     ModP::set_mod(1_000_000_007).unwrap();
+/// }
+/// let a: Vec<ModP> = vec![1, 2, 3].into_iter().map(ModP::new).collect();
+/// let b: Vec<ModP> = vec![1_000_000_006, 1].into_iter().map(ModP::new).collect();
+/// // (1 + 2x + 3x^2)(-1 + x) = -1 - x - x^2 + 3x^3
+/// assert_eq!(convolution_arbitrary(&a, &b), vec![1_000_000_006, 1_000_000_006, 1_000_000_006, 3]);
+/// ```
+pub fn convolution_arbitrary(a: &[ModP], b: &[ModP]) -> Vec<ModP> {
+    let a: Vec<ModPBase> = a.iter().map(|x| x.base()).collect();
+    let b: Vec<ModPBase> = b.iter().map(|x| x.base()).collect();
+    convolution_arbitrary_mod(&a, &b, ModP::modulus())
+        .into_iter()
+        .map(|x| unsafe { ModP::new_unchecked(x) })
+        .collect()
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, Rng};
+
+    const MOD: u64 = 998_244_353;
+
+    fn naive(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+        if a.is_empty() || b.is_empty() {
+            return Vec::new();
+        }
+        let mut c = vec![0; a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                c[i + j] = (c[i + j] + x * y) % modulus;
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn test_primitive_root() {
+        assert_eq!(ntt_primitive_root(998_244_353), 3);
+        assert_eq!(ntt_primitive_root(754_974_721), 11);
+        assert_eq!(ntt_primitive_root(167_772_161), 3);
+        assert_eq!(ntt_primitive_root(469_762_049), 3);
+        assert_eq!(ntt_primitive_root(7), 3);
+    }
+
+    #[test]
+    fn test_small() {
+        assert_eq!(convolution_mod(&[], &[1, 2], MOD), Vec::<u64>::new());
+        assert_eq!(convolution_mod(&[1, 2], &[], MOD), Vec::<u64>::new());
+        assert_eq!(convolution_mod(&[3], &[5], MOD), vec![15]);
+        assert_eq!(convolution_mod(&[3], &[1, 2, 3], MOD), vec![3, 6, 9]);
+        assert_eq!(convolution_mod(&[MOD - 1], &[MOD - 1], MOD), vec![1]);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = Xorshift::with_seed(1031);
+        for _ in 0..100 {
+            let n = rng.gen_range(1..300) as usize;
+            let m = rng.gen_range(1..300) as usize;
+            let a: Vec<u64> = (0..n).map(|_| rng.gen_range(0..MOD)).collect();
+            let b: Vec<u64> = (0..m).map(|_| rng.gen_range(0..MOD)).collect();
+            assert_eq!(convolution_mod(&a, &b, MOD), naive(&a, &b, MOD));
+        }
+    }
+
+    #[test]
+    fn test_other_moduli() {
+        let mut rng = Xorshift::with_seed(1031);
+        // 65537 - 1 = 2^16
+        for &modulus in &[167_772_161, 469_762_049, 754_974_721, 65537] {
+            let a: Vec<u64> = (0..500).map(|_| rng.gen_range(0..modulus)).collect();
+            let b: Vec<u64> = (0..700).map(|_| rng.gen_range(0..modulus)).collect();
+            assert_eq!(convolution_mod(&a, &b, modulus), naive(&a, &b, modulus));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't support NTT")]
+    fn test_unfriendly_modulus() {
+        let a = vec![1; 100];
+        convolution_mod(&a, &a, 1_000_000_007);
+    }
+
+    #[test]
+    #[should_panic(expected = "doesn't support NTT")]
+    fn test_unfriendly_modulus_short() {
+        convolution_mod(&[1, 2], &[3, 4], 1_000_000_007);
+    }
+
+    fn naive_arbitrary(a: &[u64], b: &[u64], modulus: u64) -> Vec<u64> {
+        if a.is_empty() || b.is_empty() {
+            return Vec::new();
+        }
+        let mut c = vec![0u128; a.len() + b.len() - 1];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                c[i + j] += x as u128 * y as u128;
+            }
+        }
+        c.into_iter().map(|x| (x % modulus as u128) as u64).collect()
+    }
+
+    #[test]
+    fn test_arbitrary_random() {
+        let mut rng = Xorshift::with_seed(1032);
+        for &modulus in &[1_000_000_007, 1_000_000, 12, 1] {
+            for _ in 0..20 {
+                let n = rng.gen_range(0..300) as usize;
+                let m = rng.gen_range(0..300) as usize;
+                let a: Vec<u64> = (0..n).map(|_| rng.gen_range(0..modulus)).collect();
+                let b: Vec<u64> = (0..m).map(|_| rng.gen_range(0..modulus)).collect();
+                assert_eq!(convolution_arbitrary_mod(&a, &b, modulus), naive_arbitrary(&a, &b, modulus));
+            }
+        }
+    }
+
+    #[test]
+    fn test_arbitrary_exceeding_u64() {
+        let modulus = 1_000_000_007;
+        let a = vec![modulus - 1; 2000];
+        let b = vec![modulus - 2; 3000];
+        // Each true value is up to 2000 * 10^18, which exceeds u64.
+        assert_eq!(convolution_arbitrary_mod(&a, &b, modulus), naive_arbitrary(&a, &b, modulus));
+
+        let a = vec![1_100_000_000 - 1; 1000];
+        assert_eq!(convolution_arbitrary_mod(&a, &a, 1_100_000_000), naive_arbitrary(&a, &a, 1_100_000_000));
+    }
+
+    #[test]
+    fn test_large() {
+        let mut rng = Xorshift::with_seed(1031);
+        let len = 1 << 18;
+        let a: Vec<u64> = (0..len).map(|_| rng.gen_range(0..MOD)).collect();
+        let b: Vec<u64> = (0..len).map(|_| rng.gen_range(0..MOD)).collect();
+        let c = convolution_mod(&a, &b, MOD);
+        assert_eq!(c.len(), 2 * len - 1);
+
+        for _ in 0..20 {
+            let k = rng.gen_range(0..c.len() as u64) as usize;
+            let lo = k.saturating_sub(len - 1);
+            let hi = std::cmp::min(k, len - 1);
+            let expected = (lo..=hi).fold(0, |acc, i| (acc + a[i] * b[k - i]) % MOD);
+            assert_eq!(c[k], expected);
+        }
+    }
+}
@@ -0,0 +1,941 @@
+//! Arithmetics modulo a prime number fixed at compile time.
+//!
+//! Unlike [`ModP`](../modp/struct.ModP.html), the modulus is a part of the type,
+//! so no setup is required, and numbers of different moduli can be used in one program.
+
+use crate::read::{Readable, Words};
+use crate::write::Writable;
+use crate::num::{WithZero, WithOne};
+
+// BEGIN SNIPPET static_modp DEPENDS ON read write op_macros num_types
+
+/// Prime modulus of [`StaticModP`](struct.StaticModP.html).
+///
+/// Other moduli than predefined ones can be used by implementing this trait
+/// for a unit struct.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::modulo::static_modp::*;
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+/// struct Mod7;
+///
+/// impl Modulus for Mod7 {
+///     const MODULUS: u64 = 7;
+/// }
+///
+/// assert_eq!(StaticModP::<Mod7>::new(3) * 5, StaticModP::new(1));
+/// ```
+pub trait Modulus: Copy + Eq + std::hash::Hash {
+    /// The modulus, which must be a prime number less than 2^32.
+    const MODULUS: u64;
+}
+
+/// Modulus 1_000_000_007.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Mod1000000007;
+
+impl Modulus for Mod1000000007 {
+    const MODULUS: u64 = 1_000_000_007;
+}
+
+/// Modulus 998_244_353.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Mod998244353;
+
+impl Modulus for Mod998244353 {
+    const MODULUS: u64 = 998_244_353;
+}
+
+/// A number whose arithmetics is carried modulo `M::MODULUS`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::modulo::static_modp::*;
+/// let x = ModP1000000007::new(1_000_000_000);
+/// let y = ModP998244353::new(1_000_000_000);
+/// assert_eq!(x * 2, ModP1000000007::new(999_999_993));
+/// assert_eq!(y * 2, ModP998244353::new(3_511_294));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StaticModP<M: Modulus> {
+    base: u64,
+    phantom: std::marker::PhantomData<M>
+}
+
+pub type ModP1000000007 = StaticModP<Mod1000000007>;
+pub type ModP998244353 = StaticModP<Mod998244353>;
+
+impl<M: Modulus> StaticModP<M> {
+    /// Create a number.
+    pub fn new(n: u64) -> StaticModP<M> {
+        StaticModP {
+            base: n % M::MODULUS,
+            phantom: std::marker::PhantomData
+        }
+    }
+
+    /// Create a number without taking remainder by the modulus.
+    ///
+    /// # Safety
+    ///
+    /// If n is greater than or equal to the modulus,
+    /// the correctness of calculations is not guaranteed.
+    pub unsafe fn new_unchecked(n: u64) -> StaticModP<M> {
+        StaticModP {
+            base: n,
+            phantom: std::marker::PhantomData
+        }
+    }
+
+    /// Returns a `u64` satisfying `0 <= x < modulus`.
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// Calculate power using exponentiation by squaring.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::modulo::static_modp::*;
+    /// // 2^30 = 1073741824 = 73741817 mod 1_000_000_007.
+    /// assert_eq!(ModP1000000007::new(2).pow(30), ModP1000000007::new(73741817));
+    /// ```
+    pub fn pow(self, exp: u64) -> StaticModP<M> {
+        let mut ans = StaticModP::new(1);
+        let mut base = self;
+        let mut exp = exp;
+        while exp > 0 {
+            if exp % 2 == 1 {
+                ans *= base;
+            }
+            base *= base;
+            exp /= 2;
+        }
+        ans
+    }
+
+    /// Inverse element.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `self` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::modulo::static_modp::*;
+    /// assert_eq!(ModP1000000007::new(2).inv(), ModP1000000007::new(500_000_004));
+    /// assert_eq!(ModP998244353::new(2).inv(), ModP998244353::new(499_122_177));
+    /// ```
+    pub fn inv(self) -> StaticModP<M> {
+        assert!(self.base() != 0);
+        self.pow(M::MODULUS - 2)
+    }
+
+    pub fn fact_cache() -> StaticFactCache<M> {
+        StaticFactCache {
+            table: vec![StaticModP::new(1)]
+        }
+    }
+
+    pub fn inv_cache() -> StaticInvCache<M> {
+        StaticInvCache {
+            table: vec![StaticModP::new(0), StaticModP::new(1)]
+        }
+    }
+
+    pub fn pow_cache(base: u64) -> StaticPowCache<M> {
+        StaticPowCache {
+            base,
+            table: vec![StaticModP::new(1)]
+        }
+    }
+
+    /// Cache for faster calculation.
+    ///
+    /// See [`StaticCombinatoricsCache`](struct.StaticCombinatoricsCache.html).
+    pub fn combinatorics_cache() -> StaticCombinatoricsCache<M> {
+        StaticCombinatoricsCache {
+            facts: StaticModP::fact_cache(),
+            invs: StaticModP::inv_cache(),
+            finvs: vec![StaticModP::new(1)],
+        }
+    }
+}
+
+impl<M: Modulus> std::fmt::Display for StaticModP<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.base())
+    }
+}
+
+impl<M: Modulus> Writable for StaticModP<M> {
+    fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write!(writer, "{}", self.base())
+    }
+}
+
+impl<M: Modulus> std::fmt::Debug for StaticModP<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} mod {}", self.base(), M::MODULUS)
+    }
+}
+
+impl<M: Modulus> PartialEq<u64> for StaticModP<M> {
+    fn eq(&self, other: &u64) -> bool {
+        self.base() == other % M::MODULUS
+    }
+}
+
+impl<M: Modulus> PartialEq<StaticModP<M>> for u64 {
+    fn eq(&self, other: &StaticModP<M>) -> bool {
+        self % M::MODULUS == other.base()
+    }
+}
+
+macro_rules! impl_from_signed_for_static_modp {
+    ( $($t: ty)* ) => { $(
+        impl<M: Modulus> From<$t> for StaticModP<M> {
+            fn from(num: $t) -> StaticModP<M> {
+                unsafe { StaticModP::new_unchecked((num as i64).rem_euclid(M::MODULUS as i64) as u64) }
+            }
+        }
+    )* }
+}
+
+impl_from_signed_for_static_modp!(i8 i16 i32 i64 isize);
+
+macro_rules! impl_from_unsigned_for_static_modp {
+    ( $($t: ty)* ) => { $(
+        impl<M: Modulus> From<$t> for StaticModP<M> {
+            fn from(num: $t) -> StaticModP<M> {
+                StaticModP::new(num as u64)
+            }
+        }
+    )* }
+}
+
+impl_from_unsigned_for_static_modp!(u8 u16 u32 u64 usize);
+
+impl<M: Modulus> From<i128> for StaticModP<M> {
+    fn from(num: i128) -> StaticModP<M> {
+        unsafe { StaticModP::new_unchecked(num.rem_euclid(M::MODULUS as i128) as u64) }
+    }
+}
+
+impl<M: Modulus> From<u128> for StaticModP<M> {
+    fn from(num: u128) -> StaticModP<M> {
+        unsafe { StaticModP::new_unchecked((num % M::MODULUS as u128) as u64) }
+    }
+}
+
+impl<M: Modulus> std::ops::Add for StaticModP<M> {
+    type Output = StaticModP<M>;
+
+    fn add(self, rhs: StaticModP<M>) -> StaticModP<M> {
+        StaticModP::new(self.base() + rhs.base() % M::MODULUS)
+    }
+}
+
+impl<M: Modulus> std::ops::Add<u64> for StaticModP<M> {
+    type Output = StaticModP<M>;
+
+    fn add(self, rhs: u64) -> StaticModP<M> {
+        self + StaticModP::new(rhs)
+    }
+}
+
+impl<M: Modulus> std::ops::Add<StaticModP<M>> for u64 {
+    type Output = StaticModP<M>;
+
+    fn add(self, rhs: StaticModP<M>) -> StaticModP<M> {
+        StaticModP::new(self) + rhs
+    }
+}
+
+impl<M: Modulus> std::ops::AddAssign for StaticModP<M> {
+    fn add_assign(&mut self, rhs: StaticModP<M>) {
+        *self = *self + rhs
+    }
+}
+
+impl<M: Modulus> std::ops::AddAssign<u64> for StaticModP<M> {
+    fn add_assign(&mut self, rhs: u64) {
+        *self = *self + StaticModP::new(rhs)
+    }
+}
+
+impl<M: Modulus> std::ops::Neg for StaticModP<M> {
+    type Output = StaticModP<M>;
+
+    fn neg(self) -> StaticModP<M> {
+        StaticModP::new(M::MODULUS - self.base())
+    }
+}
+
+impl<M: Modulus> std::ops::Sub for StaticModP<M> {
+    type Output = StaticModP<M>;
+
+    fn sub(self, rhs: StaticModP<M>) -> StaticModP<M> {
+        self + (-rhs)
+    }
+}
+
+impl<M: Modulus> std::ops::Sub<u64> for StaticModP<M> {
+    type Output = StaticModP<M>;
+
+    fn sub(self, rhs: u64) -> StaticModP<M> {
+        self - StaticModP::new(rhs)
+    }
+}
+
+impl<M: Modulus> std::ops::Sub<StaticModP<M>> for u64 {
+    type Output = StaticModP<M>;
+
+    fn sub(self, rhs: StaticModP<M>) -> StaticModP<M> {
+        StaticModP::new(self) - rhs
+    }
+}
+
+impl<M: Modulus> std::ops::SubAssign for StaticModP<M> {
+    fn sub_assign(&mut self, rhs: StaticModP<M>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<M: Modulus> std::ops::SubAssign<u64> for StaticModP<M> {
+    fn sub_assign(&mut self, rhs: u64) {
+        *self = *self - StaticModP::new(rhs)
+    }
+}
+
+impl<M: Modulus> std::ops::Mul for StaticModP<M> {
+    type Output = StaticModP<M>;
+
+    fn mul(self, rhs: StaticModP<M>) -> StaticModP<M> {
+        StaticModP::new(self.base() * (rhs.base() % M::MODULUS))
+    }
+}
+
+impl<M: Modulus> std::ops::Mul<u64> for StaticModP<M> {
+    type Output = StaticModP<M>;
+
+    fn mul(self, rhs: u64) -> StaticModP<M> {
+        self * StaticModP::new(rhs)
+    }
+}
+
+impl<M: Modulus> std::ops::Mul<StaticModP<M>> for u64 {
+    type Output = StaticModP<M>;
+
+    fn mul(self, rhs: StaticModP<M>) -> StaticModP<M> {
+        StaticModP::new(self) * rhs
+    }
+}
+
+impl<M: Modulus> std::ops::MulAssign for StaticModP<M> {
+    fn mul_assign(&mut self, rhs: StaticModP<M>) {
+        *self = *self * rhs
+    }
+}
+
+impl<M: Modulus> std::ops::MulAssign<u64> for StaticModP<M> {
+    fn mul_assign(&mut self, rhs: u64) {
+        *self = *self * StaticModP::new(rhs)
+    }
+}
+
+impl<M: Modulus> std::ops::Div for StaticModP<M> {
+    type Output = StaticModP<M>;
+
+    fn div(self, rhs: StaticModP<M>) -> StaticModP<M> {
+        std::ops::Mul::mul(self, rhs.inv())
+    }
+}
+
+impl<M: Modulus> std::ops::Div<u64> for StaticModP<M> {
+    type Output = StaticModP<M>;
+
+    fn div(self, rhs: u64) -> StaticModP<M> {
+        self / StaticModP::new(rhs)
+    }
+}
+
+impl<M: Modulus> std::ops::Div<StaticModP<M>> for u64 {
+    type Output = StaticModP<M>;
+
+    fn div(self, rhs: StaticModP<M>) -> StaticModP<M> {
+        StaticModP::new(self) / rhs
+    }
+}
+
+impl<M: Modulus> std::ops::DivAssign for StaticModP<M> {
+    fn div_assign(&mut self, rhs: StaticModP<M>) {
+        *self = *self / rhs;
+    }
+}
+
+impl<M: Modulus> std::ops::DivAssign<u64> for StaticModP<M> {
+    fn div_assign(&mut self, rhs: u64) {
+        *self = *self / StaticModP::new(rhs)
+    }
+}
+
+forward_ref_binop_generic!(impl[M: Modulus] Add, add for StaticModP<M>, StaticModP<M>);
+forward_ref_binop_generic!(impl[M: Modulus] Add, add for StaticModP<M>, u64);
+forward_ref_binop_generic!(impl[M: Modulus] Add, add for u64, StaticModP<M>);
+forward_ref_op_assign_generic!(impl[M: Modulus] AddAssign, add_assign for StaticModP<M>, StaticModP<M>);
+forward_ref_op_assign_generic!(impl[M: Modulus] AddAssign, add_assign for StaticModP<M>, u64);
+
+forward_ref_unop_generic!(impl[M: Modulus] Neg, neg for StaticModP<M>);
+
+forward_ref_binop_generic!(impl[M: Modulus] Sub, sub for StaticModP<M>, StaticModP<M>);
+forward_ref_binop_generic!(impl[M: Modulus] Sub, sub for StaticModP<M>, u64);
+forward_ref_binop_generic!(impl[M: Modulus] Sub, sub for u64, StaticModP<M>);
+forward_ref_op_assign_generic!(impl[M: Modulus] SubAssign, sub_assign for StaticModP<M>, StaticModP<M>);
+forward_ref_op_assign_generic!(impl[M: Modulus] SubAssign, sub_assign for StaticModP<M>, u64);
+
+forward_ref_binop_generic!(impl[M: Modulus] Mul, mul for StaticModP<M>, StaticModP<M>);
+forward_ref_binop_generic!(impl[M: Modulus] Mul, mul for StaticModP<M>, u64);
+forward_ref_binop_generic!(impl[M: Modulus] Mul, mul for u64, StaticModP<M>);
+forward_ref_op_assign_generic!(impl[M: Modulus] MulAssign, mul_assign for StaticModP<M>, StaticModP<M>);
+forward_ref_op_assign_generic!(impl[M: Modulus] MulAssign, mul_assign for StaticModP<M>, u64);
+
+forward_ref_binop_generic!(impl[M: Modulus] Div, div for StaticModP<M>, StaticModP<M>);
+forward_ref_binop_generic!(impl[M: Modulus] Div, div for StaticModP<M>, u64);
+forward_ref_binop_generic!(impl[M: Modulus] Div, div for u64, StaticModP<M>);
+forward_ref_op_assign_generic!(impl[M: Modulus] DivAssign, div_assign for StaticModP<M>, StaticModP<M>);
+forward_ref_op_assign_generic!(impl[M: Modulus] DivAssign, div_assign for StaticModP<M>, u64);
+
+impl<M: Modulus> std::iter::Sum for StaticModP<M> {
+    fn sum<I: Iterator<Item=StaticModP<M>>>(iter: I) -> StaticModP<M> {
+        iter.fold(StaticModP::new(0), |acc, n| acc + n)
+    }
+}
+
+impl<'a, M: Modulus> std::iter::Sum<&'a StaticModP<M>> for StaticModP<M> {
+    fn sum<I: Iterator<Item=&'a StaticModP<M>>>(iter: I) -> StaticModP<M> {
+        iter.fold(StaticModP::new(0), |acc, n| acc + n)
+    }
+}
+
+impl<M: Modulus> std::iter::Product for StaticModP<M> {
+    fn product<I: Iterator<Item=StaticModP<M>>>(iter: I) -> StaticModP<M> {
+        iter.fold(StaticModP::new(1), |acc, n| acc * n)
+    }
+}
+
+impl<'a, M: Modulus> std::iter::Product<&'a StaticModP<M>> for StaticModP<M> {
+    fn product<I: Iterator<Item=&'a StaticModP<M>>>(iter: I) -> StaticModP<M> {
+        iter.fold(StaticModP::new(1), |acc, n| acc * n)
+    }
+}
+
+impl<M: Modulus> WithZero for StaticModP<M> {
+    fn zero() -> StaticModP<M> {
+        StaticModP::new(0)
+    }
+}
+
+impl<M: Modulus> WithOne for StaticModP<M> {
+    fn one() -> StaticModP<M> {
+        StaticModP::new(1)
+    }
+}
+
+impl<M: Modulus> Readable for StaticModP<M> {
+    type Output = StaticModP<M>;
+    const WORD_COUNT: usize = 1;
+
+    fn read_words(words: &[&str]) -> Result<StaticModP<M>, String> {
+        Ok(StaticModP::new(words[0].read::<u64>()))
+    }
+}
+
+pub struct StaticFactCache<M: Modulus> {
+    table: Vec<StaticModP<M>>
+}
+
+impl<M: Modulus> StaticFactCache<M> {
+    pub fn get(&mut self, n: u64) -> StaticModP<M> {
+        self.extend(n as usize);
+        self.table[n as usize]
+    }
+
+    fn extend(&mut self, max: usize) {
+        for i in self.table.len()..max+1 {
+            let prev = self.table[i-1];
+            self.table.push(prev * i as u64);
+        }
+    }
+}
+
+pub struct StaticInvCache<M: Modulus> {
+    table: Vec<StaticModP<M>>
+}
+
+impl<M: Modulus> StaticInvCache<M> {
+    pub fn get(&mut self, n: u64) -> StaticModP<M> {
+        assert!(n > 0);
+        self.extend(n as usize);
+        self.table[n as usize]
+    }
+
+    fn extend(&mut self, max: usize) {
+        for i in self.table.len()..max+1 {
+            let m = M::MODULUS;
+            // cf. http://drken1215.hatenablog.com/entry/2018/06/08/210000
+            let prev = self.table[m as usize % i];
+            self.table.push(m / i as u64 * (-prev));
+        }
+    }
+}
+
+pub struct StaticPowCache<M: Modulus> {
+    base: u64,
+    table: Vec<StaticModP<M>>
+}
+
+impl<M: Modulus> StaticPowCache<M> {
+    pub fn get(&mut self, n: u64) -> StaticModP<M> {
+        self.extend(n as usize);
+        self.table[n as usize]
+    }
+
+    fn extend(&mut self, max: usize) {
+        for i in self.table.len()..max+1 {
+            let prev = self.table[i-1];
+            self.table.push(prev * self.base);
+        }
+    }
+}
+
+pub struct StaticCombinatoricsCache<M: Modulus> {
+    facts: StaticFactCache<M>,
+    invs: StaticInvCache<M>,
+    finvs: Vec<StaticModP<M>>,
+}
+
+impl<M: Modulus> StaticCombinatoricsCache<M> {
+    /// Binomial coefficient.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::modulo::static_modp::*;
+    /// let mut cc = ModP1000000007::combinatorics_cache();
+    /// assert_eq!(cc.choose(5, 3), ModP1000000007::new(10));
+    /// // 100 choose 50 = 100891344545564193334812497256 = 538992043 mod 1_000_000_007
+    /// assert_eq!(cc.choose(100, 50), ModP1000000007::new(538_992_043));
+    /// ```
+    pub fn choose(&mut self, n: u64, m: u64) -> StaticModP<M> {
+        if n < m {
+            return StaticModP::new(0);
+        }
+        self.extend_finvs(std::cmp::max(m, n-m) as usize);
+        self.fact(n) * self.finvs[m as usize] * self.finvs[(n-m) as usize]
+    }
+
+    /// Number of permutations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::modulo::static_modp::*;
+    /// let mut cc = ModP998244353::combinatorics_cache();
+    /// assert_eq!(cc.permutation(5, 3), ModP998244353::new(60));
+    /// ```
+    pub fn permutation(&mut self, n: u64, m: u64) -> StaticModP<M> {
+        if n < m {
+            return StaticModP::new(0);
+        }
+        self.extend_finvs((n-m) as usize);
+        self.fact(n) * self.finvs[(n-m) as usize]
+    }
+
+    /// Number of combinations with replacement.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::modulo::static_modp::*;
+    /// let mut cc = ModP1000000007::combinatorics_cache();
+    /// // 2 multichoose 5 = (2+5-1) choose 5 = 6
+    /// assert_eq!(cc.multichoose(2, 5), ModP1000000007::new(6));
+    /// ```
+    pub fn multichoose(&mut self, n: u64, m: u64) -> StaticModP<M> {
+        if m == 0 {
+            StaticModP::new(1)
+        } else {
+            self.choose(n+m-1, m)
+        }
+    }
+
+    /// Shorthand of `choose`
+    pub fn c(&mut self, n: u64, m: u64) -> StaticModP<M> {
+        self.choose(n, m)
+    }
+
+    /// Shorthand of `permutaion`
+    pub fn p(&mut self, n: u64, m: u64) -> StaticModP<M> {
+        self.permutation(n, m)
+    }
+
+    /// Shorthand of `multichoose`
+    pub fn h(&mut self, n: u64, m: u64) -> StaticModP<M> {
+        self.multichoose(n, m)
+    }
+
+    pub fn fact(&mut self, n: u64) -> StaticModP<M> {
+        self.facts.get(n)
+    }
+
+    pub fn inv(&mut self, n: u64) -> StaticModP<M> {
+        self.invs.get(n)
+    }
+
+    fn extend_finvs(&mut self, max: usize) {
+        for i in self.finvs.len()..max+1 {
+            let prev = self.finvs[i-1];
+            self.finvs.push(prev * self.invs.get(i as u64))
+        }
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    struct Mod7;
+
+    impl Modulus for Mod7 {
+        const MODULUS: u64 = 7;
+    }
+
+    type P = StaticModP<Mod7>;
+
+    #[test]
+    fn test_new() {
+        assert_eq!(P::new(3), P::new(10));
+        assert_eq!(P::from(-4), P::new(3));
+        assert_eq!(P::from(10u8), P::new(3));
+        assert_eq!(P::from(-4i128), P::new(3));
+    }
+
+    #[test]
+    fn test_write() {
+        let mut out = Vec::new();
+        vec![P::new(10), P::new(6)].write_to(&mut out).unwrap();
+        assert_eq!(out, b"3 6");
+    }
+
+    #[test]
+    fn test_pow() {
+        let zero = P::new(0);
+        assert_eq!(zero.pow(0), P::new(1));
+        assert_eq!(zero.pow(1), P::new(0));
+        assert_eq!(zero.pow(u64::max_value()), P::new(0));
+
+        let n = P::new(3);
+        assert_eq!(n.pow(0), P::new(1));
+        assert_eq!(n.pow(1), P::new(3));
+        assert_eq!(n.pow(2), P::new(2));
+        assert_eq!(n.pow(3), P::new(6));
+        assert_eq!(n.pow(u64::max_value()), P::new(6));
+    }
+
+    #[test]
+    fn test_inv() {
+        assert_eq!(P::new(1).inv(), P::new(1));
+        assert_eq!(P::new(2).inv(), P::new(4));
+        assert_eq!(P::new(3).inv(), P::new(5));
+        assert_eq!(P::new(4).inv(), P::new(2));
+        assert_eq!(P::new(5).inv(), P::new(3));
+        assert_eq!(P::new(6).inv(), P::new(6));
+    }
+
+    #[test]
+    fn test_partial_eq() {
+        assert_eq!(P::new(3), 10);
+        assert_eq!(P::new(10), 3);
+        assert_eq!(3, P::new(10));
+        assert_eq!(10, P::new(3));
+    }
+
+    #[test]
+    fn test_add() {
+        assert_eq!(P::new(5) + P::new(5), P::new(3));
+        assert_eq!(P::new(5) + &P::new(5), P::new(3));
+        assert_eq!(&P::new(5) + P::new(5), P::new(3));
+        assert_eq!(&P::new(5) + &P::new(5), P::new(3));
+        assert_eq!(P::new(5) + 5, P::new(3));
+        assert_eq!(P::new(5) + &5, P::new(3));
+        assert_eq!(&P::new(5) + 5, P::new(3));
+        assert_eq!(&P::new(5) + &5, P::new(3));
+        assert_eq!(5 + P::new(5), P::new(3));
+        assert_eq!(5 + &P::new(5), P::new(3));
+        assert_eq!(&5 + P::new(5), P::new(3));
+        assert_eq!(&5 + &P::new(5), P::new(3));
+    }
+
+    #[test]
+    fn test_add_avoiding_overflow() {
+        assert_eq!(P::new(5) + u64::max_value(), P::new(6));
+        assert_eq!(u64::max_value() + P::new(5), P::new(6))
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let mut n1 = P::new(5);
+        n1 += P::new(5);
+        assert_eq!(n1, P::new(3));
+
+        let mut n1_for_ref = P::new(5);
+        n1_for_ref += &P::new(5);
+        assert_eq!(n1_for_ref, P::new(3));
+
+        let mut n2 = P::new(5);
+        n2 += 5;
+        assert_eq!(n2, P::new(3));
+
+        let mut n2_for_ref = P::new(5);
+        n2_for_ref += &5;
+        assert_eq!(n2_for_ref, P::new(3));
+    }
+
+    #[test]
+    fn test_add_assign_avoiding_overflow() {
+        let mut n = P::new(5);
+        n += u64::max_value();
+        assert_eq!(n, P::new(6));
+    }
+
+    #[test]
+    fn test_neg() {
+        assert_eq!(-P::new(3), P::new(4));
+        assert_eq!(-(&P::new(3)), P::new(4));
+        assert_eq!(-P::new(0), P::new(0));
+        assert_eq!(-(&P::new(0)), P::new(0));
+    }
+
+    #[test]
+    fn test_sub() {
+        assert_eq!(P::new(3) - P::new(4), P::new(6));
+        assert_eq!(P::new(3) - &P::new(4), P::new(6));
+        assert_eq!(&P::new(3) - P::new(4), P::new(6));
+        assert_eq!(&P::new(3) - &P::new(4), P::new(6));
+        assert_eq!(P::new(3) - 4, P::new(6));
+        assert_eq!(P::new(3) - &4, P::new(6));
+        assert_eq!(&P::new(3) - 4, P::new(6));
+        assert_eq!(&P::new(3) - &4, P::new(6));
+        assert_eq!(3 - P::new(4), P::new(6));
+        assert_eq!(3 - &P::new(4), P::new(6));
+        assert_eq!(&3 - P::new(4), P::new(6));
+        assert_eq!(&3 - &P::new(4), P::new(6));
+    }
+
+    #[test]
+    fn test_sub_avoiding_overflow() {
+        assert_eq!(P::new(5) - u64::max_value(), P::new(4));
+    }
+
+    #[test]
+    fn test_sub_assign() {
+        let mut n1 = P::new(3);
+        n1 -= P::new(4);
+        assert_eq!(n1, P::new(6));
+
+        let mut n1_for_ref = P::new(3);
+        n1_for_ref -= &P::new(4);
+        assert_eq!(n1_for_ref, P::new(6));
+
+        let mut n2 = P::new(3);
+        n2 -= 4;
+        assert_eq!(n2, P::new(6));
+
+        let mut n2_for_ref = P::new(3);
+        n2_for_ref -= &4;
+        assert_eq!(n2_for_ref, P::new(6));
+    }
+
+    #[test]
+    fn test_sub_assign_avoiding_overflow() {
+        let mut n = P::new(5);
+        n -= u64::max_value();
+        assert_eq!(n, P::new(4));
+    }
+
+    #[test]
+    fn test_mul() {
+        assert_eq!(P::new(5) * P::new(5), P::new(4));
+        assert_eq!(P::new(5) * &P::new(5), P::new(4));
+        assert_eq!(&P::new(5) * P::new(5), P::new(4));
+        assert_eq!(&P::new(5) * &P::new(5), P::new(4));
+        assert_eq!(P::new(5) * 5, P::new(4));
+        assert_eq!(P::new(5) * &5, P::new(4));
+        assert_eq!(&P::new(5) * 5, P::new(4));
+        assert_eq!(&P::new(5) * &5, P::new(4));
+        assert_eq!(5 * P::new(5), P::new(4));
+        assert_eq!(5 * &P::new(5), P::new(4));
+        assert_eq!(&5 * P::new(5), P::new(4));
+        assert_eq!(&5 * &P::new(5), P::new(4));
+    }
+
+    #[test]
+    fn test_mul_avoiding_overflow() {
+        assert_eq!(P::new(5) * u64::max_value(), P::new(5));
+        assert_eq!(u64::max_value() * P::new(5), P::new(5))
+    }
+
+    #[test]
+    fn test_mul_assign() {
+        let mut n1 = P::new(5);
+        n1 *= P::new(5);
+        assert_eq!(n1, P::new(4));
+
+        let mut n1_for_ref = P::new(5);
+        n1_for_ref *= &P::new(5);
+        assert_eq!(n1_for_ref, P::new(4));
+
+        let mut n2 = P::new(5);
+        n2 *= 5;
+        assert_eq!(n2, P::new(4));
+
+        let mut n2_for_ref = P::new(5);
+        n2_for_ref *= &5;
+        assert_eq!(n2_for_ref, P::new(4));
+    }
+
+    #[test]
+    fn test_mul_assign_avoiding_overflow() {
+        let mut n = P::new(5);
+        n *= u64::max_value();
+        assert_eq!(n, P::new(5));
+    }
+
+    #[test]
+    fn test_div() {
+        assert_eq!(P::new(3) / P::new(4), P::new(6));
+        assert_eq!(P::new(3) / &P::new(4), P::new(6));
+        assert_eq!(&P::new(3) / P::new(4), P::new(6));
+        assert_eq!(&P::new(3) / &P::new(4), P::new(6));
+        assert_eq!(P::new(3) / 4, P::new(6));
+        assert_eq!(P::new(3) / &4, P::new(6));
+        assert_eq!(&P::new(3) / 4, P::new(6));
+        assert_eq!(&P::new(3) / &4, P::new(6));
+        assert_eq!(3 / P::new(4), P::new(6));
+        assert_eq!(3 / &P::new(4), P::new(6));
+        assert_eq!(&3 / P::new(4), P::new(6));
+        assert_eq!(&3 / &P::new(4), P::new(6));
+    }
+
+    #[test]
+    fn test_div_assign() {
+        let mut n1 = P::new(3);
+        n1 /= P::new(4);
+        assert_eq!(n1, P::new(6));
+
+        let mut n1_for_ref = P::new(3);
+        n1_for_ref /= &P::new(4);
+        assert_eq!(n1_for_ref, P::new(6));
+
+        let mut n2 = P::new(3);
+        n2 /= 4;
+        assert_eq!(n2, P::new(6));
+
+        let mut n2_for_ref = P::new(3);
+        n2_for_ref /= &4;
+        assert_eq!(n2_for_ref, P::new(6));
+    }
+
+    #[test]
+    fn test_sum() {
+        let seq: Vec<P> = (1..=6).map(|n| P::new(n)).collect();
+        assert_eq!(seq.iter().sum::<P>(), P::new(0));
+        assert_eq!(seq.into_iter().sum::<P>(), P::new(0));
+    }
+
+    #[test]
+    fn test_product() {
+        let seq: Vec<P> = (1..=6).map(|n| P::new(n)).collect();
+        assert_eq!(seq.iter().product::<P>(), P::new(6));
+        assert_eq!(seq.into_iter().product::<P>(), P::new(6));
+    }
+
+    #[test]
+    fn test_read() {
+        assert_eq!(P::read_words(&["10"]), Ok(P::new(3)));
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", P::new(10)), "3 mod 7");
+        assert_eq!(format!("{:?}", ModP998244353::new(5)), "5 mod 998244353");
+    }
+
+    #[test]
+    fn test_caches() {
+        let mut facts = P::fact_cache();
+        assert_eq!(facts.get(3), P::new(6));
+        assert_eq!(facts.get(7), P::new(0));
+
+        let mut invs = P::inv_cache();
+        for n in 1..7 {
+            assert_eq!(invs.get(n), P::new(n).inv());
+        }
+
+        let mut pows = P::pow_cache(3);
+        for n in 0..10 {
+            assert_eq!(pows.get(n), P::new(3).pow(n));
+        }
+
+        let mut cc = P::combinatorics_cache();
+        assert_eq!(cc.choose(5, 3), P::new(3));
+        assert_eq!(cc.permutation(5, 3), P::new(4));
+        assert_eq!(cc.multichoose(2, 5), P::new(6));
+        assert_eq!(cc.choose(3, 5), P::new(0));
+    }
+
+    #[test]
+    fn test_large_moduli() {
+        type P1 = ModP1000000007;
+        type P2 = ModP998244353;
+
+        assert_eq!(P1::new(1_000_000_006) + 2, P1::new(1));
+        assert_eq!(P1::new(0) - 1, P1::new(1_000_000_006));
+        assert_eq!(P1::new(1_000_000_006) * P1::new(1_000_000_006), P1::new(1));
+        assert_eq!(P2::new(998_244_352) * P2::new(998_244_352), P2::new(1));
+        for n in 1..100 {
+            assert_eq!(P1::new(n) * P1::new(n).inv(), P1::new(1));
+            assert_eq!(P2::new(n) * P2::new(n).inv(), P2::new(1));
+        }
+
+        let mut cc1 = P1::combinatorics_cache();
+        let mut cc2 = P2::combinatorics_cache();
+        for n in 0..50u64 {
+            for m in 0..=n {
+                assert_eq!(cc1.choose(n + 1, m + 1), cc1.choose(n, m) + cc1.choose(n, m + 1));
+                assert_eq!(cc2.choose(n + 1, m + 1), cc2.choose(n, m) + cc2.choose(n, m + 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_both_moduli_simultaneously() {
+        let x = ModP1000000007::new(1_000_000_000);
+        let y = ModP998244353::new(1_000_000_000);
+        assert_eq!(x.base(), 1_000_000_000);
+        assert_eq!(y.base(), 1_755_647);
+        assert_eq!((x + x).base(), 999_999_993);
+        assert_eq!((y + y).base(), 3_511_294);
+        assert_eq!(ModP1000000007::read_words(&["1000000008"]), Ok(ModP1000000007::new(1)));
+        assert_eq!(ModP998244353::read_words(&["1000000008"]), Ok(ModP998244353::new(1_755_655)));
+    }
+}
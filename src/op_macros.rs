@@ -4,6 +4,7 @@
 /// based on "op T" where T is expected to be `Copy`able.
 ///
 /// Based on [https://doc.rust-lang.org/src/core/internal_macros.rs.html].
+#[macro_export]
 macro_rules! forward_ref_unop {
     (impl $op:ident, $method:ident for $t:ty) => {
         impl std::ops::$op for &$t {
@@ -20,6 +21,7 @@ macro_rules! forward_ref_unop {
 /// based on "T op U" where T and U are expected to be `Copy`able.
 ///
 /// Based on [https://doc.rust-lang.org/src/core/internal_macros.rs.html].
+#[macro_export]
 macro_rules! forward_ref_binop {
     (impl $op:ident, $method:ident for $t:ty, $u:ty) => {
         impl<'a> std::ops::$op<$u> for &'a $t {
@@ -51,6 +53,7 @@ macro_rules! forward_ref_binop {
 /// Implements "T op= &U", based on "T op= U" where U is expected to be `Copy`able.
 ///
 /// Based on [https://doc.rust-lang.org/src/core/internal_macros.rs.html].
+#[macro_export]
 macro_rules! forward_ref_op_assign {
     (impl $op:ident, $method:ident for $t:ty, $u:ty) => {
         impl std::ops::$op<&$u> for $t {
@@ -61,4 +64,269 @@ macro_rules! forward_ref_op_assign {
     }
 }
 
+/// Generic version of [`forward_ref_unop`](macro.forward_ref_unop.html).
+///
+/// The generic parameters are written in brackets instead of angle brackets,
+/// followed by an optional where clause, e.g.
+/// `forward_ref_unop_generic!(impl[T: Copy + Neg] Neg, neg for Point<T>)`.
+#[macro_export]
+macro_rules! forward_ref_unop_generic {
+    (impl[$($gen:tt)*] $op:ident, $method:ident for $t:ty $(where $($w:tt)+)?) => {
+        impl<'a, $($gen)*> std::ops::$op for &'a $t $(where $($w)+)? {
+            type Output = <$t as std::ops::$op>::Output;
+
+            fn $method(self) -> <$t as std::ops::$op>::Output {
+                std::ops::$op::$method(*self)
+            }
+        }
+    }
+}
+
+/// Generic version of [`forward_ref_binop`](macro.forward_ref_binop.html).
+///
+/// The generic parameters are written in brackets instead of angle brackets,
+/// followed by an optional where clause, e.g.
+/// `forward_ref_binop_generic!(impl[T: Copy + Add<Output=T>] Add, add for Point<T>, Point<T>)`.
+#[macro_export]
+macro_rules! forward_ref_binop_generic {
+    (impl[$($gen:tt)*] $op:ident, $method:ident for $t:ty, $u:ty $(where $($w:tt)+)?) => {
+        impl<'a, $($gen)*> std::ops::$op<$u> for &'a $t $(where $($w)+)? {
+            type Output = <$t as std::ops::$op<$u>>::Output;
+
+            fn $method(self, other: $u) -> <$t as std::ops::$op<$u>>::Output {
+                std::ops::$op::$method(*self, other)
+            }
+        }
+
+        impl<'a, $($gen)*> std::ops::$op<&'a $u> for $t $(where $($w)+)? {
+            type Output = <$t as std::ops::$op<$u>>::Output;
+
+            fn $method(self, other: &'a $u) -> <$t as std::ops::$op<$u>>::Output {
+                std::ops::$op::$method(self, *other)
+            }
+        }
+
+        impl<'a, 'b, $($gen)*> std::ops::$op<&'a $u> for &'b $t $(where $($w)+)? {
+            type Output = <$t as std::ops::$op<$u>>::Output;
+
+            fn $method(self, other: &'a $u) -> <$t as std::ops::$op<$u>>::Output {
+                std::ops::$op::$method(*self, *other)
+            }
+        }
+    }
+}
+
+/// Generic version of [`forward_ref_op_assign`](macro.forward_ref_op_assign.html).
+///
+/// The generic parameters are written in brackets instead of angle brackets,
+/// followed by an optional where clause, e.g.
+/// `forward_ref_op_assign_generic!(impl[T: Copy + AddAssign] AddAssign, add_assign for Point<T>, Point<T>)`.
+#[macro_export]
+macro_rules! forward_ref_op_assign_generic {
+    (impl[$($gen:tt)*] $op:ident, $method:ident for $t:ty, $u:ty $(where $($w:tt)+)?) => {
+        impl<'a, $($gen)*> std::ops::$op<&'a $u> for $t $(where $($w)+)? {
+            fn $method(&mut self, other: &'a $u) {
+                std::ops::$op::$method(self, *other);
+            }
+        }
+    }
+}
+
+/// Implements arithmetic operators for a newtype of a signed number type.
+///
+/// `impl_arith_newtype!(T(U))` implements the following traits
+/// for a single-field tuple struct `T` wrapping `U`:
+///
+/// - `Add`, `Sub` for `T op T`, `T op U` and `U op T`, including references
+/// - `AddAssign`, `SubAssign` for `T op= T` and `T op= U`, including references
+/// - `Neg` for `T` and `&T`
+/// - `Sum` for `T` and `&T`
+/// - `PartialOrd` and `Ord` comparing the inner values
+/// - `Display` printing the inner value
+///
+/// `T` must derive `Clone`, `Copy`, `PartialEq` and `Eq`, but not `PartialOrd` and `Ord`.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+/// struct Score(i64);
+///
+/// impl_arith_newtype!(Score(i64));
+///
+/// assert_eq!(Score(5) + Score(3), Score(8));
+/// assert_eq!(Score(5) + &Score(3), Score(8));
+/// assert_eq!(&Score(5) + Score(3), Score(8));
+/// assert_eq!(&Score(5) + &Score(3), Score(8));
+/// assert_eq!(Score(5) + 3, Score(8));
+/// assert_eq!(Score(5) + &3, Score(8));
+/// assert_eq!(&Score(5) + 3, Score(8));
+/// assert_eq!(&Score(5) + &3, Score(8));
+/// assert_eq!(5 + Score(3), Score(8));
+/// assert_eq!(5 + &Score(3), Score(8));
+/// assert_eq!(&5 + Score(3), Score(8));
+/// assert_eq!(&5 + &Score(3), Score(8));
+///
+/// assert_eq!(Score(5) - Score(8), Score(-3));
+/// assert_eq!(Score(5) - &Score(8), Score(-3));
+/// assert_eq!(&Score(5) - Score(8), Score(-3));
+/// assert_eq!(&Score(5) - &Score(8), Score(-3));
+/// assert_eq!(Score(5) - 8, Score(-3));
+/// assert_eq!(Score(5) - &8, Score(-3));
+/// assert_eq!(&Score(5) - 8, Score(-3));
+/// assert_eq!(&Score(5) - &8, Score(-3));
+/// assert_eq!(5 - Score(8), Score(-3));
+/// assert_eq!(5 - &Score(8), Score(-3));
+/// assert_eq!(&5 - Score(8), Score(-3));
+/// assert_eq!(&5 - &Score(8), Score(-3));
+///
+/// assert_eq!(-Score(3), Score(-3));
+/// assert_eq!(-&Score(3), Score(-3));
+///
+/// let mut score = Score(10);
+/// score += Score(1);
+/// score += &Score(2);
+/// score += 3;
+/// score += &4;
+/// assert_eq!(score, Score(20));
+/// score -= Score(1);
+/// score -= &Score(2);
+/// score -= 3;
+/// score -= &4;
+/// assert_eq!(score, Score(10));
+///
+/// let scores = vec![Score(3), Score(-1), Score(4)];
+/// assert_eq!(scores.iter().sum::<Score>(), Score(6));
+/// assert_eq!(scores.iter().max(), Some(&Score(4)));
+/// assert!(Score(-1) < Score(3));
+/// assert_eq!(scores.into_iter().sum::<Score>().to_string(), "6");
+/// ```
+#[macro_export]
+macro_rules! impl_arith_newtype {
+    ($t: ident($inner: ty)) => {
+        impl std::ops::Add for $t {
+            type Output = $t;
+
+            fn add(self, rhs: $t) -> $t {
+                $t(self.0 + rhs.0)
+            }
+        }
+
+        impl std::ops::Add<$inner> for $t {
+            type Output = $t;
+
+            fn add(self, rhs: $inner) -> $t {
+                $t(self.0 + rhs)
+            }
+        }
+
+        impl std::ops::Add<$t> for $inner {
+            type Output = $t;
+
+            fn add(self, rhs: $t) -> $t {
+                $t(self + rhs.0)
+            }
+        }
+
+        impl std::ops::Sub for $t {
+            type Output = $t;
+
+            fn sub(self, rhs: $t) -> $t {
+                $t(self.0 - rhs.0)
+            }
+        }
+
+        impl std::ops::Sub<$inner> for $t {
+            type Output = $t;
+
+            fn sub(self, rhs: $inner) -> $t {
+                $t(self.0 - rhs)
+            }
+        }
+
+        impl std::ops::Sub<$t> for $inner {
+            type Output = $t;
+
+            fn sub(self, rhs: $t) -> $t {
+                $t(self - rhs.0)
+            }
+        }
+
+        impl std::ops::Neg for $t {
+            type Output = $t;
+
+            fn neg(self) -> $t {
+                $t(-self.0)
+            }
+        }
+
+        impl std::ops::AddAssign for $t {
+            fn add_assign(&mut self, rhs: $t) {
+                self.0 += rhs.0;
+            }
+        }
+
+        impl std::ops::AddAssign<$inner> for $t {
+            fn add_assign(&mut self, rhs: $inner) {
+                self.0 += rhs;
+            }
+        }
+
+        impl std::ops::SubAssign for $t {
+            fn sub_assign(&mut self, rhs: $t) {
+                self.0 -= rhs.0;
+            }
+        }
+
+        impl std::ops::SubAssign<$inner> for $t {
+            fn sub_assign(&mut self, rhs: $inner) {
+                self.0 -= rhs;
+            }
+        }
+
+        forward_ref_binop!(impl Add, add for $t, $t);
+        forward_ref_binop!(impl Add, add for $t, $inner);
+        forward_ref_binop!(impl Add, add for $inner, $t);
+        forward_ref_binop!(impl Sub, sub for $t, $t);
+        forward_ref_binop!(impl Sub, sub for $t, $inner);
+        forward_ref_binop!(impl Sub, sub for $inner, $t);
+        forward_ref_unop!(impl Neg, neg for $t);
+        forward_ref_op_assign!(impl AddAssign, add_assign for $t, $t);
+        forward_ref_op_assign!(impl AddAssign, add_assign for $t, $inner);
+        forward_ref_op_assign!(impl SubAssign, sub_assign for $t, $t);
+        forward_ref_op_assign!(impl SubAssign, sub_assign for $t, $inner);
+
+        impl std::iter::Sum for $t {
+            fn sum<I: Iterator<Item=$t>>(iter: I) -> $t {
+                iter.fold($t(Default::default()), |acc, x| acc + x)
+            }
+        }
+
+        impl<'a> std::iter::Sum<&'a $t> for $t {
+            fn sum<I: Iterator<Item=&'a $t>>(iter: I) -> $t {
+                iter.fold($t(Default::default()), |acc, x| acc + x)
+            }
+        }
+
+        impl PartialOrd for $t {
+            fn partial_cmp(&self, other: &$t) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $t {
+            fn cmp(&self, other: &$t) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        impl std::fmt::Display for $t {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
 // END SNIPPET
@@ -61,6 +61,106 @@ impl<T> OptionExt<T> for Option<T> {
     */
 }
 
+/// Enriches `Option` for DP treating `None` as an unreachable state.
+///
+/// # Example
+///
+/// The minimum number of coins to pay each amount, or `None` if impossible.
+///
+/// ```
+/// # use atcoder_snippets::option::*;
+/// let coins = [3, 5];
+/// let mut dp: Vec<Option<i64>> = vec![None; 10];
+/// dp[0] = Some(0);
+/// for amount in 1..10 {
+///     for &coin in &coins {
+///         if coin <= amount {
+///             let count = dp[amount - coin].add_opt(1);
+///             dp[amount].chmin_opt(count);
+///         }
+///     }
+/// }
+/// assert_eq!(dp, vec![
+///     Some(0), None, None, Some(1), None, Some(1), Some(2), None, Some(2), Some(3)
+/// ]);
+/// ```
+pub trait OptionMinMaxExt<T> {
+    /// Gets the smaller value, where `None` is the identity.
+    ///
+    /// If either is `None`, the other wins.
+    fn min_opt(self, other: Option<T>) -> Option<T>;
+
+    /// Gets the larger value, where `None` is the identity.
+    ///
+    /// If either is `None`, the other wins.
+    fn max_opt(self, other: Option<T>) -> Option<T>;
+
+    /// Adds `rhs` to the value if it is `Some`, otherwise `None`.
+    fn add_opt(self, rhs: T) -> Option<T> where T: std::ops::Add<Output=T>;
+
+    /// Updates `self` to `self.min_opt(value)`,
+    /// and returns whether `self` is changed.
+    ///
+    /// `value` can be either `T` or `Option<T>`.
+    fn chmin_opt(&mut self, value: impl Into<Option<T>>) -> bool;
+
+    /// Updates `self` to `self.max_opt(value)`,
+    /// and returns whether `self` is changed.
+    ///
+    /// `value` can be either `T` or `Option<T>`.
+    fn chmax_opt(&mut self, value: impl Into<Option<T>>) -> bool;
+}
+
+impl<T: Ord> OptionMinMaxExt<T> for Option<T> {
+    fn min_opt(self, other: Option<T>) -> Option<T> {
+        match (self, other) {
+            (Some(x), Some(y)) => Some(std::cmp::min(x, y)),
+            (x, None) => x,
+            (None, y) => y
+        }
+    }
+
+    fn max_opt(self, other: Option<T>) -> Option<T> {
+        match (self, other) {
+            (Some(x), Some(y)) => Some(std::cmp::max(x, y)),
+            (x, None) => x,
+            (None, y) => y
+        }
+    }
+
+    fn add_opt(self, rhs: T) -> Option<T> where T: std::ops::Add<Output=T> {
+        self.map(|x| x + rhs)
+    }
+
+    fn chmin_opt(&mut self, value: impl Into<Option<T>>) -> bool {
+        let value = match value.into() {
+            Some(value) => value,
+            None => return false
+        };
+        if let Some(current) = self.as_ref() {
+            if value >= *current {
+                return false;
+            }
+        }
+        *self = Some(value);
+        true
+    }
+
+    fn chmax_opt(&mut self, value: impl Into<Option<T>>) -> bool {
+        let value = match value.into() {
+            Some(value) => value,
+            None => return false
+        };
+        if let Some(current) = self.as_ref() {
+            if value <= *current {
+                return false;
+            }
+        }
+        *self = Some(value);
+        true
+    }
+}
+
 /// Enrich all types by adding `guard` method
 pub trait Guard: Sized {
     /// `Some(self)` if `pred(&self)` holds, otherwise `None`.
@@ -74,3 +174,69 @@ impl<T> Guard for T {
 }
 
 // END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmp::Total;
+
+    #[test]
+    fn test_min_opt_max_opt() {
+        assert_eq!(None::<i64>.min_opt(None), None);
+        assert_eq!(None.min_opt(Some(3)), Some(3));
+        assert_eq!(Some(3).min_opt(None), Some(3));
+        assert_eq!(Some(3).min_opt(Some(-1)), Some(-1));
+        assert_eq!(Some(-1).min_opt(Some(3)), Some(-1));
+
+        assert_eq!(None::<i64>.max_opt(None), None);
+        assert_eq!(None.max_opt(Some(3)), Some(3));
+        assert_eq!(Some(3).max_opt(None), Some(3));
+        assert_eq!(Some(3).max_opt(Some(-1)), Some(3));
+        assert_eq!(Some(-1).max_opt(Some(3)), Some(3));
+
+        assert_eq!(Some(Total(0.5)).min_opt(Some(Total(-0.5))), Some(Total(-0.5)));
+        assert_eq!(None.max_opt(Some(Total(0.5))), Some(Total(0.5)));
+    }
+
+    #[test]
+    fn test_add_opt() {
+        assert_eq!(Some(3i64).add_opt(4), Some(7));
+        assert_eq!(None::<i64>.add_opt(4), None);
+    }
+
+    #[test]
+    fn test_chmin_opt() {
+        let mut x = None;
+        assert!(!x.chmin_opt(None));
+        assert_eq!(x, None);
+        assert!(x.chmin_opt(5));
+        assert_eq!(x, Some(5));
+        assert!(!x.chmin_opt(None));
+        assert_eq!(x, Some(5));
+        assert!(!x.chmin_opt(5));
+        assert!(!x.chmin_opt(Some(7)));
+        assert_eq!(x, Some(5));
+        assert!(x.chmin_opt(Some(2)));
+        assert_eq!(x, Some(2));
+
+        let mut y = None;
+        assert!(y.chmin_opt(Total(1.5)));
+        assert!(y.chmin_opt(Total(-1.5)));
+        assert_eq!(y, Some(Total(-1.5)));
+    }
+
+    #[test]
+    fn test_chmax_opt() {
+        let mut x = None;
+        assert!(!x.chmax_opt(None));
+        assert_eq!(x, None);
+        assert!(x.chmax_opt(Some(5)));
+        assert_eq!(x, Some(5));
+        assert!(!x.chmax_opt(None));
+        assert!(!x.chmax_opt(5));
+        assert!(!x.chmax_opt(2));
+        assert_eq!(x, Some(5));
+        assert!(x.chmax_opt(7));
+        assert_eq!(x, Some(7));
+    }
+}
@@ -47,6 +47,7 @@
 //! - 標準入力の一様な行を一定行数、あるいはすべて読むには、[`read_lines`](fn.read_lines.html)関数を用いる。
 //! - 標準入力の複数行に渡る入力を繰り返し読むには、[`read_chunks`](fn.read_chunks.html)関数を用いる。
 //!   Typically useful for reading queries in Codeforces.
+//! - 標準入力から迷路などの文字のグリッドを読むには、[`read_grid`](fn.read_grid.html)関数を用いる。
 
 // BEGIN SNIPPET read
 
@@ -190,6 +191,60 @@ impl Readable for Bytes {
     }
 }
 
+/// Reads a string of digits as `Vec<u8>` of the digits, keeping leading zeros.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "0123"
+/// read!(ds = Digits);
+/// assert_eq!(ds, vec![0, 1, 2, 3]);
+/// ```
+pub struct Digits();
+
+impl Readable for Digits {
+    type Output = Vec<u8>;
+    const WORD_COUNT: usize = 1;
+
+    fn read_words(words: &[&str]) -> Result<Vec<u8>, String> {
+        words[0].bytes().map(|b| {
+            if b.is_ascii_digit() {
+                Ok(b - b'0')
+            } else {
+                Err(format!("cannot parse `{}` as digits: found `{}`", words[0], b as char))
+            }
+        }).collect()
+    }
+}
+
+/// Reads a string of `0` and `1` as `Vec<bool>`.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "0110"
+/// read!(bs = Bits);
+/// assert_eq!(bs, vec![false, true, true, false]);
+/// ```
+pub struct Bits();
+
+impl Readable for Bits {
+    type Output = Vec<bool>;
+    const WORD_COUNT: usize = 1;
+
+    fn read_words(words: &[&str]) -> Result<Vec<bool>, String> {
+        words[0].bytes().map(|b| match b {
+            b'0' => Ok(false),
+            b'1' => Ok(true),
+            _ => Err(format!("cannot parse `{}` as bits: found `{}`", words[0], b as char))
+        }).collect()
+    }
+}
+
 // Primitive integers
 // Implemented by copy and paste instead of macro for compilation speedup
 
@@ -480,6 +535,291 @@ impl Readable for usize_ {
     }
 }
 
+/// 1-origin integer read from stdin, holding the value converted into 0-origin.
+///
+/// Unlike [`usize_`](struct.usize_.html), reading 0 fails
+/// since it means the input is not actually 1-origin.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "1 3"
+/// read!(u = Usize1, v = Usize1);
+/// assert_eq!(u.get(), 0);
+/// assert_eq!(*v, 2);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Usize1(usize);
+
+impl Usize1 {
+    /// Gets the 0-origin value.
+    pub fn get(self) -> usize {
+        self.0
+    }
+}
+
+impl std::ops::Deref for Usize1 {
+    type Target = usize;
+
+    fn deref(&self) -> &usize {
+        &self.0
+    }
+}
+
+impl Readable for Usize1 {
+    type Output = Usize1;
+    const WORD_COUNT: usize = 1;
+
+    fn read_words(words: &[&str]) -> Result<Usize1, String> {
+        let n = <usize>::read_words(words)?;
+        if n == 0 {
+            Err(format!("cannot read `{}` as 1-origin integer", words[0]))
+        } else {
+            Ok(Usize1(n - 1))
+        }
+    }
+}
+
+/// Makes an existing struct readable from words, by reading the fields left to right.
+///
+/// Each field is given with a `Readable` type, whose `Output` is the type of the field.
+/// If the words cannot be read, the error message tells which field failed.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// struct Edge {
+///     from: usize,
+///     to: usize,
+///     weight: i64
+/// }
+///
+/// readable_struct!(Edge { from: usize_, to: usize_, weight: i64 });
+///
+/// // Stdin: "3 2\n1 2 10\n2 3 -5\n"
+/// read!(!, m = usize);
+/// let edges = read_rows::<Edge>(m);
+/// assert_eq!((edges[1].from, edges[1].to, edges[1].weight), (1, 2, -5));
+/// ```
+#[macro_export]
+macro_rules! readable_struct {
+    ( $t:ident { $( $field:ident : $field_t:ty ),* $(,)* } ) => {
+        impl Readable for $t {
+            type Output = $t;
+            const WORD_COUNT: usize = 0 $( + <$field_t as Readable>::WORD_COUNT )*;
+
+            #[allow(unused_assignments)]
+            fn read_words(words: &[&str]) -> Result<$t, String> {
+                assert_eq!(words.len(), Self::WORD_COUNT);
+
+                let mut start = 0;
+                $(
+                    let count = <$field_t as Readable>::WORD_COUNT;
+                    let $field = <$field_t as Readable>::read_words(&words[start .. start+count])
+                        .map_err(|msg| format!("field `{}` of {}: {}", stringify!($field), stringify!($t), msg))?;
+                    start += count;
+                )*
+                Ok($t { $( $field ),* })
+            }
+        }
+    };
+}
+
+/// Reads `-1` as `None`, and other words as `Some` of `T`.
+///
+/// Useful for inputs such as parents of vertices where `-1` means no parent.
+/// `T` is expected to be read from one word.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "-1 1 1 2"
+/// read!(parents = Vec<NegOneAsNone<usize_>>);
+/// assert_eq!(parents, vec![None, Some(0), Some(0), Some(1)]);
+/// ```
+pub struct NegOneAsNone<T>(std::marker::PhantomData<T>);
+
+impl<T: Readable> Readable for NegOneAsNone<T> {
+    type Output = Option<T::Output>;
+    const WORD_COUNT: usize = T::WORD_COUNT;
+
+    fn read_words(words: &[&str]) -> Result<Self::Output, String> {
+        if words == ["-1"] {
+            Ok(None)
+        } else {
+            T::read_words(words).map(Some)
+        }
+    }
+}
+
+/// Makes an enum readable from a word, by mapping words into variants.
+///
+/// Reading a word not in the mapping fails with the list of the valid words.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// #[derive(Debug, PartialEq)]
+/// enum Dir { Up, Down, Left, Right }
+///
+/// readable_enum!(Dir { "U" => Up, "D" => Down, "L" => Left, "R" => Right });
+///
+/// // Stdin: "U R"
+/// read!(dirs = Vec<Dir>);
+/// assert_eq!(dirs, vec![Dir::Up, Dir::Right]);
+/// ```
+#[macro_export]
+macro_rules! readable_enum {
+    ( $t:ident { $( $word:literal => $variant:ident ),* $(,)* } ) => {
+        impl Readable for $t {
+            type Output = $t;
+            const WORD_COUNT: usize = 1;
+
+            fn read_words(words: &[&str]) -> Result<$t, String> {
+                match words[0] {
+                    $( $word => Ok($t::$variant), )*
+                    word => Err(format!(
+                        "cannot parse `{}` as {}, expected one of {}",
+                        word, stringify!($t), [$( $word ),*].join(", ")
+                    ))
+                }
+            }
+        }
+    };
+}
+
+/// Number of digits after the decimal point of [`Fixed`](struct.Fixed.html).
+///
+/// `Scale1`, ..., `Scale9` are provided.
+pub trait DecimalScale {
+    const SCALE: u32;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Scale1;
+impl DecimalScale for Scale1 { const SCALE: u32 = 1; }
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Scale2;
+impl DecimalScale for Scale2 { const SCALE: u32 = 2; }
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Scale3;
+impl DecimalScale for Scale3 { const SCALE: u32 = 3; }
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Scale4;
+impl DecimalScale for Scale4 { const SCALE: u32 = 4; }
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Scale5;
+impl DecimalScale for Scale5 { const SCALE: u32 = 5; }
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Scale6;
+impl DecimalScale for Scale6 { const SCALE: u32 = 6; }
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Scale7;
+impl DecimalScale for Scale7 { const SCALE: u32 = 7; }
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Scale8;
+impl DecimalScale for Scale8 { const SCALE: u32 = 8; }
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Scale9;
+impl DecimalScale for Scale9 { const SCALE: u32 = 9; }
+
+/// Decimal read exactly as an integer scaled by 10^`S::SCALE`.
+///
+/// The word must be an integer part optionally preceded by `-`,
+/// followed by an optional fractional part of at most `S::SCALE` digits.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "3.5 -0.05"
+/// read!(x = Fixed<Scale2>, y = Fixed<Scale2>);
+/// assert_eq!(x.raw(), 350);
+/// assert_eq!(y.raw(), -5);
+/// assert_eq!(x.to_string(), "3.50");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Fixed<S: DecimalScale> {
+    raw: i64,
+    scale: std::marker::PhantomData<S>
+}
+
+impl<S: DecimalScale> Fixed<S> {
+    /// Creates a decimal of `raw` / 10^`S::SCALE`.
+    pub fn from_raw(raw: i64) -> Fixed<S> {
+        Fixed { raw, scale: std::marker::PhantomData }
+    }
+
+    /// Gets the value multiplied by 10^`S::SCALE`.
+    pub fn raw(self) -> i64 {
+        self.raw
+    }
+}
+
+impl<S: DecimalScale> std::fmt::Display for Fixed<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let unit = 10u64.pow(S::SCALE);
+        let abs = self.raw.wrapping_abs() as u64;
+        let sign = if self.raw < 0 { "-" } else { "" };
+        if S::SCALE == 0 {
+            write!(f, "{}{}", sign, abs)
+        } else {
+            write!(f, "{}{}.{:0width$}", sign, abs / unit, abs % unit, width = S::SCALE as usize)
+        }
+    }
+}
+
+impl<S: DecimalScale> Readable for Fixed<S> {
+    type Output = Fixed<S>;
+    const WORD_COUNT: usize = 1;
+
+    fn read_words(words: &[&str]) -> Result<Fixed<S>, String> {
+        let word = words[0];
+        let err = || format!("cannot parse `{}` as decimal of scale {}", word, S::SCALE);
+        let negative = word.starts_with('-');
+        let mut parts = word[negative as usize..].splitn(2, '.');
+        let int_part = parts.next().unwrap();
+        let frac_part = parts.next();
+        let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+        let frac_ok = match frac_part {
+            Some(frac) => is_digits(frac) && frac.len() <= S::SCALE as usize,
+            None => true
+        };
+        if !is_digits(int_part) || !frac_ok {
+            return Err(err());
+        }
+        let frac_part = frac_part.unwrap_or("");
+
+        let mut raw: i64 = 0;
+        for b in int_part.bytes().chain(frac_part.bytes()) {
+            raw = raw.checked_mul(10)
+                .and_then(|raw| raw.checked_add((b - b'0') as i64))
+                .ok_or_else(err)?;
+        }
+        let shift = 10i64.pow(S::SCALE - frac_part.len() as u32);
+        raw.checked_mul(shift)
+            .map(|raw| Fixed::from_raw(if negative { -raw } else { raw }))
+            .ok_or_else(err)
+    }
+}
+
 // Tuples
 // Copy and paste instead of using macro for compilation speedup
 
@@ -619,6 +959,88 @@ impl<T1: Readable, T2: Readable, T3: Readable, T4: Readable, T5: Readable, T6: R
     }
 }
 
+impl<T1: Readable, T2: Readable, T3: Readable, T4: Readable, T5: Readable, T6: Readable, T7: Readable> Readable for (T1, T2, T3, T4, T5, T6, T7) {
+    type Output = (T1::Output, T2::Output, T3::Output, T4::Output, T5::Output, T6::Output, T7::Output);
+    const WORD_COUNT: usize = T1::WORD_COUNT + T2::WORD_COUNT + T3::WORD_COUNT + T4::WORD_COUNT + T5::WORD_COUNT + T6::WORD_COUNT + T7::WORD_COUNT;
+
+    fn read_words(words: &[&str]) -> Result<Self::Output, String> {
+        assert_eq!(words.len(), Self::WORD_COUNT);
+
+        let mut start = 0;
+
+        let count1 = T1::WORD_COUNT;
+        let val1 = T1::read_words(&words[start .. start+count1])?;
+        start += count1;
+
+        let count2 = T2::WORD_COUNT;
+        let val2 = T2::read_words(&words[start .. start+count2])?;
+        start += count2;
+
+        let count3 = T3::WORD_COUNT;
+        let val3 = T3::read_words(&words[start .. start+count3])?;
+        start += count3;
+
+        let count4 = T4::WORD_COUNT;
+        let val4 = T4::read_words(&words[start .. start+count4])?;
+        start += count4;
+
+        let count5 = T5::WORD_COUNT;
+        let val5 = T5::read_words(&words[start .. start+count5])?;
+        start += count5;
+
+        let count6 = T6::WORD_COUNT;
+        let val6 = T6::read_words(&words[start .. start+count6])?;
+        start += count6;
+
+        let val7 = T7::read_words(&words[start..])?;
+
+        Ok((val1, val2, val3, val4, val5, val6, val7))
+    }
+}
+
+impl<T1: Readable, T2: Readable, T3: Readable, T4: Readable, T5: Readable, T6: Readable, T7: Readable, T8: Readable> Readable for (T1, T2, T3, T4, T5, T6, T7, T8) {
+    type Output = (T1::Output, T2::Output, T3::Output, T4::Output, T5::Output, T6::Output, T7::Output, T8::Output);
+    const WORD_COUNT: usize = T1::WORD_COUNT + T2::WORD_COUNT + T3::WORD_COUNT + T4::WORD_COUNT + T5::WORD_COUNT + T6::WORD_COUNT + T7::WORD_COUNT + T8::WORD_COUNT;
+
+    fn read_words(words: &[&str]) -> Result<Self::Output, String> {
+        assert_eq!(words.len(), Self::WORD_COUNT);
+
+        let mut start = 0;
+
+        let count1 = T1::WORD_COUNT;
+        let val1 = T1::read_words(&words[start .. start+count1])?;
+        start += count1;
+
+        let count2 = T2::WORD_COUNT;
+        let val2 = T2::read_words(&words[start .. start+count2])?;
+        start += count2;
+
+        let count3 = T3::WORD_COUNT;
+        let val3 = T3::read_words(&words[start .. start+count3])?;
+        start += count3;
+
+        let count4 = T4::WORD_COUNT;
+        let val4 = T4::read_words(&words[start .. start+count4])?;
+        start += count4;
+
+        let count5 = T5::WORD_COUNT;
+        let val5 = T5::read_words(&words[start .. start+count5])?;
+        start += count5;
+
+        let count6 = T6::WORD_COUNT;
+        let val6 = T6::read_words(&words[start .. start+count6])?;
+        start += count6;
+
+        let count7 = T7::WORD_COUNT;
+        let val7 = T7::read_words(&words[start .. start+count7])?;
+        start += count7;
+
+        let val8 = T8::read_words(&words[start..])?;
+
+        Ok((val1, val2, val3, val4, val5, val6, val7, val8))
+    }
+}
+
 impl<T: Readable> Readable for [T; 2] {
     type Output = [T::Output; 2];
     const WORD_COUNT: usize = T::WORD_COUNT * 2;
@@ -711,8 +1133,8 @@ impl<T: Readable> ReadableFromLine for T {
     fn read_line(line: &str) -> Result<T::Output, String> {
         let words = split_into_words(line);
         if words.len() != T::WORD_COUNT {
-            return Err(format!("line `{}` has {} words, expected {}",
-                               line, words.len(), T::WORD_COUNT));
+            return Err(format!("line `{}` has {} words, expected {} for {}",
+                               line, words.len(), T::WORD_COUNT, std::any::type_name::<T>()));
         }
 
         T::read_words(&words)
@@ -756,8 +1178,8 @@ pub fn split_into_words_for_collection<T: Readable>(
     }
     if (words.len() - prefix_words_count) % T::WORD_COUNT != 0 {
         return Err(
-            format!("line `{}` has {} words, expected {} + {}",
-                    line, words.len(), prefix_words_count, n)
+            format!("line `{}` has {} words, expected {} + a multiple of {} for {}",
+                    line, words.len(), prefix_words_count, n, std::any::type_name::<T>())
         );
     }
     Ok(words)
@@ -975,10 +1397,12 @@ readable_collection!(U => Vec<U>, Vec<U::Output>);
 /// ```
 
 pub fn read<T: ReadableFromLine>() -> T::Output {
+    use std::io::BufRead;
+
     let mut line = String::new();
     // Can be faster by removing UTF-8 validation,
     // but enables validation in case of feeding a wrong test case manually.
-    std::io::stdin().read_line(&mut line).unwrap();
+    lock_input().read_line(&mut line).unwrap();
     T::read_line(&line).unwrap()
 }
 
@@ -1053,10 +1477,7 @@ pub fn read<T: ReadableFromLine>() -> T::Output {
 macro_rules! read {
     // Discards a line
     () => {
-        let mut line = String::new();
-        // Can be faster by disabling UTF-8 validation,
-        // but keeps it enabled in case of feeding a wrong test case manually.
-        std::io::stdin().read_line(&mut line).unwrap();
+        let _ = read::<Vec<String>>();
     };
 
     // Handles one-pattern case separately because of
@@ -1104,31 +1525,205 @@ macro_rules! read_inner {
     };
 }
 
-/// Readable by `read_chunk` function/macro.
-pub trait ReadableFromChunk {
-    type Output;
+/// Defines an enum of queries, readable from a line by its first word.
+///
+/// Each variant is given with the first word of the line, followed by
+/// the `Readable` types of the rest words if any.
+/// The field types of the generated variants are the `Output`s of the `Readable` types.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// readable_query!(
+///     #[derive(Debug)]
+///     enum Query {
+///         "1" => Add(usize_, i64),
+///         "2" => Sum(usize_, usize_),
+///         "3" => Clear
+///     }
+/// );
+///
+/// // Stdin: "3\n1 2 10\n2 1 3\n3\n"
+/// read!(q = usize);
+/// for query in read_rows::<Query>(q) {
+///     match query {
+///         Query::Add(i, x) => println!("add {} to {}", x, i),
+///         Query::Sum(l, r) => println!("sum of {}..={}", l, r),
+///         Query::Clear => println!("clear")
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! readable_query {
+    (
+        $( #[$attr:meta] )*
+        $vis:vis enum $t:ident {
+            $( $word:literal => $variant:ident $( ( $( $field_t:ty ),* ) )* ),* $(,)*
+        }
+    ) => {
+        $( #[$attr] )*
+        $vis enum $t {
+            $( $variant $( ( $( <$field_t as Readable>::Output ),* ) )* ),*
+        }
 
-    fn lines_count() -> usize;
+        impl ReadableFromLine for $t {
+            type Output = $t;
 
-    fn read_chunk(lines: &[String]) -> Result<Self::Output, String>;
+            #[allow(unused_assignments, unused_mut)]
+            fn read_line(line: &str) -> Result<$t, String> {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                let (first, rest) = match words.split_first() {
+                    Some(split) => split,
+                    None => return Err(format!("line `{}` has no words, expected a query", line))
+                };
+                match *first {
+                    $(
+                        $word => {
+                            let count = 0 $( $( + <$field_t as Readable>::WORD_COUNT )* )*;
+                            if rest.len() != count {
+                                return Err(format!(
+                                    "line `{}` has {} words after `{}`, expected {}",
+                                    line, rest.len(), first, count
+                                ));
+                            }
+                            let mut start = 0;
+                            Ok($t::$variant $( ( $( {
+                                let count = <$field_t as Readable>::WORD_COUNT;
+                                let value = <$field_t as Readable>::read_words(&rest[start .. start+count])
+                                    .map_err(|msg| format!("line `{}`: {}", line, msg))?;
+                                start += count;
+                                value
+                            } ),* ) )*)
+                        }
+                    )*
+                    word => Err(format!(
+                        "line `{}` starts with `{}`, expected one of {}",
+                        line, word, [$( $word ),*].join(", ")
+                    ))
+                }
+            }
+        }
+    };
 }
 
-impl<T1: ReadableFromLine, T2: ReadableFromLine> ReadableFromChunk for (T1, T2) {
-    type Output = (T1::Output, T2::Output);
+/// Values whose all items can be checked against bounds by [`read_assert`](../macro.read_assert.html).
+///
+/// Implemented for primitive numeric types, `char`, and `Vec`s and tuples of them.
+pub trait InBounds<T> {
+    /// Returns the debug representation of the first item out of `range`, if any.
+    fn find_out_of_bounds<R: std::ops::RangeBounds<T>>(&self, range: &R) -> Option<String>;
+}
 
-    fn lines_count() -> usize { 2 }
+macro_rules! impl_in_bounds {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl InBounds<$t> for $t {
+                fn find_out_of_bounds<R: std::ops::RangeBounds<$t>>(&self, range: &R) -> Option<String> {
+                    if range.contains(self) { None } else { Some(format!("{:?}", self)) }
+                }
+            }
+        )*
+    };
+}
 
-    fn read_chunk(lines: &[String]) -> Result<Self::Output, String> {
-        let out1 = T1::read_line(&lines[0])?;
-        let out2 = T2::read_line(&lines[1])?;
-        Ok((out1, out2))
+impl_in_bounds!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize, f32, f64, char);
+
+impl<T, U: InBounds<T>> InBounds<T> for Vec<U> {
+    fn find_out_of_bounds<R: std::ops::RangeBounds<T>>(&self, range: &R) -> Option<String> {
+        self.iter().filter_map(|item| item.find_out_of_bounds(range)).next()
     }
 }
 
-impl<T1: ReadableFromLine, T2: ReadableFromLine, T3: ReadableFromLine> ReadableFromChunk for (T1, T2, T3) {
-    type Output = (T1::Output, T2::Output, T3::Output);
+impl<T, U1: InBounds<T>, U2: InBounds<T>> InBounds<T> for (U1, U2) {
+    fn find_out_of_bounds<R: std::ops::RangeBounds<T>>(&self, range: &R) -> Option<String> {
+        self.0.find_out_of_bounds(range)
+            .or_else(|| self.1.find_out_of_bounds(range))
+    }
+}
 
-    fn lines_count() -> usize { 3 }
+impl<T, U1: InBounds<T>, U2: InBounds<T>, U3: InBounds<T>> InBounds<T> for (U1, U2, U3) {
+    fn find_out_of_bounds<R: std::ops::RangeBounds<T>>(&self, range: &R) -> Option<String> {
+        self.0.find_out_of_bounds(range)
+            .or_else(|| self.1.find_out_of_bounds(range))
+            .or_else(|| self.2.find_out_of_bounds(range))
+    }
+}
+
+/// Reads a line like [`read`](macro.read.html), checking constraints of the values.
+///
+/// Each binding is followed by `=>` and the range the value must be in.
+/// For `Vec`s and tuples, all the items must be in the range.
+///
+/// The constraints are checked only if `cfg(local)` is enabled,
+/// so that hand-made inputs violating them are found in the local machine.
+/// In the judge server, it is the same as `read` macro.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "3 5"
+/// read_assert!(n = usize => 1..=200_000, k = usize => 1..=n);
+/// // Panics if `cfg(local)` is enabled:
+/// // "k = 5 is out of bounds 1..=n"
+/// ```
+#[macro_export]
+#[cfg(local)]
+macro_rules! read_assert {
+    ( $( $var:ident = $t:ty => $range:expr ),+ $(,)* ) => {
+        read!($( $var = $t ),+);
+        $(
+            if let Some(item) = InBounds::find_out_of_bounds(&$var, &$range) {
+                panic!("{} = {} is out of bounds {}", stringify!($var), item, stringify!($range));
+            }
+        )+
+    };
+}
+
+/// Reads a line like [`read`](macro.read.html), checking constraints of the values.
+///
+/// Each binding is followed by `=>` and the range the value must be in.
+/// For `Vec`s and tuples, all the items must be in the range.
+///
+/// The constraints are checked only if `cfg(local)` is enabled,
+/// so that hand-made inputs violating them are found in the local machine.
+/// In the judge server, it is the same as `read` macro.
+#[macro_export]
+#[cfg(not(local))]
+macro_rules! read_assert {
+    ( $( $var:ident = $t:ty => $range:expr ),+ $(,)* ) => {
+        read!($( $var = $t ),+);
+    };
+}
+
+/// Readable by `read_chunk` function/macro.
+pub trait ReadableFromChunk {
+    type Output;
+
+    fn lines_count() -> usize;
+
+    fn read_chunk(lines: &[String]) -> Result<Self::Output, String>;
+}
+
+impl<T1: ReadableFromLine, T2: ReadableFromLine> ReadableFromChunk for (T1, T2) {
+    type Output = (T1::Output, T2::Output);
+
+    fn lines_count() -> usize { 2 }
+
+    fn read_chunk(lines: &[String]) -> Result<Self::Output, String> {
+        let out1 = T1::read_line(&lines[0])?;
+        let out2 = T2::read_line(&lines[1])?;
+        Ok((out1, out2))
+    }
+}
+
+impl<T1: ReadableFromLine, T2: ReadableFromLine, T3: ReadableFromLine> ReadableFromChunk for (T1, T2, T3) {
+    type Output = (T1::Output, T2::Output, T3::Output);
+
+    fn lines_count() -> usize { 3 }
 
     fn read_chunk(lines: &[String]) -> Result<Self::Output, String> {
         let out1 = T1::read_line(&lines[0])?;
@@ -1154,14 +1749,10 @@ impl<T1: ReadableFromLine, T2: ReadableFromLine, T3: ReadableFromLine, T4: Reada
 
 /// Reads multiple lines from stdin.
 pub fn read_chunk<T: ReadableFromChunk>() -> T::Output {
-    let stdin = std::io::stdin();
-    let mut handle = stdin.lock();
-    read_chunk_from_handle::<T>(&mut handle).unwrap()
+    read_chunk_from_handle::<T, _>(&mut lock_input()).unwrap()
 }
 
-fn read_chunk_from_handle<T: ReadableFromChunk>(handle: &mut std::io::StdinLock) -> Option<T::Output> {
-    use std::io::BufRead;
-
+fn read_chunk_from_handle<T: ReadableFromChunk, R: std::io::BufRead>(handle: &mut R) -> Option<T::Output> {
     let mut lines = vec![String::new(); T::lines_count()];
     let mut first = true;
     for line in &mut lines {
@@ -1225,9 +1816,120 @@ macro_rules! read_chunk {
 
 static mut STDIN: Option<std::io::Stdin> = None;
 
+thread_local! {
+    static SOURCE: std::cell::RefCell<Option<InputSource>> = std::cell::RefCell::new(None);
+}
+
+#[derive(Clone)]
+struct InputSource {
+    data: std::rc::Rc<Vec<u8>>,
+    pos: std::rc::Rc<std::cell::Cell<usize>>
+}
+
+/// Input read by functions and macros in this module.
+///
+/// It is the lock of stdin, or the source set by
+/// [`set_source_for_test`](fn.set_source_for_test.html).
+pub struct Input(InputKind);
+
+enum InputKind {
+    Stdin(std::io::StdinLock<'static>),
+    Source(InputSource)
+}
+
+impl std::io::Read for Input {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        use std::io::BufRead;
+
+        let len = {
+            let available = self.fill_buf()?;
+            let len = std::cmp::min(available.len(), buf.len());
+            buf[..len].copy_from_slice(&available[..len]);
+            len
+        };
+        self.consume(len);
+        Ok(len)
+    }
+}
+
+impl std::io::BufRead for Input {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        match self.0 {
+            InputKind::Stdin(ref mut lock) => lock.fill_buf(),
+            InputKind::Source(ref source) => Ok(&source.data[source.pos.get()..])
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        match self.0 {
+            InputKind::Stdin(ref mut lock) => lock.consume(amt),
+            InputKind::Source(ref source) => {
+                let pos = std::cmp::min(source.pos.get() + amt, source.data.len());
+                source.pos.set(pos);
+            }
+        }
+    }
+}
+
+/// Gets the input, locking stdin unless the source for test is set.
+///
+/// All the functions and macros in this module read the input through this function.
+/// Stdin is locked until the returned value is dropped.
+pub fn lock_input() -> Input {
+    if let Some(source) = SOURCE.with(|source| source.borrow().clone()) {
+        return Input(InputKind::Source(source));
+    }
+
+    unsafe {
+        if STDIN.is_none() {
+            STDIN = Some(std::io::stdin());
+        }
+        Input(InputKind::Stdin(STDIN.as_ref().unwrap().lock()))
+    }
+}
+
+/// Makes functions and macros in this module read `input` instead of stdin
+/// in the current thread.
+///
+/// Useful for testing a whole solution with sample inputs.
+/// The reading position is shared by all the functions and macros,
+/// as it is for stdin.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// fn solve() -> String {
+///     read!(n = usize);
+///     let sum: u32 = read_lines::<(u32, u32)>().take(n).map(|(a, b)| a * b).sum();
+///     read!(s = String);
+///     format!("{} {}", sum, s)
+/// }
+///
+/// set_source_for_test("2\n1 2\n3 4\nend\n");
+/// assert_eq!(solve(), "14 end");
+///
+/// set_source_for_test("1\n5 5\nok");
+/// assert_eq!(solve(), "25 ok");
+/// clear_source_for_test();
+/// ```
+pub fn set_source_for_test(input: &str) {
+    let source = InputSource {
+        data: std::rc::Rc::new(input.as_bytes().to_vec()),
+        pos: std::rc::Rc::new(std::cell::Cell::new(0))
+    };
+    SOURCE.with(|s| *s.borrow_mut() = Some(source));
+}
+
+/// Makes functions and macros in this module read stdin again in the current thread.
+pub fn clear_source_for_test() {
+    SOURCE.with(|s| *s.borrow_mut() = None);
+}
+
 /// Iterator created by [`read_lines`](fn.read_lines.html) function.
 pub struct ReadLines<T: ReadableFromLine> {
-    lock: std::io::StdinLock<'static>,
+    lock: Input,
     phantom: std::marker::PhantomData<T>
 }
 
@@ -1306,21 +2008,15 @@ impl<T: ReadableFromLine> Iterator for ReadLines<T> {
 /// `read_lines` gets the mutex for stdin, and release it when the iterator is dropped.
 /// So, it causes deadlock to read stdin before the iterator is dropped.
 pub fn read_lines<T: ReadableFromLine>() -> ReadLines<T> {
-    unsafe {
-        if STDIN.is_none() {
-            STDIN = Some(std::io::stdin());
-        }
-    }
-
     ReadLines {
-        lock: unsafe { STDIN.as_ref().unwrap().lock() },
+        lock: lock_input(),
         phantom: std::marker::PhantomData::<T>
     }
 }
 
 /// Iterator created by [`read_chunks`](fn.read_chunks.html) function.
 pub struct ReadChunks<T: ReadableFromChunk> {
-    lock: std::io::StdinLock<'static>,
+    lock: Input,
     phantom: std::marker::PhantomData<T>
 }
 
@@ -1328,21 +2024,586 @@ impl<T: ReadableFromChunk> Iterator for ReadChunks<T> {
     type Item = T::Output;
 
     fn next(&mut self) -> Option<T::Output> {
-        read_chunk_from_handle::<T>(&mut self.lock)
+        read_chunk_from_handle::<T, _>(&mut self.lock)
     }
 }
 
 /// Creates an iterator reading stdin chunk by chunk.
 pub fn read_chunks<T: ReadableFromChunk>() -> ReadChunks<T> {
-    unsafe {
-        if STDIN.is_none() {
-            STDIN = Some(std::io::stdin());
+    ReadChunks {
+        lock: lock_input(),
+        phantom: std::marker::PhantomData::<T>
+    }
+}
+
+/// Reads `n` lines from stdin.
+///
+/// It is the same as `read_lines::<T>().take(n).collect::<Vec<_>>()`,
+/// but panics if stdin has less than `n` lines.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "2\n1 2 3\n4 5 6\n"
+/// read!(n = usize);
+/// assert_eq!(read_rows::<(u8, u8, u8)>(n), vec![(1, 2, 3), (4, 5, 6)]);
+/// ```
+pub fn read_rows<T: ReadableFromLine>(n: usize) -> Vec<T::Output> {
+    let mut handle = lock_input();
+    read_rows_from_handle::<T, _>(&mut handle, n).unwrap()
+}
+
+/// Reads lines from stdin, where `lens[i]` is the number of `T`s in the `i`-th line.
+///
+/// # Panics
+///
+/// Panics if stdin has less than `lens.len()` lines,
+/// or a line has a wrong number of `T`s.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "2 1\n1 2\n3\n"
+/// read!(lens = Vec<usize>);
+/// assert_eq!(read_jagged_rows::<u8>(&lens), vec![vec![1, 2], vec![3]]);
+/// ```
+pub fn read_jagged_rows<T: Readable>(lens: &[usize]) -> Vec<Vec<T::Output>> {
+    let mut handle = lock_input();
+    read_jagged_rows_from_handle::<T, _>(&mut handle, lens).unwrap()
+}
+
+fn read_rows_from_handle<T: ReadableFromLine, R: std::io::BufRead>(
+    handle: &mut R, n: usize
+) -> Result<Vec<T::Output>, String> {
+    let mut result = Vec::with_capacity(n);
+    let mut line = String::new();
+    for i in 0..n {
+        line.clear();
+        if handle.read_line(&mut line).unwrap() == 0 {
+            return Err(format!("expected {} lines, found {}", n, i));
         }
+        result.push(T::read_line(&line)?);
     }
+    Ok(result)
+}
 
-    ReadChunks {
-        lock: unsafe { STDIN.as_ref().unwrap().lock() },
-        phantom: std::marker::PhantomData::<T>
+fn read_jagged_rows_from_handle<T: Readable, R: std::io::BufRead>(
+    handle: &mut R, lens: &[usize]
+) -> Result<Vec<Vec<T::Output>>, String> {
+    let rows = read_rows_from_handle::<Vec<T>, _>(handle, lens.len())?;
+    for (i, (row, &len)) in rows.iter().zip(lens).enumerate() {
+        if row.len() != len {
+            return Err(format!("line {} has {} items, expected {}", i + 1, row.len(), len));
+        }
+    }
+    Ok(rows)
+}
+
+/// Reads all the rest lines of stdin.
+///
+/// Useful when the number of lines is not given.
+///
+/// # Panics
+///
+/// Panics if a line cannot be read as `T`.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "1 10\n2 20\n3 30"
+/// let pairs = read_all_lines::<(u8, u8)>();
+/// assert_eq!(pairs, vec![(1, 10), (2, 20), (3, 30)]);
+/// ```
+pub fn read_all_lines<T: ReadableFromLine>() -> Vec<T::Output> {
+    let mut handle = lock_input();
+    read_all_lines_from_handle::<T, _>(&mut handle).unwrap()
+}
+
+/// Reads all the rest of stdin as whitespace-separated words, ignoring line breaks.
+///
+/// # Panics
+///
+/// Panics if the words cannot be read as `T`,
+/// including the case the input ends in the middle of a `T`.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "1 10 2\n20 3 30\n"
+/// let pairs = read_all_words::<(u8, u8)>();
+/// assert_eq!(pairs, vec![(1, 10), (2, 20), (3, 30)]);
+/// ```
+pub fn read_all_words<T: Readable>() -> Vec<T::Output> {
+    use std::io::Read;
+
+    let mut input = String::new();
+    lock_input().read_to_string(&mut input).unwrap();
+    read_all_words_from_str::<T>(&input).unwrap()
+}
+
+fn read_all_lines_from_handle<T: ReadableFromLine, R: std::io::BufRead>(
+    handle: &mut R
+) -> Result<Vec<T::Output>, String> {
+    let mut result = Vec::new();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if handle.read_line(&mut line).unwrap() == 0 {
+            return Ok(result);
+        }
+        let item = T::read_line(&line).map_err(|msg| {
+            format!("reading {} from line {}: {}", std::any::type_name::<T>(), result.len() + 1, msg)
+        })?;
+        result.push(item);
+    }
+}
+
+fn read_all_words_from_str<T: Readable>(input: &str) -> Result<Vec<T::Output>, String> {
+    let n = T::WORD_COUNT;
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let rest = words.len() % n;
+    if rest > 0 {
+        return Err(format!(
+            "reading {}: input ended with {} words, expected {}",
+            std::any::type_name::<T>(), rest, n
+        ));
+    }
+    words.chunks(n).enumerate().map(|(i, chunk)| {
+        T::read_words(chunk).map_err(|msg| {
+            format!("reading {} from words {}-{}: {}", std::any::type_name::<T>(), n*i + 1, n*(i+1), msg)
+        })
+    }).collect()
+}
+
+/// Reads stdin line by line until EOF, and runs the block for each line.
+///
+/// The usage is the same as [`read`](macro.read.html) macro,
+/// except that the bindings are followed by `=>` and a block.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "1 10\n2 20\n3 30\n"
+/// let mut sum = 0;
+/// read_while_some!(a = u32, b = u32 => {
+///     sum += a * b;
+/// });
+/// assert_eq!(sum, 140);
+/// ```
+///
+/// # Deadlock
+///
+/// Like [`read_lines`](read/fn.read_lines.html), stdin is locked during the loop.
+/// So, reading stdin in the block causes deadlock.
+#[macro_export]
+macro_rules! read_while_some {
+    ( $pat:pat = $t:ty => $body:block ) => {
+        for $pat in read_lines::<$t>() $body
+    };
+    ( $( $pat:pat = $t:ty ),+ => $body:block ) => {
+        for ($($pat),+) in read_lines::<($($t),+)>() $body
+    };
+}
+
+/// Reads `h` lines from stdin as a grid of bytes.
+///
+/// Trailing newline characters are removed from each line.
+///
+/// Can be mixed with other functions and macros in this module,
+/// as they share the buffer of stdin.
+///
+/// # Panics
+///
+/// Panics if stdin has less than `h` lines or the lines have different widths.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "2 3\n.#.\n##.\n"
+/// read!(h = usize, w = usize);
+/// let grid = read_grid(h);
+/// assert_eq!(grid, vec![b".#.".to_vec(), b"##.".to_vec()]);
+/// assert_eq!(grid[0].len(), w);
+/// ```
+pub fn read_grid(h: usize) -> Vec<Vec<u8>> {
+    let mut handle = lock_input();
+    read_grid_from_handle(&mut handle, h).unwrap()
+}
+
+/// Reads `h` lines of digits from stdin as a grid of numbers.
+///
+/// # Panics
+///
+/// Panics if stdin has less than `h` lines, the lines have different widths
+/// or a character other than digits is found.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "2\n019\n283\n"
+/// read!(h = usize);
+/// assert_eq!(read_digit_grid(h), vec![vec![0, 1, 9], vec![2, 8, 3]]);
+/// ```
+pub fn read_digit_grid(h: usize) -> Vec<Vec<u32>> {
+    let mut handle = lock_input();
+    read_digit_grid_from_handle(&mut handle, h).unwrap()
+}
+
+fn read_grid_from_handle<R: std::io::BufRead>(handle: &mut R, h: usize) -> Result<Vec<Vec<u8>>, String> {
+    let mut grid: Vec<Vec<u8>> = Vec::with_capacity(h);
+    let mut line = String::new();
+    for i in 0..h {
+        line.clear();
+        if handle.read_line(&mut line).unwrap() == 0 {
+            return Err(format!("expected {} lines of grid, found {}", h, i));
+        }
+        let row = line.trim_end_matches(&['\n', '\r'][..]).as_bytes().to_vec();
+        if let Some(first) = grid.first() {
+            if row.len() != first.len() {
+                return Err(format!(
+                    "line {} of grid `{}`: expected width {}, found {}",
+                    i + 1, line, first.len(), row.len()
+                ));
+            }
+        }
+        grid.push(row);
+    }
+    Ok(grid)
+}
+
+fn read_digit_grid_from_handle<R: std::io::BufRead>(handle: &mut R, h: usize) -> Result<Vec<Vec<u32>>, String> {
+    let grid = read_grid_from_handle(handle, h)?;
+    grid.into_iter().enumerate().map(|(i, row)| {
+        row.into_iter().map(|c| {
+            (c as char).to_digit(10).ok_or_else(|| {
+                format!("line {} of grid: cannot parse `{}` as digit", i + 1, c as char)
+            })
+        }).collect()
+    }).collect()
+}
+
+/// Reader of the whole input as a stream of whitespace-separated words.
+///
+/// Unlike other functions and macros in this module, line breaks are ignored.
+/// It is useful when a record is broken across lines,
+/// and is faster than line-oriented reading for a large input.
+///
+/// # Example
+///
+/// ```no_run
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "3\n1 2\n3\n1 10 2 20\n"
+/// let mut tokens = Tokens::from_stdin();
+/// let n = tokens.next::<usize>();
+/// assert_eq!(tokens.next_vec::<u32>(n), vec![1, 2, 3]);
+/// assert_eq!(tokens.next_vec::<(usize_, u32)>(2), vec![(0, 10), (1, 20)]);
+/// assert!(tokens.is_empty());
+/// ```
+pub struct Tokens {
+    input: String,
+    pos: usize
+}
+
+impl Tokens {
+    /// Creates a reader of the given input.
+    pub fn new(input: String) -> Tokens {
+        Tokens { input, pos: 0 }
+    }
+
+    /// Reads all the rest of stdin, and creates a reader of it.
+    pub fn from_stdin() -> Tokens {
+        use std::io::Read;
+
+        let mut input = String::new();
+        lock_input().read_to_string(&mut input).unwrap();
+        Tokens::new(input)
+    }
+
+    fn skip_whitespaces(&mut self) {
+        let bytes = self.input.as_bytes();
+        while self.pos < bytes.len() && bytes[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+    }
+
+    fn next_word_range(&mut self) -> Option<std::ops::Range<usize>> {
+        self.skip_whitespaces();
+        let bytes = self.input.as_bytes();
+        let start = self.pos;
+        while self.pos < bytes.len() && !bytes[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        if start < self.pos { Some(start..self.pos) } else { None }
+    }
+
+    /// Returns whether all the words have been read.
+    pub fn is_empty(&mut self) -> bool {
+        self.skip_whitespaces();
+        self.pos == self.input.len()
+    }
+
+    /// Reads the next `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input ends or the words cannot be read as `T`.
+    pub fn next<T: Readable>(&mut self) -> T::Output {
+        let result = if T::WORD_COUNT == 1 {
+            self.next_word_range()
+                .ok_or_else(|| "input ended".to_string())
+                .and_then(|range| T::read_words(&[&self.input[range]]))
+        } else {
+            let ranges: Vec<_> = (0..T::WORD_COUNT).filter_map(|_| self.next_word_range()).collect();
+            if ranges.len() < T::WORD_COUNT {
+                Err(format!("input ended with {} words, expected {}", ranges.len(), T::WORD_COUNT))
+            } else {
+                let words: Vec<&str> = ranges.into_iter().map(|range| &self.input[range]).collect();
+                T::read_words(&words)
+            }
+        };
+        result.unwrap_or_else(|msg| panic!("reading {}: {}", std::any::type_name::<T>(), msg))
+    }
+
+    /// Reads the next `n` items of `T`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input ends or the words cannot be read as `T`.
+    pub fn next_vec<T: Readable>(&mut self, n: usize) -> Vec<T::Output> {
+        (0..n).map(|_| self.next::<T>()).collect()
+    }
+}
+
+/// Reader parsing words of input directly from bytes.
+///
+/// Faster than other functions and macros in this module,
+/// since the input is not validated as UTF-8 or split into `str`s.
+/// Line breaks are ignored except by `line` method.
+///
+/// # Example
+///
+/// ```no_run
+/// # use atcoder_snippets::read::*;
+/// // Stdin: "2 -3\n10 20\r\nabc def\n"
+/// let mut reader = FastReader::from_stdin();
+/// assert_eq!(reader.usize(), 2);
+/// assert_eq!(reader.i64(), -3);
+/// assert_eq!(reader.u64() + reader.u64(), 30);
+/// assert_eq!(reader.token(), b"abc");
+/// assert_eq!(reader.line(), b" def");
+/// ```
+///
+/// # Deadlock
+///
+/// Like [`read_lines`](fn.read_lines.html), the reader created by `from_stdin`
+/// holds the lock of stdin until it is dropped.
+pub struct FastReader<R: std::io::BufRead> {
+    reader: R,
+    buf: Vec<u8>
+}
+
+impl FastReader<Input> {
+    /// Creates a reader of stdin.
+    pub fn from_stdin() -> FastReader<Input> {
+        FastReader::new(lock_input())
+    }
+}
+
+impl<R: std::io::BufRead> FastReader<R> {
+    /// Creates a reader of the given buffered reader, such as `&[u8]`.
+    pub fn new(reader: R) -> FastReader<R> {
+        FastReader { reader, buf: Vec::new() }
+    }
+
+    fn skip_whitespaces(&mut self) {
+        loop {
+            let (skipped, rest) = {
+                let buf = self.reader.fill_buf().unwrap();
+                let skipped = buf.iter().take_while(|b| b.is_ascii_whitespace()).count();
+                (skipped, buf.len() - skipped)
+            };
+            self.reader.consume(skipped);
+            if skipped == 0 || rest > 0 {
+                return;
+            }
+        }
+    }
+
+    /// Reads the next word, which is empty if the input ended.
+    pub fn token(&mut self) -> &[u8] {
+        self.skip_whitespaces();
+        self.buf.clear();
+        loop {
+            let (len, rest) = {
+                let buf = self.reader.fill_buf().unwrap();
+                let len = buf.iter().take_while(|b| !b.is_ascii_whitespace()).count();
+                self.buf.extend_from_slice(&buf[..len]);
+                (len, buf.len() - len)
+            };
+            self.reader.consume(len);
+            if len == 0 || rest > 0 {
+                return &self.buf;
+            }
+        }
+    }
+
+    /// Reads the rest of the current line, without the line break.
+    ///
+    /// Leading whitespaces are kept.
+    pub fn line(&mut self) -> &[u8] {
+        self.buf.clear();
+        self.reader.read_until(b'\n', &mut self.buf).unwrap();
+        while let Some(&b'\n') | Some(&b'\r') = self.buf.last() {
+            self.buf.pop();
+        }
+        &self.buf
+    }
+
+    // Parses a word of digits as `type_name`, which may be preceded by a sign if `signed`.
+    // Returns the sign and the absolute value, which is at most `max` or `max + 1` if negative.
+    // Panics in the same format as `Readable`.
+    fn unsigned_word(&mut self, type_name: &str, signed: bool, max: u64) -> (bool, u64) {
+        let word = self.token();
+        if word.is_empty() {
+            panic!("reading {}: input ended", type_name);
+        }
+        let (negative, digits) = match word.first() {
+            Some(b'-') if signed => (true, &word[1..]),
+            _ => (false, word)
+        };
+        let limit = if negative { max + 1 } else { max };
+        let value = digits.iter().try_fold(0u64, |acc, &b| {
+            if b.is_ascii_digit() {
+                acc.checked_mul(10)?.checked_add((b - b'0') as u64)
+            } else {
+                None
+            }
+        });
+        match value {
+            Some(value) if !digits.is_empty() && value <= limit => (negative, value),
+            _ => panic!("reading {}: cannot parse `{}` as {}", type_name, String::from_utf8_lossy(word), type_name)
+        }
+    }
+
+    /// Reads the next word as `u64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input ended or the word is not an integer in the range of `u64`.
+    pub fn u64(&mut self) -> u64 {
+        self.unsigned_word("u64", false, std::u64::MAX).1
+    }
+
+    /// Reads the next word as `i64`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input ended or the word is not an integer in the range of `i64`.
+    pub fn i64(&mut self) -> i64 {
+        let (negative, value) = self.unsigned_word("i64", true, std::i64::MAX as u64);
+        if negative { (value as i64).wrapping_neg() } else { value as i64 }
+    }
+
+    /// Reads the next word as `usize`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input ended or the word is not an integer in the range of `usize`.
+    pub fn usize(&mut self) -> usize {
+        self.unsigned_word("usize", false, std::usize::MAX as u64).1 as usize
+    }
+}
+
+/// Lockstep reader and writer for interactive problems.
+///
+/// Each query is flushed as soon as it is written,
+/// so that the judge can reply to it.
+///
+/// # Example
+///
+/// Guesses a hidden number in `1..=n`,
+/// where the judge replies `<`, `>` or `=` to a query `? x`.
+///
+/// ```no_run
+/// # use atcoder_snippets::read::*;
+/// let mut io = Interactor::from_stdio();
+/// let n = io.read::<u32>();
+/// let (mut lo, mut hi) = (1, n);
+/// loop {
+///     let mid = (lo + hi) / 2;
+///     match io.ask::<char>(format_args!("? {}", mid)) {
+///         '<' => hi = mid - 1,
+///         '>' => lo = mid + 1,
+///         _ => break
+///     }
+/// }
+/// ```
+///
+/// An interactive strategy can be tested locally by `Interactor::new`
+/// with a reader and a writer of a mock judge, such as `&[u8]` and `Vec<u8>`.
+pub struct Interactor<R: std::io::BufRead, W: std::io::Write> {
+    reader: R,
+    writer: W
+}
+
+impl Interactor<Input, std::io::Stdout> {
+    /// Creates an interactor reading stdin and writing stdout.
+    ///
+    /// Like [`read_lines`](fn.read_lines.html), the interactor holds the lock of stdin
+    /// until it is dropped.
+    pub fn from_stdio() -> Interactor<Input, std::io::Stdout> {
+        Interactor::new(lock_input(), std::io::stdout())
+    }
+}
+
+impl<R: std::io::BufRead, W: std::io::Write> Interactor<R, W> {
+    /// Creates an interactor reading replies from `reader` and writing queries to `writer`.
+    pub fn new(reader: R, writer: W) -> Interactor<R, W> {
+        Interactor { reader, writer }
+    }
+
+    /// Reads a line of the reply.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the reader ended or the line cannot be read as `T`.
+    pub fn read<T: ReadableFromLine>(&mut self) -> T::Output {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line).unwrap() == 0 {
+            panic!("judge closed the input");
+        }
+        T::read_line(&line).unwrap()
+    }
+
+    /// Writes `line` with a line break and flushes it, without waiting for the reply.
+    pub fn answer<D: std::fmt::Display>(&mut self, line: D) {
+        writeln!(self.writer, "{}", line).unwrap();
+        self.writer.flush().unwrap();
+    }
+
+    /// Writes `query` with a line break, flushes it, and reads a line of the reply.
+    pub fn ask<T: ReadableFromLine>(&mut self, query: impl std::fmt::Display) -> T::Output {
+        self.answer(query);
+        self.read::<T>()
+    }
+
+    /// Gets the reader and the writer back.
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
     }
 }
 
@@ -1402,6 +2663,27 @@ mod test {
         assert_eq!(s, Ok(vec!['C', 'H', 'A', 'R', 'A', 'C', 'T', 'E', 'R', 'S']));
     }
 
+    #[test]
+    fn test_read_digits() {
+        assert_eq!(Digits::read_words(&["0012"]), Ok(vec![0, 0, 1, 2]));
+        assert_eq!(Digits::read_words(&["00000"]), Ok(vec![0; 5]));
+        assert_eq!(Digits::read_words(&["12a4"]),
+                   Err("cannot parse `12a4` as digits: found `a`".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot parse `12a4` as digits: found `a`")]
+    fn test_read_digits_panic() {
+        "12a4".read::<Digits>();
+    }
+
+    #[test]
+    fn test_read_bits() {
+        assert_eq!(Bits::read_words(&["0010"]), Ok(vec![false, false, true, false]));
+        assert_eq!(Bits::read_words(&["000"]), Ok(vec![false; 3]));
+        assert!(Bits::read_words(&["0120"]).is_err());
+    }
+
     #[test]
     fn test_read_words_one_origin_integers() {
         assert_eq!(u8_::read_words(&["1"]), Ok(0));
@@ -1411,6 +2693,159 @@ mod test {
         assert_eq!(usize_::read_words(&["1"]), Ok(0));
     }
 
+    #[test]
+    fn test_read_usize1() {
+        let edges: Vec<(Usize1, Usize1)> = ["1 2\n", "2 3\n"].iter()
+            .map(|line| <(Usize1, Usize1)>::read_line(line).unwrap())
+            .collect();
+        let edges: Vec<(usize, usize)> = edges.into_iter().map(|(u, v)| (*u, v.get())).collect();
+        assert_eq!(edges, vec![(0, 1), (1, 2)]);
+        assert_eq!(Vec::<Usize1>::read_line("3 1\n"), Ok(vec![Usize1(2), Usize1(0)]));
+        assert!(Usize1::read_line("0\n").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot read `0` as 1-origin integer")]
+    fn test_read_usize1_zero() {
+        "0".read::<Usize1>();
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Dir { Up, Down, Left, Right }
+
+    readable_enum!(Dir { "U" => Up, "D" => Down, "L" => Left, "R" => Right, });
+
+    #[test]
+    fn test_read_neg_one_as_none() {
+        assert_eq!(Vec::<NegOneAsNone<usize_>>::read_line("-1 1 1 2\n"),
+                   Ok(vec![None, Some(0), Some(0), Some(1)]));
+        assert_eq!(Vec::<NegOneAsNone<i32>>::read_line("3 -1 -2\n"),
+                   Ok(vec![Some(3), None, Some(-2)]));
+        assert!(NegOneAsNone::<usize>::read_line("-2\n").is_err());
+    }
+
+    #[test]
+    fn test_readable_enum() {
+        assert_eq!(Vec::<Dir>::read_line("U D L R U\n"),
+                   Ok(vec![Dir::Up, Dir::Down, Dir::Left, Dir::Right, Dir::Up]));
+        assert_eq!(<(usize, Dir)>::read_line("3 L\n"), Ok((3, Dir::Left)));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot parse `X` as Dir, expected one of U, D, L, R")]
+    fn test_readable_enum_invalid() {
+        "X".read::<Dir>();
+    }
+
+    #[test]
+    fn test_read_fixed() {
+        let read = |word| Fixed::<Scale2>::read_words(&[word]).map(|x| x.raw());
+        assert_eq!(read("3"), Ok(300));
+        assert_eq!(read("3.5"), Ok(350));
+        assert_eq!(read("3.50"), Ok(350));
+        assert_eq!(read("-0.05"), Ok(-5));
+        assert_eq!(read("0"), Ok(0));
+        assert!(read("3.505").is_err());
+        assert!(read("3.").is_err());
+        assert!(read(".5").is_err());
+        assert!(read("-").is_err());
+        assert!(read("1.-5").is_err());
+        assert!(read("1e5").is_err());
+        assert!(read("100000000000000000000").is_err());
+
+        assert_eq!(Fixed::<Scale2>::from_raw(350).to_string(), "3.50");
+        assert_eq!(Fixed::<Scale2>::from_raw(-5).to_string(), "-0.05");
+        assert_eq!(Fixed::<Scale3>::from_raw(-12345).to_string(), "-12.345");
+        assert_eq!(Fixed::<Scale1>::read_words(&["0.3"]), Ok(Fixed::from_raw(3)));
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Edge {
+        from: Usize1,
+        to: usize,
+        weight: i64
+    }
+
+    readable_struct!(Edge { from: Usize1, to: usize_, weight: i64 });
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Query {
+        kind: Dir,
+        edge: Edge,
+        pairs: (Pair, [Pair; 2]),
+        start: Option<i32>
+    }
+
+    readable_struct!(Query {
+        kind: Dir,
+        edge: Edge,
+        pairs: (Pair, [Pair; 2]),
+        start: NegOneAsNone<i32>,
+    });
+
+    #[test]
+    fn test_readable_struct() {
+        assert_eq!(Edge::WORD_COUNT, 3);
+        assert_eq!(Vec::<Edge>::read_line("1 2 10 2 3 -5\n"), Ok(vec![
+            Edge { from: Usize1(0), to: 1, weight: 10 },
+            Edge { from: Usize1(1), to: 2, weight: -5 }
+        ]));
+
+        assert_eq!(Query::WORD_COUNT, 11);
+        assert_eq!(Query::read_line("U 1 2 3 4 5 6 7 8 9 -1\n"), Ok(Query {
+            kind: Dir::Up,
+            edge: Edge { from: Usize1(0), to: 1, weight: 3 },
+            pairs: (Pair(4, 5), [Pair(6, 7), Pair(8, 9)]),
+            start: None
+        }));
+
+        let err = Edge::read_line("1 2 x\n").unwrap_err();
+        assert!(err.starts_with("field `weight` of Edge: "));
+        let err = Query::read_line("U 0 2 3 4 5 6 7 8 9 10\n").unwrap_err();
+        assert!(err.starts_with("field `edge` of Query: field `from` of Edge: "));
+    }
+
+    readable_query!(
+        #[derive(Debug, PartialEq, Eq)]
+        enum Query2 {
+            "1" => Add(usize_, i64),
+            "2" => Ask(usize),
+            "L" => Move(char, Pair),
+            "reset" => Reset,
+        }
+    );
+
+    #[test]
+    fn test_read_mixed_types() {
+        assert_eq!(Vec::<(char, i64)>::read_line("L 3 R -2\n"), Ok(vec![('L', 3), ('R', -2)]));
+        assert_eq!(<(String, i64)>::read_line("abc 3\n"), Ok(("abc".to_string(), 3)));
+    }
+
+    #[test]
+    fn test_readable_query() {
+        let mut input: &[u8] = b"1 3 -10\n2 5\nL x 1 2\nreset\n";
+        let queries = read_rows_from_handle::<Query2, _>(&mut input, 4).unwrap();
+        assert_eq!(queries, vec![
+            Query2::Add(2, -10),
+            Query2::Ask(5),
+            Query2::Move('x', Pair(1, 2)),
+            Query2::Reset
+        ]);
+        let sum: i64 = queries.iter().map(|q| match *q {
+            Query2::Add(_, x) => x,
+            Query2::Ask(i) => i as i64,
+            _ => 0
+        }).sum();
+        assert_eq!(sum, -5);
+
+        assert_eq!(Query2::read_line("3 1\n").unwrap_err(),
+                   "line `3 1\n` starts with `3`, expected one of 1, 2, L, reset");
+        assert_eq!(Query2::read_line("1 2\n").unwrap_err(),
+                   "line `1 2\n` has 1 words after `1`, expected 2");
+        assert!(Query2::read_line("2 x\n").unwrap_err().starts_with("line `2 x\n`: "));
+        assert!(Query2::read_line("\n").is_err());
+    }
+
     #[test]
     fn test_read_words_custom() {
         assert_eq!(Pair::read_words(&["1", "2"]), Ok(Pair(1, 2)));
@@ -1486,6 +2921,20 @@ mod test {
         assert!(T6::read_words(&["10", "10", "10", "10", "10", "10", "10", "10", "10", "10", "10", "10"]).is_ok());
     }
 
+    #[test]
+    fn test_read_words_tuple_7() {
+        type T = (i32, char, Pair, i32, i32, i32, String);
+        assert_eq!(T::read_words(&["1", "a", "2", "3", "4", "5", "6", "b"]),
+                   Ok((1, 'a', Pair(2, 3), 4, 5, 6, "b".to_string())));
+    }
+
+    #[test]
+    fn test_read_words_tuple_8() {
+        type T = (i32, i32, i32, i32, i32, i32, i32, Pair);
+        assert_eq!(T::read_line("1 2 3 4 5 6 7 8 9  \n"),
+                   Ok((1, 2, 3, 4, 5, 6, 7, Pair(8, 9))));
+    }
+
     #[test]
     fn test_read_words_array_2 () {
         let val = <[Pair; 2]>::read_words(&["0", "1", "2", "3"]).unwrap();
@@ -1612,6 +3061,347 @@ mod test {
         assert!(Vec::<Pair>::read_line("1 2 3\n").is_err());
     }
 
+    #[test]
+    fn test_read_rows() {
+        type T = (u8, u8, u8, u8, u8, char);
+        let mut input: &[u8] = b"1 2 3 4 5 a \n6 7 8 9 10  b\r\nrest\n";
+        assert_eq!(read_rows_from_handle::<T, _>(&mut input, 2),
+                   Ok(vec![(1, 2, 3, 4, 5, 'a'), (6, 7, 8, 9, 10, 'b')]));
+        assert_eq!(input, b"rest\n");
+
+        let mut input: &[u8] = b"1 2 3 4 5 a\n";
+        assert_eq!(read_rows_from_handle::<T, _>(&mut input, 2),
+                   Err("expected 2 lines, found 1".to_string()));
+
+        let mut input: &[u8] = b"1 2 3 4 5\n";
+        assert_eq!(read_rows_from_handle::<T, _>(&mut input, 1),
+                   Err("line `1 2 3 4 5\n` has 5 words, expected 6 for (u8, u8, u8, u8, u8, char)".to_string()));
+    }
+
+    #[test]
+    fn test_read_jagged_rows() {
+        let mut input: &[u8] = b"1 2 3\n\n4 \n";
+        assert_eq!(read_jagged_rows_from_handle::<u8, _>(&mut input, &[3, 0, 1]),
+                   Ok(vec![vec![1, 2, 3], vec![], vec![4]]));
+
+        let mut input: &[u8] = b"1 2 3\n4 5\n";
+        let err = read_jagged_rows_from_handle::<Pair, _>(&mut input, &[1, 1]).unwrap_err();
+        assert!(err.starts_with("line `1 2 3\n` has 3 words, expected 0 + a multiple of 2 for "));
+        let mut input: &[u8] = b"1 2 3\n4 5\n";
+        assert_eq!(read_jagged_rows_from_handle::<u8, _>(&mut input, &[3, 1]).unwrap_err(),
+                   "line 2 has 2 items, expected 1");
+    }
+
+    #[test]
+    fn test_read_all_lines() {
+        let mut input: &[u8] = b"1 10\n2 20\n3 30\n";
+        assert_eq!(read_all_lines_from_handle::<(u8, u8), _>(&mut input),
+                   Ok(vec![(1, 10), (2, 20), (3, 30)]));
+        let mut input: &[u8] = b"1 10\r\n2 20\r\n3 30";
+        assert_eq!(read_all_lines_from_handle::<(u8, u8), _>(&mut input),
+                   Ok(vec![(1, 10), (2, 20), (3, 30)]));
+        let mut input: &[u8] = b"";
+        assert_eq!(read_all_lines_from_handle::<u8, _>(&mut input), Ok(Vec::new()));
+
+        let mut input: &[u8] = b"1 10\n2";
+        let err = read_all_lines_from_handle::<(u8, u8), _>(&mut input).unwrap_err();
+        assert!(err.starts_with("reading (u8, u8) from line 2:"));
+    }
+
+    #[test]
+    fn test_read_all_words() {
+        assert_eq!(read_all_words_from_str::<(u8, u8)>("1 10 2\n20\n 3 30\n"),
+                   Ok(vec![(1, 10), (2, 20), (3, 30)]));
+        assert_eq!(read_all_words_from_str::<(u8, u8)>("1 10 2\n20\n 3 30"),
+                   Ok(vec![(1, 10), (2, 20), (3, 30)]));
+        assert_eq!(read_all_words_from_str::<u8>(""), Ok(Vec::new()));
+
+        let err = read_all_words_from_str::<(u8, u8)>("1 10\n2\n").unwrap_err();
+        assert_eq!(err, "reading (u8, u8): input ended with 1 words, expected 2");
+        let err = read_all_words_from_str::<(u8, u8)>("1 10\n2 a\n").unwrap_err();
+        assert!(err.starts_with("reading (u8, u8) from words 3-4:"));
+    }
+
+    #[test]
+    fn test_tokens() {
+        let mut tokens = Tokens::new("  3\n1 2\r\n\n3 \n1 10 2\n20\n".to_string());
+        let n = tokens.next::<usize>();
+        assert_eq!(tokens.next_vec::<u32>(n), vec![1, 2, 3]);
+        assert!(!tokens.is_empty());
+        assert_eq!(tokens.next_vec::<(usize_, Pair)>(1), vec![(0, Pair(10, 2))]);
+        assert_eq!(tokens.next::<String>(), "20");
+        assert!(tokens.is_empty());
+        assert_eq!(tokens.next_vec::<u32>(0), Vec::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "reading (u32, u32): input ended with 1 words, expected 2")]
+    fn test_tokens_ended() {
+        Tokens::new("1 2 3".to_string()).next_vec::<(u32, u32)>(2);
+    }
+
+    #[test]
+    fn test_tokens_large() {
+        let n = 1_000_000u64;
+        let mut input = n.to_string();
+        for i in 0..n {
+            input.push(if i % 80 == 0 { '\n' } else { ' ' });
+            input.push_str(&i.to_string());
+        }
+
+        let mut tokens = Tokens::new(input);
+        let n = tokens.next::<usize>();
+        let sum: u64 = tokens.next_vec::<u64>(n).into_iter().sum();
+        assert_eq!(sum, 499_999_500_000);
+        assert!(tokens.is_empty());
+    }
+
+    #[test]
+    fn test_fast_reader() {
+        let input: &[u8] = b"  \t12 -34\r\n\n  +5 0 -0 -9223372036854775808 abc\r\n de f\r\nlast";
+        let mut reader = FastReader::new(input);
+        assert_eq!(reader.u64(), 12);
+        assert_eq!(reader.i64(), -34);
+        assert_eq!(reader.token(), b"+5");
+        assert_eq!(reader.usize(), 0);
+        assert_eq!(reader.i64(), 0);
+        assert_eq!(reader.i64(), std::i64::MIN);
+        assert_eq!(reader.token(), b"abc");
+        assert_eq!(reader.line(), b"");
+        assert_eq!(reader.line(), b" de f");
+        assert_eq!(reader.line(), b"last");
+        assert_eq!(reader.token(), b"");
+    }
+
+    #[test]
+    #[should_panic(expected = "reading u64: cannot parse `-1` as u64")]
+    fn test_fast_reader_negative_unsigned() {
+        FastReader::new(&b"-1"[..]).u64();
+    }
+
+    #[test]
+    #[should_panic(expected = "reading i64: input ended")]
+    fn test_fast_reader_ended() {
+        FastReader::new(&b" \n"[..]).i64();
+    }
+
+    #[test]
+    fn test_fast_reader_bounds() {
+        let mut reader = FastReader::new(&b"18446744073709551615 9223372036854775807 -9223372036854775808"[..]);
+        assert_eq!(reader.u64(), std::u64::MAX);
+        assert_eq!(reader.i64(), std::i64::MAX);
+        assert_eq!(reader.i64(), std::i64::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "reading u64: cannot parse `18446744073709551616` as u64")]
+    fn test_fast_reader_overflow_unsigned() {
+        FastReader::new(&b"18446744073709551616"[..]).u64();
+    }
+
+    #[test]
+    #[should_panic(expected = "reading i64: cannot parse `9223372036854775808` as i64")]
+    fn test_fast_reader_overflow_signed() {
+        FastReader::new(&b"9223372036854775808"[..]).i64();
+    }
+
+    #[test]
+    #[should_panic(expected = "reading i64: cannot parse `-` as i64")]
+    fn test_fast_reader_sign_only() {
+        FastReader::new(&b"-"[..]).i64();
+    }
+
+    #[test]
+    fn test_fast_reader_large() {
+        let n = 1_000_000i64;
+        let mut input = Vec::new();
+        input.extend_from_slice(n.to_string().as_bytes());
+        for i in 0..n {
+            input.extend_from_slice(if i % 80 == 0 { b"\r\n" } else { b"  " });
+            let x = if i % 2 == 0 { i * 1000 } else { -i * 1000 };
+            input.extend_from_slice(x.to_string().as_bytes());
+        }
+
+        // Reads through a small buffer so that words are split across refills.
+        let mut reader = FastReader::new(std::io::BufReader::with_capacity(7, &input[..]));
+        let n = reader.usize();
+        let sum: i64 = (0..n).map(|_| reader.i64()).sum();
+        assert_eq!(sum, -500_000_000);
+        assert_eq!(reader.token(), b"");
+    }
+
+    #[test]
+    fn test_interactor_scripted() {
+        let replies: &[u8] = b"3\n10 20\nYes\n";
+        let mut io = Interactor::new(replies, Vec::new());
+        let n = io.read::<usize>();
+        assert_eq!(io.ask::<(u32, u32)>(format_args!("? {}", n)), (10, 20));
+        assert_eq!(io.ask::<String>("? 4"), "Yes");
+        io.answer(format_args!("! {} {}", 1, 2));
+        let (_, queries) = io.into_inner();
+        assert_eq!(queries, b"? 3\n? 4\n! 1 2\n".to_vec());
+    }
+
+    #[test]
+    #[should_panic(expected = "judge closed the input")]
+    fn test_interactor_closed() {
+        Interactor::new(&b""[..], Vec::new()).ask::<u32>("? 1");
+    }
+
+    // Judge of a guessing game, replying to each query line as soon as it is flushed.
+    struct Judge {
+        secret: u32,
+        query: Vec<u8>,
+        replies: std::collections::VecDeque<u8>,
+        query_count: usize
+    }
+
+    struct JudgeReader(std::rc::Rc<std::cell::RefCell<Judge>>);
+    struct JudgeWriter(std::rc::Rc<std::cell::RefCell<Judge>>);
+
+    impl std::io::Read for JudgeReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            std::io::Read::read(&mut self.0.borrow_mut().replies, buf)
+        }
+    }
+
+    impl std::io::Write for JudgeWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().query.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            let mut judge = self.0.borrow_mut();
+            let query = String::from_utf8(std::mem::replace(&mut judge.query, Vec::new())).unwrap();
+            let guess: u32 = query.trim_start_matches("? ").trim_end().parse().unwrap();
+            let reply = match judge.secret.cmp(&guess) {
+                std::cmp::Ordering::Less => b"<\n",
+                std::cmp::Ordering::Greater => b">\n",
+                std::cmp::Ordering::Equal => b"=\n"
+            };
+            judge.replies.extend(reply.iter());
+            judge.query_count += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_interactor_binary_search() {
+        let n = 1000;
+        for secret in 1..n+1 {
+            let judge = std::rc::Rc::new(std::cell::RefCell::new(Judge {
+                secret,
+                query: Vec::new(),
+                replies: std::collections::VecDeque::new(),
+                query_count: 0
+            }));
+            let reader = std::io::BufReader::new(JudgeReader(judge.clone()));
+            let mut io = Interactor::new(reader, JudgeWriter(judge.clone()));
+
+            let (mut lo, mut hi) = (1, n);
+            let guess = loop {
+                let mid = (lo + hi) / 2;
+                match io.ask::<char>(format_args!("? {}", mid)) {
+                    '<' => hi = mid - 1,
+                    '>' => lo = mid + 1,
+                    _ => break mid
+                }
+            };
+            assert_eq!(guess, secret);
+            assert!(judge.borrow().query_count <= 10);
+        }
+    }
+
+    #[test]
+    fn test_set_source_for_test() {
+        set_source_for_test("3 x\n1 2 3\n\n4\n5 6\n#.\n.#\n7 8\n9\n10 11\nrest of line\n12\n");
+        read!(n = usize, c = char);
+        assert_eq!((n, c), (3, 'x'));
+        read!(v = Vec<Usize1>);
+        assert_eq!(v, vec![Usize1(0), Usize1(1), Usize1(2)]);
+        read!();
+        read_chunk!(a = i32, (b, d) = (i32, i32));
+        assert_eq!((a, b, d), (4, 5, 6));
+        assert_eq!(read_grid(2), vec![b"#.".to_vec(), b".#".to_vec()]);
+        assert_eq!(read_lines::<Vec<u32>>().take(2).collect::<Vec<_>>(), vec![vec![7, 8], vec![9]]);
+        {
+            let mut reader = FastReader::from_stdin();
+            assert_eq!(reader.u64() + reader.u64(), 21);
+            assert_eq!(reader.line(), b"");
+        }
+        assert_eq!(read::<Vec<String>>(), vec!["rest", "of", "line"]);
+        assert_eq!(Tokens::from_stdin().next::<u32>(), 12);
+        assert_eq!(read_all_lines::<u32>(), Vec::new());
+
+        set_source_for_test("1\n2");
+        assert_eq!(read_all_words::<u32>(), vec![1, 2]);
+        clear_source_for_test();
+    }
+
+    #[test]
+    fn test_in_bounds() {
+        assert_eq!(5usize.find_out_of_bounds(&(1..=5)), None);
+        assert_eq!(6usize.find_out_of_bounds(&(1..=5)), Some("6".to_string()));
+        assert_eq!(vec![1i64, -3, 2].find_out_of_bounds(&(-2..)), Some("-3".to_string()));
+        assert_eq!(vec![(1u32, 1u32)].find_out_of_bounds(&(..2)), None);
+        assert_eq!(('b', 'z').find_out_of_bounds(&('a'..='y')), Some("'z'".to_string()));
+    }
+
+    #[test]
+    fn test_read_assert_accepted() {
+        set_source_for_test("3 5\n\n1 2 3\n");
+        read_assert!(n = usize => 1..=200_000, k = usize => n..);
+        read_assert!(v = Vec<(u8, u8)> => ..=k as u8);
+        assert_eq!((n, k), (3, 5));
+        assert_eq!(v, vec![]);
+        read_assert!(v = Vec<u8> => 1..4);
+        assert_eq!(v, vec![1, 2, 3]);
+        clear_source_for_test();
+    }
+
+    #[test]
+    #[cfg(local)]
+    #[should_panic(expected = "k = 5 is out of bounds 1..=n")]
+    fn test_read_assert_rejected() {
+        set_source_for_test("3 5\n");
+        read_assert!(n = usize => 1..=200_000, k = usize => 1..=n);
+    }
+
+    #[test]
+    #[cfg(not(local))]
+    fn test_read_assert_unchecked() {
+        set_source_for_test("3 5\n");
+        read_assert!(n = usize => 1..=200_000, k = usize => 1..=n);
+        assert_eq!((n, k), (3, 5));
+        clear_source_for_test();
+    }
+
+    #[test]
+    fn test_read_grid() {
+        let mut input: &[u8] = b"#.#\n..#\r\n###\nrest\n";
+        let grid = read_grid_from_handle(&mut input, 3).unwrap();
+        assert_eq!(grid, vec![b"#.#".to_vec(), b"..#".to_vec(), b"###".to_vec()]);
+        // The following lines are left unread.
+        assert_eq!(input, b"rest\n");
+
+        let mut input: &[u8] = b"";
+        assert_eq!(read_grid_from_handle(&mut input, 0), Ok(Vec::new()));
+        let mut input: &[u8] = b"..\n..\n";
+        assert!(read_grid_from_handle(&mut input, 3).is_err());
+        let mut input: &[u8] = b"..\n...\n";
+        assert!(read_grid_from_handle(&mut input, 2).is_err());
+    }
+
+    #[test]
+    fn test_read_digit_grid() {
+        let mut input: &[u8] = b"0123\n4567\n8900\n";
+        let grid = read_digit_grid_from_handle(&mut input, 3).unwrap();
+        assert_eq!(grid, vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9, 0, 0]]);
+        let mut input: &[u8] = b"01\n2a\n";
+        assert!(read_digit_grid_from_handle(&mut input, 2).is_err());
+    }
+
     #[test]
     fn test_words() {
         let words: Vec<&str> = "1 2".split_whitespace().collect();
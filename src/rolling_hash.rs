@@ -2,8 +2,11 @@
 // Example: ABC135 F
 
 use crate::iter::IteratorExt;
+use crate::xorshift::Rng;
+use crate::range::UsizeRangeBoundsExt;
+use crate::bsearch::partition_point_usize;
 
-// BEGIN SNIPPET rolling_hash DEPENDS ON iter
+// BEGIN SNIPPET rolling_hash DEPENDS ON iter xorshift range bsearch
 
 /// Unsigned integer type for rolling hash.
 pub type RollingHashBase = u64;
@@ -11,50 +14,111 @@ pub type RollingHashBase = u64;
 mod rolling_hash_internal {
     use super::RollingHashBase;
 
-    // Prime numbers less than 2^32
+    // 2^61 - 1, a Mersenne prime.
+    pub const MOD61: RollingHashBase = (1 << 61) - 1;
+    pub const ROLL61: RollingHashBase = 1_548_419_651_217_049_381;
+
+    // Prime numbers less than 2^32, used as the 2nd modulus of double hashes.
     pub const ROLL: RollingHashBase = 1154491469;
     pub const MOD: RollingHashBase = 4290357497;
 
+    pub fn mul61(a: RollingHashBase, b: RollingHashBase) -> RollingHashBase {
+        let t = a as u128 * b as u128;
+        // 2^61 = 1 (mod `MOD61`).
+        let t = (t >> 61) as RollingHashBase + (t as RollingHashBase & MOD61);
+        if t >= MOD61 { t - MOD61 } else { t }
+    }
+
+    // Computes `a + b` modulo `MOD61` for `a, b < MOD61`.
+    pub fn add61(a: RollingHashBase, b: RollingHashBase) -> RollingHashBase {
+        let t = a + b;
+        if t >= MOD61 { t - MOD61 } else { t }
+    }
+
     pub fn pow(base: RollingHashBase, exp: RollingHashBase) -> RollingHashBase {
         if exp == 0 { 1 } else {
             let pow_half = pow(base, exp/2);
             if exp % 2 == 0 {
-                (pow_half * pow_half) % MOD
+                mul61(pow_half, pow_half)
             } else {
-                (base * pow_half % MOD) * pow_half % MOD
+                mul61(mul61(base, pow_half), pow_half)
             }
         }
     }
 }
 
+/// Picks a base for [`RollingHash`](trait.RollingHash.html) uniformly at random,
+/// avoiding values close to 0 or the modulus.
+///
+/// Hashes computed with a random base are robust against inputs
+/// made to collide hashes with a fixed base.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::rolling_hash::*;
+/// use atcoder_snippets::xorshift::Xorshift;
+///
+/// let base = random_rolling_hash_base(&mut Xorshift::new());
+/// let haystack = [1, 2, 3, 1, 2].prefix_rolling_hash_with_base(base);
+/// let needle = [1, 2].rolling_hash_with_base(base);
+/// assert_eq!(haystack.matches(needle).collect::<Vec<_>>(), vec![0, 3]);
+/// ```
+pub fn random_rolling_hash_base(rng: &mut impl Rng) -> RollingHashBase {
+    use self::rolling_hash_internal::*;
+    rng.next_u64() % (MOD61 - 512) + 256
+}
+
 /// A rolling-hashable sequence.
+///
+/// Hashes are computed modulo 2^61-1.
+/// No single hash modulo the former prime less than 2^32 is offered,
+/// because hashes of the 10^5 or so substrings of a typical input are likely to collide
+/// modulo such a small prime.
+/// The prime is still used as the second modulus of
+/// [`DoubleRollingHash`](struct.DoubleRollingHash.html).
 pub trait RollingHash {
-    fn rolling_hash(&self) -> RollingHashValue<Self>;
-    fn prefix_rolling_hash(&self) -> PrefixRollingHash<Self>;
+    /// Computes the hash with `base`, which must be less than 2^61-1.
+    fn rolling_hash_with_base(&self, base: RollingHashBase) -> RollingHashValue<Self>;
+
+    /// Computes the prefix hashes with `base`, which must be less than 2^61-1.
+    fn prefix_rolling_hash_with_base(&self, base: RollingHashBase) -> PrefixRollingHash<Self>;
+
+    /// Computes the hash with the fixed base.
+    fn rolling_hash(&self) -> RollingHashValue<Self> {
+        self.rolling_hash_with_base(rolling_hash_internal::ROLL61)
+    }
+
+    /// Computes the prefix hashes with the fixed base.
+    fn prefix_rolling_hash(&self) -> PrefixRollingHash<Self> {
+        self.prefix_rolling_hash_with_base(rolling_hash_internal::ROLL61)
+    }
 }
 
 impl RollingHash for [u8] {
-    fn rolling_hash(&self) -> RollingHashValue<[u8]> {
+    fn rolling_hash_with_base(&self, base: RollingHashBase) -> RollingHashValue<[u8]> {
         use self::rolling_hash_internal::*;
 
         let value = self.iter().fold(0u64, |acc, &next| {
-            (acc * ROLL + next as RollingHashBase) % MOD
+            add61(mul61(acc, base), next as RollingHashBase)
         });
         RollingHashValue {
             len: self.len(),
-            value: value,
+            base,
+            value,
             seq_type: std::marker::PhantomData
         }
     }
 
-    fn prefix_rolling_hash(&self) -> PrefixRollingHash<[u8]> {
+    fn prefix_rolling_hash_with_base(&self, base: RollingHashBase) -> PrefixRollingHash<[u8]> {
         use self::rolling_hash_internal::*;
 
         let prefix_hash = self.iter().lscan(0 as RollingHashBase, |&acc, &next| {
-            (acc * ROLL + next as RollingHashBase) % MOD
+            add61(mul61(acc, base), next as RollingHashBase)
         }).collect() ;
         PrefixRollingHash {
-            prefix_hash: prefix_hash,
+            prefix_hash,
+            base,
             seq_type: std::marker::PhantomData
         }
     }
@@ -63,6 +127,7 @@ impl RollingHash for [u8] {
 /// A rolling hash of a sequence.
 pub struct RollingHashValue<T: ?Sized + RollingHash> {
     len: usize,
+    base: RollingHashBase,
     value: RollingHashBase,
     seq_type: std::marker::PhantomData<T>
 }
@@ -72,6 +137,7 @@ impl<T: ?Sized + RollingHash> Clone for RollingHashValue<T> {
     fn clone(&self) -> Self {
         RollingHashValue {
             len: self.len,
+            base: self.base,
             value: self.value,
             seq_type: self.seq_type.clone()
         }
@@ -91,11 +157,17 @@ impl<T: ?Sized + RollingHash> RollingHashValue<T> {
     pub fn value(&self) -> RollingHashBase {
         self.value
     }
+
+    /// Base used for the hash.
+    pub fn base(&self) -> RollingHashBase {
+        self.base
+    }
 }
 
 /// Sliding rolling hashes of a sequence.
 pub struct PrefixRollingHash<T: ?Sized + RollingHash> {
     prefix_hash: Vec<RollingHashBase>,
+    base: RollingHashBase,
     seq_type: std::marker::PhantomData<T>
 }
 
@@ -105,11 +177,20 @@ impl<T: ?Sized + RollingHash> PrefixRollingHash<T> {
         self.prefix_hash.len() - 1
     }
 
+    /// Base used for the hashes.
+    pub fn base(&self) -> RollingHashBase {
+        self.base
+    }
+
     /// Checks if the subsequence starting from `index` matches `pattern`.
     ///
     /// If the subsequence starting from `index` with the same length of `pattern`
     /// exceeds the range of the original sequence, returns `None`.
     ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is computed with a base different from `self`.
+    ///
     /// # Example
     ///
     /// ```
@@ -124,6 +205,7 @@ impl<T: ?Sized + RollingHash> PrefixRollingHash<T> {
     pub fn matches_at(&self, pattern: RollingHashValue<T>, index: usize) -> Option<bool> {
         use self::rolling_hash_internal::*;
 
+        assert_eq!(self.base, pattern.base(), "bases of hashes differ");
         let hash_right = match self.prefix_hash.get(index + pattern.len()) {
             Some(&hash) => hash,
             None => return None
@@ -132,8 +214,8 @@ impl<T: ?Sized + RollingHash> PrefixRollingHash<T> {
         // so the indexing never panics.
         let hash_left = self.prefix_hash[index];
 
-        let m = pow(ROLL, pattern.len() as RollingHashBase);
-        Some((hash_right + MOD - (hash_left * m % MOD)) % MOD == pattern.value())
+        let m = pow(self.base, pattern.len() as RollingHashBase);
+        Some(add61(hash_right, MOD61 - mul61(hash_left, m)) == pattern.value())
     }
 
     /// Returns an iterator yielding all indices matching `pattern`.
@@ -141,6 +223,10 @@ impl<T: ?Sized + RollingHash> PrefixRollingHash<T> {
     /// Matching is performed from left to right,
     /// so yielded indices are strictly increasing.
     ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is computed with a base different from `self`.
+    ///
     /// # Example
     ///
     /// ```
@@ -159,10 +245,11 @@ impl<T: ?Sized + RollingHash> PrefixRollingHash<T> {
     {
         use self::rolling_hash_internal::*;
 
+        assert_eq!(self.base, pattern.base(), "bases of hashes differ");
         RollingHashMatches {
             prefix_hash: self,
             pattern: pattern,
-            m: pow(ROLL, pattern.len() as RollingHashBase),
+            m: pow(self.base, pattern.len() as RollingHashBase),
             index: 0
         }
     }
@@ -188,7 +275,7 @@ impl<'a, T: ?Sized + RollingHash> Iterator for RollingHashMatches<'a, T> {
             self.index += 1;
 
             let hash_left = prefix[old_index];
-            let hash = (hash_right + MOD - (hash_left * self.m % MOD)) % MOD;
+            let hash = add61(*hash_right, MOD61 - mul61(hash_left, self.m));
             if hash == self.pattern.value() {
                 return Some(old_index)
             }
@@ -198,11 +285,924 @@ impl<'a, T: ?Sized + RollingHash> Iterator for RollingHashMatches<'a, T> {
     }
 }
 
+mod double_rolling_hash_internal {
+    use super::RollingHashBase;
+    use super::rolling_hash_internal::{MOD, MOD61, mul61};
+
+    // Bases for columns of `RollingHash2D`.
+    pub const COLUMN_ROLL61: RollingHashBase = 1_112_673_919_478_293_307;
+    pub const COLUMN_ROLL: RollingHashBase = 2_860_486_313;
+
+    pub const MODS: [RollingHashBase; 2] = [MOD61, MOD];
+
+    pub fn mul_mod(a: RollingHashBase, b: RollingHashBase, m: RollingHashBase) -> RollingHashBase {
+        if m == MOD61 { mul61(a, b) } else { a * b % m }
+    }
+
+    // Maps a token to the values added to hashes.
+    // 1 is added so that 0-valued tokens contribute to hashes.
+    pub fn token(x: RollingHashBase) -> (RollingHashBase, RollingHashBase) {
+        (x % MOD61 + 1, x % MOD + 1)
+    }
+
+    // Computes `a - b` for each modulus.
+    pub fn sub(
+        a: (RollingHashBase, RollingHashBase),
+        b: (RollingHashBase, RollingHashBase)
+    ) -> (RollingHashBase, RollingHashBase) {
+        ((a.0 + MOD61 - b.0) % MOD61, (a.1 + MOD - b.1) % MOD)
+    }
+
+    // Computes `hash * multiplier + x` for each modulus.
+    pub fn mul_add(
+        hash: (RollingHashBase, RollingHashBase),
+        multiplier: (RollingHashBase, RollingHashBase),
+        x: (RollingHashBase, RollingHashBase)
+    ) -> (RollingHashBase, RollingHashBase) {
+        let h1 = mul61(hash.0, multiplier.0) + x.0 % MOD61;
+        (
+            if h1 >= MOD61 { h1 - MOD61 } else { h1 },
+            (hash.1 * multiplier.1 + x.1 % MOD) % MOD
+        )
+    }
+}
+
+/// A hash value of a sequence given by [`DoubleRollingHash`](struct.DoubleRollingHash.html).
+///
+/// Two hash values are compared for equality,
+/// which implies the equality of the sequences with very high probability.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct HashValue {
+    len: usize,
+    value: (RollingHashBase, RollingHashBase)
+}
+
+impl HashValue {
+    /// Length of the original sequence.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the original sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// A hash value of a sequence given by [`Mod61Hash`](struct.Mod61Hash.html).
+///
+/// Two hash values are compared for equality,
+/// which implies the equality of the sequences with high probability.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Mod61HashValue {
+    len: usize,
+    value: RollingHashBase
+}
+
+impl Mod61HashValue {
+    /// Length of the original sequence.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the original sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Rolling hashes of all the subsequences of a sequence, computed modulo 2^61-1.
+///
+/// The modulus and the fixed base are the same as [`RollingHash`](trait.RollingHash.html),
+/// but unlike `RollingHash`, hashes of any subsequences are given by indices
+/// and tokens are not limited to bytes.
+/// For the strongest guarantee, use [`DoubleRollingHash`](struct.DoubleRollingHash.html).
+///
+/// Tokens are mapped as `DoubleRollingHash` does.
+/// Hashes computed with different bases are not comparable.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::rolling_hash::*;
+/// let hash = Mod61Hash::new(b"abcabd");
+/// assert_eq!(hash.hash(0..2), hash.hash(3..5));
+/// assert_ne!(hash.hash(0..3), hash.hash(3..6));
+///
+/// let pattern = Mod61Hash::with_base(b"ab", hash.base());
+/// assert_eq!(hash.matches(&pattern).collect::<Vec<_>>(), vec![0, 3]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Mod61Hash {
+    base: RollingHashBase,
+    prefix_hash: Vec<RollingHashBase>,
+    pow: Vec<RollingHashBase>
+}
+
+impl Mod61Hash {
+    /// Computes the hashes of `seq` with the fixed base in Θ(`seq.len()`) time.
+    pub fn new<T: Copy + Into<RollingHashBase>>(seq: &[T]) -> Mod61Hash {
+        use self::rolling_hash_internal::*;
+
+        Mod61Hash::with_base(seq, ROLL61)
+    }
+
+    /// Computes the hashes of `seq` with a base picked by
+    /// [`random_rolling_hash_base`](fn.random_rolling_hash_base.html).
+    pub fn with_random_base<T>(seq: &[T], rng: &mut impl Rng) -> Mod61Hash
+    where
+        T: Copy + Into<RollingHashBase>
+    {
+        Mod61Hash::with_base(seq, random_rolling_hash_base(rng))
+    }
+
+    /// Computes the hashes of `seq` with `base`, which must be less than 2^61-1.
+    pub fn with_base<T: Copy + Into<RollingHashBase>>(seq: &[T], base: RollingHashBase) -> Mod61Hash {
+        use self::rolling_hash_internal::*;
+        use self::double_rolling_hash_internal::*;
+
+        let mut prefix_hash = Vec::with_capacity(seq.len() + 1);
+        let mut pow = Vec::with_capacity(seq.len() + 1);
+        prefix_hash.push(0);
+        pow.push(1);
+        for &x in seq {
+            let h = mul61(*prefix_hash.last().unwrap(), base) + token(x.into()).0;
+            prefix_hash.push(if h >= MOD61 { h - MOD61 } else { h });
+            let p = mul61(*pow.last().unwrap(), base);
+            pow.push(p);
+        }
+
+        Mod61Hash { base, prefix_hash, pow }
+    }
+
+    /// Base used for the hashes.
+    pub fn base(&self) -> RollingHashBase {
+        self.base
+    }
+
+    /// Length of the original sequence.
+    pub fn len(&self) -> usize {
+        self.prefix_hash.len() - 1
+    }
+
+    /// Returns whether the original sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the hash of `seq[range]` in constant time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of the original sequence.
+    pub fn hash(&self, range: std::ops::Range<usize>) -> Mod61HashValue {
+        use self::rolling_hash_internal::*;
+
+        assert!(range.start <= range.end && range.end <= self.len(), "range is out of the sequence");
+        let left = mul61(self.prefix_hash[range.start], self.pow[range.end - range.start]);
+        let h = self.prefix_hash[range.end] + MOD61 - left;
+        Mod61HashValue {
+            len: range.end - range.start,
+            value: if h >= MOD61 { h - MOD61 } else { h }
+        }
+    }
+
+    /// Returns the hash of the whole sequence.
+    pub fn whole_hash(&self) -> Mod61HashValue {
+        self.hash(0..self.len())
+    }
+
+    /// Checks if the subsequence starting from `index` matches `pattern`.
+    ///
+    /// If the subsequence starting from `index` with the same length of `pattern`
+    /// exceeds the range of the original sequence, returns `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is computed with a base different from `self`.
+    pub fn matches_at(&self, pattern: &Mod61Hash, index: usize) -> Option<bool> {
+        assert_eq!(self.base, pattern.base, "bases of hashes differ");
+        if index + pattern.len() <= self.len() {
+            Some(self.hash(index..index + pattern.len()) == pattern.whole_hash())
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator yielding all indices matching `pattern`, in increasing order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is computed with a base different from `self`.
+    pub fn matches<'a>(&'a self, pattern: &Mod61Hash) -> impl Iterator<Item=usize> + 'a {
+        assert_eq!(self.base, pattern.base, "bases of hashes differ");
+        let pattern_hash = pattern.whole_hash();
+        (0..(self.len() + 1).saturating_sub(pattern_hash.len()))
+            .filter(move |&i| self.hash(i..i + pattern_hash.len()) == pattern_hash)
+    }
+}
+
+/// Rolling hashes of all the subsequences of a sequence,
+/// computed with two independent moduli, 2^61-1 and a prime less than 2^32.
+///
+/// Hashes computed with different bases are not comparable.
+/// Use `with_bases(seq, other.bases())` to hash another sequence
+/// with the same bases as `other`.
+///
+/// Collisions are less likely than [`RollingHash`](trait.RollingHash.html)
+/// and [`Mod61Hash`](struct.Mod61Hash.html) with a single modulus.
+///
+/// Tokens of any type convertible into `u64` can be hashed.
+/// Each token `x` is reduced modulo each modulus and then added 1,
+/// so that 0-valued tokens do not vanish from hashes.
+/// Use [`from_iter_with`](#method.from_iter_with) to hash other types.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::rolling_hash::*;
+/// let hash = DoubleRollingHash::new(b"abcabd");
+/// assert_eq!(hash.hash(0..2), hash.hash(3..5));
+/// assert_ne!(hash.hash(0..3), hash.hash(3..6));
+///
+/// let pattern = DoubleRollingHash::new(&[97u64, 98]);
+/// assert_eq!(hash.hash(3..5), pattern.whole_hash());
+/// assert_eq!(hash.matches(&pattern).collect::<Vec<_>>(), vec![0, 3]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DoubleRollingHash {
+    bases: (RollingHashBase, RollingHashBase),
+    // Tokens converted into `RollingHashBase`, used to compare sequences.
+    tokens: Vec<RollingHashBase>,
+    prefix_hash: Vec<(RollingHashBase, RollingHashBase)>,
+    pow: Vec<(RollingHashBase, RollingHashBase)>
+}
+
+impl DoubleRollingHash {
+    /// Computes the hashes of `seq` with the fixed bases in Θ(`seq.len()`) time.
+    pub fn new<T: Copy + Into<RollingHashBase>>(seq: &[T]) -> DoubleRollingHash {
+        use self::rolling_hash_internal::*;
+
+        DoubleRollingHash::with_bases(seq, (ROLL61, ROLL))
+    }
+
+    /// Computes the hashes of the tokens in `iter` mapped by `f`, with the fixed bases.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::rolling_hash::*;
+    /// let colors = vec![(1u32, 2u32), (3, 4), (1, 2)];
+    /// let hash = DoubleRollingHash::from_iter_with(&colors, |&&(r, g)| (r as u64) << 32 | g as u64);
+    /// assert_eq!(hash.hash(0..1), hash.hash(2..3));
+    /// ```
+    pub fn from_iter_with<I, F>(iter: I, f: F) -> DoubleRollingHash
+    where
+        I: IntoIterator,
+        F: Fn(&I::Item) -> RollingHashBase
+    {
+        let tokens: Vec<RollingHashBase> = iter.into_iter().map(|x| f(&x)).collect();
+        DoubleRollingHash::new(&tokens)
+    }
+
+    /// Computes the hashes of `seq` with bases picked uniformly at random,
+    /// avoiding values close to 0 or the moduli.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::rolling_hash::*;
+    /// use atcoder_snippets::xorshift::Xorshift;
+    ///
+    /// let text = DoubleRollingHash::with_random_base(b"abcab", &mut Xorshift::new());
+    /// let pattern = DoubleRollingHash::with_bases(b"ab", text.bases());
+    /// assert_eq!(text.matches(&pattern).collect::<Vec<_>>(), vec![0, 3]);
+    /// ```
+    pub fn with_random_base<T>(seq: &[T], rng: &mut impl Rng) -> DoubleRollingHash
+    where
+        T: Copy + Into<RollingHashBase>
+    {
+        use self::rolling_hash_internal::*;
+
+        let base61 = random_rolling_hash_base(rng);
+        DoubleRollingHash::with_bases(seq, (base61, rng.next_u64() % (MOD - 512) + 256))
+    }
+
+    /// Computes the hashes of `seq` with `bases`,
+    /// where the 0th is for the modulus 2^61-1 and the 1st is less than 2^32.
+    pub fn with_bases<T>(seq: &[T], bases: (RollingHashBase, RollingHashBase)) -> DoubleRollingHash
+    where
+        T: Copy + Into<RollingHashBase>
+    {
+        use self::double_rolling_hash_internal::*;
+
+        let tokens: Vec<RollingHashBase> = seq.iter().map(|&x| x.into()).collect();
+        let mut prefix_hash = Vec::with_capacity(seq.len() + 1);
+        let mut pow = Vec::with_capacity(seq.len() + 1);
+        prefix_hash.push((0, 0));
+        pow.push((1, 1));
+        for &x in &tokens {
+            let hash = mul_add(*prefix_hash.last().unwrap(), bases, token(x));
+            prefix_hash.push(hash);
+            let p = mul_add(*pow.last().unwrap(), bases, (0, 0));
+            pow.push(p);
+        }
+
+        DoubleRollingHash { bases, tokens, prefix_hash, pow }
+    }
+
+    /// Bases used for the hashes.
+    pub fn bases(&self) -> (RollingHashBase, RollingHashBase) {
+        self.bases
+    }
+
+    /// Length of the original sequence.
+    pub fn len(&self) -> usize {
+        self.prefix_hash.len() - 1
+    }
+
+    /// Returns whether the original sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the hash of `seq[range]` in constant time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of the original sequence.
+    pub fn hash(&self, range: std::ops::Range<usize>) -> HashValue {
+        use self::rolling_hash_internal::*;
+
+        assert!(range.start <= range.end && range.end <= self.len(), "range is out of the sequence");
+        let (l1, l2) = self.prefix_hash[range.start];
+        let (r1, r2) = self.prefix_hash[range.end];
+        let (p1, p2) = self.pow[range.end - range.start];
+        let h1 = r1 + MOD61 - mul61(l1, p1);
+        HashValue {
+            len: range.end - range.start,
+            value: (
+                if h1 >= MOD61 { h1 - MOD61 } else { h1 },
+                (r2 + MOD - l2 * p2 % MOD) % MOD
+            )
+        }
+    }
+
+    /// Returns the hash of the whole sequence.
+    pub fn whole_hash(&self) -> HashValue {
+        self.hash(0..self.len())
+    }
+
+    /// Checks if the subsequence starting from `index` matches `pattern`.
+    ///
+    /// If the subsequence starting from `index` with the same length of `pattern`
+    /// exceeds the range of the original sequence, returns `None`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is computed with bases different from `self`.
+    pub fn matches_at(&self, pattern: &DoubleRollingHash, index: usize) -> Option<bool> {
+        assert_eq!(self.bases, pattern.bases, "bases of hashes differ");
+        if index + pattern.len() <= self.len() {
+            Some(self.hash(index..index + pattern.len()) == pattern.whole_hash())
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator yielding all indices matching `pattern`, in increasing order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pattern` is computed with bases different from `self`.
+    pub fn matches<'a>(&'a self, pattern: &DoubleRollingHash) -> impl Iterator<Item=usize> + 'a {
+        assert_eq!(self.bases, pattern.bases, "bases of hashes differ");
+        let pattern_hash = pattern.whole_hash();
+        (0..(self.len() + 1).saturating_sub(pattern_hash.len()))
+            .filter(move |&i| self.hash(i..i + pattern_hash.len()) == pattern_hash)
+    }
+
+    /// Returns the length of the longest common prefix of
+    /// the suffix of `self` starting at `i` and the suffix of `other` starting at `j`.
+    ///
+    /// It takes O(log(*n*)) time, as *n* is the length of the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is out of the sequence (the length is allowed),
+    /// or `other` is computed with bases different from `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::rolling_hash::*;
+    /// let s = DoubleRollingHash::new(b"abcab");
+    /// let t = DoubleRollingHash::new(b"cabd");
+    /// assert_eq!(s.lcp_with(&t, 2, 0), 3);
+    /// assert_eq!(s.lcp(0, 3), 2);
+    /// ```
+    pub fn lcp_with(&self, other: &DoubleRollingHash, i: usize, j: usize) -> usize {
+        assert_eq!(self.bases, other.bases, "bases of hashes differ");
+        assert!(i <= self.len() && j <= other.len(), "index is out of the sequence");
+        let max_len = std::cmp::min(self.len() - i, other.len() - j);
+        partition_point_usize(1..max_len+1, |len| self.hash(i..i+len) != other.hash(j..j+len)) - 1
+    }
+
+    /// Returns the length of the longest common prefix of the suffixes starting at `i` and `j`.
+    ///
+    /// See [`lcp_with`](#method.lcp_with).
+    pub fn lcp(&self, i: usize, j: usize) -> usize {
+        self.lcp_with(self, i, j)
+    }
+
+    /// Compares `self[r1]` and `other[r2]` lexicographically in O(log(*n*)) time.
+    ///
+    /// Tokens are compared as `u64` values they are converted into.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ranges are out of the sequences,
+    /// or `other` is computed with bases different from `self`.
+    pub fn compare_substrings_with(
+        &self, other: &DoubleRollingHash, r1: std::ops::Range<usize>, r2: std::ops::Range<usize>
+    ) -> std::cmp::Ordering {
+        assert!(r1.start <= r1.end && r1.end <= self.len(), "range is out of the sequence");
+        assert!(r2.start <= r2.end && r2.end <= other.len(), "range is out of the sequence");
+        let (len1, len2) = (r1.end - r1.start, r2.end - r2.start);
+        let lcp = self.lcp_with(other, r1.start, r2.start);
+        if lcp >= std::cmp::min(len1, len2) {
+            len1.cmp(&len2)
+        } else {
+            self.tokens[r1.start + lcp].cmp(&other.tokens[r2.start + lcp])
+        }
+    }
+
+    /// Compares `seq[r1]` and `seq[r2]` lexicographically in O(log(*n*)) time.
+    ///
+    /// See [`compare_substrings_with`](#method.compare_substrings_with).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::rolling_hash::*;
+    /// use std::cmp::Ordering;
+    ///
+    /// let hash = DoubleRollingHash::new(b"abcabd");
+    /// assert_eq!(hash.compare_substrings(0..3, 3..6), Ordering::Less);
+    /// assert_eq!(hash.compare_substrings(0..2, 3..5), Ordering::Equal);
+    /// assert_eq!(hash.compare_substrings(0..2, 3..6), Ordering::Less);
+    /// ```
+    pub fn compare_substrings(
+        &self, r1: std::ops::Range<usize>, r2: std::ops::Range<usize>
+    ) -> std::cmp::Ordering {
+        self.compare_substrings_with(self, r1, r2)
+    }
+
+    /// Compares the suffixes starting at `i` and `j` lexicographically in O(log(*n*)) time.
+    pub fn compare_suffixes(&self, i: usize, j: usize) -> std::cmp::Ordering {
+        self.compare_substrings(i..self.len(), j..self.len())
+    }
+
+    /// Returns the starting indices of all the suffixes sorted lexicographically,
+    /// that is, the suffix array.
+    ///
+    /// It takes O(*n* log(*n*)^2) time, as *n* is the length of the sequence.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::rolling_hash::*;
+    /// let hash = DoubleRollingHash::new(b"banana");
+    /// assert_eq!(hash.sort_suffixes(), vec![5, 3, 1, 0, 4, 2]);
+    /// ```
+    pub fn sort_suffixes(&self) -> Vec<usize> {
+        let mut suffixes: Vec<usize> = (0..self.len()).collect();
+        suffixes.sort_by(|&i, &j| self.compare_suffixes(i, j));
+        suffixes
+    }
+}
+
+/// Powers of the bases of [`DoubleRollingHash`](struct.DoubleRollingHash.html),
+/// extended on demand, to concatenate hash values.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::rolling_hash::*;
+/// let hash = DoubleRollingHash::new(b"abcde");
+/// let mut cache = HashPowCache::with_bases(hash.bases());
+/// assert_eq!(cache.concat(hash.hash(0..2), hash.hash(2..5)), hash.whole_hash());
+/// ```
+#[derive(Clone, Debug)]
+pub struct HashPowCache {
+    bases: (RollingHashBase, RollingHashBase),
+    table: Vec<(RollingHashBase, RollingHashBase)>
+}
+
+impl HashPowCache {
+    /// Creates a cache for the fixed bases used by `DoubleRollingHash::new`.
+    pub fn new() -> HashPowCache {
+        use self::rolling_hash_internal::*;
+
+        HashPowCache::with_bases((ROLL61, ROLL))
+    }
+
+    /// Creates a cache for `bases`.
+    pub fn with_bases(bases: (RollingHashBase, RollingHashBase)) -> HashPowCache {
+        HashPowCache {
+            bases,
+            table: vec![(1, 1)]
+        }
+    }
+
+    /// Returns the `n`-th powers of the bases.
+    pub fn get(&mut self, n: usize) -> (RollingHashBase, RollingHashBase) {
+        self.extend(n);
+        self.table[n]
+    }
+
+    fn extend(&mut self, max: usize) {
+        use self::double_rolling_hash_internal::*;
+
+        for i in self.table.len()..max+1 {
+            let prev = self.table[i-1];
+            self.table.push(mul_add(prev, self.bases, (0, 0)));
+        }
+    }
+
+    /// Returns the hash of the concatenation of the sequences
+    /// whose hashes are `h1` and `h2`.
+    ///
+    /// It takes amortized constant time.
+    pub fn concat(&mut self, h1: HashValue, h2: HashValue) -> HashValue {
+        use self::double_rolling_hash_internal::*;
+
+        let pow = self.get(h2.len());
+        HashValue {
+            len: h1.len() + h2.len(),
+            value: mul_add(h1.value, pow, h2.value)
+        }
+    }
+}
+
+impl Default for HashPowCache {
+    fn default() -> HashPowCache {
+        HashPowCache::new()
+    }
+}
+
+/// A hash of a sequence modified by pushing and popping items at the end.
+///
+/// The hash is the same as computed by [`DoubleRollingHash`](struct.DoubleRollingHash.html)
+/// with the same bases.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::rolling_hash::*;
+/// let mut builder = HashBuilder::new();
+/// builder.push_str(b"abc");
+/// builder.push(b'x');
+/// assert_eq!(builder.pop(), Some(b'x' as u64));
+/// assert_eq!(builder.current(), DoubleRollingHash::new(b"abc").whole_hash());
+/// ```
+#[derive(Clone, Debug)]
+pub struct HashBuilder {
+    bases: (RollingHashBase, RollingHashBase),
+    items: Vec<RollingHashBase>,
+    // `prefix_hash[i]` is the hash of `items[..i]`.
+    prefix_hash: Vec<(RollingHashBase, RollingHashBase)>
+}
+
+impl HashBuilder {
+    /// Creates a builder of the empty sequence with the fixed bases
+    /// used by `DoubleRollingHash::new`.
+    pub fn new() -> HashBuilder {
+        use self::rolling_hash_internal::*;
+
+        HashBuilder::with_bases((ROLL61, ROLL))
+    }
+
+    /// Creates a builder of the empty sequence with `bases`.
+    pub fn with_bases(bases: (RollingHashBase, RollingHashBase)) -> HashBuilder {
+        HashBuilder {
+            bases,
+            items: Vec::new(),
+            prefix_hash: vec![(0, 0)]
+        }
+    }
+
+    /// Length of the current sequence.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns whether the current sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Appends `item` in constant time.
+    pub fn push<T: Into<RollingHashBase>>(&mut self, item: T) {
+        use self::double_rolling_hash_internal::*;
+
+        let x = item.into();
+        let hash = mul_add(*self.prefix_hash.last().unwrap(), self.bases, token(x));
+        self.items.push(x);
+        self.prefix_hash.push(hash);
+    }
+
+    /// Appends all the items in `s`.
+    pub fn push_str(&mut self, s: &[u8]) {
+        for &x in s {
+            self.push(x);
+        }
+    }
+
+    /// Removes the last item and returns it, or `None` if the sequence is empty.
+    ///
+    /// It takes constant time.
+    pub fn pop(&mut self) -> Option<RollingHashBase> {
+        let item = self.items.pop()?;
+        self.prefix_hash.pop();
+        Some(item)
+    }
+
+    /// Returns the hash of the current sequence.
+    pub fn current(&self) -> HashValue {
+        HashValue {
+            len: self.len(),
+            value: *self.prefix_hash.last().unwrap()
+        }
+    }
+}
+
+impl Default for HashBuilder {
+    fn default() -> HashBuilder {
+        HashBuilder::new()
+    }
+}
+
+/// Hashes of a sliding window, in both forward and backward order,
+/// maintained under pushing items to the back and popping them from the front.
+///
+/// The forward hash is the same as computed by
+/// [`DoubleRollingHash`](struct.DoubleRollingHash.html) with the same bases.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::rolling_hash::*;
+/// let mut window = WindowHash::new();
+/// for &c in b"xabcba" {
+///     window.push_back(c);
+/// }
+/// assert!(!window.is_palindrome());
+/// assert_eq!(window.pop_front(), Some(b'x' as u64));
+/// assert!(window.is_palindrome());
+/// assert_eq!(window.hash(), DoubleRollingHash::new(b"abcba").whole_hash());
+/// ```
+#[derive(Clone, Debug)]
+pub struct WindowHash {
+    pow: HashPowCache,
+    window: std::collections::VecDeque<RollingHashBase>,
+    // Hash of `window` in the forward order.
+    forward: (RollingHashBase, RollingHashBase),
+    // Hash of `window` in the backward order, multiplied by `base^popped`
+    // so that popping needs no inverse of the bases.
+    backward: (RollingHashBase, RollingHashBase),
+    popped: usize,
+    // `base^popped`.
+    popped_pow: (RollingHashBase, RollingHashBase)
+}
+
+impl WindowHash {
+    /// Creates an empty window with the fixed bases used by `DoubleRollingHash::new`.
+    pub fn new() -> WindowHash {
+        WindowHash::from_pow_cache(HashPowCache::new())
+    }
+
+    /// Creates an empty window with `bases`.
+    pub fn with_bases(bases: (RollingHashBase, RollingHashBase)) -> WindowHash {
+        WindowHash::from_pow_cache(HashPowCache::with_bases(bases))
+    }
+
+    fn from_pow_cache(pow: HashPowCache) -> WindowHash {
+        WindowHash {
+            pow,
+            window: std::collections::VecDeque::new(),
+            forward: (0, 0),
+            backward: (0, 0),
+            popped: 0,
+            popped_pow: (1, 1)
+        }
+    }
+
+    /// Length of the window.
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Returns whether the window is empty.
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Appends `item` to the back of the window in amortized constant time.
+    pub fn push_back<T: Into<RollingHashBase>>(&mut self, item: T) {
+        use self::double_rolling_hash_internal::*;
+
+        let x = item.into();
+        let pow = self.pow.get(self.popped + self.len());
+        self.forward = mul_add(self.forward, self.pow.bases, token(x));
+        self.backward = mul_add(token(x), pow, self.backward);
+        self.window.push_back(x);
+    }
+
+    /// Removes the front item of the window and returns it,
+    /// or `None` if the window is empty.
+    ///
+    /// It takes amortized constant time.
+    pub fn pop_front(&mut self) -> Option<RollingHashBase> {
+        use self::double_rolling_hash_internal::*;
+
+        let x = self.window.pop_front()?;
+        let forward_pow = self.pow.get(self.len());
+        self.forward = sub(self.forward, mul_add(token(x), forward_pow, (0, 0)));
+        self.backward = sub(self.backward, mul_add(token(x), self.popped_pow, (0, 0)));
+        self.popped += 1;
+        self.popped_pow = mul_add(self.popped_pow, self.pow.bases, (0, 0));
+        Some(x)
+    }
+
+    /// Returns the hash of the window.
+    pub fn hash(&self) -> HashValue {
+        HashValue {
+            len: self.len(),
+            value: self.forward
+        }
+    }
+
+    /// Checks if the window is a palindrome in constant time.
+    pub fn is_palindrome(&self) -> bool {
+        use self::double_rolling_hash_internal::*;
+
+        mul_add(self.forward, self.popped_pow, (0, 0)) == self.backward
+    }
+}
+
+impl Default for WindowHash {
+    fn default() -> WindowHash {
+        WindowHash::new()
+    }
+}
+
+/// Rolling hashes of all the rectangles in a grid,
+/// computed with two independent moduli as [`DoubleRollingHash`](struct.DoubleRollingHash.html).
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::rolling_hash::*;
+/// let grid = vec![
+///     b"abab".to_vec(),
+///     b"baba".to_vec(),
+///     b"abab".to_vec()
+/// ];
+/// let hash = RollingHash2D::new(&grid);
+/// assert_eq!(hash.hash_rect(0..2, 0..2), hash.hash_rect(1..3, 1..3));
+/// assert_ne!(hash.hash_rect(0..2, 0..2), hash.hash_rect(1.., 0..2));
+///
+/// let pattern = vec![b"ab".to_vec(), b"ba".to_vec()];
+/// assert_eq!(hash.find_pattern(&pattern), vec![(0, 0), (0, 2), (1, 1)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct RollingHash2D {
+    height: usize,
+    width: usize,
+    // `prefix_hash[k][i * (width + 1) + j]` is the hash of the rectangle `[0, i) x [0, j)`
+    // modulo `MODS[k]`.
+    prefix_hash: [Vec<RollingHashBase>; 2],
+    // `row_pow[k][i]` is the `i`-th power of the row base modulo `MODS[k]`.
+    row_pow: [Vec<RollingHashBase>; 2],
+    column_pow: [Vec<RollingHashBase>; 2]
+}
+
+impl RollingHash2D {
+    /// Computes the hashes of `grid` in Θ(*hw*) time,
+    /// as *h* and *w* are the height and the width of `grid`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows of `grid` have different lengths.
+    pub fn new(grid: &[Vec<u8>]) -> RollingHash2D {
+        use self::rolling_hash_internal::*;
+        use self::double_rolling_hash_internal::*;
+
+        let height = grid.len();
+        let width = grid.first().map_or(0, |row| row.len());
+        assert!(grid.iter().all(|row| row.len() == width), "rows have different lengths");
+
+        let powers = |base: RollingHashBase, m: RollingHashBase, len: usize| {
+            let mut pow = vec![1; len + 1];
+            for i in 0..len {
+                pow[i+1] = mul_mod(pow[i], base, m);
+            }
+            pow
+        };
+        let row_pow = [powers(ROLL61, MOD61, height), powers(ROLL, MOD, height)];
+        let column_pow = [powers(COLUMN_ROLL61, MOD61, width), powers(COLUMN_ROLL, MOD, width)];
+
+        let build_prefix_hash = |k: usize| {
+            let m = MODS[k];
+            let mut prefix = vec![0; (height + 1) * (width + 1)];
+            for i in 0..height {
+                // Hash of the (`i`+1)-th row from 0 to `j`.
+                let mut row_hash = 0;
+                for j in 0..width {
+                    // Shifted by 1 so that rectangles of 0s with different shapes differ.
+                    row_hash = (mul_mod(row_hash, column_pow[k][1], m) + grid[i][j] as RollingHashBase + 1) % m;
+                    let above = mul_mod(prefix[i * (width + 1) + j + 1], row_pow[k][1], m);
+                    prefix[(i + 1) * (width + 1) + j + 1] = (above + row_hash) % m;
+                }
+            }
+            prefix
+        };
+        let prefix_hash = [build_prefix_hash(0), build_prefix_hash(1)];
+
+        RollingHash2D { height, width, prefix_hash, row_pow, column_pow }
+    }
+
+    /// Returns the height and the width of the original grid.
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Returns the hash of the rectangle `rows` x `columns` in constant time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rectangle is out of the grid.
+    pub fn hash_rect<R, C>(&self, rows: R, columns: C) -> HashValue
+    where
+        R: std::ops::RangeBounds<usize>,
+        C: std::ops::RangeBounds<usize>
+    {
+        use self::double_rolling_hash_internal::*;
+
+        let rows = rows.to_range(self.height).expect("rows are out of the grid");
+        let columns = columns.to_range(self.width).expect("columns are out of the grid");
+        let at = |k: usize, i: usize, j: usize| self.prefix_hash[k][i * (self.width + 1) + j];
+        let value = |k: usize| {
+            let m = MODS[k];
+            let dy = self.row_pow[k][rows.end - rows.start];
+            let dx = self.column_pow[k][columns.end - columns.start];
+            let whole = at(k, rows.end, columns.end);
+            let top = mul_mod(at(k, rows.start, columns.end), dy, m);
+            let left = mul_mod(at(k, rows.end, columns.start), dx, m);
+            let corner = mul_mod(mul_mod(at(k, rows.start, columns.start), dy, m), dx, m);
+            (whole + 2 * m - top - left + corner) % m
+        };
+        HashValue {
+            len: (rows.end - rows.start) * (columns.end - columns.start),
+            value: (value(0), value(1))
+        }
+    }
+
+    /// Finds all occurrences of `pattern` in the grid,
+    /// and returns their top-left corners in row-major order.
+    ///
+    /// It takes Θ(*HW* + *hw*) time, as *H* x *W* and *h* x *w* are
+    /// the size of the grid and `pattern`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rows of `pattern` have different lengths.
+    pub fn find_pattern(&self, pattern: &[Vec<u8>]) -> Vec<(usize, usize)> {
+        let pattern_hash = RollingHash2D::new(pattern);
+        let (h, w) = pattern_hash.size();
+        if h > self.height || w > self.width {
+            return Vec::new();
+        }
+        let target = pattern_hash.hash_rect(.., ..);
+        let mut corners = Vec::new();
+        for i in 0..self.height - h + 1 {
+            for j in 0..self.width - w + 1 {
+                if self.hash_rect(i..i+h, j..j+w) == target {
+                    corners.push((i, j));
+                }
+            }
+        }
+        corners
+    }
+}
+
 // END SNIPPET
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::xorshift::Xorshift;
 
     #[test]
     fn test_matches_at() {
@@ -223,4 +1223,395 @@ mod tests {
         let matches: Vec<usize> = haystack.matches(needle).collect();
         assert_eq!(matches, vec![0, 3, 4]);
     }
+
+    #[test]
+    fn test_double_rolling_hash_equal_substrings() {
+        let hash = DoubleRollingHash::new(b"abcabcab");
+        assert_eq!(hash.len(), 8);
+        assert_eq!(hash.hash(0..5), hash.hash(3..8));
+        assert_eq!(hash.hash(2..2), hash.hash(5..5));
+        assert_ne!(hash.hash(0..0), hash.hash(0..1));
+
+        let zeros = DoubleRollingHash::new(&[0u64, 0, std::u64::MAX, std::u64::MAX]);
+        assert_ne!(zeros.hash(0..1), zeros.hash(0..2));
+        assert_eq!(zeros.hash(2..3), zeros.hash(3..4));
+
+        let pattern = DoubleRollingHash::new(b"cab");
+        assert_eq!(hash.matches_at(&pattern, 2), Some(true));
+        assert_eq!(hash.matches_at(&pattern, 3), Some(false));
+        assert_eq!(hash.matches_at(&pattern, 6), None);
+        assert_eq!(hash.matches(&pattern).collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(pattern.matches(&hash).count(), 0);
+    }
+
+    #[test]
+    fn test_double_rolling_hash_no_false_positive() {
+        use std::collections::HashMap;
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(947);
+
+        let n = 1000;
+        let text: Vec<u8> = (0..n).map(|_| rng.gen_range(b'a', b'c')).collect();
+        let hash = DoubleRollingHash::new(&text);
+        let mut seen: HashMap<HashValue, (usize, usize)> = HashMap::new();
+        for _ in 0..100_000 {
+            let len = rng.gen_range(0, 30);
+            let l = rng.gen_range(0, n - len + 1);
+            let l2 = rng.gen_range(0, n - len + 1);
+            assert_eq!(hash.hash(l..l+len) == hash.hash(l2..l2+len), text[l..l+len] == text[l2..l2+len]);
+
+            let &mut (l0, r0) = seen.entry(hash.hash(l..l+len)).or_insert((l, l + len));
+            assert_eq!(&text[l0..r0], &text[l..l+len]);
+        }
+    }
+
+    #[test]
+    fn test_random_base_reproducible() {
+        let base1 = random_rolling_hash_base(&mut Xorshift::with_seed(948));
+        let base2 = random_rolling_hash_base(&mut Xorshift::with_seed(948));
+        assert_eq!(base1, base2);
+        assert_eq!([1, 2, 3].rolling_hash_with_base(base1).value(),
+                   [1, 2, 3].rolling_hash_with_base(base2).value());
+
+        let hash1 = DoubleRollingHash::with_random_base(b"abc", &mut Xorshift::with_seed(948));
+        let hash2 = DoubleRollingHash::with_random_base(b"abc", &mut Xorshift::with_seed(948));
+        assert_eq!(hash1.bases(), hash2.bases());
+        assert_eq!(hash1.whole_hash(), hash2.whole_hash());
+    }
+
+    #[test]
+    fn test_random_base_substring_equality() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(948);
+        let mut xorshift = Xorshift::with_seed(948);
+
+        for _ in 0..100 {
+            let text: Vec<u8> = (0..50).map(|_| rng.gen_range(0, 2)).collect();
+            let pattern: Vec<u8> = (0..rng.gen_range(1, 4)).map(|_| rng.gen_range(0, 2)).collect();
+            let expected: Vec<usize> = (0..text.len())
+                .filter(|&i| text[i..].starts_with(&pattern))
+                .collect();
+
+            let base = random_rolling_hash_base(&mut xorshift);
+            let haystack = text.prefix_rolling_hash_with_base(base);
+            assert_eq!(haystack.base(), base);
+            let needle = pattern.rolling_hash_with_base(base);
+            assert_eq!(haystack.matches(needle).collect::<Vec<_>>(), expected);
+
+            let double = DoubleRollingHash::with_random_base(&text, &mut xorshift);
+            let double_pattern = DoubleRollingHash::with_bases(&pattern, double.bases());
+            assert_eq!(double.matches(&double_pattern).collect::<Vec<_>>(), expected);
+        }
+    }
+
+    #[test]
+    fn test_rolling_hash_modulo_mersenne_prime() {
+        let m = (1u128 << 61) - 1;
+        let base = (1u64 << 61) - 3;
+        let seq: Vec<u8> = (0..100).map(|i| (i * 37 % 256) as u8).collect();
+        let expected = seq.iter().fold(0u128, |acc, &x| (acc * base as u128 + x as u128) % m);
+        assert_eq!(seq.rolling_hash_with_base(base).value() as u128, expected);
+
+        let haystack = seq.prefix_rolling_hash_with_base(base);
+        assert_eq!(haystack.matches(seq[40..60].rolling_hash_with_base(base)).collect::<Vec<_>>(), vec![40]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_matches_different_bases() {
+        let haystack = [1, 2].prefix_rolling_hash_with_base(1000);
+        haystack.matches_at([1].rolling_hash(), 0);
+    }
+
+    fn brute_force_find(grid: &[Vec<u8>], pattern: &[Vec<u8>]) -> Vec<(usize, usize)> {
+        let (gh, gw) = (grid.len(), grid[0].len());
+        let (ph, pw) = (pattern.len(), pattern[0].len());
+        let mut corners = Vec::new();
+        for i in 0..(gh + 1).saturating_sub(ph) {
+            for j in 0..(gw + 1).saturating_sub(pw) {
+                if (0..ph).all(|y| grid[i+y][j..j+pw] == pattern[y][..]) {
+                    corners.push((i, j));
+                }
+            }
+        }
+        corners
+    }
+
+    #[test]
+    fn test_find_pattern_planted() {
+        let mut grid = vec![vec![b'.'; 6]; 5];
+        let pattern = vec![b"##".to_vec(), b"##".to_vec()];
+        // Overlapping occurrences and occurrences at the borders.
+        for &(i, j) in &[(0, 0), (3, 4), (1, 1), (0, 4)] {
+            for y in 0..2 {
+                for x in 0..2 {
+                    grid[i+y][j+x] = pattern[y][x];
+                }
+            }
+        }
+        let hash = RollingHash2D::new(&grid);
+        assert_eq!(hash.size(), (5, 6));
+        assert_eq!(hash.find_pattern(&pattern), brute_force_find(&grid, &pattern));
+        assert_eq!(hash.find_pattern(&pattern), vec![(0, 0), (0, 4), (1, 1), (3, 4)]);
+
+        assert_eq!(hash.find_pattern(&vec![vec![b'.'; 7]]), vec![]);
+        assert_eq!(hash.find_pattern(&vec![vec![b'.']; 6]), vec![]);
+    }
+
+    #[test]
+    fn test_hash_rect_shapes() {
+        let hash = RollingHash2D::new(&vec![vec![0; 6]; 6]);
+        assert_eq!(hash.hash_rect(0..2, 0..3), hash.hash_rect(4..6, 3..6));
+        assert_ne!(hash.hash_rect(0..2, 0..3), hash.hash_rect(0..3, 0..2));
+        assert_ne!(hash.hash_rect(0..1, 0..6), hash.hash_rect(0..6, 0..1));
+        assert_eq!(hash.hash_rect(2..2, 0..3), hash.hash_rect(0..3, 1..1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hash_rect_out_of_grid() {
+        RollingHash2D::new(&vec![vec![0; 3]; 3]).hash_rect(0..4, ..);
+    }
+
+    #[test]
+    fn test_find_pattern_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(949);
+
+        for _ in 0..200 {
+            let (gh, gw) = (rng.gen_range(1, 8), rng.gen_range(1, 8));
+            let (ph, pw) = (rng.gen_range(1, 4), rng.gen_range(1, 4));
+            let grid: Vec<Vec<u8>> = (0..gh).map(|_| (0..gw).map(|_| rng.gen_range(0, 2)).collect()).collect();
+            let pattern: Vec<Vec<u8>> = (0..ph).map(|_| (0..pw).map(|_| rng.gen_range(0, 2)).collect()).collect();
+            assert_eq!(RollingHash2D::new(&grid).find_pattern(&pattern), brute_force_find(&grid, &pattern));
+        }
+    }
+
+    #[test]
+    fn test_concat_random_splits() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(950);
+
+        let mut cache = HashPowCache::new();
+        for _ in 0..300 {
+            let text: Vec<u8> = (0..rng.gen_range(0, 30)).map(|_| rng.gen()).collect();
+            let split = rng.gen_range(0, text.len() + 1);
+            let a = DoubleRollingHash::new(&text[..split]).whole_hash();
+            let b = DoubleRollingHash::new(&text[split..]).whole_hash();
+            assert_eq!(cache.concat(a, b), DoubleRollingHash::new(&text).whole_hash());
+        }
+
+        let bases = DoubleRollingHash::with_random_base(b"", &mut Xorshift::with_seed(950)).bases();
+        let hash = DoubleRollingHash::with_bases(b"abcabc", bases);
+        let mut cache = HashPowCache::with_bases(bases);
+        assert_eq!(cache.concat(hash.hash(0..3), hash.hash(3..6)), hash.whole_hash());
+        assert_eq!(cache.concat(hash.hash(0..0), hash.hash(1..4)), hash.hash(1..4));
+    }
+
+    #[test]
+    fn test_hash_builder_round_trip() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(950);
+
+        let mut builder = HashBuilder::new();
+        let mut seq: Vec<u8> = Vec::new();
+        assert_eq!(builder.pop(), None);
+        for _ in 0..1000 {
+            if rng.gen_range(0, 3) == 0 {
+                assert_eq!(builder.pop(), seq.pop().map(|x| x as u64));
+            } else {
+                let x: u8 = rng.gen_range(0, 3);
+                builder.push(x);
+                seq.push(x);
+            }
+            assert_eq!(builder.len(), seq.len());
+            assert_eq!(builder.current(), DoubleRollingHash::new(&seq).whole_hash());
+        }
+    }
+
+    #[test]
+    fn test_hash_large_tokens() {
+        let big = 1u64 << 32;
+        // Naive truncation into `u32` makes these collide.
+        let hash1 = DoubleRollingHash::new(&[1u64, 2, 3]);
+        let hash2 = DoubleRollingHash::new(&[1 + big, 2, 3 + 5 * big]);
+        assert_ne!(hash1.whole_hash(), hash2.whole_hash());
+        assert_eq!(hash1.hash(1..2), hash2.hash(1..2));
+
+        let tokens: Vec<u64> = vec![std::u64::MAX, (1 << 61) - 1, 0, std::u64::MAX, (1 << 61) - 1, 0];
+        let hash = DoubleRollingHash::new(&tokens);
+        assert_eq!(hash.hash(0..3), hash.hash(3..6));
+        assert_ne!(hash.hash(0..1), hash.hash(1..2));
+        assert_ne!(hash.hash(1..2), hash.hash(2..3));
+
+        // 0-valued tokens are not ignored.
+        let zeros = DoubleRollingHash::new(&[0u64, 0, 1]);
+        let ones = DoubleRollingHash::new(&[0u64, 1]);
+        assert_ne!(zeros.whole_hash().value, ones.whole_hash().value);
+        assert_eq!(zeros.hash(1..3), ones.whole_hash());
+    }
+
+    #[test]
+    fn test_from_iter_with() {
+        let pairs = vec![(0u64, 1u64), (1, 0), (0, 1)];
+        let hash = DoubleRollingHash::from_iter_with(pairs.iter(), |&&(a, b)| a * 1000 + b);
+        assert_eq!(hash.hash(0..1), hash.hash(2..3));
+        assert_ne!(hash.hash(0..1), hash.hash(1..2));
+        let expected = DoubleRollingHash::new(&[1u64, 1000, 1]);
+        assert_eq!(hash.whole_hash(), expected.whole_hash());
+    }
+
+    #[test]
+    fn test_mul61_near_boundary() {
+        use super::rolling_hash_internal::*;
+
+        let reference = |a: u64, b: u64| (a as u128 * b as u128 % MOD61 as u128) as u64;
+        let values: Vec<u64> = (0..100).chain(MOD61 - 100..MOD61)
+            .chain((0..100).map(|i| (1 << 60) + i - 50))
+            .collect();
+        for &a in &values {
+            for &b in &values {
+                assert_eq!(mul61(a, b), reference(a, b), "{} * {}", a, b);
+            }
+        }
+
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(952);
+        for _ in 0..100_000 {
+            let a = rng.gen_range(0, MOD61);
+            let b = rng.gen_range(0, MOD61);
+            assert_eq!(mul61(a, b), reference(a, b));
+        }
+    }
+
+    #[test]
+    fn test_mod61_hash_near_boundary() {
+        let max = (1u64 << 61) - 2;
+        let tokens = vec![max, max - 1, 0, max, max - 1, 0, std::u64::MAX];
+        let hash = Mod61Hash::new(&tokens);
+        assert_eq!(hash.hash(0..3), hash.hash(3..6));
+        assert_ne!(hash.hash(0..2), hash.hash(1..3));
+        assert_ne!(hash.hash(5..6), hash.hash(6..7));
+    }
+
+    #[test]
+    fn test_mod61_hash_long_string() {
+        let block = b"abracadabra";
+        let text: Vec<u8> = block.iter().cycle().take(block.len() * 100_000).cloned().collect();
+        let hash = Mod61Hash::new(&text);
+        let half = text.len() / 2;
+        assert_eq!(hash.hash(0..half), hash.hash(half..text.len()));
+        assert_ne!(hash.hash(0..half), hash.hash(1..half+1));
+
+        let pattern = Mod61Hash::new(&block[..]);
+        assert_eq!(hash.matches(&pattern).count(), 100_000);
+        assert_eq!(hash.matches_at(&pattern, 11), Some(true));
+        assert_eq!(hash.matches_at(&pattern, 12), Some(false));
+        assert_eq!(hash.matches_at(&pattern, text.len() - 10), None);
+    }
+
+    #[test]
+    fn test_mod61_hash_random_base() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(952);
+        let mut xorshift = Xorshift::with_seed(952);
+
+        for _ in 0..100 {
+            let text: Vec<u8> = (0..30).map(|_| rng.gen_range(0, 2)).collect();
+            let hash = Mod61Hash::with_random_base(&text, &mut xorshift);
+            for _ in 0..100 {
+                let len = rng.gen_range(0, 10);
+                let l1 = rng.gen_range(0, text.len() - len + 1);
+                let l2 = rng.gen_range(0, text.len() - len + 1);
+                assert_eq!(hash.hash(l1..l1+len) == hash.hash(l2..l2+len), text[l1..l1+len] == text[l2..l2+len]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_suffix_comparison_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(953);
+
+        let mut texts: Vec<Vec<u8>> = vec![b"aaaaab".to_vec(), b"aaaaaa".to_vec(), b"".to_vec()];
+        for _ in 0..50 {
+            let alphabet = rng.gen_range(1, 4);
+            texts.push((0..rng.gen_range(1, 30)).map(|_| rng.gen_range(b'a', b'a' + alphabet)).collect());
+        }
+        let common_prefix = |s: &[u8], t: &[u8]| s.iter().zip(t).take_while(|(x, y)| x == y).count();
+
+        for text in &texts {
+            let n = text.len();
+            let hash = DoubleRollingHash::new(text);
+            let other_text: Vec<u8> = text.iter().rev().cloned().collect();
+            let other = DoubleRollingHash::new(&other_text);
+
+            for i in 0..n+1 {
+                for j in 0..n+1 {
+                    assert_eq!(hash.lcp(i, j), common_prefix(&text[i..], &text[j..]));
+                    assert_eq!(hash.lcp_with(&other, i, j), common_prefix(&text[i..], &other_text[j..]));
+                    assert_eq!(hash.compare_suffixes(i, j), text[i..].cmp(&text[j..]));
+                }
+            }
+            for _ in 0..100 {
+                let l1 = rng.gen_range(0, n+1);
+                let r1 = rng.gen_range(l1, n+1);
+                let l2 = rng.gen_range(0, n+1);
+                let r2 = rng.gen_range(l2, n+1);
+                assert_eq!(hash.compare_substrings(l1..r1, l2..r2), text[l1..r1].cmp(&text[l2..r2]));
+                assert_eq!(
+                    hash.compare_substrings_with(&other, l1..r1, l2..r2),
+                    text[l1..r1].cmp(&other_text[l2..r2])
+                );
+            }
+
+            let mut expected: Vec<usize> = (0..n).collect();
+            expected.sort_by_key(|&i| &text[i..]);
+            assert_eq!(hash.sort_suffixes(), expected);
+        }
+    }
+
+    #[test]
+    fn test_window_hash_sliding() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(954);
+
+        for _ in 0..50 {
+            let text: Vec<u8> = (0..100).map(|_| rng.gen_range(b'a', b'c')).collect();
+            let size = rng.gen_range(0, 6);
+            let mut window = WindowHash::new();
+            for &c in &text[..size] {
+                window.push_back(c);
+            }
+            for start in 0..text.len() - size {
+                let expected = &text[start..start+size];
+                assert_eq!(window.len(), size);
+                assert_eq!(window.hash(), DoubleRollingHash::new(expected).whole_hash());
+                assert_eq!(window.is_palindrome(), expected.iter().eq(expected.iter().rev()));
+                window.push_back(text[start + size]);
+                assert_eq!(window.pop_front(), Some(text[start] as u64));
+            }
+        }
+    }
+
+    #[test]
+    fn test_window_hash_small() {
+        let mut window = WindowHash::new();
+        assert!(window.is_empty());
+        assert!(window.is_palindrome());
+        assert_eq!(window.pop_front(), None);
+
+        window.push_back(0u8);
+        assert!(window.is_palindrome());
+        assert_eq!(window.pop_front(), Some(0));
+        assert!(window.is_palindrome());
+        assert_eq!(window.hash(), DoubleRollingHash::new::<u8>(&[]).whole_hash());
+
+        let bases = (12345, 67890);
+        let mut window = WindowHash::with_bases(bases);
+        window.push_back(1u8);
+        window.push_back(2u8);
+        assert_eq!(window.hash(), DoubleRollingHash::with_bases(&[1u8, 2], bases).whole_hash());
+        assert!(!window.is_palindrome());
+    }
 }
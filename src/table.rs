@@ -3,9 +3,10 @@
 use crate::read::{Readable, Chars, read_lines};
 use crate::option::BoolExt;
 use crate::range::{UsizeRangeBoundsExt, BoundExt};
-use crate::iter::IteratorExt;
+use crate::num::WithZero;
+use crate::write::{Writable, display_grid};
 
-// BEGIN SNIPPET table DEPENDS ON read option range iter
+// BEGIN SNIPPET table DEPENDS ON read option range num_types write
 
 /// 2-dimentional array.
 ///
@@ -103,6 +104,18 @@ pub struct CumulativeTable<T, F1, F2> {
     inner: Table<T>
 }
 
+/// A `Display`able object created by [`Table::display_with_sep`](struct.Table.html#method.display_with_sep).
+pub struct TableDisplay<'a, T> {
+    table: &'a Table<T>,
+    sep: &'a str
+}
+
+/// A table created by [`Table::cumulative_sum`](struct.Table.html#method.cumulative_sum).
+pub struct CumTable<T> {
+    // `(height+1) x (width+1)` table whose `[y][x]` is the sum of `[..y][..x]`.
+    inner: Table<T>
+}
+
 impl<T> Table<T> {
     /// Creates a new table from rows represented as `Vec<Vec<T>>`.
     ///
@@ -190,10 +203,114 @@ impl<T> Table<T> {
         self.inner.get_mut(y).and_then(|row| row.get_mut(x))
     }
 
-    pub fn rows(&self) -> TableRows<T> {
+    pub fn rows(&self) -> TableRows<'_, T> {
         TableRows { table: self, index: 0 }
     }
 
+    /// Creates an iterator yielding `((y, x), &item)` for all the cells.
+    ///
+    /// Cells are always yielded in the row-major order, the same order as `indices`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec!['a', 'b'],
+    ///     vec!['c', 'd']
+    /// ]).unwrap();
+    /// let cells: Vec<((usize, usize), &char)> = table.iter_cells().collect();
+    /// assert_eq!(cells, vec![
+    ///     ((0, 0), &'a'), ((0, 1), &'b'),
+    ///     ((1, 0), &'c'), ((1, 1), &'d')
+    /// ]);
+    /// ```
+    pub fn iter_cells(&self) -> impl Iterator<Item=((usize, usize), &T)> {
+        self.inner.iter().enumerate().flat_map(|(y, row)| {
+            row.iter().enumerate().map(move |(x, item)| ((y, x), item))
+        })
+    }
+
+    /// Same as `iter_cells`, but yields mutable references.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let mut table = Table::from_rows(vec![
+    ///     vec![0, 0, 0],
+    ///     vec![0, 0, 0]
+    /// ]).unwrap();
+    /// for ((y, x), item) in table.iter_cells_mut() {
+    ///     *item = y * 10 + x;
+    /// }
+    /// assert_eq!(table, Table::from_rows(vec![
+    ///     vec![0, 1, 2],
+    ///     vec![10, 11, 12]
+    /// ]).unwrap());
+    /// ```
+    pub fn iter_cells_mut(&mut self) -> impl Iterator<Item=((usize, usize), &mut T)> {
+        self.inner.iter_mut().enumerate().flat_map(|(y, row)| {
+            row.iter_mut().enumerate().map(move |(x, item)| ((y, x), item))
+        })
+    }
+
+    /// Gets the indices of all the cells satisfying `pred`, in the row-major order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec!['S', '.', '#'],
+    ///     vec!['#', '.', 'G']
+    /// ]).unwrap();
+    /// assert_eq!(table.positions_where(|&c| c == '#'), vec![(0, 2), (1, 0)]);
+    /// if let [s, g] = table.positions_where(|&c| c == 'S' || c == 'G')[..] {
+    ///     assert_eq!((s, g), ((0, 0), (1, 2)));
+    /// }
+    /// ```
+    pub fn positions_where(&self, mut pred: impl FnMut(&T) -> bool) -> Vec<(usize, usize)> {
+        self.iter_cells()
+            .filter(|&(_, item)| pred(item))
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Gets the index of the only cell satisfying `pred`.
+    ///
+    /// If no cell satisfies `pred`, returns `None`.
+    ///
+    /// Two or more matching cells usually mean a bug in parsing input,
+    /// so this method panics in that case if `cfg(local)` is enabled.
+    /// Otherwise, returns the first one in the row-major order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec!['S', '.', '#'],
+    ///     vec!['#', '.', 'G']
+    /// ]).unwrap();
+    /// assert_eq!(table.find_unique(|&c| c == 'S'), Some((0, 0)));
+    /// assert_eq!(table.find_unique(|&c| c == 'G'), Some((1, 2)));
+    /// assert_eq!(table.find_unique(|&c| c == 'X'), None);
+    /// ```
+    pub fn find_unique(&self, mut pred: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        let mut positions = self.iter_cells()
+            .filter(|&(_, item)| pred(item))
+            .map(|(pos, _)| pos);
+        let first = positions.next();
+        #[cfg(local)]
+        {
+            if let Some(second) = positions.next() {
+                panic!("Multiple cells match: {:?} and {:?}", first.unwrap(), second);
+            }
+        }
+        first
+    }
+
     /// Maps each element by `f` and gets a new table with the same shape.
     ///
     /// # Example
@@ -329,6 +446,336 @@ impl<T> Table<T> {
         }
     }
 
+    /// Same as `transpose`, but returns a new table instead of modifying `self`.
+    ///
+    /// The item at `(y, x)` moves to `(x, y)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec![1, 2, 3],
+    ///     vec![4, 5, 6]
+    /// ]).unwrap();
+    /// assert_eq!(table.transposed(), Table::from_rows(vec![
+    ///     vec![1, 4],
+    ///     vec![2, 5],
+    ///     vec![3, 6]
+    /// ]).unwrap());
+    /// ```
+    pub fn transposed(&self) -> Table<T> where T: Clone {
+        let rows = (0..self.width()).map(|x| {
+            self.inner.iter().map(|row| row[x].clone()).collect()
+        }).collect();
+        Table { inner: rows }
+    }
+
+    /// Rotates the table clockwise by 90 degrees.
+    ///
+    /// For a table of shape `(h, w)`, the returned table has shape `(w, h)`,
+    /// and the item at `(y, x)` moves to `(x, h-1-y)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec![1, 2, 3],
+    ///     vec![4, 5, 6]
+    /// ]).unwrap();
+    /// assert_eq!(table.rotated_cw(), Table::from_rows(vec![
+    ///     vec![4, 1],
+    ///     vec![5, 2],
+    ///     vec![6, 3]
+    /// ]).unwrap());
+    /// ```
+    pub fn rotated_cw(&self) -> Table<T> where T: Clone {
+        let rows = (0..self.width()).map(|x| {
+            self.inner.iter().rev().map(|row| row[x].clone()).collect()
+        }).collect();
+        Table { inner: rows }
+    }
+
+    /// Rotates the table counterclockwise by 90 degrees.
+    ///
+    /// For a table of shape `(h, w)`, the returned table has shape `(w, h)`,
+    /// and the item at `(y, x)` moves to `(w-1-x, y)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec![1, 2, 3],
+    ///     vec![4, 5, 6]
+    /// ]).unwrap();
+    /// assert_eq!(table.rotated_ccw(), Table::from_rows(vec![
+    ///     vec![3, 6],
+    ///     vec![2, 5],
+    ///     vec![1, 4]
+    /// ]).unwrap());
+    /// ```
+    pub fn rotated_ccw(&self) -> Table<T> where T: Clone {
+        let rows = (0..self.width()).rev().map(|x| {
+            self.inner.iter().map(|row| row[x].clone()).collect()
+        }).collect();
+        Table { inner: rows }
+    }
+
+    /// Flips the table horizontally, that is, reverses each row.
+    ///
+    /// The item at `(y, x)` moves to `(y, w-1-x)`.
+    pub fn flipped_h(&self) -> Table<T> where T: Clone {
+        let rows = self.inner.iter().map(|row| {
+            row.iter().rev().cloned().collect()
+        }).collect();
+        Table { inner: rows }
+    }
+
+    /// Flips the table vertically, that is, reverses the order of rows.
+    ///
+    /// The item at `(y, x)` moves to `(h-1-y, x)`.
+    pub fn flipped_v(&self) -> Table<T> where T: Clone {
+        Table { inner: self.inner.iter().rev().cloned().collect() }
+    }
+
+    /// Surrounds the table by 1-cell border filled with `border`.
+    ///
+    /// For a table of shape `(h, w)`, the returned table has shape `(h+2, w+2)`,
+    /// and the item at `(y, x)` moves to `(y+1, x+1)`.
+    ///
+    /// Useful for grid problems to avoid out-of-range checks on neighbors,
+    /// by padding with a wall such as `'#'`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec!['.', '.'],
+    ///     vec!['.', '#']
+    /// ]).unwrap();
+    /// let padded = table.padded('#');
+    /// assert_eq!(padded, Table::from_rows(vec![
+    ///     vec!['#', '#', '#', '#'],
+    ///     vec!['#', '.', '.', '#'],
+    ///     vec!['#', '.', '#', '#'],
+    ///     vec!['#', '#', '#', '#']
+    /// ]).unwrap());
+    /// assert_eq!(padded.unpadded(), table);
+    /// ```
+    pub fn padded(&self, border: T) -> Table<T> where T: Clone {
+        let width = self.width() + 2;
+        let mut rows = Vec::with_capacity(self.height() + 2);
+        rows.push(vec![border.clone(); width]);
+        for row in &self.inner {
+            let mut new_row = Vec::with_capacity(width);
+            new_row.push(border.clone());
+            new_row.extend(row.iter().cloned());
+            new_row.push(border.clone());
+            rows.push(new_row);
+        }
+        rows.push(vec![border; width]);
+        Table { inner: rows }
+    }
+
+    /// Removes the 1-cell border, the inverse of [`padded`](#method.padded).
+    ///
+    /// The item at `(y, x)` moves to `(y-1, x-1)`.
+    ///
+    /// If the table has less than 2 rows, returns an empty table.
+    /// If the table has less than 2 columns, each row of the returned table is empty.
+    pub fn unpadded(&self) -> Table<T> where T: Clone {
+        let height = self.height();
+        let width = self.width();
+        if height < 2 {
+            return Table { inner: Vec::new() };
+        }
+        let rows = self.inner[1..height-1].iter().map(|row| {
+            if width < 2 { Vec::new() } else { row[1..width-1].to_vec() }
+        }).collect();
+        Table { inner: rows }
+    }
+
+    /// Clones the cells in `y_range` and `x_range` into a new table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y_range` or `x_range` is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec![1, 2, 3],
+    ///     vec![4, 5, 6],
+    ///     vec![7, 8, 9]
+    /// ]).unwrap();
+    /// assert_eq!(table.subtable(1.., ..2), Table::from_rows(vec![
+    ///     vec![4, 5],
+    ///     vec![7, 8]
+    /// ]).unwrap());
+    /// ```
+    pub fn subtable(&self, y_range: impl std::ops::RangeBounds<usize>,
+                    x_range: impl std::ops::RangeBounds<usize>) -> Table<T> where T: Clone {
+        let y = y_range.to_range(self.height()).unwrap_or_else(|| {
+            panic!("row range is out of bounds: the table height is {}", self.height())
+        });
+        let x = x_range.to_range(self.width()).unwrap_or_else(|| {
+            panic!("column range is out of bounds: the table width is {}", self.width())
+        });
+        Table { inner: self.inner[y].iter().map(|row| row[x.clone()].to_vec()).collect() }
+    }
+
+    /// Creates an iterator splitting the table into `block_height x block_width` blocks.
+    ///
+    /// Yields `((y, x), block)` in the row-major order,
+    /// where `(y, x)` is the index of the top-left cell of the block.
+    ///
+    /// If the height or width is not divisible by the block size,
+    /// the blocks at the bottom or right edge are smaller than the others.
+    /// Check `height() % block_height == 0` beforehand if the problem requires exact tiling.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_height` or `block_width` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec![1, 2, 3],
+    ///     vec![4, 5, 6]
+    /// ]).unwrap();
+    /// let blocks: Vec<((usize, usize), Table<i32>)> = table.blocks(2, 2).collect();
+    /// assert_eq!(blocks, vec![
+    ///     ((0, 0), Table::from_rows(vec![vec![1, 2], vec![4, 5]]).unwrap()),
+    ///     ((0, 2), Table::from_rows(vec![vec![3], vec![6]]).unwrap())
+    /// ]);
+    /// ```
+    pub fn blocks(&self, block_height: usize, block_width: usize)
+                  -> impl Iterator<Item=((usize, usize), Table<T>)> + '_ where T: Clone {
+        assert!(block_height > 0 && block_width > 0, "block size must be positive");
+        let (height, width) = self.shape();
+        (0..height).step_by(block_height).flat_map(move |y| {
+            (0..width).step_by(block_width).map(move |x| {
+                let block = self.subtable(y..std::cmp::min(y + block_height, height),
+                                          x..std::cmp::min(x + block_width, width));
+                ((y, x), block)
+            })
+        })
+    }
+
+    /// Creates an iterator yielding diagonals, on which `y - x` is constant.
+    ///
+    /// The `i`-th diagonal yielded consists of cells whose [`diag_id`](#method.diag_id) is `i`,
+    /// that is, diagonals are yielded from the top-right corner to the bottom-left corner.
+    /// Each diagonal is ordered from top to bottom.
+    ///
+    /// An `h x w` table has `h + w - 1` diagonals, and an empty table has none.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec![1, 2, 3],
+    ///     vec![4, 5, 6]
+    /// ]).unwrap();
+    /// let diagonals: Vec<Vec<&i32>> = table.diagonals().collect();
+    /// assert_eq!(diagonals, vec![
+    ///     vec![&3],
+    ///     vec![&2, &6],
+    ///     vec![&1, &5],
+    ///     vec![&4]
+    /// ]);
+    /// ```
+    pub fn diagonals(&self) -> impl Iterator<Item=Vec<&T>> {
+        let (height, width) = self.shape();
+        let count = if height == 0 || width == 0 { 0 } else { height + width - 1 };
+        (0..count).map(move |id| {
+            let ys = id.saturating_sub(width - 1)..std::cmp::min(height, id + 1);
+            ys.map(|y| &self.inner[y][y + width - 1 - id]).collect()
+        })
+    }
+
+    /// Creates an iterator yielding anti-diagonals, on which `y + x` is constant.
+    ///
+    /// The `i`-th anti-diagonal yielded consists of cells
+    /// whose [`anti_diag_id`](#method.anti_diag_id) is `i`,
+    /// that is, anti-diagonals are yielded from the top-left corner to the bottom-right corner.
+    /// Each anti-diagonal is ordered from top to bottom.
+    ///
+    /// An `h x w` table has `h + w - 1` anti-diagonals, and an empty table has none.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec![1, 2, 3],
+    ///     vec![4, 5, 6]
+    /// ]).unwrap();
+    /// let anti_diagonals: Vec<Vec<&i32>> = table.anti_diagonals().collect();
+    /// assert_eq!(anti_diagonals, vec![
+    ///     vec![&1],
+    ///     vec![&2, &4],
+    ///     vec![&3, &5],
+    ///     vec![&6]
+    /// ]);
+    /// ```
+    pub fn anti_diagonals(&self) -> impl Iterator<Item=Vec<&T>> {
+        let (height, width) = self.shape();
+        let count = if height == 0 || width == 0 { 0 } else { height + width - 1 };
+        (0..count).map(move |id| {
+            let ys = (id + 1).saturating_sub(width)..std::cmp::min(height, id + 1);
+            ys.map(|y| &self.inner[y][id - y]).collect()
+        })
+    }
+
+    /// Id of the diagonal containing `(y, x)`, in `0..height+width-1`.
+    ///
+    /// Two cells are on the same diagonal if and only if their ids are equal.
+    /// The id is `y + (width - 1) - x`, so it is never negative.
+    ///
+    /// `(y, x)` must be inside the table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[macro_use] extern crate atcoder_snippets;
+    /// # use atcoder_snippets::table::*;
+    /// let table = table![0; 2,3];
+    /// assert_eq!(table.diag_id((0, 2)), 0);
+    /// assert_eq!(table.diag_id((0, 1)), table.diag_id((1, 2)));
+    /// assert_eq!(table.diag_id((1, 0)), 3);
+    /// ```
+    pub fn diag_id(&self, (y, x): (usize, usize)) -> usize {
+        y + (self.width() - 1) - x
+    }
+
+    /// Id of the anti-diagonal containing `(y, x)`, in `0..height+width-1`.
+    ///
+    /// Two cells are on the same anti-diagonal if and only if their ids are equal.
+    /// The id is `y + x`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[macro_use] extern crate atcoder_snippets;
+    /// # use atcoder_snippets::table::*;
+    /// let table = table![0; 2,3];
+    /// assert_eq!(table.anti_diag_id((0, 0)), 0);
+    /// assert_eq!(table.anti_diag_id((0, 2)), table.anti_diag_id((1, 1)));
+    /// assert_eq!(table.anti_diag_id((1, 2)), 3);
+    /// ```
+    pub fn anti_diag_id(&self, (y, x): (usize, usize)) -> usize {
+        y + x
+    }
+
     // Ant Book p. 37
     // TODO: Maybe out-of-range should not be treated as an error.
     /// Indices of vertically and horizontlly adjacent cells in the dictionary order.
@@ -414,6 +861,113 @@ impl<T> Table<T> {
         })
     }
 
+    /// Labels each cell by the id of its connected component.
+    ///
+    /// Two adjacent cells are connected if `same` returns `true` for their values.
+    /// Adjacency is vertical and horizontal one, and also diagonal one if `diag` is `true`.
+    ///
+    /// Returns the table of labels and the number of components.
+    /// Labels are `0..count`, numbered in the row-major order of the first cell of each component.
+    ///
+    /// Traversal uses an explicit stack instead of recursion,
+    /// so a large grid never overflows the call stack.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec!['.', '#', '.'],
+    ///     vec!['#', '.', '.'],
+    /// ]).unwrap();
+    ///
+    /// let (labels, count) = table.components(|a, b| a == b, false);
+    /// assert_eq!(count, 4);
+    /// assert_eq!(labels, Table::from_rows(vec![
+    ///     vec![0, 1, 2],
+    ///     vec![3, 2, 2]
+    /// ]).unwrap());
+    ///
+    /// let (_, count_diag) = table.components(|a, b| a == b, true);
+    /// assert_eq!(count_diag, 2);
+    /// ```
+    pub fn components(&self, same: impl Fn(&T, &T) -> bool, diag: bool) -> (Table<usize>, usize) {
+        const UNLABELED: usize = std::usize::MAX;
+
+        let mut labels = Table { inner: vec![vec![UNLABELED; self.width()]; self.height()] };
+        let mut count = 0;
+        let mut stack = Vec::new();
+
+        for start in self.indices() {
+            if labels[start] != UNLABELED {
+                continue;
+            }
+
+            labels[start] = count;
+            stack.push(start);
+            while let Some(pos) = stack.pop() {
+                let neighbors = if diag {
+                    self.adjacent8_indices(pos)
+                } else {
+                    self.adjacent4_indices(pos)
+                };
+                for next in neighbors.unwrap() {
+                    if labels[next] == UNLABELED && same(&self[pos], &self[next]) {
+                        labels[next] = count;
+                        stack.push(next);
+                    }
+                }
+            }
+            count += 1;
+        }
+
+        (labels, count)
+    }
+
+    /// Paints `new_value` over the cell at `start` and all the cells connected to it,
+    /// like the bucket tool of paint software.
+    ///
+    /// Cells are connected if they are vertically or horizontally adjacent
+    /// and have the same value as the cell at `start`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `start` is out of range.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let mut table = Table::from_rows(vec![
+    ///     vec!['.', '#', '.'],
+    ///     vec!['.', '#', '.'],
+    ///     vec!['.', '.', '#'],
+    /// ]).unwrap();
+    /// table.flood_fill((0, 0), 'o');
+    /// assert_eq!(table, Table::from_rows(vec![
+    ///     vec!['o', '#', '.'],
+    ///     vec!['o', '#', '.'],
+    ///     vec!['o', 'o', '#'],
+    /// ]).unwrap());
+    /// ```
+    pub fn flood_fill(&mut self, start: (usize, usize), new_value: T) where T: Clone + PartialEq {
+        let old_value = self[start].clone();
+        if old_value == new_value {
+            return;
+        }
+
+        self[start] = new_value.clone();
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            for next in self.adjacent4_indices(pos).unwrap() {
+                if self[next] == old_value {
+                    self[next] = new_value.clone();
+                    stack.push(next);
+                }
+            }
+        }
+    }
+
     // ABC005 D
     /// Creates a cumulative table that can handle 2-dimentional range sum queries, etc.
     ///
@@ -457,16 +1011,216 @@ impl<T> Table<T> {
         }
         CumulativeTable { op, op_inv, inner: Table { inner } }
     }
+
+    /// Creates a 2-dimentional cumulative sum, which answers rectangle sums in constant time.
+    ///
+    /// Works for any type having zero and subtraction,
+    /// such as `i64` and [`ModP`](../modulo/modp/struct.ModP.html).
+    /// Unsigned integers are also OK if all values are non-negative,
+    /// because intermediate values never go below zero.
+    ///
+    /// Building the table takes Θ(`height * width`) time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let rows = vec![
+    ///     vec![  1,   2,   3,   4],
+    ///     vec![ 10,  20,  30,  40],
+    ///     vec![100, 200, 300, 400],
+    /// ];
+    /// let table = Table::from_rows(rows).unwrap();
+    /// let cum = table.cumulative_sum();
+    /// assert_eq!(cum.sum(1..3, 1..=2), 20 + 30 + 200 + 300);
+    /// assert_eq!(cum.sum(.., ..), 1110);
+    /// assert_eq!(cum.sum(2..2, ..), 0);
+    /// ```
+    pub fn cumulative_sum(&self) -> CumTable<T>
+    where
+        T: Clone + WithZero + for<'a> std::ops::Sub<&'a T, Output=T>
+    {
+        let mut inner = vec![vec![T::zero(); self.width() + 1]];
+        for (y, table_row) in self.rows().enumerate() {
+            let mut new_row = Vec::with_capacity(self.width() + 1);
+            new_row.push(T::zero());
+            for (x, value) in table_row.iter().enumerate() {
+                // Adds before subtraction in order to avoid underflow for unsigned integers.
+                let acc = value.clone() + &inner[y][x+1] + &new_row[x] - &inner[y][x];
+                new_row.push(acc);
+            }
+            inner.push(new_row);
+        }
+        CumTable { inner: Table { inner } }
+    }
+}
+
+impl Table<char> {
+    /// Concatenates each row into a `String`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec!['#', '.', '#'],
+    ///     vec!['.', '#', '.']
+    /// ]).unwrap();
+    /// assert_eq!(table.to_lines(), vec!["#.#", ".#."]);
+    /// ```
+    pub fn to_lines(&self) -> Vec<String> {
+        self.rows().map(|row| row.iter().collect()).collect()
+    }
+}
+
+impl<T: std::fmt::Display> Table<T> {
+    /// Creates a `Display`able object printing items in each row separated by `sep`.
+    ///
+    /// Rows are separated by newlines, and no newline follows the last row.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec![0, 1, 2],
+    ///     vec![1, 2, 3]
+    /// ]).unwrap();
+    /// assert_eq!(table.display_with_sep(",").to_string(), "0,1,2\n1,2,3");
+    ///
+    /// let grid = Table::from_rows(vec![
+    ///     vec!['#', '.'],
+    ///     vec!['.', '#']
+    /// ]).unwrap();
+    /// assert_eq!(grid.display_with_sep("").to_string(), "#.\n.#");
+    /// ```
+    pub fn display_with_sep<'a>(&'a self, sep: &'a str) -> TableDisplay<'a, T> {
+        TableDisplay { table: self, sep }
+    }
+
+    /// Formats the table with row and column indices, for debugging.
+    ///
+    /// Each column is right-aligned.
+    /// [`dbg_grid`](../macro.dbg_grid.html) macro prints this string to stderr.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(vec![
+    ///     vec![0, 1, 2],
+    ///     vec![10, 100, 3]
+    /// ]).unwrap();
+    /// assert_eq!(table.to_debug_grid(), [
+    ///     "   0   1 2",
+    ///     "0  0   1 2",
+    ///     "1 10 100 3"
+    /// ].join("\n"));
+    /// ```
+    pub fn to_debug_grid(&self) -> String {
+        let ys: Vec<Option<usize>> = (0..self.height()).map(Some).collect();
+        let xs: Vec<Option<usize>> = (0..self.width()).map(Some).collect();
+        self.debug_grid_of(&ys, &xs)
+    }
+
+    /// Formats the table like [`to_debug_grid`](#method.to_debug_grid),
+    /// showing only the first and last `limit` rows and columns.
+    ///
+    /// Omitted rows and columns are shown as `...`.
+    /// [`dbg_table`](../macro.dbg_table.html) macro prints this string to stderr.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::table::*;
+    /// let table = Table::from_rows(
+    ///     (0..5).map(|y| (0..5).map(|x| y * 10 + x).collect()).collect()
+    /// ).unwrap();
+    /// assert_eq!(table.to_debug_grid_truncated(1), [
+    ///     "     0 ...  4",
+    ///     "  0  0 ...  4",
+    ///     "...",
+    ///     "  4 40 ... 44"
+    /// ].join("\n"));
+    /// ```
+    pub fn to_debug_grid_truncated(&self, limit: usize) -> String {
+        fn indices(len: usize, limit: usize) -> Vec<Option<usize>> {
+            if len <= 2 * limit {
+                (0..len).map(Some).collect()
+            } else {
+                (0..limit).map(Some)
+                    .chain(std::iter::once(None))
+                    .chain((len - limit..len).map(Some))
+                    .collect()
+            }
+        }
+
+        self.debug_grid_of(&indices(self.height(), limit), &indices(self.width(), limit))
+    }
+
+    // `None` in `ys` and `xs` represents omitted rows and columns.
+    fn debug_grid_of(&self, ys: &[Option<usize>], xs: &[Option<usize>]) -> String {
+        fn label(index: Option<usize>) -> String {
+            index.map_or_else(|| "...".to_string(), |i| i.to_string())
+        }
+
+        let cells: Vec<Option<Vec<String>>> = ys.iter().map(|&y| {
+            y.map(|y| xs.iter().map(|&x| match x {
+                Some(x) => self.inner[y][x].to_string(),
+                None => "...".to_string()
+            }).collect())
+        }).collect();
+        let y_width = ys.iter().map(|&y| label(y).len()).max().unwrap_or(0);
+        let mut x_widths: Vec<usize> = xs.iter().map(|&x| label(x).len()).collect();
+        for row in cells.iter().flatten() {
+            for (width, cell) in x_widths.iter_mut().zip(row) {
+                *width = std::cmp::max(*width, cell.chars().count());
+            }
+        }
+
+        let mut lines = Vec::with_capacity(ys.len() + 1);
+        let header: String = xs.iter().zip(&x_widths)
+            .map(|(&x, &width)| format!(" {:>w$}", label(x), w = width))
+            .collect();
+        lines.push(format!("{:w$}{}", "", header, w = y_width));
+        for (&y, row) in ys.iter().zip(&cells) {
+            match row {
+                Some(row) => {
+                    let line: String = row.iter().zip(&x_widths)
+                        .map(|(cell, &width)| format!(" {:>w$}", cell, w = width))
+                        .collect();
+                    lines.push(format!("{:>w$}{}", label(y), line, w = y_width));
+                }
+                None => lines.push(label(y))
+            }
+        }
+        lines.join("\n")
+    }
 }
 
 impl<T: std::fmt::Display> std::fmt::Display for Table<T> {
+    /// Prints items in each row separated by spaces, and rows separated by newlines.
+    ///
+    /// No spaces follow the last item in a row, and no newline follows the last row,
+    /// so `println!("{}", table)` prints the table as is.
+    ///
     /// Useful when you solve a problem to print whole the table,
     /// such as [AtCoder Beginner Contest 164 F: I hate Matrix Construction](https://atcoder.jp/contests/abc164/tasks/abc164_f).
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        for row in self.rows() {
-            writeln!(f, "{}", row.iter().join(" "))?;
-        }
-        Ok(())
+        write!(f, "{}", self.display_with_sep(" "))
+    }
+}
+
+impl<T: std::fmt::Display> Writable for Table<T> {
+    /// Writes the table in the same way as `Display`.
+    fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        write!(writer, "{}", self)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for TableDisplay<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", display_grid(&self.table.inner, self.sep))
     }
 }
 
@@ -547,6 +1301,34 @@ impl<T, F1: Fn(&T, &T) -> T, F2: Fn(&T, &T) -> T> CumulativeTable<T, F1, F2> {
     }
 }
 
+impl<T> CumTable<T>
+where
+    T: Clone + WithZero + for<'a> std::ops::Sub<&'a T, Output=T>
+{
+    /// Sum of the rectangle of rows `y_range` and columns `x_range`.
+    ///
+    /// If either range is empty, returns zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either range is out of the original table.
+    pub fn sum(&self, y_range: impl std::ops::RangeBounds<usize>,
+               x_range: impl std::ops::RangeBounds<usize>) -> T {
+        let (height, width) = (self.inner.height() - 1, self.inner.width() - 1);
+        let y = y_range.to_range(height).unwrap_or_else(|| {
+            panic!("row range is out of bounds: the table height is {}", height)
+        });
+        let x = x_range.to_range(width).unwrap_or_else(|| {
+            panic!("column range is out of bounds: the table width is {}", width)
+        });
+
+        let rows = &self.inner.inner;
+        // Adds before subtraction in order to avoid underflow for unsigned integers.
+        rows[y.end][x.end].clone() + &rows[y.start][x.start]
+            - &rows[y.start][x.end] - &rows[y.end][x.start]
+    }
+}
+
 pub fn backward2_indices((y, x): (usize, usize)) -> Vec<(usize, usize)> {
     let mut res = Vec::with_capacity(2);
     if y > 0 {
@@ -587,6 +1369,82 @@ macro_rules! table {
     };
 }
 
+/// Prints a table with row and column indices to stderr.
+///
+/// The output is made only in the local machine, not in the judge server.
+///
+/// See [`Table::to_debug_grid`](table/struct.Table.html#method.to_debug_grid) for the format.
+#[macro_export]
+#[cfg(local)]
+macro_rules! dbg_grid {
+    ($table: expr) => {
+        {
+            use std::io::{self, Write};
+            writeln!(io::stderr(), "{}: {} =\n{}",
+                     line!(), stringify!($table), $table.to_debug_grid())
+                .unwrap();
+        }
+    }
+}
+
+/// Prints a table with row and column indices to stderr.
+///
+/// The output is made only in the local machine, not in the judge server.
+///
+/// See [`Table::to_debug_grid`](table/struct.Table.html#method.to_debug_grid) for the format.
+#[macro_export]
+#[cfg(not(local))]
+macro_rules! dbg_grid {
+    ($table: expr) => {
+        { let _ = &$table; }
+    }
+}
+
+/// Prints the first and last rows and columns of a table with indices to stderr.
+///
+/// `dbg_table!(table, k)` shows at most `k` first and `k` last rows and columns,
+/// and `dbg_table!(table)` is the same as `dbg_table!(table, 5)`.
+/// The output is made only in the local machine, not in the judge server.
+///
+/// See [`Table::to_debug_grid_truncated`](table/struct.Table.html#method.to_debug_grid_truncated)
+/// for the format.
+#[macro_export]
+#[cfg(local)]
+macro_rules! dbg_table {
+    ($table: expr) => {
+        dbg_table!($table, 5)
+    };
+
+    ($table: expr, $k: expr) => {
+        {
+            use std::io::{self, Write};
+            writeln!(io::stderr(), "{}: {} =\n{}",
+                     line!(), stringify!($table), $table.to_debug_grid_truncated($k))
+                .unwrap();
+        }
+    }
+}
+
+/// Prints the first and last rows and columns of a table with indices to stderr.
+///
+/// `dbg_table!(table, k)` shows at most `k` first and `k` last rows and columns,
+/// and `dbg_table!(table)` is the same as `dbg_table!(table, 5)`.
+/// The output is made only in the local machine, not in the judge server.
+///
+/// See [`Table::to_debug_grid_truncated`](table/struct.Table.html#method.to_debug_grid_truncated)
+/// for the format.
+#[macro_export]
+#[cfg(not(local))]
+macro_rules! dbg_table {
+    ($table: expr $(, $k: expr)?) => {
+        {
+            if false {
+                let _ = &$table;
+            }
+        }
+    }
+}
+
 pub fn read_table<T: Readable>() -> Table<T::Output> {
     let res: Vec<Vec<T::Output>> = read_lines::<Vec<T>>().collect();
     Table::from_rows(res).unwrap()
@@ -722,6 +1580,76 @@ mod tests {
         assert_eq!(square, square_transposed);
     }
 
+    #[test]
+    fn test_rotations() {
+        let table = Table::from_rows(vec![
+            vec![0, 1, 2],
+            vec![3, 4, 5]
+        ]).unwrap();
+        let (h, w) = table.shape();
+
+        let cw = table.rotated_cw();
+        assert_eq!(cw.shape(), (w, h));
+        let ccw = table.rotated_ccw();
+        assert_eq!(ccw.shape(), (w, h));
+        let transposed = table.transposed();
+        assert_eq!(transposed.shape(), (w, h));
+        let flipped_h = table.flipped_h();
+        let flipped_v = table.flipped_v();
+
+        for (y, x) in table.indices() {
+            let v = table[(y, x)];
+            assert_eq!(cw[(x, h-1-y)], v);
+            assert_eq!(ccw[(w-1-x, y)], v);
+            assert_eq!(transposed[(x, y)], v);
+            assert_eq!(flipped_h[(y, w-1-x)], v);
+            assert_eq!(flipped_v[(h-1-y, x)], v);
+        }
+
+        assert_eq!(cw.rotated_ccw(), table);
+        assert_eq!(cw.rotated_cw().rotated_cw().rotated_cw(), table);
+        assert_eq!(cw.rotated_cw(), table.flipped_h().flipped_v());
+
+        let mut transposed_in_place = table.clone();
+        transposed_in_place.transpose();
+        assert_eq!(transposed_in_place, transposed);
+    }
+
+    #[test]
+    fn test_transforms_zero_sized() {
+        let empty: Table<i32> = table![];
+        assert_eq!(empty.transposed(), empty);
+        assert_eq!(empty.rotated_cw(), empty);
+        assert_eq!(empty.rotated_ccw(), empty);
+        assert_eq!(empty.flipped_h(), empty);
+        assert_eq!(empty.flipped_v(), empty);
+        assert_eq!(empty.padded(0), table![0; 2,2]);
+        assert_eq!(empty.unpadded(), empty);
+        assert_eq!(empty.padded(0).unpadded(), empty);
+
+        let no_columns: Table<i32> = table![0; 3,0];
+        assert_eq!(no_columns.transposed(), empty);
+        assert_eq!(no_columns.rotated_cw(), empty);
+        assert_eq!(no_columns.flipped_h(), no_columns);
+        assert_eq!(no_columns.padded(1), table![1; 5,2]);
+        assert_eq!(no_columns.padded(1).unpadded(), no_columns);
+        assert_eq!(table![1; 1,5].unpadded(), empty);
+    }
+
+    #[test]
+    fn test_padded() {
+        let table = Table::from_rows(vec![
+            vec![1, 2, 3]
+        ]).unwrap();
+        let padded = table.padded(0);
+        assert_eq!(padded, Table::from_rows(vec![
+            vec![0, 0, 0, 0, 0],
+            vec![0, 1, 2, 3, 0],
+            vec![0, 0, 0, 0, 0]
+        ]).unwrap());
+        assert_eq!(padded.unpadded(), table);
+    }
+
     #[test]
     fn test_indices() {
         fn indices(table: &Table<i32>) -> Vec<(usize, usize)> {
@@ -801,6 +1729,531 @@ mod tests {
         assert_eq!(check(2,2), vec![(1,1), (1,2), (2,1)]);
     }
 
+    #[test]
+    fn test_cumulative_sum() {
+        use rand::prelude::*;
+
+        let rows: Vec<Vec<i64>> = (0..10).map(|_| {
+            (0..10).map(|_| random::<i64>() % 1000).collect()
+        }).collect();
+        let table = Table::from_rows(rows).unwrap();
+        let cum = table.cumulative_sum();
+
+        for y1 in 0..=10 {
+            for y2 in y1..=10 {
+                for x1 in 0..=10 {
+                    for x2 in x1..=10 {
+                        let mut expected = 0;
+                        for y in y1..y2 {
+                            for x in x1..x2 {
+                                expected += table[(y, x)];
+                            }
+                        }
+                        assert_eq!(cum.sum(y1..y2, x1..x2), expected);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cumulative_sum_empty() {
+        let empty: Table<i64> = table![];
+        assert_eq!(empty.cumulative_sum().sum(.., ..), 0);
+
+        let no_columns: Table<u64> = table![0; 3,0];
+        assert_eq!(no_columns.cumulative_sum().sum(1..3, ..), 0);
+    }
+
+    #[test]
+    fn test_cumulative_sum_unsigned() {
+        let table = Table::from_rows(vec![
+            vec![1u64, 2],
+            vec![3, 4]
+        ]).unwrap();
+        let cum = table.cumulative_sum();
+        assert_eq!(cum.sum(1.., 1..), 4);
+        assert_eq!(cum.sum(..1, ..), 3);
+        assert_eq!(cum.sum(.., ..=0), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cumulative_sum_out_of_range() {
+        let table = table![0i64; 2,2];
+        table.cumulative_sum().sum(0..3, ..);
+    }
+
+    #[test]
+    fn test_cumulative_sum_modp() {
+        use crate::modulo::modp::*;
+
+        unsafe { ModP::set_mod(7).unwrap(); }
+        let table = Table::from_rows(vec![
+            vec![modp(5), modp(6)],
+            vec![modp(4), modp(3)]
+        ]).unwrap();
+        let cum = table.cumulative_sum();
+        assert_eq!(cum.sum(.., ..), modp(18));
+        assert_eq!(cum.sum(.., 1..), modp(2));
+        assert_eq!(cum.sum(1.., ..), modp(0));
+    }
+
+    fn char_table(rows: &[&str]) -> Table<char> {
+        Table::from_rows(rows.iter().map(|row| row.chars().collect()).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_components_donut() {
+        let table = char_table(&[
+            "#####",
+            "#...#",
+            "#.#.#",
+            "#...#",
+            "#####",
+        ]);
+
+        let (labels, count) = table.components(|a, b| a == b, false);
+        assert_eq!(count, 3);
+        assert_eq!(labels[(0, 0)], 0);
+        assert_eq!(labels[(1, 1)], 1);
+        assert_eq!(labels[(2, 2)], 2);
+        for (y, x) in table.indices() {
+            let expected = match table[(y, x)] {
+                '.' => 1,
+                _ => if (y, x) == (2, 2) { 2 } else { 0 }
+            };
+            assert_eq!(labels[(y, x)], expected);
+        }
+
+        // The center does not touch the outer ring even diagonally.
+        let (_, count_diag) = table.components(|a, b| a == b, true);
+        assert_eq!(count_diag, 3);
+    }
+
+    #[test]
+    fn test_components_single_cells() {
+        let table = char_table(&[
+            "#.#",
+            ".#.",
+            "#.#",
+        ]);
+
+        let (labels, count) = table.components(|a, b| a == b, false);
+        assert_eq!(count, 9);
+        assert_eq!(labels, Table::from_rows(vec![
+            vec![0, 1, 2],
+            vec![3, 4, 5],
+            vec![6, 7, 8]
+        ]).unwrap());
+
+        let (labels_diag, count_diag) = table.components(|a, b| a == b, true);
+        assert_eq!(count_diag, 2);
+        assert_eq!(labels_diag, Table::from_rows(vec![
+            vec![0, 1, 0],
+            vec![1, 0, 1],
+            vec![0, 1, 0]
+        ]).unwrap());
+    }
+
+    #[test]
+    fn test_components_compatible_values() {
+        let table = Table::from_rows(vec![
+            vec![1, 2, 3, 10],
+            vec![9, 8, 4, 11]
+        ]).unwrap();
+        // Connects cells whose values differ by at most 1.
+        let (labels, count) = table.components(|&a, &b| (a - b as i32).abs() <= 1, false);
+        assert_eq!(count, 3);
+        assert_eq!(labels, Table::from_rows(vec![
+            vec![0, 0, 0, 1],
+            vec![2, 2, 0, 1]
+        ]).unwrap());
+    }
+
+    #[test]
+    fn test_components_large() {
+        let table = table!['.'; 2000,2000];
+        let (labels, count) = table.components(|a, b| a == b, false);
+        assert_eq!(count, 1);
+        assert!(labels.rows().all(|row| row.iter().all(|&label| label == 0)));
+
+        let empty: Table<char> = table![];
+        assert_eq!(empty.components(|a, b| a == b, true), (table![], 0));
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let mut table = char_table(&[
+            "#####",
+            "#...#",
+            "#.#.#",
+            "#...#",
+            "#####",
+        ]);
+        table.flood_fill((1, 1), 'o');
+        assert_eq!(table, char_table(&[
+            "#####",
+            "#ooo#",
+            "#o#o#",
+            "#ooo#",
+            "#####",
+        ]));
+
+        // Filling with the same value does nothing.
+        table.flood_fill((2, 2), '#');
+        assert_eq!(table[(2, 2)], '#');
+        table.flood_fill((0, 0), 'x');
+        assert_eq!(table, char_table(&[
+            "xxxxx",
+            "xooox",
+            "xo#ox",
+            "xooox",
+            "xxxxx",
+        ]));
+
+        let mut large = table![0; 2000,2000];
+        large.flood_fill((1000, 1000), 1);
+        assert!(large.rows().all(|row| row.iter().all(|&v| v == 1)));
+    }
+
+    #[test]
+    fn test_iter_cells() {
+        let mut table = Table::from_rows(vec![
+            vec![1, 2, 3],
+            vec![4, 5, 6]
+        ]).unwrap();
+        let positions: Vec<(usize, usize)> = table.iter_cells().map(|(pos, _)| pos).collect();
+        assert_eq!(positions, table.indices().collect::<Vec<_>>());
+        let items: Vec<i32> = table.iter_cells().map(|(_, &item)| item).collect();
+        assert_eq!(items, vec![1, 2, 3, 4, 5, 6]);
+
+        let mut order = Vec::new();
+        for (pos, item) in table.iter_cells_mut() {
+            order.push(pos);
+            *item *= -1;
+        }
+        assert_eq!(order, positions);
+        assert_eq!(table, Table::from_rows(vec![
+            vec![-1, -2, -3],
+            vec![-4, -5, -6]
+        ]).unwrap());
+
+        let mut empty: Table<i32> = table![];
+        assert_eq!(empty.iter_cells().count(), 0);
+        assert_eq!(empty.iter_cells_mut().count(), 0);
+        let mut no_columns: Table<i32> = Table::from_rows(vec![vec![], vec![]]).unwrap();
+        assert_eq!(no_columns.iter_cells().count(), 0);
+        assert_eq!(no_columns.iter_cells_mut().count(), 0);
+    }
+
+    #[test]
+    fn test_positions_where() {
+        let table = Table::from_rows(vec![
+            vec!['#', '.', '#'],
+            vec!['.', '#', '.'],
+            vec!['#', '.', '#']
+        ]).unwrap();
+        assert_eq!(
+            table.positions_where(|&c| c == '#'),
+            vec![(0, 0), (0, 2), (1, 1), (2, 0), (2, 2)]
+        );
+        assert_eq!(table.positions_where(|&c| c == 'S'), vec![]);
+
+        let empty: Table<char> = table![];
+        assert_eq!(empty.positions_where(|_| true), vec![]);
+        assert_eq!(empty.find_unique(|_| true), None);
+    }
+
+    #[test]
+    fn test_find_unique() {
+        let table = Table::from_rows(vec![
+            vec!['S', '.', '.'],
+            vec!['.', '#', 'G']
+        ]).unwrap();
+        assert_eq!(table.find_unique(|&c| c == 'S'), Some((0, 0)));
+        assert_eq!(table.find_unique(|&c| c == 'G'), Some((1, 2)));
+        assert_eq!(table.find_unique(|&c| c == 'X'), None);
+    }
+
+    #[test]
+    #[cfg(not(local))]
+    fn test_find_unique_multiple() {
+        let table = Table::from_rows(vec![
+            vec!['.', '.', '#'],
+            vec!['#', '.', '.']
+        ]).unwrap();
+        assert_eq!(table.find_unique(|&c| c == '#'), Some((0, 2)));
+    }
+
+    #[test]
+    #[cfg(local)]
+    #[should_panic]
+    fn test_find_unique_multiple() {
+        let table = Table::from_rows(vec![
+            vec!['.', '.', '#'],
+            vec!['#', '.', '.']
+        ]).unwrap();
+        table.find_unique(|&c| c == '#');
+    }
+
+    #[test]
+    fn test_diagonals() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(916);
+
+        for &(height, width) in &[(1, 1), (1, 5), (5, 1), (3, 4), (4, 3), (5, 5)] {
+            let table = Table {
+                inner: (0..height).map(|_| (0..width).map(|_| rng.gen_range(0, 100)).collect()).collect()
+            };
+
+            let mut diag_sums = vec![0; height + width - 1];
+            let mut anti_diag_sums = vec![0; height + width - 1];
+            for (pos, &item) in table.iter_cells() {
+                diag_sums[table.diag_id(pos)] += item;
+                anti_diag_sums[table.anti_diag_id(pos)] += item;
+            }
+
+            let diagonals: Vec<Vec<&i32>> = table.diagonals().collect();
+            let anti_diagonals: Vec<Vec<&i32>> = table.anti_diagonals().collect();
+            assert_eq!(diagonals.len(), height + width - 1);
+            assert_eq!(anti_diagonals.len(), height + width - 1);
+            let total_len: usize = diagonals.iter().map(|diag| diag.len()).sum();
+            assert_eq!(total_len, height * width);
+            let total_len: usize = anti_diagonals.iter().map(|diag| diag.len()).sum();
+            assert_eq!(total_len, height * width);
+
+            let sums: Vec<i32> = diagonals.iter().map(|diag| diag.iter().cloned().sum()).collect();
+            assert_eq!(sums, diag_sums);
+            let sums: Vec<i32> = anti_diagonals.iter().map(|diag| diag.iter().cloned().sum()).collect();
+            assert_eq!(sums, anti_diag_sums);
+        }
+    }
+
+    #[test]
+    fn test_diagonals_empty() {
+        let empty: Table<i32> = table![];
+        assert_eq!(empty.diagonals().count(), 0);
+        assert_eq!(empty.anti_diagonals().count(), 0);
+
+        let no_columns: Table<i32> = Table::from_rows(vec![vec![], vec![]]).unwrap();
+        assert_eq!(no_columns.diagonals().count(), 0);
+        assert_eq!(no_columns.anti_diagonals().count(), 0);
+    }
+
+    #[test]
+    fn test_diag_ids_n_queens() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(916);
+
+        fn attacks_brute_force(queens: &[(usize, usize)]) -> bool {
+            queens.iter().enumerate().any(|(i, &(y1, x1))| {
+                queens[i+1..].iter().any(|&(y2, x2)| {
+                    let dy = if y1 < y2 { y2 - y1 } else { y1 - y2 };
+                    let dx = if x1 < x2 { x2 - x1 } else { x1 - x2 };
+                    y1 == y2 || x1 == x2 || dy == dx
+                })
+            })
+        }
+
+        fn attacks_by_ids(board: &Table<bool>, queens: &[(usize, usize)]) -> bool {
+            let n = board.height();
+            let mut rows = vec![false; n];
+            let mut cols = vec![false; n];
+            let mut diags = vec![false; 2*n - 1];
+            let mut anti_diags = vec![false; 2*n - 1];
+            for &(y, x) in queens {
+                let diag = board.diag_id((y, x));
+                let anti_diag = board.anti_diag_id((y, x));
+                if rows[y] || cols[x] || diags[diag] || anti_diags[anti_diag] {
+                    return true;
+                }
+                rows[y] = true;
+                cols[x] = true;
+                diags[diag] = true;
+                anti_diags[anti_diag] = true;
+            }
+            false
+        }
+
+        for n in 1..9 {
+            let board = table![false; n,n];
+            for _ in 0..200 {
+                let mut queens: Vec<(usize, usize)> = board.indices().collect();
+                queens.shuffle(&mut rng);
+                queens.truncate(rng.gen_range(1, n+1));
+                assert_eq!(attacks_by_ids(&board, &queens), attacks_brute_force(&queens));
+            }
+        }
+
+        // 8-queens solution
+        let board = table![false; 8,8];
+        let queens: Vec<(usize, usize)> = [0, 4, 7, 5, 2, 6, 1, 3].iter()
+            .enumerate()
+            .map(|(y, &x)| (y, x))
+            .collect();
+        assert!(!attacks_by_ids(&board, &queens));
+    }
+
+    #[test]
+    fn test_display() {
+        let table = Table::from_rows(vec![
+            vec![1, 2, 3],
+            vec![40, 50, 60]
+        ]).unwrap();
+        assert_eq!(format!("{}", table), "1 2 3\n40 50 60");
+        assert_eq!(table.display_with_sep("").to_string(), "123\n405060");
+        assert_eq!(table.display_with_sep(", ").to_string(), "1, 2, 3\n40, 50, 60");
+
+        let single: Table<i32> = table![7; 1,1];
+        assert_eq!(format!("{}", single), "7");
+
+        let empty: Table<i32> = table![];
+        assert_eq!(format!("{}", empty), "");
+        let no_columns: Table<i32> = Table::from_rows(vec![vec![], vec![]]).unwrap();
+        assert_eq!(format!("{}", no_columns), "\n");
+    }
+
+    #[test]
+    fn test_to_lines() {
+        let table = Table::from_rows(vec![
+            vec!['S', '.', '#'],
+            vec!['#', '.', 'G']
+        ]).unwrap();
+        assert_eq!(table.to_lines(), vec!["S.#".to_string(), "#.G".to_string()]);
+        assert_eq!(table.to_lines().join("\n"), table.display_with_sep("").to_string());
+        assert_eq!(format!("{}", table), "S . #\n# . G");
+
+        let empty: Table<char> = table![];
+        assert_eq!(empty.to_lines(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_to_debug_grid() {
+        let table = Table::from_rows(
+            (0..11).map(|y| (0..2).map(|x| if x == 0 { '#' } else { (b'a' + y as u8) as char }).collect()).collect()
+        ).unwrap();
+        assert_eq!(table.to_debug_grid(), [
+            "   0 1",
+            " 0 # a",
+            " 1 # b",
+            " 2 # c",
+            " 3 # d",
+            " 4 # e",
+            " 5 # f",
+            " 6 # g",
+            " 7 # h",
+            " 8 # i",
+            " 9 # j",
+            "10 # k"
+        ].join("\n"));
+
+        let empty: Table<char> = table![];
+        assert_eq!(empty.to_debug_grid(), "");
+        dbg_grid!(table);
+    }
+
+    #[test]
+    fn test_to_debug_grid_truncated() {
+        let table = Table::from_rows(
+            (0..12).map(|y| (0..8).map(|x| y * 100 + x).collect()).collect()
+        ).unwrap();
+        assert_eq!(table.to_debug_grid_truncated(2), [
+            "       0    1 ...    6    7",
+            "  0    0    1 ...    6    7",
+            "  1  100  101 ...  106  107",
+            "...",
+            " 10 1000 1001 ... 1006 1007",
+            " 11 1100 1101 ... 1106 1107"
+        ].join("\n"));
+        assert_eq!(table.to_debug_grid_truncated(4), [
+            "       0    1    2    3    4    5    6    7",
+            "  0    0    1    2    3    4    5    6    7",
+            "  1  100  101  102  103  104  105  106  107",
+            "  2  200  201  202  203  204  205  206  207",
+            "  3  300  301  302  303  304  305  306  307",
+            "...",
+            "  8  800  801  802  803  804  805  806  807",
+            "  9  900  901  902  903  904  905  906  907",
+            " 10 1000 1001 1002 1003 1004 1005 1006 1007",
+            " 11 1100 1101 1102 1103 1104 1105 1106 1107"
+        ].join("\n"));
+        assert_eq!(table.to_debug_grid_truncated(6), table.to_debug_grid());
+
+        let empty: Table<char> = table![];
+        assert_eq!(empty.to_debug_grid_truncated(0), "");
+        dbg_table!(table);
+        dbg_table!(table, 1);
+    }
+
+    #[test]
+    fn test_subtable() {
+        let table = Table::from_rows(vec![
+            vec![1, 2, 3],
+            vec![4, 5, 6]
+        ]).unwrap();
+        assert_eq!(table.subtable(.., ..), table);
+        assert_eq!(table.subtable(1..2, 1..=2), Table::from_rows(vec![vec![5, 6]]).unwrap());
+        assert_eq!(table.subtable(..0, ..), table![]);
+        assert_eq!(table.subtable(.., 3..), Table::from_rows(vec![vec![], vec![]]).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_subtable_out_of_range() {
+        let table = table![0; 2,3];
+        table.subtable(.., 1..4);
+    }
+
+    fn reassemble<T: Clone>(height: usize, width: usize,
+                            blocks: impl Iterator<Item=((usize, usize), Table<T>)>) -> Table<Option<T>> {
+        let mut table = table![None; height,width];
+        for ((y0, x0), block) in blocks {
+            for ((y, x), item) in block.iter_cells() {
+                assert!(table[(y0 + y, x0 + x)].is_none());
+                table[(y0 + y, x0 + x)] = Some(item.clone());
+            }
+        }
+        table
+    }
+
+    #[test]
+    fn test_blocks() {
+        let table = Table { inner: (0..6).map(|y| (0..4).map(|x| y * 4 + x).collect()).collect() };
+
+        let blocks: Vec<((usize, usize), Table<usize>)> = table.blocks(3, 2).collect();
+        let origins: Vec<(usize, usize)> = blocks.iter().map(|&(pos, _)| pos).collect();
+        assert_eq!(origins, vec![(0, 0), (0, 2), (3, 0), (3, 2)]);
+        assert!(blocks.iter().all(|(_, block)| block.shape() == (3, 2)));
+        assert_eq!(reassemble(6, 4, blocks.into_iter()), table.ref_map(|&item| Some(item)));
+
+        let blocks: Vec<((usize, usize), Table<usize>)> = table.blocks(1, 1).collect();
+        assert_eq!(blocks.len(), 24);
+        assert_eq!(reassemble(6, 4, blocks.into_iter()), table.ref_map(|&item| Some(item)));
+
+        let empty: Table<usize> = table![];
+        assert_eq!(empty.blocks(2, 2).count(), 0);
+    }
+
+    #[test]
+    fn test_blocks_non_divisible() {
+        let table = Table { inner: (0..5).map(|y| (0..7).map(|x| y * 7 + x).collect()).collect() };
+
+        let blocks: Vec<((usize, usize), Table<usize>)> = table.blocks(2, 3).collect();
+        let shapes: Vec<((usize, usize), (usize, usize))> = blocks.iter()
+            .map(|(pos, block)| (*pos, block.shape()))
+            .collect();
+        assert_eq!(shapes, vec![
+            ((0, 0), (2, 3)), ((0, 3), (2, 3)), ((0, 6), (2, 1)),
+            ((2, 0), (2, 3)), ((2, 3), (2, 3)), ((2, 6), (2, 1)),
+            ((4, 0), (1, 3)), ((4, 3), (1, 3)), ((4, 6), (1, 1))
+        ]);
+        assert_eq!(reassemble(5, 7, blocks.into_iter()), table.ref_map(|&item| Some(item)));
+
+        let blocks: Vec<((usize, usize), Table<usize>)> = table.blocks(10, 10).collect();
+        assert_eq!(blocks, vec![((0, 0), table.clone())]);
+    }
+
     #[test]
     fn test_backward2_indices() {
         assert_eq!(backward2_indices((0, 0)), vec![]);
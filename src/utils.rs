@@ -1,16 +1,50 @@
 //! Utilities.
 
-// BEGIN SNIPPET utils
+use crate::write::{Writable, EchoArg, EchoByWritable, with_sentinel, exit_with_output};
 
-/// Output values by `println!("{} {} ... {}", value_1, value_2, ..., value_n`)`.
+// BEGIN SNIPPET utils DEPENDS ON write
+
+/// Output values separated by spaces, followed by a newline.
+///
+/// Similar to `println!("{} {} ... {}", value_1, value_2, ..., value_n`)`.
+/// Values are written by [`Writable`](write/trait.Writable.html) if possible,
+/// or by `Display` otherwise.
+///
+/// `echo!(@iter iter)` outputs the items of `iter` separated by spaces instead.
+/// The items are written directly to the locked stdout without building a `String`.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::write::*;
+/// echo!(1, true, "a");  // "1 true a\n"
+/// let v = vec![1, 2, 3];
+/// echo!(@iter v.iter());  // "1 2 3\n"
+/// echo!(@iter v.iter().filter(|&&x| x > 5));  // "\n"
+/// ```
 #[macro_export]
 macro_rules! echo {
     () => {
         println!()
     };
 
+    (@iter $it: expr $(,)?) => {
+        {
+            let stdout = std::io::stdout();
+            let mut handle = std::io::BufWriter::new(stdout.lock());
+            echo_to!(&mut handle, @iter $it);
+        }
+    };
+
     ($e: expr $(,)?) => {
-        println!("{}", $e)
+        {
+            use std::io::Write;
+            let stdout = std::io::stdout();
+            let mut handle = stdout.lock();
+            (&EchoArg(&$e)).write_echo(&mut handle).unwrap();
+            writeln!(handle).unwrap();
+        }
     };
 
     ($e: expr, $($es: expr),+ $(,)?) => {
@@ -18,15 +52,64 @@ macro_rules! echo {
             use std::io::Write;
             let stdout = std::io::stdout();
             let mut handle = stdout.lock();
-            write!(handle, "{}", $e).unwrap();
+            (&EchoArg(&$e)).write_echo(&mut handle).unwrap();
             $(
-                write!(handle, " {}", $es).unwrap();
+                write!(handle, " ").unwrap();
+                (&EchoArg(&$es)).write_echo(&mut handle).unwrap();
             )+
             writeln!(handle).unwrap();
         }
     };
 }
 
+/// Output values to the writer `out` like [`echo`](macro.echo.html).
+///
+/// Useful with [`with_stdout`](write/fn.with_stdout.html).
+#[macro_export]
+macro_rules! echo_to {
+    ($out: expr $(,)?) => {
+        {
+            use std::io::Write;
+            writeln!($out).unwrap();
+        }
+    };
+
+    ($out: expr, @iter $it: expr $(,)?) => {
+        {
+            use std::io::Write;
+            let mut iter = IntoIterator::into_iter($it);
+            if let Some(first) = iter.next() {
+                (&EchoArg(&first)).write_echo($out).unwrap();
+                for item in iter {
+                    write!($out, " ").unwrap();
+                    (&EchoArg(&item)).write_echo($out).unwrap();
+                }
+            }
+            writeln!($out).unwrap();
+        }
+    };
+
+    ($out: expr, $e: expr $(,)?) => {
+        {
+            use std::io::Write;
+            (&EchoArg(&$e)).write_echo($out).unwrap();
+            writeln!($out).unwrap();
+        }
+    };
+
+    ($out: expr, $e: expr, $($es: expr),+ $(,)?) => {
+        {
+            use std::io::Write;
+            (&EchoArg(&$e)).write_echo($out).unwrap();
+            $(
+                write!($out, " ").unwrap();
+                (&EchoArg(&$es)).write_echo($out).unwrap();
+            )+
+            writeln!($out).unwrap();
+        }
+    };
+}
+
 /// Prints "Yes" or "No" according to `result`.
 pub fn yn(result: bool) {
     if result {
@@ -47,20 +130,599 @@ pub fn YN(result: bool) {
     }
 }
 
+/// Output the items of an iterator, each followed by a newline.
+///
+/// Stdout is locked once, and the items are written without building a `String`.
+/// If the iterator is empty, outputs nothing.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::write::*;
+/// let v = vec![(1, 2), (3, 4)];
+/// echo_each!(v.iter());  // "1 2\n3 4\n"
+/// ```
+#[macro_export]
+macro_rules! echo_each {
+    ($it: expr $(,)?) => {
+        {
+            let stdout = std::io::stdout();
+            let mut handle = std::io::BufWriter::new(stdout.lock());
+            echo_each_to!(&mut handle, $it);
+        }
+    };
+}
+
+/// Output the items of an iterator to the writer `out`
+/// like [`echo_each`](macro.echo_each.html).
+#[macro_export]
+macro_rules! echo_each_to {
+    ($out: expr, $it: expr $(,)?) => {
+        {
+            use std::io::Write;
+            for item in $it {
+                (&EchoArg(&item)).write_echo($out).unwrap();
+                writeln!($out).unwrap();
+            }
+        }
+    };
+}
+
+/// Writes `yes` or `no` according to `result`, followed by a newline.
+///
+/// Writer version of [`yn_with`](fn.yn_with.html).
+pub fn write_yn_with<W: std::io::Write>(writer: &mut W, result: bool, yes: &str, no: &str) -> std::io::Result<()> {
+    writeln!(writer, "{}", if result { yes } else { no })
+}
+
+/// Prints `yes` or `no` according to `result`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::utils::*;
+/// yn_with(true, "Possible", "Impossible");  // "Possible\n"
+/// ```
+pub fn yn_with(result: bool, yes: &str, no: &str) {
+    let stdout = std::io::stdout();
+    write_yn_with(&mut stdout.lock(), result, yes, no).unwrap();
+}
+
+/// Prints "First" or "Second" according to `first_wins`.
+pub fn first_second(first_wins: bool) {
+    yn_with(first_wins, "First", "Second");
+}
+
+/// Prints "Takahashi" or "Aoki" according to `takahashi_wins`.
+pub fn takahashi_aoki(takahashi_wins: bool) {
+    yn_with(takahashi_wins, "Takahashi", "Aoki");
+}
+
+/// Writes the value in `opt`, or `sentinel` if it is `None`, followed by a newline.
+///
+/// Writer version of [`print_or`](fn.print_or.html).
+pub fn write_or<W: std::io::Write, T: Writable>(writer: &mut W, opt: Option<T>, sentinel: &str) -> std::io::Result<()> {
+    with_sentinel(&opt, sentinel).write_to(writer)?;
+    writeln!(writer)
+}
+
+/// Prints the value in `opt`, or `sentinel` if it is `None`.
+///
+/// Typically `sentinel` is "-1", for problems requiring to print -1 if there is no answer.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::utils::*;
+/// let answer = (1..10).find(|x| x * x > 50);
+/// print_or(answer, "-1");  // "8\n"
+/// ```
+pub fn print_or<T: Writable>(opt: Option<T>, sentinel: &str) {
+    let stdout = std::io::stdout();
+    write_or(&mut stdout.lock(), opt, sentinel).unwrap();
+}
+
+/// Prints "Yes" or "No" according to `result` and exits the process successfully.
+pub fn exit_yn(result: bool) -> ! {
+    exit(if result { "Yes" } else { "No" })
+}
+
 /// Prints the given message with newline and exits the process successfully.
 ///
 /// Useful for exiting after printing "-1" or "No" when it is found that
 /// there is no solution for the given input.
+///
+/// Like the other exit helpers, it can be used in [`with_stdout`](write/fn.with_stdout.html);
+/// the buffered output is flushed before exiting.
+/// See [`exit_with_output`](write/fn.exit_with_output.html).
 pub fn exit(msg: impl std::fmt::Display) -> ! {
-    println!("{}", msg);
-    std::process::exit(0)
+    exit_with_output(format!("{}\n", msg).as_bytes())
+}
+
+/// Prints the given message with newline and exits the process successfully,
+/// if `cond` holds.
+///
+/// # Example
+///
+/// ```no_run
+/// # use atcoder_snippets::utils::*;
+/// let answers: Vec<usize> = vec![];
+/// exit_if(answers.is_empty(), -1);
+/// // Reached only if there are answers.
+/// ```
+pub fn exit_if(cond: bool, msg: impl std::fmt::Display) {
+    if cond {
+        exit(msg);
+    }
+}
+
+/// Prints each item followed by a newline and exits the process successfully.
+///
+/// The output is buffered, so printing a lot of items is fast.
+pub fn exit_with_lines<I>(iter: I) -> !
+where
+    I: IntoIterator,
+    I::Item: Writable
+{
+    let mut output = Vec::new();
+    echo_each_to!(&mut output, iter);
+    exit_with_output(&output)
+}
+
+/// Timer measuring the time since it is created.
+///
+/// Useful for heuristic problems, to search for better solutions until the time limit.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::utils::*;
+/// let timer = Timer::new();
+/// let mut best = 0;
+/// let iterations = timer.loop_until(10, |_elapsed_ms| {
+///     best += 1;
+///     true
+/// });
+/// assert_eq!(best, iterations);
+/// assert!(timer.elapsed_ms() >= 10);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Timer {
+    start: std::time::Instant
+}
+
+impl Timer {
+    /// Number of iterations between clock checks in [`loop_until`](#method.loop_until).
+    pub const DEFAULT_CHECK_INTERVAL: u32 = 100;
+
+    /// Starts a timer.
+    pub fn new() -> Timer {
+        Timer { start: std::time::Instant::now() }
+    }
+
+    /// Milliseconds passed since the timer started.
+    pub fn elapsed_ms(&self) -> u64 {
+        let elapsed = self.start.elapsed();
+        elapsed.as_secs() * 1000 + elapsed.subsec_millis() as u64
+    }
+
+    /// Milliseconds remaining until `limit_ms` milliseconds have passed since the timer started.
+    ///
+    /// Negative if the limit has already passed.
+    pub fn remaining(&self, limit_ms: u64) -> i64 {
+        limit_ms as i64 - self.elapsed_ms() as i64
+    }
+
+    /// Calls `body` repeatedly until `limit_ms` milliseconds have passed since the timer started,
+    /// or `body` returns `false`.
+    ///
+    /// `body` is called with the elapsed milliseconds.
+    /// To avoid the overhead of reading the clock, the clock is read once per
+    /// [`DEFAULT_CHECK_INTERVAL`](#associatedconstant.DEFAULT_CHECK_INTERVAL) calls,
+    /// so the loop may run a little after the limit.
+    ///
+    /// Returns the number of times `body` is called.
+    pub fn loop_until(&self, limit_ms: u64, body: impl FnMut(u64) -> bool) -> u64 {
+        self.loop_until_with_interval(limit_ms, Timer::DEFAULT_CHECK_INTERVAL, body)
+    }
+
+    /// Same as [`loop_until`](#method.loop_until),
+    /// but reads the clock once per `check_interval` calls of `body`.
+    ///
+    /// If `body` is heavy, small `check_interval` keeps the loop close to the limit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `check_interval` is 0.
+    pub fn loop_until_with_interval(
+        &self, limit_ms: u64, check_interval: u32, mut body: impl FnMut(u64) -> bool
+    ) -> u64 {
+        assert!(check_interval > 0, "check_interval must be positive");
+
+        let mut iterations = 0;
+        loop {
+            let elapsed = self.elapsed_ms();
+            if elapsed >= limit_ms {
+                return iterations;
+            }
+            for _ in 0..check_interval {
+                iterations += 1;
+                if !body(elapsed) {
+                    return iterations;
+                }
+            }
+        }
+    }
+}
+
+impl Default for Timer {
+    fn default() -> Timer {
+        Timer::new()
+    }
+}
+
+/// Temperature for simulated annealing.
+///
+/// Interpolates from `t0` at `progress = 0.0` to `t1` at `progress = 1.0` exponentially.
+/// `progress` is typically the elapsed time divided by the time limit.
+/// Both `t0` and `t1` must be positive.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::utils::*;
+/// assert!((temp(100.0, 1.0, 0.0) - 100.0).abs() < 1e-9);
+/// assert!((temp(100.0, 1.0, 0.5) - 10.0).abs() < 1e-9);
+/// assert!((temp(100.0, 1.0, 1.0) - 1.0).abs() < 1e-9);
+/// ```
+pub fn temp(t0: f64, t1: f64, progress: f64) -> f64 {
+    t0 * (t1 / t0).powf(progress)
+}
+
+/// Cache of a recursive function.
+///
+/// [`get_or_compute`](#method.get_or_compute) passes the cache itself to the function,
+/// so the function can call `get_or_compute` recursively without fighting the borrow checker.
+///
+/// The recursion is not converted into a loop, so deep recursion may overflow the stack.
+/// In that case, use [`run_with_stack`](fn.run_with_stack.html).
+///
+/// # Example
+///
+/// Computes the number of steps for Collatz sequences to reach 1.
+///
+/// ```
+/// # use atcoder_snippets::utils::*;
+/// fn collatz(memo: &mut Memo<u64, u32>, n: u64) -> u32 {
+///     memo.get_or_compute(n, |memo, &n| {
+///         if n == 1 {
+///             0
+///         } else if n % 2 == 0 {
+///             collatz(memo, n / 2) + 1
+///         } else {
+///             collatz(memo, 3 * n + 1) + 1
+///         }
+///     })
+/// }
+///
+/// let mut memo = Memo::new();
+/// let table: Vec<u32> = (1..=10).map(|n| collatz(&mut memo, n)).collect();
+/// assert_eq!(table, vec![0, 1, 7, 2, 5, 8, 16, 3, 19, 6]);
+/// assert_eq!(memo.get(&16), Some(&4));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: std::collections::HashMap<K, V>
+}
+
+impl<K: std::hash::Hash + Eq, V: Clone> Memo<K, V> {
+    /// Creates an empty cache.
+    pub fn new() -> Memo<K, V> {
+        Memo { cache: std::collections::HashMap::new() }
+    }
+
+    /// Gets the cached value for `key`.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// Gets the cached value for `key`, or computes it by `f` and caches it.
+    ///
+    /// `f` takes this cache and `key`.
+    pub fn get_or_compute(&mut self, key: K, f: impl FnOnce(&mut Memo<K, V>, &K) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            return value.clone();
+        }
+
+        let value = f(self, &key);
+        self.cache.insert(key, value.clone());
+        value
+    }
+}
+
+impl<K: std::hash::Hash + Eq, V: Clone> Default for Memo<K, V> {
+    fn default() -> Memo<K, V> {
+        Memo::new()
+    }
+}
+
+/// Defines a function caching its results for each arguments.
+///
+/// The arguments must be `Clone + Hash + Eq`, and the return value must be `Clone`.
+/// The cache is kept in a thread-local storage while the program runs.
+///
+/// The recursion is not converted into a loop, so deep recursion may overflow the stack.
+/// In that case, use [`run_with_stack`](utils/fn.run_with_stack.html).
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// memoize! {
+///     fn binom(n: usize, k: usize) -> u64 {
+///         if k == 0 || k == n {
+///             1
+///         } else {
+///             binom(n - 1, k - 1) + binom(n - 1, k)
+///         }
+///     }
+/// }
+///
+/// assert_eq!(binom(60, 30), 118264581564861424);
+/// ```
+#[macro_export]
+macro_rules! memoize {
+    ($(#[$attr: meta])* $vis: vis fn $name: ident($($arg: ident : $arg_ty: ty),* $(,)?) -> $ret: ty $body: block) => {
+        $(#[$attr])*
+        $vis fn $name($($arg: $arg_ty),*) -> $ret {
+            thread_local! {
+                static CACHE: std::cell::RefCell<std::collections::HashMap<($($arg_ty,)*), $ret>>
+                    = std::cell::RefCell::new(std::collections::HashMap::new());
+            }
+
+            fn compute($($arg: $arg_ty),*) -> $ret $body
+
+            let key = ($($arg.clone(),)*);
+            if let Some(value) = CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+                return value;
+            }
+            let value = compute($($arg),*);
+            CACHE.with(|cache| cache.borrow_mut().insert(key, value.clone()));
+            value
+        }
+    };
+}
+
+#[derive(Default)]
+struct ProfileRecords {
+    entries: std::collections::BTreeMap<String, ProfileEntry>
+}
+
+#[derive(Default)]
+struct ProfileEntry {
+    count: usize,
+    total: std::time::Duration,
+    // Number of running blocks with the label, to measure recursive blocks only once.
+    active: usize
+}
+
+impl ProfileRecords {
+    fn summary(&self) -> String {
+        let mut summary = String::from("profile:\n");
+        for (label, entry) in &self.entries {
+            summary.push_str(&format!(
+                "  {}: {:.3} ms in {} calls\n",
+                label, entry.total.as_secs_f64() * 1000.0, entry.count
+            ));
+        }
+        summary
+    }
+}
+
+impl Drop for ProfileRecords {
+    fn drop(&mut self) {
+        if !self.entries.is_empty() {
+            eprint!("{}", self.summary());
+        }
+    }
+}
+
+thread_local! {
+    static PROFILE: std::cell::RefCell<ProfileRecords> = std::cell::RefCell::new(ProfileRecords::default());
+}
+
+/// Guard measuring the time until dropped, used by [`profile`](../macro.profile.html) macro.
+pub struct ProfileGuard {
+    label: String,
+    start: std::time::Instant
+}
+
+impl ProfileGuard {
+    /// Starts measuring a block with `label`.
+    pub fn new(label: &str) -> ProfileGuard {
+        PROFILE.with(|profile| {
+            let mut profile = profile.borrow_mut();
+            let entry = profile.entries.entry(label.to_string()).or_insert_with(ProfileEntry::default);
+            entry.count += 1;
+            entry.active += 1;
+        });
+        ProfileGuard { label: label.to_string(), start: std::time::Instant::now() }
+    }
+}
+
+impl Drop for ProfileGuard {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        PROFILE.with(|profile| {
+            let mut profile = profile.borrow_mut();
+            let entry = profile.entries.get_mut(&self.label).unwrap();
+            entry.active -= 1;
+            if entry.active == 0 {
+                entry.total += elapsed;
+            }
+        });
+    }
+}
+
+/// Summary of the blocks measured by [`profile`](../macro.profile.html) macro in the current thread.
+///
+/// For each label in the dictionary order, the total time and the number of calls are shown.
+pub fn profile_summary() -> String {
+    PROFILE.with(|profile| profile.borrow().summary())
+}
+
+/// Measures the time to run the given block.
+///
+/// `profile!("label", { ... })` runs the block and returns its value.
+/// In the local machine, the total time and the number of runs are recorded for each label,
+/// and the summary is printed to stderr when the thread exits.
+/// The summary is not printed if the process exits by `std::process::exit`,
+/// but [`profile_summary`](utils/fn.profile_summary.html) returns it anytime.
+///
+/// Blocks can be nested. If a block with the same label runs recursively,
+/// only the outermost one is counted in the total time.
+///
+/// In the judge server, it is just the block.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::utils::*;
+/// let sum = profile!("sum", {
+///     (0..1000u64).sum::<u64>()
+/// });
+/// assert_eq!(sum, 499500);
+/// ```
+#[macro_export]
+#[cfg(local)]
+macro_rules! profile {
+    ($label: expr, $body: block) => {
+        {
+            let _guard = ProfileGuard::new($label);
+            $body
+        }
+    };
 }
 
-/// Make a debug output of the given expression to stderr.
+/// Measures the time to run the given block.
+///
+/// `profile!("label", { ... })` runs the block and returns its value.
+/// In the local machine, the total time and the number of runs are recorded for each label,
+/// and the summary is printed to stderr when the thread exits.
+/// The summary is not printed if the process exits by `std::process::exit`,
+/// but [`profile_summary`](utils/fn.profile_summary.html) returns it anytime.
+///
+/// Blocks can be nested. If a block with the same label runs recursively,
+/// only the outermost one is counted in the total time.
+///
+/// In the judge server, it is just the block.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::utils::*;
+/// let sum = profile!("sum", {
+///     (0..1000u64).sum::<u64>()
+/// });
+/// assert_eq!(sum, 499500);
+/// ```
+#[macro_export]
+#[cfg(not(local))]
+macro_rules! profile {
+    ($label: expr, $body: block) => {
+        $body
+    };
+}
+
+/// Stack size used by [`main_with_stack`](../macro.main_with_stack.html) by default.
+pub const DEFAULT_STACK_SIZE: usize = 256 << 20;
+
+/// Runs `f` on a new thread with a stack of `bytes` bytes, and returns its result.
+///
+/// Useful for deep recursion, such as DFS on a path-shaped tree of 2×10^5 nodes,
+/// which overflows the default stack of the main thread.
+///
+/// If `f` panics, the panic is propagated to the caller with the original message.
+///
+/// Stdout is not locked by this function, so `echo!` and
+/// [`with_stdout`](write/fn.with_stdout.html) can be used in `f` as usual.
+/// Exit helpers such as [`exit`](fn.exit.html) called in `f` are propagated to the caller,
+/// so they work even if this function is called in `with_stdout`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::utils::*;
+/// fn depth(n: u64) -> u64 {
+///     if n == 0 { 0 } else { depth(n - 1) + 1 }
+/// }
+///
+/// // Overflows the stack without `run_with_stack`.
+/// assert_eq!(run_with_stack(DEFAULT_STACK_SIZE, || depth(1_000_000)), 1_000_000);
+/// ```
+pub fn run_with_stack<T, F>(bytes: usize, f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static
+{
+    let handle = std::thread::Builder::new()
+        .stack_size(bytes)
+        .spawn(f)
+        .expect("failed to spawn a thread");
+    match handle.join() {
+        Ok(result) => result,
+        Err(payload) => std::panic::resume_unwind(payload)
+    }
+}
+
+/// Defines `main` function running its body by [`run_with_stack`](utils/fn.run_with_stack.html).
+///
+/// `main_with_stack!(fn main() { ... })` uses [`DEFAULT_STACK_SIZE`](utils/constant.DEFAULT_STACK_SIZE.html),
+/// and `main_with_stack!(bytes; fn main() { ... })` uses a stack of `bytes` bytes.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::utils::*;
+/// main_with_stack!(512 << 20; fn main() {
+///     println!("Hello, world!");
+/// });
+/// ```
+#[macro_export]
+macro_rules! main_with_stack {
+    (fn main() $body: block) => {
+        main_with_stack!(DEFAULT_STACK_SIZE; fn main() $body);
+    };
+
+    ($bytes: expr; fn main() $body: block) => {
+        fn main() {
+            run_with_stack($bytes, || $body)
+        }
+    };
+}
+
+/// Make a debug output of the given expressions to stderr.
 ///
 /// The output is made only in the local machine, not in the judge server.
 ///
 /// Similar to `dbg` macro in Rust 1.32.0.
+/// `dbg!(e)` returns the value of `e`, and `dbg!(e1, e2, ...)` returns
+/// the tuple of the values.
+/// Each expression is evaluated exactly once, both in the local machine and the judge server.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// let a = 2;
+/// let b = dbg!(a * 3);
+/// let (c, d) = dbg!(a + b, b * b);
+/// assert_eq!((b, c, d), (6, 8, 36));
+/// ```
 #[macro_export]
 #[cfg(local)]
 macro_rules! dbg {
@@ -71,7 +733,7 @@ macro_rules! dbg {
         }
     };
 
-    ($e: expr) => {
+    ($e: expr $(,)?) => {
         {
             use std::io::{self, Write};
             let result = $e;
@@ -80,21 +742,560 @@ macro_rules! dbg {
                 .unwrap();
             result
         }
+    };
+
+    ($($e: expr),+ $(,)?) => {
+        ($(dbg!($e)),+)
     }
 }
 
-/// Make a debug output of the given expression to stderr.
+/// Make a debug output of the given expressions to stderr.
 ///
 /// The output is made only in the local machine, not in the judge server.
 ///
 /// Similar to `dbg` macro in Rust 1.32.0.
+/// `dbg!(e)` returns the value of `e`, and `dbg!(e1, e2, ...)` returns
+/// the tuple of the values.
+/// Each expression is evaluated exactly once, both in the local machine and the judge server.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// let a = 2;
+/// let b = dbg!(a * 3);
+/// let (c, d) = dbg!(a + b, b * b);
+/// assert_eq!((b, c, d), (6, 8, 36));
+/// ```
 #[macro_export]
 #[cfg(not(local))]
 macro_rules! dbg {
     () => {};
-    ($e: expr) => {
+
+    ($e: expr $(,)?) => {
         { $e }
+    };
+
+    ($($e: expr),+ $(,)?) => {
+        ($($e),+)
+    }
+}
+
+/// Formats the first and last `k` items of a slice and its length, for debugging.
+///
+/// [`dbg_slice`](../macro.dbg_slice.html) macro prints this string to stderr.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::utils::*;
+/// let v: Vec<u32> = (0..200_000).collect();
+/// assert_eq!(debug_slice(&v, 2), "[0, 1, ..., 199998, 199999] (len 200000)");
+/// assert_eq!(debug_slice(&v[..3], 2), "[0, 1, 2] (len 3)");
+/// ```
+pub fn debug_slice<T: std::fmt::Debug>(slice: &[T], k: usize) -> String {
+    if slice.len() <= 2 * k {
+        format!("{:?} (len {})", slice, slice.len())
+    } else {
+        let items: Vec<String> = slice[..k].iter()
+            .map(|item| format!("{:?}", item))
+            .chain(std::iter::once("...".to_string()))
+            .chain(slice[slice.len() - k..].iter().map(|item| format!("{:?}", item)))
+            .collect();
+        format!("[{}] (len {})", items.join(", "), slice.len())
+    }
+}
+
+/// Formats entries of a map sorted by keys, for debugging.
+///
+/// Useful to get a stable output of `HashMap`.
+/// [`dbg_map`](../macro.dbg_map.html) macro prints this string to stderr.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::utils::*;
+/// use std::collections::HashMap;
+/// let map: HashMap<char, u32> = "hello".chars().zip(0..).collect();
+/// assert_eq!(debug_map(&map), "{'e': 1, 'h': 0, 'l': 3, 'o': 4}");
+/// ```
+pub fn debug_map<'a, K, V, I>(map: I) -> String
+where
+    K: Ord + std::fmt::Debug + 'a,
+    V: std::fmt::Debug + 'a,
+    I: IntoIterator<Item=(&'a K, &'a V)>
+{
+    format!("{:?}", map.into_iter().collect::<std::collections::BTreeMap<_, _>>())
+}
+
+/// Prints the first and last items of a slice and its length to stderr.
+///
+/// `dbg_slice!(v, k)` shows at most `k` first and `k` last items,
+/// and `dbg_slice!(v)` is the same as `dbg_slice!(v, 5)`.
+/// The output is made only in the local machine, not in the judge server.
+///
+/// See [`debug_slice`](utils/fn.debug_slice.html) for the format.
+#[macro_export]
+#[cfg(local)]
+macro_rules! dbg_slice {
+    ($v: expr) => {
+        dbg_slice!($v, 5)
+    };
+
+    ($v: expr, $k: expr) => {
+        {
+            use std::io::{self, Write};
+            writeln!(io::stderr(), "{}: {} = {}",
+                     line!(), stringify!($v), debug_slice(&$v, $k))
+                .unwrap();
+        }
+    }
+}
+
+/// Prints the first and last items of a slice and its length to stderr.
+///
+/// `dbg_slice!(v, k)` shows at most `k` first and `k` last items,
+/// and `dbg_slice!(v)` is the same as `dbg_slice!(v, 5)`.
+/// The output is made only in the local machine, not in the judge server.
+///
+/// See [`debug_slice`](utils/fn.debug_slice.html) for the format.
+#[macro_export]
+#[cfg(not(local))]
+macro_rules! dbg_slice {
+    ($v: expr $(, $k: expr)?) => {
+        {
+            if false {
+                let _ = &$v;
+            }
+        }
+    }
+}
+
+/// Prints entries of a map sorted by keys to stderr.
+///
+/// The output is made only in the local machine, not in the judge server.
+///
+/// See [`debug_map`](utils/fn.debug_map.html) for the format.
+#[macro_export]
+#[cfg(local)]
+macro_rules! dbg_map {
+    ($m: expr) => {
+        {
+            use std::io::{self, Write};
+            writeln!(io::stderr(), "{}: {} = {}",
+                     line!(), stringify!($m), debug_map(&$m))
+                .unwrap();
+        }
+    }
+}
+
+/// Prints entries of a map sorted by keys to stderr.
+///
+/// The output is made only in the local machine, not in the judge server.
+///
+/// See [`debug_map`](utils/fn.debug_map.html) for the format.
+#[macro_export]
+#[cfg(not(local))]
+macro_rules! dbg_map {
+    ($m: expr) => {
+        {
+            if false {
+                let _ = &$m;
+            }
+        }
     }
 }
 
 // END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(f: impl FnOnce(&mut Vec<u8>) -> std::io::Result<()>) -> String {
+        let mut buf = Vec::new();
+        f(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    fn echo_iter_output<I>(iter: I) -> String
+    where
+        I: IntoIterator,
+        I::Item: Writable
+    {
+        output(|w| {
+            echo_to!(w, @iter iter);
+            Ok(())
+        })
+    }
+
+    fn echo_each_output<I>(iter: I) -> String
+    where
+        I: IntoIterator,
+        I::Item: Writable
+    {
+        output(|w| {
+            echo_each_to!(w, iter);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn test_echo_iter() {
+        assert_eq!(echo_iter_output(Vec::<u32>::new()), "\n");
+        assert_eq!(echo_iter_output(vec![1]), "1\n");
+        assert_eq!(echo_iter_output(&[1, 2, 3]), "1 2 3\n");
+        assert_eq!(echo_iter_output(vec![(1, 'a'), (2, 'b')]), "1 a 2 b\n");
+
+        let v: Vec<u64> = (0..100_000).map(|i| i * 997 % 100_003).collect();
+        let expected = v.iter().map(|x| x.to_string()).collect::<Vec<_>>().join(" ") + "\n";
+        let actual = echo_iter_output(v.iter());
+        assert!(!actual.ends_with(" \n"));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_echo_each() {
+        assert_eq!(echo_each_output(Vec::<u32>::new()), "");
+        assert_eq!(echo_each_output(vec![1]), "1\n");
+        assert_eq!(echo_each_output(vec![(1, 2), (3, 4)]), "1 2\n3 4\n");
+
+        let v: Vec<i64> = (0..100_000).map(|i| (i * 997 % 100_003) - 50_000).collect();
+        let expected: String = v.iter().map(|x| format!("{}\n", x)).collect();
+        let actual = echo_each_output(&v);
+        assert!(actual.lines().all(|line| !line.ends_with(' ')));
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_echo_display_fallback() {
+        use crate::read::{Readable, Fixed, Scale2};
+        use crate::write::EchoByDisplay;
+
+        struct Point(i32, i32);
+
+        impl std::fmt::Display for Point {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "({}, {})", self.0, self.1)
+            }
+        }
+
+        #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+        struct Score(i64);
+        impl_arith_newtype!(Score(i64));
+
+        let fixed = Fixed::<Scale2>::read_words(&["1.5"]).unwrap();
+        assert_eq!(output(|w| {
+            echo_to!(w, true);
+            echo_to!(w, 1, false);
+            echo_to!(w, Point(1, -2), fixed, Score(3));
+            echo_to!(w, @iter vec![Point(0, 0), Point(1, 1)]);
+            Ok(())
+        }), format!("true\n1 false\n(1, -2) {} 3\n(0, 0) (1, 1)\n", fixed));
+    }
+
+    #[test]
+    fn test_dbg() {
+        let mut count = 0;
+        let mut next = || {
+            count += 1;
+            count
+        };
+
+        dbg!();
+        assert_eq!(dbg!(next()), 1);
+        assert_eq!(dbg!(next(),), 2);
+        assert_eq!(dbg!(next(), next() * 10), (3, 40));
+        let (a, b, c) = dbg!(next(), "label", vec![next()]);
+        assert_eq!((a, b, c), (5, "label", vec![6]));
+        assert_eq!(count, 6);
+    }
+
+    #[test]
+    fn test_profile() {
+        let mut sum = 0;
+        for i in 0..100 {
+            let square = profile!("loop", {
+                profile!("inner", {
+                    sum += i;
+                });
+                i * i
+            });
+            assert_eq!(square, i * i);
+        }
+        assert_eq!(sum, 4950);
+    }
+
+    #[test]
+    #[cfg(local)]
+    fn test_profile_summary() {
+        fn recursion(n: usize) -> usize {
+            profile!("recursion", {
+                if n == 0 { 0 } else { recursion(n - 1) + 1 }
+            })
+        }
+
+        for _ in 0..10 {
+            profile!("outer", {
+                std::thread::sleep(std::time::Duration::from_millis(2));
+                profile!("inner", {
+                    std::thread::sleep(std::time::Duration::from_millis(1));
+                });
+            });
+        }
+        assert_eq!(recursion(5), 5);
+
+        PROFILE.with(|profile| {
+            let profile = profile.borrow();
+            let outer = &profile.entries["outer"];
+            let inner = &profile.entries["inner"];
+            assert_eq!(outer.count, 10);
+            assert_eq!(inner.count, 10);
+            assert!(outer.total >= std::time::Duration::from_millis(30));
+            assert!(inner.total >= std::time::Duration::from_millis(10));
+            assert!(outer.total > inner.total);
+            assert_eq!(profile.entries["recursion"].count, 6);
+            assert!(profile.entries.values().all(|entry| entry.active == 0));
+        });
+
+        let summary = profile_summary();
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "profile:");
+        assert!(lines[1].starts_with("  inner: ") && lines[1].ends_with(" ms in 10 calls"));
+        assert!(lines[2].starts_with("  outer: ") && lines[2].ends_with(" ms in 10 calls"));
+        assert!(lines[3].starts_with("  recursion: ") && lines[3].ends_with(" ms in 6 calls"));
+    }
+
+    #[test]
+    fn test_debug_slice() {
+        let v: Vec<i32> = (0..200_000).collect();
+        assert_eq!(debug_slice(&v, 3), "[0, 1, 2, ..., 199997, 199998, 199999] (len 200000)");
+        assert_eq!(debug_slice(&v[..6], 3), "[0, 1, 2, 3, 4, 5] (len 6)");
+        assert_eq!(debug_slice(&v[..7], 3), "[0, 1, 2, ..., 4, 5, 6] (len 7)");
+        assert_eq!(debug_slice(&v[..3], 0), "[...] (len 3)");
+        assert_eq!(debug_slice::<i32>(&[], 3), "[] (len 0)");
+        assert_eq!(debug_slice(&["a", "b", "c"], 1), r#"["a", ..., "c"] (len 3)"#);
+        dbg_slice!(v);
+        dbg_slice!(v, 1);
+    }
+
+    #[test]
+    fn test_debug_map() {
+        use std::collections::HashMap;
+
+        let map: HashMap<u32, &str> = vec![(30, "c"), (10, "a"), (20, "b")].into_iter().collect();
+        assert_eq!(debug_map(&map), r#"{10: "a", 20: "b", 30: "c"}"#);
+        assert_eq!(debug_map(&HashMap::<u32, u32>::new()), "{}");
+        dbg_map!(map);
+    }
+
+    #[test]
+    #[cfg(not(local))]
+    fn test_dbg_evaluates_nothing() {
+        let mut count = 0;
+        let mut side_effect = || {
+            count += 1;
+            vec![0]
+        };
+        dbg_slice!(side_effect());
+        dbg_map!(side_effect().iter().map(|x| (x, x)).collect::<std::collections::HashMap<_, _>>());
+        assert_eq!(count, 0);
+    }
+
+    // Each call holds an array so that the recursion is not optimized away.
+    fn deep_recursion(n: usize) -> usize {
+        let frame = [n; 4];
+        if n == 0 {
+            0
+        } else {
+            deep_recursion(n - 1) + frame[n % 4] - n + 1
+        }
+    }
+
+    #[test]
+    fn test_run_with_stack() {
+        // Overflows the stack of a test thread (2 MiB) without `run_with_stack`.
+        assert_eq!(run_with_stack(DEFAULT_STACK_SIZE, || deep_recursion(1_000_000)), 1_000_000);
+
+        let v: Vec<u32> = (0..10).collect();
+        assert_eq!(run_with_stack(1 << 20, move || v.iter().sum::<u32>()), 45);
+    }
+
+    #[test]
+    #[should_panic(expected = "failed inside the thread")]
+    fn test_run_with_stack_panic() {
+        run_with_stack(1 << 20, || panic!("failed inside the thread"));
+    }
+
+    #[test]
+    fn test_timer_loop_until() {
+        let timer = Timer::new();
+        let mut last_elapsed = 0;
+        let iterations = timer.loop_until(50, |elapsed| {
+            assert!(elapsed >= last_elapsed);
+            last_elapsed = elapsed;
+            true
+        });
+        let elapsed = timer.elapsed_ms();
+        assert!(iterations > 0);
+        assert!(last_elapsed < 50);
+        assert!(elapsed >= 50, "elapsed {} ms", elapsed);
+        assert!(timer.remaining(50) <= 0);
+        assert!(timer.remaining(1_000_000) > 0);
+    }
+
+    #[test]
+    fn test_timer_loop_until_with_interval() {
+        let sleep = std::time::Duration::from_millis(1);
+
+        // The clock is read every 10 calls, each of which takes 1 ms or more.
+        let timer = Timer::new();
+        let mut elapsed_args = Vec::new();
+        let iterations = timer.loop_until_with_interval(30, 10, |elapsed| {
+            elapsed_args.push(elapsed);
+            std::thread::sleep(sleep);
+            true
+        });
+        let elapsed = timer.elapsed_ms();
+        assert_eq!(iterations % 10, 0);
+        assert_eq!(elapsed_args.len() as u64, iterations);
+        for batch in elapsed_args.chunks(10) {
+            assert!(batch.iter().all(|&e| e == batch[0] && e < 30));
+        }
+        for batches in elapsed_args.chunks(10).collect::<Vec<_>>().windows(2) {
+            assert!(batches[0][0] < batches[1][0]);
+        }
+        assert!(elapsed >= 30, "elapsed {} ms", elapsed);
+
+        let timer = Timer::new();
+        let mut count = 0;
+        let iterations = timer.loop_until(1_000_000, |_| {
+            count += 1;
+            count < 250
+        });
+        assert_eq!(iterations, 250);
+        assert_eq!(count, 250);
+    }
+
+    #[test]
+    fn test_temp() {
+        assert!((temp(1000.0, 10.0, 0.0) - 1000.0).abs() < 1e-9);
+        assert!((temp(1000.0, 10.0, 0.5) - 100.0).abs() < 1e-9);
+        assert!((temp(1000.0, 10.0, 1.0) - 10.0).abs() < 1e-9);
+        assert!(temp(1000.0, 10.0, 0.3) > temp(1000.0, 10.0, 0.7));
+    }
+
+    #[test]
+    fn test_memo() {
+        use std::collections::HashMap;
+
+        fn fib(memo: &mut Memo<u64, u64>, counts: &mut HashMap<u64, usize>, n: u64) -> u64 {
+            memo.get_or_compute(n, |memo, &n| {
+                *counts.entry(n).or_insert(0) += 1;
+                if n < 2 {
+                    n
+                } else {
+                    fib(memo, counts, n - 1) + fib(memo, counts, n - 2)
+                }
+            })
+        }
+
+        let mut memo = Memo::new();
+        let mut counts = HashMap::new();
+        assert_eq!(fib(&mut memo, &mut counts, 90), 2880067194370816120);
+        assert_eq!(fib(&mut memo, &mut counts, 50), 12586269025);
+        assert_eq!(counts.len(), 91);
+        assert!(counts.values().all(|&count| count == 1));
+        assert_eq!(memo.get(&10), Some(&55));
+        assert_eq!(memo.get(&91), None);
+    }
+
+    thread_local! {
+        static GRID_PATHS_COUNTS: std::cell::RefCell<std::collections::HashMap<(usize, usize), usize>>
+            = std::cell::RefCell::new(std::collections::HashMap::new());
+    }
+
+    memoize! {
+        fn grid_paths(h: usize, w: usize) -> u64 {
+            GRID_PATHS_COUNTS.with(|counts| *counts.borrow_mut().entry((h, w)).or_insert(0) += 1);
+            if h == 0 || w == 0 {
+                1
+            } else {
+                grid_paths(h - 1, w) + grid_paths(h, w - 1)
+            }
+        }
+    }
+
+    #[test]
+    fn test_memoize() {
+        assert_eq!(grid_paths(16, 16), 601080390);
+        assert_eq!(grid_paths(10, 12), 646646);
+        GRID_PATHS_COUNTS.with(|counts| {
+            let counts = counts.borrow();
+            assert_eq!(counts.len(), 17 * 17 - 1);
+            assert!(counts.values().all(|&count| count == 1));
+        });
+    }
+
+    #[test]
+    fn test_exit_in_run_with_stack() {
+        use crate::write::with_writer_catching_exit;
+
+        let (out, exit_requested) = with_writer_catching_exit(Vec::new(), |out| {
+            echo_to!(out, "before exit");
+            run_with_stack(1 << 20, || exit_yn(true));
+        });
+        assert_eq!(String::from_utf8(out).unwrap(), "before exit\nYes\n");
+        assert!(exit_requested);
+    }
+
+    #[test]
+    fn test_exit_flushes_buffer() {
+        use crate::write::with_writer_catching_exit;
+
+        let (out, exit_requested) = with_writer_catching_exit(Vec::new(), |out| {
+            echo_to!(out, "before exit");
+            exit_if(false, -1);
+            exit_if(true, -1);
+        });
+        assert_eq!(String::from_utf8(out).unwrap(), "before exit\n-1\n");
+        assert!(exit_requested);
+
+        let (out, exit_requested) = with_writer_catching_exit(Vec::new(), |out| {
+            echo_to!(out, 2);
+            exit_with_lines(vec![(1, 2), (3, 4)]);
+        });
+        assert_eq!(String::from_utf8(out).unwrap(), "2\n1 2\n3 4\n");
+        assert!(exit_requested);
+
+        let (out, exit_requested) = with_writer_catching_exit(Vec::new(), |out| {
+            echo_to!(out, "answer");
+            exit_yn(false);
+        });
+        assert_eq!(String::from_utf8(out).unwrap(), "answer\nNo\n");
+        assert!(exit_requested);
+
+        let (out, _) = with_writer_catching_exit(Vec::new(), |_| {
+            exit_with_lines(0..100_000);
+        });
+        let expected: String = (0..100_000).map(|i| format!("{}\n", i)).collect();
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_write_yn_with() {
+        assert_eq!(output(|w| write_yn_with(w, true, "Possible", "Impossible")), "Possible\n");
+        assert_eq!(output(|w| write_yn_with(w, false, "Possible", "Impossible")), "Impossible\n");
+        assert_eq!(output(|w| write_yn_with(w, true, "First", "Second")), "First\n");
+        assert_eq!(output(|w| write_yn_with(w, false, "Takahashi", "Aoki")), "Aoki\n");
+    }
+
+    #[test]
+    fn test_write_or() {
+        assert_eq!(output(|w| write_or(w, Some(42), "-1")), "42\n");
+        assert_eq!(output(|w| write_or(w, None::<i32>, "-1")), "-1\n");
+        assert_eq!(output(|w| write_or(w, Some((1, 2)), "-1")), "1 2\n");
+        assert_eq!(output(|w| write_or(w, None::<(i32, i32)>, "No")), "No\n");
+    }
+}
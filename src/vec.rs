@@ -20,8 +20,9 @@
 //! ```
 
 use crate::read::Readable;
+use crate::write::Writable;
 
-// BEGIN SNIPPET vec DEPENDS ON read
+// BEGIN SNIPPET vec DEPENDS ON read write op_macros
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Vec2<T> {
@@ -75,6 +76,12 @@ impl<T: std::fmt::Display> std::fmt::Display for Vec2<T> {
     }
 }
 
+impl<T: Writable> Writable for Vec2<T> {
+    fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        (&self.x, &self.y).write_to(writer)
+    }
+}
+
 impl<S, T: std::ops::Add<S>> std::ops::Add<Vec2<S>> for Vec2<T> {
     type Output = Vec2<T::Output>;
 
@@ -113,6 +120,12 @@ impl<T: std::ops::Neg> std::ops::Neg for Vec2<T> {
     }
 }
 
+forward_ref_binop_generic!(impl[S: Copy, T: Copy + std::ops::Add<S>] Add, add for Vec2<T>, Vec2<S>);
+forward_ref_op_assign_generic!(impl[S: Copy, T: std::ops::AddAssign<S>] AddAssign, add_assign for Vec2<T>, Vec2<S>);
+forward_ref_binop_generic!(impl[S: Copy, T: Copy + std::ops::Sub<S>] Sub, sub for Vec2<T>, Vec2<S>);
+forward_ref_op_assign_generic!(impl[S: Copy, T: std::ops::SubAssign<S>] SubAssign, sub_assign for Vec2<T>, Vec2<S>);
+forward_ref_unop_generic!(impl[T: Copy + std::ops::Neg] Neg, neg for Vec2<T>);
+
 macro_rules! impl_mul_vec2 {
     ( $($t: ty)* ) => { $(
         impl std::ops::Mul<Vec2<$t>> for $t {
@@ -204,6 +217,12 @@ impl<T: std::fmt::Display> std::fmt::Display for Vec3<T> {
     }
 }
 
+impl<T: Writable> Writable for Vec3<T> {
+    fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        (&self.x, &self.y, &self.z).write_to(writer)
+    }
+}
+
 impl<S, T: std::ops::Add<S>> std::ops::Add<Vec3<S>> for Vec3<T> {
     type Output = Vec3<T::Output>;
 
@@ -244,6 +263,12 @@ impl<T: std::ops::Neg> std::ops::Neg for Vec3<T> {
     }
 }
 
+forward_ref_binop_generic!(impl[S: Copy, T: Copy + std::ops::Add<S>] Add, add for Vec3<T>, Vec3<S>);
+forward_ref_op_assign_generic!(impl[S: Copy, T: std::ops::AddAssign<S>] AddAssign, add_assign for Vec3<T>, Vec3<S>);
+forward_ref_binop_generic!(impl[S: Copy, T: Copy + std::ops::Sub<S>] Sub, sub for Vec3<T>, Vec3<S>);
+forward_ref_op_assign_generic!(impl[S: Copy, T: std::ops::SubAssign<S>] SubAssign, sub_assign for Vec3<T>, Vec3<S>);
+forward_ref_unop_generic!(impl[T: Copy + std::ops::Neg] Neg, neg for Vec3<T>);
+
 macro_rules! impl_mul_vec3 {
     ( $($t: ty)* ) => { $(
         impl std::ops::Mul<Vec3<$t>> for $t {
@@ -330,6 +355,9 @@ mod test {
         let v1 = Vec2::new(1, 2);
         let v2 = Vec2::new(10, 20);
         assert_eq!(v1 + v2, Vec2::new(11, 22));
+        assert_eq!(v1 + &v2, Vec2::new(11, 22));
+        assert_eq!(&v1 + v2, Vec2::new(11, 22));
+        assert_eq!(&v1 + &v2, Vec2::new(11, 22));
     }
 
     #[test]
@@ -337,6 +365,10 @@ mod test {
         let mut v = Vec2::new(1, 2);
         v += Vec2::new(10, 20);
         assert_eq!(v, Vec2::new(11, 22));
+
+        let mut v_for_ref = Vec2::new(1, 2);
+        v_for_ref += &Vec2::new(10, 20);
+        assert_eq!(v_for_ref, Vec2::new(11, 22));
     }
 
     #[test]
@@ -344,6 +376,9 @@ mod test {
         let v1 = Vec2::new(1, 2);
         let v2 = Vec2::new(10, 20);
         assert_eq!(v1 - v2, Vec2::new(-9, -18));
+        assert_eq!(v1 - &v2, Vec2::new(-9, -18));
+        assert_eq!(&v1 - v2, Vec2::new(-9, -18));
+        assert_eq!(&v1 - &v2, Vec2::new(-9, -18));
     }
 
     #[test]
@@ -351,12 +386,17 @@ mod test {
         let mut v = Vec2::new(1, 2);
         v -= Vec2::new(10, 20);
         assert_eq!(v, Vec2::new(-9, -18));
+
+        let mut v_for_ref = Vec2::new(1, 2);
+        v_for_ref -= &Vec2::new(10, 20);
+        assert_eq!(v_for_ref, Vec2::new(-9, -18));
     }
 
     #[test]
     fn test_vec2_neg() {
         let v = Vec2::new(1, 2);
         assert_eq!(-v, Vec2::new(-1, -2));
+        assert_eq!(-&v, Vec2::new(-1, -2));
     }
 
     #[test]
@@ -404,6 +444,9 @@ mod test {
         let v1 = Vec3::new(1, 2, 3);
         let v2 = Vec3::new(10, 20, 30);
         assert_eq!(v1 + v2, Vec3::new(11, 22, 33));
+        assert_eq!(v1 + &v2, Vec3::new(11, 22, 33));
+        assert_eq!(&v1 + v2, Vec3::new(11, 22, 33));
+        assert_eq!(&v1 + &v2, Vec3::new(11, 22, 33));
     }
 
     #[test]
@@ -411,6 +454,10 @@ mod test {
         let mut v = Vec3::new(1, 2, 3);
         v += Vec3::new(10, 20, 30);
         assert_eq!(v, Vec3::new(11, 22, 33));
+
+        let mut v_for_ref = Vec3::new(1, 2, 3);
+        v_for_ref += &Vec3::new(10, 20, 30);
+        assert_eq!(v_for_ref, Vec3::new(11, 22, 33));
     }
 
     #[test]
@@ -418,6 +465,9 @@ mod test {
         let v1 = Vec3::new(1, 2, 3);
         let v2 = Vec3::new(10, 20, 30);
         assert_eq!(v1 - v2, Vec3::new(-9, -18, -27));
+        assert_eq!(v1 - &v2, Vec3::new(-9, -18, -27));
+        assert_eq!(&v1 - v2, Vec3::new(-9, -18, -27));
+        assert_eq!(&v1 - &v2, Vec3::new(-9, -18, -27));
     }
 
     #[test]
@@ -425,12 +475,17 @@ mod test {
         let mut v = Vec3::new(1, 2, 3);
         v -= Vec3::new(10, 20, 30);
         assert_eq!(v, Vec3::new(-9, -18, -27));
+
+        let mut v_for_ref = Vec3::new(1, 2, 3);
+        v_for_ref -= &Vec3::new(10, 20, 30);
+        assert_eq!(v_for_ref, Vec3::new(-9, -18, -27));
     }
 
     #[test]
     fn test_vec3_neg() {
         let v = Vec3::new(1, 2, 3);
         assert_eq!(-v, Vec3::new(-1, -2, -3));
+        assert_eq!(-&v, Vec3::new(-1, -2, -3));
     }
 
     #[test]
@@ -5,14 +5,696 @@
 #[allow(unused_imports)]
 use std::io::{self, Write, BufWriter, StdoutLock};
 
+/// Writable to output by [`echo`](../macro.echo.html) and [`echo_to`](../macro.echo_to.html) macros.
+///
+/// The following types are writable:
+///
+/// - Primitive numeric types, `bool`, `char`, `str` and `String`, written in the same way as `Display`.
+///   Note that `f64` is written in the shortest form with no precision error.
+/// - Tuples, `Vec`s and slices of `Writable`s, whose items are separated by spaces.
+/// - `Option` of a `Writable`, written as `-1` if it is `None`.
+///   Use [`with_sentinel`](fn.with_sentinel.html) to change `-1` into another word.
+/// - References to `Writable`s.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::write::*;
+/// let mut out = Vec::new();
+/// vec![(1, 'a'), (2, 'b')].write_to(&mut out).unwrap();
+/// assert_eq!(out, b"1 a 2 b");
+/// ```
+pub trait Writable {
+    /// Writes `self` without a trailing newline.
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+}
+
+macro_rules! impl_writable_by_display {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl Writable for $t {
+                fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                    write!(writer, "{}", self)
+                }
+            }
+        )*
+    };
+}
+
+impl_writable_by_display!(
+    i8, u8, i16, u16, i32, u32, i64, u64, i128, u128, isize, usize, f32, f64, bool, char, str, String
+);
+
+/// Argument of [`echo`](../macro.echo.html) and [`echo_to`](../macro.echo_to.html) macros.
+///
+/// It is written by `Writable` if the value is `Writable`, or by `Display` otherwise.
+/// The choice is made by method resolution of `write_echo`,
+/// which prefers `EchoByWritable` to `EchoByDisplay` taking one more reference.
+#[doc(hidden)]
+pub struct EchoArg<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait EchoByWritable {
+    fn write_echo<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+}
+
+impl<'a, T: Writable + ?Sized> EchoByWritable for EchoArg<'a, T> {
+    fn write_echo<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.0.write_to(writer)
+    }
+}
+
+#[doc(hidden)]
+pub trait EchoByDisplay {
+    fn write_echo<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+}
+
+impl<'a, T: std::fmt::Display + ?Sized> EchoByDisplay for &EchoArg<'a, T> {
+    fn write_echo<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write!(writer, "{}", self.0)
+    }
+}
+
+impl<T: Writable + ?Sized> Writable for &T {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        (*self).write_to(writer)
+    }
+}
+
+impl<T: Writable> Writable for [T] {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for (i, item) in self.iter().enumerate() {
+            if i > 0 {
+                writer.write_all(b" ")?;
+            }
+            item.write_to(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Writable> Writable for Vec<T> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self[..].write_to(writer)
+    }
+}
+
+impl<T: Writable> Writable for Option<T> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        with_sentinel(self, "-1").write_to(writer)
+    }
+}
+
+// Tuples
+// Copy and paste instead of using macro for compilation speedup
+
+impl<T1: Writable, T2: Writable> Writable for (T1, T2) {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.0.write_to(writer)?;
+        writer.write_all(b" ")?;
+        self.1.write_to(writer)?;
+        Ok(())
+    }
+}
+
+impl<T1: Writable, T2: Writable, T3: Writable> Writable for (T1, T2, T3) {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.0.write_to(writer)?;
+        writer.write_all(b" ")?;
+        self.1.write_to(writer)?;
+        writer.write_all(b" ")?;
+        self.2.write_to(writer)?;
+        Ok(())
+    }
+}
+
+impl<T1: Writable, T2: Writable, T3: Writable, T4: Writable> Writable for (T1, T2, T3, T4) {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.0.write_to(writer)?;
+        writer.write_all(b" ")?;
+        self.1.write_to(writer)?;
+        writer.write_all(b" ")?;
+        self.2.write_to(writer)?;
+        writer.write_all(b" ")?;
+        self.3.write_to(writer)?;
+        Ok(())
+    }
+}
+
+/// `Option` written as the sentinel word if it is `None`.
+///
+/// Created by [`with_sentinel`](fn.with_sentinel.html).
+pub struct WithSentinel<'a, T> {
+    value: &'a Option<T>,
+    sentinel: &'a str
+}
+
+/// Makes `value` written as `sentinel` if it is `None`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::write::*;
+/// let mut out = Vec::new();
+/// let answers = vec![Some(1), None];
+/// answers.iter().map(|ans| with_sentinel(ans, "NA")).collect::<Vec<_>>().write_to(&mut out).unwrap();
+/// assert_eq!(out, b"1 NA");
+/// ```
+pub fn with_sentinel<'a, T: Writable>(value: &'a Option<T>, sentinel: &'a str) -> WithSentinel<'a, T> {
+    WithSentinel { value, sentinel }
+}
+
+impl<'a, T: Writable> Writable for WithSentinel<'a, T> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        match *self.value {
+            Some(ref value) => value.write_to(writer),
+            None => writer.write_all(self.sentinel.as_bytes())
+        }
+    }
+}
+
+/// Items written in separate lines.
+///
+/// Created by [`each_line`](fn.each_line.html).
+pub struct EachLine<'a, T> {
+    items: &'a [T]
+}
+
+/// Makes `items` written in separate lines, instead of separated by spaces.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::write::*;
+/// let edges = vec![(0, 1), (1, 2)];
+/// echo!(edges.len());
+/// echo!(each_line(&edges));  // "0 1\n1 2\n"
+/// ```
+pub fn each_line<T: Writable>(items: &[T]) -> EachLine<'_, T> {
+    EachLine { items }
+}
+
+impl<'a, T: Writable> Writable for EachLine<'a, T> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for (i, item) in self.items.iter().enumerate() {
+            if i > 0 {
+                writer.write_all(b"\n")?;
+            }
+            item.write_to(writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Grid displayed with items in each row separated by a separator,
+/// and rows separated by newlines.
+///
+/// Created by [`display_grid`](fn.display_grid.html).
+pub struct GridDisplay<'a, T> {
+    rows: &'a [Vec<T>],
+    sep: &'a str
+}
+
+/// Makes `rows` displayed as a grid, where items in each row are separated by `sep`.
+///
+/// No separators follow the last item in a row, and no newline follows the last row.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::write::*;
+/// let grid = vec![vec![1, 2], vec![3, 4]];
+/// assert_eq!(display_grid(&grid, " ").to_string(), "1 2\n3 4");
+/// ```
+pub fn display_grid<'a, T: std::fmt::Display>(rows: &'a [Vec<T>], sep: &'a str) -> GridDisplay<'a, T> {
+    GridDisplay { rows, sep }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for GridDisplay<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (y, row) in self.rows.iter().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for (x, item) in row.iter().enumerate() {
+                if x > 0 {
+                    f.write_str(self.sep)?;
+                }
+                write!(f, "{}", item)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes each row followed by a newline, where items are separated by `sep`.
+///
+/// Writer version of [`echo_grid`](fn.echo_grid.html).
+pub fn write_grid<W: Write, T: std::fmt::Display>(writer: &mut W, rows: &[Vec<T>], sep: &str) -> io::Result<()> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+    writeln!(writer, "{}", display_grid(rows, sep))
+}
+
+/// Prints each row followed by a newline, where items are separated by `sep`.
+///
+/// No separators follow the last item in a row.
+/// If there are no rows, prints nothing.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::write::*;
+/// echo_grid(&[vec![1, 2], vec![3, 4]], " ");  // "1 2\n3 4\n"
+/// ```
+pub fn echo_grid<T: std::fmt::Display>(rows: &[Vec<T>], sep: &str) {
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+    write_grid(&mut writer, rows, sep).unwrap();
+}
+
+/// Prints each row of characters followed by a newline, with no separators.
+///
+/// If there are no rows, prints nothing.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::write::*;
+/// echo_char_grid(&[vec!['#', '.'], vec!['.', '#']]);  // "#.\n.#\n"
+/// ```
+pub fn echo_char_grid(rows: &[Vec<char>]) {
+    echo_grid(rows, "");
+}
+
+/// Capacity of the buffer used by [`with_stdout`](fn.with_stdout.html).
+pub const OUTPUT_BUFFER_CAPACITY: usize = 1 << 16;
+
+// Number of `with_writer` calls running.
+// It is process-wide so that `exit_with_output` called in a thread spawned in `with_writer`,
+// such as by `run_with_stack`, unwinds to the thread instead of locking stdout.
+static BUFFERED_DEPTH: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+// Unwinding payload carrying the output left to write before exiting.
+struct ExitRequest(Vec<u8>);
+
+/// Writes `output` to stdout and exits the process successfully.
+///
+/// If it is called in [`with_stdout`](fn.with_stdout.html) or [`with_writer`](fn.with_writer.html),
+/// `output` is written after the buffered output, and the buffer is flushed before exiting.
+/// Otherwise the buffered output would be lost, since `std::process::exit` runs no destructors.
+///
+/// Exit helpers in [`utils`](../utils/index.html) use this function.
+pub fn exit_with_output(output: &[u8]) -> ! {
+    if BUFFERED_DEPTH.load(std::sync::atomic::Ordering::SeqCst) > 0 {
+        // Caught by `with_writer_catching_exit`, after propagated by `join` if in a spawned thread.
+        std::panic::resume_unwind(Box::new(ExitRequest(output.to_vec())));
+    }
+
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    handle.write_all(output).unwrap();
+    handle.flush().unwrap();
+    std::process::exit(0)
+}
+
 // TODO: Add a real example (maybe in marathon match)
 /// Make tons of output to stdout much faster.
 ///
+/// Runs `f` with buffered stdout, and flushes it at the end.
+/// Even if `f` panics, the output written so far is not lost.
+///
 /// See [Qiita article by hatoo](https://qiita.com/hatoo@github/items/fa14ad36a1b568d14f3e#%E3%81%8A%E3%81%BE%E3%81%91).
-pub fn with_stdout<F: FnOnce(BufWriter<StdoutLock>)>(f: F) {
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate atcoder_snippets;
+/// # use atcoder_snippets::write::*;
+/// with_stdout(|out| {
+///     for i in 0..3 {
+///         echo_to!(out, i, i * i);
+///     }
+/// });
+/// ```
+///
+/// # Writing in `f`
+///
+/// Write to the given writer with `echo_to!` or [`write_line`](fn.write_line.html).
+/// `echo!` and [`echo_line`](fn.echo_line.html) called in `f` write straight to stdout,
+/// bypassing the buffer, so their output may come before the buffered output written earlier.
+///
+/// # Interactive problems
+///
+/// Do not use this function for interactive problems,
+/// since the judge may wait for a query held in the buffer forever.
+/// Use [`Interactor`](../read/struct.Interactor.html) instead,
+/// which flushes each query.
+pub fn with_stdout<F: FnOnce(&mut BufWriter<StdoutLock>)>(f: F) {
     let stdout = io::stdout();
-    let writer = BufWriter::new(stdout.lock());
-    f(writer);
+    let _ = with_writer(stdout.lock(), f);
+}
+
+/// Runs `f` with buffered `writer`, flushes it at the end, and returns it.
+///
+/// Writer version of [`with_stdout`](fn.with_stdout.html).
+///
+/// If [`exit_with_output`](fn.exit_with_output.html) is called in `f`,
+/// flushes the buffer and exits the process.
+pub fn with_writer<W: Write, F: FnOnce(&mut BufWriter<W>)>(writer: W, f: F) -> W {
+    let (writer, exit_requested) = with_writer_catching_exit(writer, f);
+    if exit_requested {
+        // Exits the outer `with_writer` if nested, otherwise the process.
+        exit_with_output(&[]);
+    }
+    writer
+}
+
+/// Same as [`with_writer`](fn.with_writer.html), but returns whether
+/// [`exit_with_output`](fn.exit_with_output.html) is called in `f` instead of exiting the process.
+///
+/// If exit is requested, the output passed to `exit_with_output` follows the buffered output.
+/// In any case, the buffer is flushed once.
+pub fn with_writer_catching_exit<W, F>(writer: W, f: F) -> (W, bool)
+where
+    W: Write,
+    F: FnOnce(&mut BufWriter<W>)
+{
+    // If `f` panics, `BufWriter` writes out the buffer when dropped.
+    let mut writer = BufWriter::with_capacity(OUTPUT_BUFFER_CAPACITY, writer);
+    BUFFERED_DEPTH.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(&mut writer)));
+    BUFFERED_DEPTH.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+
+    let exit_requested = match result {
+        Ok(()) => false,
+        Err(payload) => match payload.downcast::<ExitRequest>() {
+            Ok(request) => {
+                writer.write_all(&request.0).unwrap();
+                true
+            }
+            Err(payload) => std::panic::resume_unwind(payload)
+        }
+    };
+    writer.flush().unwrap();
+    let writer = writer.into_inner().unwrap_or_else(|_| panic!("failed to write out the buffer"));
+    (writer, exit_requested)
+}
+
+/// Writes items separated by `sep`, followed by a newline.
+///
+/// Writer version of [`echo_joined`](fn.echo_joined.html).
+pub fn write_joined<W, I>(writer: &mut W, iter: I, sep: &str) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: std::fmt::Display
+{
+    let mut iter = iter.into_iter();
+    if let Some(first) = iter.next() {
+        write!(writer, "{}", first)?;
+        for item in iter {
+            write!(writer, "{}{}", sep, item)?;
+        }
+    }
+    writeln!(writer)
+}
+
+/// Writes items separated by spaces, followed by a newline.
+///
+/// Writer version of [`echo_line`](fn.echo_line.html).
+pub fn write_line<W, I>(writer: &mut W, iter: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: std::fmt::Display
+{
+    write_joined(writer, iter, " ")
+}
+
+/// Writes each item followed by a newline.
+///
+/// Writer version of [`echo_lines`](fn.echo_lines.html).
+pub fn write_lines<W, I>(writer: &mut W, iter: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator,
+    I::Item: std::fmt::Display
+{
+    for item in iter {
+        writeln!(writer, "{}", item)?;
+    }
+    Ok(())
+}
+
+/// Prints items separated by `sep`, followed by a newline.
+///
+/// Stdout is locked and buffered during printing,
+/// so it is much faster than `println!` for each item.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::write::*;
+/// echo_joined(&[1, 2, 3], ", ");  // "1, 2, 3\n"
+/// ```
+pub fn echo_joined<I>(iter: I, sep: &str)
+where
+    I: IntoIterator,
+    I::Item: std::fmt::Display
+{
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+    write_joined(&mut writer, iter, sep).unwrap();
+}
+
+/// Prints items separated by spaces, followed by a newline.
+///
+/// If there are no items, prints just a newline.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::write::*;
+/// echo_line(vec![1, 2, 3]);  // "1 2 3\n"
+/// echo_line(Vec::<i32>::new());  // "\n"
+/// ```
+pub fn echo_line<I>(iter: I)
+where
+    I: IntoIterator,
+    I::Item: std::fmt::Display
+{
+    echo_joined(iter, " ");
+}
+
+/// Prints each item followed by a newline.
+///
+/// If there are no items, prints nothing.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::write::*;
+/// echo_lines((1..4).map(|x| x * x));  // "1\n4\n9\n"
+/// echo_lines(Vec::<i32>::new());  // ""
+/// ```
+pub fn echo_lines<I>(iter: I)
+where
+    I: IntoIterator,
+    I::Item: std::fmt::Display
+{
+    let stdout = io::stdout();
+    let mut writer = BufWriter::new(stdout.lock());
+    write_lines(&mut writer, iter).unwrap();
 }
 
 // END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::echo_to;
+
+    fn output(f: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> String {
+        let mut buf = Vec::new();
+        f(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    // Writer counting flushes.
+    #[derive(Default)]
+    struct Recorder {
+        written: Vec<u8>,
+        flush_count: usize
+    }
+
+    impl Write for Recorder {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.written.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.flush_count += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_with_writer() {
+        let recorder = with_writer(Recorder::default(), |out| {
+            for i in 0..100_000 {
+                echo_to!(out, i, -i);
+            }
+            echo_to!(out);
+            echo_to!(out, "end");
+        });
+        let mut expected: String = (0..100_000).map(|i| format!("{} {}\n", i, -i)).collect();
+        expected.push_str("\nend\n");
+        assert_eq!(String::from_utf8(recorder.written).unwrap(), expected);
+        assert_eq!(recorder.flush_count, 1);
+    }
+
+    #[test]
+    fn test_with_writer_panic() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        struct Shared(Rc<RefCell<Vec<u8>>>);
+
+        impl Write for Shared {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.borrow_mut().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let written = Rc::new(RefCell::new(Vec::new()));
+        let shared = Shared(written.clone());
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            with_writer(shared, |out| {
+                echo_to!(out, "before panic");
+                panic!("failed");
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(&written.borrow()[..], b"before panic\n");
+    }
+
+    #[test]
+    fn test_with_writer_catching_exit() {
+        let (recorder, exit_requested) = with_writer_catching_exit(Recorder::default(), |out| {
+            for i in 0..100_000 {
+                echo_to!(out, i);
+            }
+            exit_with_output(b"-1\n");
+        });
+        let mut expected: String = (0..100_000).map(|i| format!("{}\n", i)).collect();
+        expected.push_str("-1\n");
+        assert_eq!(String::from_utf8(recorder.written).unwrap(), expected);
+        assert_eq!(recorder.flush_count, 1);
+        assert!(exit_requested);
+
+        let (recorder, exit_requested) = with_writer_catching_exit(Recorder::default(), |out| {
+            echo_to!(out, "no exit");
+        });
+        assert_eq!(recorder.written, b"no exit\n");
+        assert!(!exit_requested);
+    }
+
+    #[test]
+    fn test_with_writer_catching_exit_nested() {
+        let (outer, exit_requested) = with_writer_catching_exit(Vec::new(), |out| {
+            echo_to!(out, "outer");
+            let inner = with_writer(Vec::new(), |inner| {
+                echo_to!(inner, "inner");
+                exit_with_output(b"exit\n");
+            });
+            // Not reached, since `with_writer` requests exit to the outer one.
+            out.write_all(&inner).unwrap();
+        });
+        assert_eq!(outer, b"outer\n");
+        assert!(exit_requested);
+    }
+
+    fn written(value: impl Writable) -> String {
+        output(|w| value.write_to(w))
+    }
+
+    #[test]
+    fn test_writable() {
+        assert_eq!(written(-42i64), "-42");
+        assert_eq!(written(0.25f64), "0.25");
+        assert_eq!(written('x'), "x");
+        assert_eq!(written("str"), "str");
+        assert_eq!(written(String::from("string")), "string");
+        assert_eq!(written((1, 'a', "b")), "1 a b");
+        assert_eq!(written((1, 2, 3, (4, vec![5, 6]))), "1 2 3 4 5 6");
+        assert_eq!(written(vec![(1usize, -1i64), (2, -2)]), "1 -1 2 -2");
+        assert_eq!(written(&[1, 2][..]), "1 2");
+        assert_eq!(written(Vec::<i32>::new()), "");
+    }
+
+    #[test]
+    fn test_writable_option() {
+        assert_eq!(written(Some(3)), "3");
+        assert_eq!(written(None::<i32>), "-1");
+        assert_eq!(written(vec![Some(1), None, Some(3)]), "1 -1 3");
+        assert_eq!(written(with_sentinel(&None::<i32>, "No")), "No");
+        assert_eq!(written(with_sentinel(&Some((1, 2)), "No")), "1 2");
+    }
+
+    #[test]
+    fn test_each_line() {
+        assert_eq!(written(each_line(&[(1, -1), (2, -2)])), "1 -1\n2 -2");
+        assert_eq!(written(each_line(&[vec![1, 2], vec![], vec![3]])), "1 2\n\n3");
+        assert_eq!(written(each_line::<i32>(&[])), "");
+
+        let edges = vec![(0usize, 1i64), (1, 2)];
+        let out = with_writer(Vec::new(), |out| {
+            echo_to!(out, edges.len());
+            echo_to!(out, each_line(&edges));
+        });
+        assert_eq!(out, b"2\n0 1\n1 2\n");
+    }
+
+    #[test]
+    fn test_write_grid() {
+        let grid = vec![vec![1, -2, 3], vec![4, 5, -6]];
+        assert_eq!(output(|w| write_grid(w, &grid, " ")), "1 -2 3\n4 5 -6\n");
+        assert_eq!(output(|w| write_grid(w, &grid, ",")), "1,-2,3\n4,5,-6\n");
+        let chars = vec![vec!['#', '.', '#'], vec!['.', '.', '.']];
+        assert_eq!(output(|w| write_grid(w, &chars, "")), "#.#\n...\n");
+        assert_eq!(output(|w| write_grid(w, &[vec![1], vec![], vec![2]], " ")), "1\n\n2\n");
+        assert_eq!(output(|w| write_grid(w, &Vec::<Vec<i32>>::new(), " ")), "");
+        assert_eq!(display_grid(&grid, " ").to_string(), "1 -2 3\n4 5 -6");
+    }
+
+    #[test]
+    fn test_write_joined() {
+        assert_eq!(output(|w| write_joined(w, &[1, 2, 3], ", ")), "1, 2, 3\n");
+        assert_eq!(output(|w| write_joined(w, vec!["a"], ", ")), "a\n");
+        assert_eq!(output(|w| write_joined(w, Vec::<i32>::new(), ", ")), "\n");
+    }
+
+    #[test]
+    fn test_write_line() {
+        assert_eq!(output(|w| write_line(w, (1..4).map(|x| -x))), "-1 -2 -3\n");
+        assert_eq!(output(|w| write_line(w, Vec::<i32>::new())), "\n");
+    }
+
+    #[test]
+    fn test_write_lines() {
+        assert_eq!(output(|w| write_lines(w, &['a', 'b'])), "a\nb\n");
+        assert_eq!(output(|w| write_lines(w, Vec::<i32>::new())), "");
+
+        let expected: String = (0..200_000).map(|i| format!("{}\n", i)).collect();
+        assert_eq!(output(|w| write_lines(w, 0..200_000)), expected);
+    }
+}
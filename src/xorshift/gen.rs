@@ -0,0 +1,240 @@
+//! Random test case generators for stress tests.
+
+use super::Rng;
+
+// BEGIN SNIPPET xorshift_gen DEPENDS ON xorshift
+
+/// Returns a permutation of `0..n` chosen uniformly at random.
+pub fn random_permutation(n: usize, rng: &mut impl Rng) -> Vec<usize> {
+    let mut perm: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut perm);
+    perm
+}
+
+/// Returns the edges of a labeled tree with vertices `0..n`, chosen uniformly at random
+/// by decoding a random Prüfer sequence.
+///
+/// The order of the edges is unspecified.
+/// If `n` is 0, returns no edges.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::xorshift::*;
+/// # use atcoder_snippets::xorshift::gen::*;
+/// let mut rng = Xorshift::new();
+/// let edges = random_tree(10, &mut rng);
+/// assert_eq!(edges.len(), 9);
+/// ```
+pub fn random_tree(n: usize, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+    if n <= 1 {
+        return Vec::new();
+    }
+    let prufer: Vec<usize> = (0..n-2).map(|_| rng.gen_range(0..n as u64) as usize).collect();
+
+    let mut degree = vec![1; n];
+    for &v in &prufer {
+        degree[v] += 1;
+    }
+    let mut edges = Vec::with_capacity(n - 1);
+    // `leaf` is the smallest leaf, found by scanning from `ptr`.
+    let mut ptr = (0..n).find(|&v| degree[v] == 1).unwrap();
+    let mut leaf = ptr;
+    for &v in &prufer {
+        edges.push((leaf, v));
+        degree[v] -= 1;
+        if degree[v] == 1 && v < ptr {
+            leaf = v;
+        } else {
+            ptr = (ptr+1..n).find(|&u| degree[u] == 1).unwrap();
+            leaf = ptr;
+        }
+    }
+    edges.push((leaf, n - 1));
+    edges
+}
+
+/// Returns the edges of a random connected simple graph
+/// with vertices `0..n` and `m` edges.
+///
+/// The graph is a uniformly random spanning tree plus
+/// `m - (n - 1)` uniformly random other edges.
+/// The order of the edges is random.
+///
+/// # Panics
+///
+/// Panics if no connected simple graph has `n` vertices and `m` edges.
+pub fn random_connected_graph(n: usize, m: usize, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+    let max_m = n * n.saturating_sub(1) / 2;
+    assert!(
+        n.saturating_sub(1) <= m && m <= max_m,
+        "no connected simple graph has {} vertices and {} edges", n, m
+    );
+
+    let normalize = |(u, v): (usize, usize)| if u < v { (u, v) } else { (v, u) };
+    let mut edges: Vec<(usize, usize)> = random_tree(n, rng).into_iter().map(normalize).collect();
+    let mut used: std::collections::HashSet<(usize, usize)> = edges.iter().cloned().collect();
+    if 2 * m <= max_m {
+        while edges.len() < m {
+            let u = rng.gen_range(0..n as u64) as usize;
+            let v = rng.gen_range(0..n as u64) as usize;
+            if u != v && used.insert(normalize((u, v))) {
+                edges.push((u, v));
+            }
+        }
+    } else {
+        let mut rest: Vec<(usize, usize)> = (0..n)
+            .flat_map(|u| (u+1..n).map(move |v| (u, v)))
+            .filter(|e| !used.contains(e))
+            .collect();
+        let k = m - edges.len();
+        rng.partial_shuffle(&mut rest, k);
+        edges.extend_from_slice(&rest[..k]);
+    }
+
+    rng.shuffle(&mut edges);
+    for e in &mut edges {
+        if rng.gen_bool(0.5) {
+            *e = (e.1, e.0);
+        }
+    }
+    edges
+}
+
+/// Returns a string of length `n` whose characters are chosen from `alphabet`
+/// uniformly at random.
+///
+/// # Panics
+///
+/// Panics if `alphabet` is empty and `n` is positive.
+pub fn random_string(n: usize, alphabet: &[u8], rng: &mut impl Rng) -> Vec<u8> {
+    assert!(n == 0 || !alphabet.is_empty(), "alphabet is empty");
+    (0..n).map(|_| alphabet[rng.gen_range(0..alphabet.len() as u64) as usize]).collect()
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xorshift::{Xorshift, StepRng};
+
+    fn is_tree(n: usize, edges: &[(usize, usize)]) -> bool {
+        let mut adj = vec![Vec::new(); n];
+        for &(u, v) in edges {
+            adj[u].push(v);
+            adj[v].push(u);
+        }
+        let mut visited = vec![false; n];
+        let mut stack = vec![0];
+        while let Some(u) = stack.pop() {
+            if !visited[u] {
+                visited[u] = true;
+                stack.extend(adj[u].iter().cloned());
+            }
+        }
+        edges.len() + 1 == n && visited.iter().all(|&b| b)
+    }
+
+    #[test]
+    fn test_random_permutation() {
+        let mut rng = Xorshift::with_seed(960);
+        for n in 0..20 {
+            let mut perm = random_permutation(n, &mut rng);
+            perm.sort();
+            assert_eq!(perm, (0..n).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_random_tree() {
+        let mut rng = Xorshift::with_seed(960);
+        assert_eq!(random_tree(0, &mut rng), vec![]);
+        assert_eq!(random_tree(1, &mut rng), vec![]);
+        for n in 2..30 {
+            for _ in 0..10 {
+                assert!(is_tree(n, &random_tree(n, &mut rng)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_tree_uniformity() {
+        use std::collections::HashMap;
+
+        let mut rng = Xorshift::with_seed(960);
+        let mut counts: HashMap<Vec<(usize, usize)>, usize> = HashMap::new();
+        for _ in 0..32_000 {
+            let mut edges: Vec<(usize, usize)> = random_tree(4, &mut rng).into_iter()
+                .map(|(u, v)| if u < v { (u, v) } else { (v, u) })
+                .collect();
+            edges.sort();
+            *counts.entry(edges).or_insert(0) += 1;
+        }
+        // Cayley's formula: 4^(4-2) = 16 labeled trees
+        assert_eq!(counts.len(), 16);
+        assert!(counts.values().all(|&c| 1_800 < c && c < 2_200), "{:?}", counts);
+    }
+
+    #[test]
+    fn test_random_connected_graph() {
+        use std::collections::HashSet;
+
+        let mut rng = Xorshift::with_seed(960);
+        for n in 1..10 {
+            for m in n-1..n*(n-1)/2+1 {
+                let edges = random_connected_graph(n, m, &mut rng);
+                assert_eq!(edges.len(), m);
+                let normalized: HashSet<(usize, usize)> = edges.iter()
+                    .map(|&(u, v)| if u < v { (u, v) } else { (v, u) })
+                    .collect();
+                assert_eq!(normalized.len(), m);
+                assert!(edges.iter().all(|&(u, v)| u != v && u < n && v < n));
+
+                // Connected: some spanning tree exists, found by union-find.
+                let mut parent: Vec<usize> = (0..n).collect();
+                fn root(parent: &mut Vec<usize>, v: usize) -> usize {
+                    if parent[v] == v { v } else {
+                        let r = root(parent, parent[v]);
+                        parent[v] = r;
+                        r
+                    }
+                }
+                let mut components = n;
+                for &(u, v) in &edges {
+                    let (ru, rv) = (root(&mut parent, u), root(&mut parent, v));
+                    if ru != rv {
+                        parent[ru] = rv;
+                        components -= 1;
+                    }
+                }
+                assert_eq!(components, 1);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_random_connected_graph_too_few_edges() {
+        random_connected_graph(4, 2, &mut Xorshift::with_seed(960));
+    }
+
+    #[test]
+    fn test_random_string() {
+        let mut rng = Xorshift::with_seed(960);
+        assert_eq!(random_string(0, &[], &mut rng), vec![]);
+        let s = random_string(1000, b"ab", &mut rng);
+        assert_eq!(s.len(), 1000);
+        assert!(s.contains(&b'a') && s.contains(&b'b'));
+        assert!(s.iter().all(|&c| c == b'a' || c == b'b'));
+    }
+
+    #[test]
+    fn test_fixed_sequence_rng() {
+        // Always picks the middle of ranges.
+        let mut rng = StepRng::new(1 << 63 | 1, 0);
+        // Prüfer sequence (2, 2) gives a star centered at 2.
+        assert_eq!(random_tree(4, &mut rng), vec![(0, 2), (1, 2), (2, 3)]);
+        assert_eq!(random_string(3, b"xyz", &mut rng), b"yyy".to_vec());
+    }
+}
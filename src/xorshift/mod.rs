@@ -0,0 +1,763 @@
+//! Xorshift random number generator.
+
+pub mod gen;
+
+// BEGIN SNIPPET xorshift
+
+/// Random number generator by xorshift.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::xorshift::*;
+/// let mut rng = Xorshift::new();
+/// let random_i32: i32 = rng.next();
+/// let random_f64: f64 = rng.next();
+/// assert!(0.0 <= random_f64 && random_f64 < 1.0);
+/// ```
+pub struct Xorshift {
+    state: u64
+}
+
+mod xorshift_internal {
+    use super::Rng;
+
+    pub const GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    // SplitMix64, which maps similar inputs to very different outputs.
+    pub fn splitmix64(x: u64) -> u64 {
+        let mut z = x.wrapping_add(GOLDEN_GAMMA);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Returns a random number in `0..bound` without bias by Lemire's method,
+    // where `bound` is positive.
+    pub fn next_below<R: Rng + ?Sized>(rng: &mut R, bound: u64) -> u64 {
+        let mut m = rng.next_u64() as u128 * bound as u128;
+        if (m as u64) < bound {
+            // Rejects the lowest `2^64 % bound` values to remove the bias.
+            let threshold = bound.wrapping_neg() % bound;
+            while (m as u64) < threshold {
+                m = rng.next_u64() as u128 * bound as u128;
+            }
+        }
+        (m >> 64) as u64
+    }
+
+    // Returns a random number in `min..=max`.
+    pub fn next_inclusive<R: Rng + ?Sized>(rng: &mut R, min: u64, max: u64) -> u64 {
+        assert!(min <= max, "range is empty");
+        match (max - min).checked_add(1) {
+            Some(bound) => min + next_below(rng, bound),
+            None => rng.next_u64()
+        }
+    }
+}
+
+/// Random number generator seeded by the fixed seed 88172645463325252,
+/// so that the results are reproducible.
+impl Default for Xorshift {
+    fn default() -> Xorshift {
+        Xorshift::with_seed(88_172_645_463_325_252)
+    }
+}
+
+impl Xorshift {
+    /// Random number generator seeded by entropy. Same as `from_entropy`.
+    pub fn new() -> Xorshift {
+        Xorshift::from_entropy()
+    }
+
+    /// Random number generator seeded by the system clock,
+    /// the address of a stack variable and the number of calls so far.
+    ///
+    /// Generators created in the same process never share the seed,
+    /// even if the clock doesn't advance between the calls.
+    pub fn from_entropy() -> Xorshift {
+        use self::xorshift_internal::*;
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::time::SystemTime;
+
+        static CALLS: AtomicU64 = AtomicU64::new(0);
+
+        let now = SystemTime::now();
+        let nanos = now.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_nanos() as u64;
+        let local = 0u8;
+        let address = &local as *const u8 as u64;
+        let calls = CALLS.fetch_add(1, Ordering::Relaxed);
+        // `splitmix64` is a bijection, so distinct `calls` give distinct seeds for the same clock and address.
+        Xorshift::with_seed(splitmix64(splitmix64(nanos ^ splitmix64(address)) ^ calls))
+    }
+
+    /// Random number generator with seed.
+    ///
+    /// Generators with the same seed generates the same numbers.
+    pub fn with_seed(seed: u64) -> Xorshift {
+        let seed = if seed == 0 { 1 } else { seed };
+        Xorshift { state: seed }
+    }
+
+    /// Derives another generator, whose numbers look independent of `self`'s.
+    ///
+    /// The state of `self` advances by one number.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::xorshift::*;
+    /// let mut rng = Xorshift::with_seed(1);
+    /// let mut forked = rng.fork();
+    /// assert_ne!(rng.next::<u64>(), forked.next::<u64>());
+    /// ```
+    pub fn fork(&mut self) -> Xorshift {
+        use self::xorshift_internal::*;
+        Xorshift::with_seed(splitmix64(self.next::<u64>()))
+    }
+
+    /// Gets a random number.
+    pub fn next<T: RngOutput>(&mut self) -> T {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        T::from_u64(self.state)
+    }
+}
+
+impl Rng for Xorshift {
+    fn next_u64(&mut self) -> u64 {
+        self.next()
+    }
+}
+
+/// Random number generator, whose random values of various kinds are derived
+/// from random `u64` values by the provided methods.
+///
+/// Randomized functions take `&mut impl Rng` so that any generator,
+/// including [`StepRng`](struct.StepRng.html) for tests, can be used.
+pub trait Rng {
+    /// Gets a random `u64` value.
+    fn next_u64(&mut self) -> u64;
+
+    /// Gets a random number in `range` uniformly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::xorshift::*;
+    /// let mut rng = Xorshift::new();
+    /// let dice = rng.gen_range(1..=6);
+    /// assert!(1 <= dice && dice <= 6);
+    /// ```
+    fn gen_range<R: std::ops::RangeBounds<u64>>(&mut self, range: R) -> u64 {
+        use std::ops::Bound::*;
+        use self::xorshift_internal::*;
+
+        let min = match range.start_bound() {
+            Included(&x) => x,
+            Excluded(&x) => x.checked_add(1).expect("range is empty"),
+            Unbounded => 0
+        };
+        let max = match range.end_bound() {
+            Included(&x) => x,
+            Excluded(&x) => x.checked_sub(1).expect("range is empty"),
+            Unbounded => std::u64::MAX
+        };
+        next_inclusive(self, min, max)
+    }
+
+    /// Gets a random number in `range` uniformly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::xorshift::*;
+    /// let mut rng = Xorshift::new();
+    /// let x = rng.gen_range_i64(-5..5);
+    /// assert!(-5 <= x && x < 5);
+    /// ```
+    fn gen_range_i64<R: std::ops::RangeBounds<i64>>(&mut self, range: R) -> i64 {
+        use std::ops::Bound::*;
+        use self::xorshift_internal::*;
+
+        // Flipping the sign bit maps `i64` into `u64` preserving the order.
+        let to_u64 = |x: i64| x as u64 ^ 1 << 63;
+        let min = match range.start_bound() {
+            Included(&x) => x,
+            Excluded(&x) => x.checked_add(1).expect("range is empty"),
+            Unbounded => std::i64::MIN
+        };
+        let max = match range.end_bound() {
+            Included(&x) => x,
+            Excluded(&x) => x.checked_sub(1).expect("range is empty"),
+            Unbounded => std::i64::MAX
+        };
+        assert!(min <= max, "range is empty");
+        (next_inclusive(self, to_u64(min), to_u64(max)) ^ 1 << 63) as i64
+    }
+
+    /// Returns `true` with probability `p`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not in `[0, 1]`.
+    fn gen_bool(&mut self, p: f64) -> bool {
+        assert!((0.0..=1.0).contains(&p), "probability {} is not in [0, 1]", p);
+        self.gen_f64() < p
+    }
+
+    /// Gets a random number in [0, 1) uniformly, from the highest 53 bits.
+    ///
+    /// Unlike `Xorshift::next::<f64>()`, the result can be any multiple of 2^-53 in [0, 1).
+    fn gen_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Gets a random number in [`lo`, `hi`) uniformly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo` or `hi` is not finite, or `lo >= hi`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::xorshift::*;
+    /// let mut rng = Xorshift::new();
+    /// let x = rng.gen_f64_range(-1.5, 2.5);
+    /// assert!(-1.5 <= x && x < 2.5);
+    /// ```
+    fn gen_f64_range(&mut self, lo: f64, hi: f64) -> f64 {
+        assert!(lo.is_finite() && hi.is_finite(), "range {}..{} is not finite", lo, hi);
+        assert!(lo < hi, "range {}..{} is empty", lo, hi);
+        loop {
+            let x = lo + (hi - lo) * self.gen_f64();
+            // Rounding may make `x` equal to `hi`.
+            if x < hi {
+                return x;
+            }
+        }
+    }
+
+    /// Gets a random number following the normal distribution
+    /// with mean `mean` and standard deviation `stddev`, by Box-Muller transform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mean` or `stddev` is not finite, or `stddev` is negative.
+    fn gen_gaussian(&mut self, mean: f64, stddev: f64) -> f64 {
+        assert!(mean.is_finite() && stddev.is_finite(), "parameters must be finite");
+        assert!(stddev >= 0.0, "standard deviation {} is negative", stddev);
+        // `u1` is in (0, 1] so that its logarithm is finite.
+        let u1 = 1.0 - self.gen_f64();
+        let u2 = self.gen_f64();
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+        mean + stddev * z
+    }
+
+    /// Shuffles the slice uniformly by Fisher-Yates algorithm.
+    fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in 1..slice.len() {
+            let j = self.gen_range(0..=i as u64) as usize;
+            slice.swap(i, j);
+        }
+    }
+
+    /// Selects `k` items of the slice uniformly at random and moves them,
+    /// in a uniformly random order, to the front of the slice.
+    ///
+    /// If `k` exceeds the length of the slice, the whole slice is shuffled.
+    /// It takes Θ(`k`) time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::xorshift::*;
+    /// let mut rng = Xorshift::new();
+    /// let mut v: Vec<u32> = (0..10).collect();
+    /// rng.partial_shuffle(&mut v, 3);
+    /// let sample = &v[..3];
+    /// assert!(sample[0] != sample[1] && sample[1] != sample[2] && sample[2] != sample[0]);
+    /// ```
+    fn partial_shuffle<T>(&mut self, slice: &mut [T], k: usize) {
+        let len = slice.len();
+        for i in 0..std::cmp::min(k, len) {
+            let j = self.gen_range(i as u64..len as u64) as usize;
+            slice.swap(i, j);
+        }
+    }
+
+    /// Selects `k` distinct indices in `0..n` uniformly at random.
+    ///
+    /// The order of the result is unspecified.
+    /// It takes Θ(`k`) expected time if `k` is small compared to `n`,
+    /// and Θ(`n`) time otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > n`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::xorshift::*;
+    /// let mut rng = Xorshift::new();
+    /// let mut indices = rng.sample_indices(1_000_000_000, 3);
+    /// indices.sort();
+    /// indices.dedup();
+    /// assert_eq!(indices.len(), 3);
+    /// ```
+    fn sample_indices(&mut self, n: usize, k: usize) -> Vec<usize> {
+        assert!(k <= n, "cannot sample {} items from {} items", k, n);
+        if k * 4 < n {
+            // Floyd's algorithm
+            let mut selected = std::collections::HashSet::with_capacity(k);
+            let mut indices = Vec::with_capacity(k);
+            for j in n-k..n {
+                let t = self.gen_range(0..=j as u64) as usize;
+                let x = if selected.contains(&t) { j } else { t };
+                selected.insert(x);
+                indices.push(x);
+            }
+            indices
+        } else {
+            let mut indices: Vec<usize> = (0..n).collect();
+            self.partial_shuffle(&mut indices, k);
+            indices.truncate(k);
+            indices
+        }
+    }
+
+    /// Selects `k` items at distinct positions of `slice` uniformly at random.
+    ///
+    /// The order of the result is unspecified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > slice.len()`.
+    fn sample<T: Clone>(&mut self, slice: &[T], k: usize) -> Vec<T> {
+        self.sample_indices(slice.len(), k).into_iter().map(|i| slice[i].clone()).collect()
+    }
+}
+
+/// Random number generator by SplitMix64.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::xorshift::*;
+/// let mut rng = SplitMix64::with_seed(1);
+/// let x = rng.gen_range(0..10);
+/// assert!(x < 10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64
+}
+
+impl SplitMix64 {
+    /// Random number generator with seed.
+    pub fn with_seed(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        use self::xorshift_internal::*;
+
+        let x = splitmix64(self.state);
+        self.state = self.state.wrapping_add(GOLDEN_GAMMA);
+        x
+    }
+}
+
+/// Fake random number generator yielding an arithmetic progression,
+/// for deterministic tests.
+///
+/// Note that methods rejecting some values, such as `gen_range`,
+/// may loop forever if only such values are yielded (e.g., constant 0).
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::xorshift::*;
+/// let mut rng = StepRng::new(10, 3);
+/// assert_eq!(rng.next_u64(), 10);
+/// assert_eq!(rng.next_u64(), 13);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StepRng {
+    value: u64,
+    increment: u64
+}
+
+impl StepRng {
+    /// Generator yielding `initial`, `initial + increment`, ... (wrapping around `u64`).
+    pub fn new(initial: u64, increment: u64) -> StepRng {
+        StepRng { value: initial, increment }
+    }
+}
+
+impl Rng for StepRng {
+    fn next_u64(&mut self) -> u64 {
+        let x = self.value;
+        self.value = self.value.wrapping_add(self.increment);
+        x
+    }
+}
+
+pub trait RngOutput {
+    fn from_u64(x: u64) -> Self;
+}
+
+impl RngOutput for i8 {
+    fn from_u64(x: u64) -> i8 {
+        x as i8
+    }
+}
+
+impl RngOutput for u8 {
+    fn from_u64(x: u64) -> u8 {
+        x as u8
+    }
+}
+
+impl RngOutput for i16 {
+    fn from_u64(x: u64) -> i16 {
+        x as i16
+    }
+}
+
+impl RngOutput for u16 {
+    fn from_u64(x: u64) -> u16 {
+        x as u16
+    }
+}
+
+impl RngOutput for i32 {
+    fn from_u64(x: u64) -> i32 {
+        x as i32
+    }
+}
+
+impl RngOutput for u32 {
+    fn from_u64(x: u64) -> u32 {
+        x as u32
+    }
+}
+
+impl RngOutput for i64 {
+    fn from_u64(x: u64) -> i64 {
+        x as i64
+    }
+}
+
+impl RngOutput for u64 {
+    fn from_u64(x: u64) -> u64 {
+        x
+    }
+}
+
+impl RngOutput for isize {
+    fn from_u64(x: u64) -> isize {
+        x as isize
+    }
+}
+
+impl RngOutput for usize {
+    fn from_u64(x: u64) -> usize {
+        x as usize
+    }
+}
+
+impl RngOutput for f32 {
+    fn from_u64(x: u64) -> f32 {
+        (unsafe { std::mem::transmute::<u32, f32>(x as u32 & 0x007FFFFF | 0x3F800000) }) - 1.0
+    }
+}
+
+impl RngOutput for f64 {
+    fn from_u64(x: u64) -> f64 {
+        (unsafe { std::mem::transmute::<u64, f64>(x & 0x000FFFFFFFFFFFFF | 0x3FF0000000000000) }) - 1.0
+    }
+}
+
+// END SNIPPET
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gen_range_buckets() {
+        let mut rng = Xorshift::with_seed(955);
+        let mut counts = [0; 10];
+        let samples = 100_000;
+        for _ in 0..samples {
+            counts[rng.gen_range(10..20) as usize - 10] += 1;
+        }
+        let expected = samples as f64 / 10.0;
+        let chi_square: f64 = counts.iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum();
+        // 99.9th percentile of the chi-square distribution with 9 degrees of freedom
+        assert!(chi_square < 27.88, "{:?}", counts);
+
+        let mut counts = [0; 7];
+        for _ in 0..70_000 {
+            counts[(rng.gen_range_i64(-3..=3) + 3) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| 9_000 < c && c < 11_000), "{:?}", counts);
+    }
+
+    #[test]
+    fn test_gen_range_small() {
+        let mut rng = Xorshift::with_seed(955);
+        for _ in 0..100 {
+            assert_eq!(rng.gen_range(5..=5), 5);
+            assert_eq!(rng.gen_range_i64(-5..-4), -5);
+            assert_eq!(rng.gen_range(std::u64::MAX..), std::u64::MAX);
+            assert_eq!(rng.gen_range_i64(..=std::i64::MIN), std::i64::MIN);
+        }
+
+        let mut seen = [false; 2];
+        let mut seen_i64 = [false; 2];
+        for _ in 0..100 {
+            seen[rng.gen_range(7..9) as usize - 7] = true;
+            seen_i64[(rng.gen_range_i64(std::i64::MAX - 1..) - (std::i64::MAX - 1)) as usize] = true;
+        }
+        assert_eq!(seen, [true, true]);
+        assert_eq!(seen_i64, [true, true]);
+
+        // The whole ranges
+        rng.gen_range(..);
+        rng.gen_range_i64(..);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_range_empty() {
+        Xorshift::with_seed(955).gen_range(5..5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_range_i64_empty() {
+        Xorshift::with_seed(955).gen_range_i64(3..=2);
+    }
+
+    #[test]
+    fn test_gen_bool() {
+        let mut rng = Xorshift::with_seed(955);
+        assert!((0..100).all(|_| rng.gen_bool(1.0)));
+        assert!((0..100).all(|_| !rng.gen_bool(0.0)));
+        let count = (0..10_000).filter(|_| rng.gen_bool(0.3)).count();
+        assert!(2_800 < count && count < 3_200);
+    }
+
+    #[test]
+    fn test_shuffle_permutation() {
+        let mut rng = Xorshift::with_seed(956);
+        let mut empty: Vec<u32> = Vec::new();
+        rng.shuffle(&mut empty);
+        rng.partial_shuffle(&mut empty, 3);
+        assert!(empty.is_empty());
+        let mut single = vec![42];
+        rng.shuffle(&mut single);
+        rng.partial_shuffle(&mut single, 1);
+        assert_eq!(single, vec![42]);
+
+        for len in 0..20 {
+            let original: Vec<u32> = (0..len).map(|i| i / 2).collect();
+            let mut v = original.clone();
+            rng.shuffle(&mut v);
+            v.sort();
+            assert_eq!(v, original);
+            for k in 0..len as usize + 2 {
+                rng.partial_shuffle(&mut v, k);
+                let mut sorted = v.clone();
+                sorted.sort();
+                assert_eq!(sorted, original);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shuffle_uniformity() {
+        use std::collections::HashMap;
+
+        let mut rng = Xorshift::with_seed(956);
+        let mut counts: HashMap<Vec<u32>, usize> = HashMap::new();
+        for _ in 0..24_000 {
+            let mut v = vec![0, 1, 2, 3];
+            rng.shuffle(&mut v);
+            *counts.entry(v).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 24);
+        assert!(counts.values().all(|&c| 800 < c && c < 1200), "{:?}", counts);
+
+        let mut counts: HashMap<Vec<u32>, usize> = HashMap::new();
+        for _ in 0..12_000 {
+            let mut v = vec![0, 1, 2, 3];
+            rng.partial_shuffle(&mut v, 2);
+            *counts.entry(v[..2].to_vec()).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 12);
+        assert!(counts.values().all(|&c| 800 < c && c < 1200), "{:?}", counts);
+    }
+
+    #[test]
+    fn test_sample_indices() {
+        let mut rng = Xorshift::with_seed(957);
+        for n in 0..30 {
+            for k in 0..n+1 {
+                let mut indices = rng.sample_indices(n, k);
+                assert_eq!(indices.len(), k);
+                indices.sort();
+                indices.dedup();
+                assert_eq!(indices.len(), k);
+                assert!(indices.iter().all(|&i| i < n));
+            }
+        }
+
+        let items = vec!['a', 'b', 'c'];
+        let mut sample = rng.sample(&items, 3);
+        sample.sort();
+        assert_eq!(sample, items);
+    }
+
+    #[test]
+    fn test_sample_indices_uniformity() {
+        let mut rng = Xorshift::with_seed(957);
+        // Both the Floyd's algorithm and the partial shuffle
+        for &(n, k) in &[(10, 2), (6, 3)] {
+            let mut counts = vec![0; n];
+            let trials = 30_000;
+            for _ in 0..trials {
+                for i in rng.sample_indices(n, k) {
+                    counts[i] += 1;
+                }
+            }
+            let expected = trials * k / n;
+            assert!(counts.iter().all(|&c| c * 10 > expected * 9 && c * 10 < expected * 11), "{:?}", counts);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample 4 items from 3 items")]
+    fn test_sample_too_many() {
+        Xorshift::with_seed(957).sample(&[1, 2, 3], 4);
+    }
+
+    #[test]
+    fn test_gen_f64_range() {
+        let mut rng = Xorshift::with_seed(958);
+        for _ in 0..100_000 {
+            let x = rng.gen_f64();
+            assert!(0.0 <= x && x < 1.0);
+            let y = rng.gen_f64_range(-3.0, 1e-9);
+            assert!(-3.0 <= y && y < 1e-9);
+        }
+        let tiny = rng.gen_f64_range(1.0, 1.0 + std::f64::EPSILON);
+        assert_eq!(tiny, 1.0);
+    }
+
+    #[test]
+    fn test_gen_gaussian() {
+        let mut rng = Xorshift::with_seed(958);
+        let n = 1_000_000;
+        let samples: Vec<f64> = (0..n).map(|_| rng.gen_gaussian(3.0, 2.0)).collect();
+        let mean = samples.iter().sum::<f64>() / n as f64;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+        assert!((mean - 3.0).abs() < 0.01, "{}", mean);
+        assert!((variance - 4.0).abs() < 0.05, "{}", variance);
+        assert!(samples.iter().all(|x| x.is_finite()));
+
+        let mut rng1 = Xorshift::with_seed(958);
+        let mut rng2 = Xorshift::with_seed(958);
+        for _ in 0..100 {
+            assert_eq!(rng1.gen_gaussian(0.0, 1.0), rng2.gen_gaussian(0.0, 1.0));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_f64_range_infinite() {
+        Xorshift::with_seed(958).gen_f64_range(0.0, std::f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_gaussian_nan() {
+        Xorshift::with_seed(958).gen_gaussian(std::f64::NAN, 1.0);
+    }
+
+    #[test]
+    fn test_seeding() {
+        let mut rng1 = Xorshift::with_seed(959);
+        let mut rng2 = Xorshift::with_seed(959);
+        for _ in 0..100 {
+            assert_eq!(rng1.next::<u64>(), rng2.next::<u64>());
+        }
+
+        let mut default1 = Xorshift::default();
+        let mut default2 = Xorshift::default();
+        assert_eq!(default1.next::<u64>(), default2.next::<u64>());
+
+        let mut entropy1 = Xorshift::from_entropy();
+        let mut entropy2 = Xorshift::from_entropy();
+        let xs1: Vec<u64> = (0..4).map(|_| entropy1.next()).collect();
+        let xs2: Vec<u64> = (0..4).map(|_| entropy2.next()).collect();
+        assert_ne!(xs1, xs2);
+    }
+
+    #[test]
+    fn test_fork() {
+        let mut rng = Xorshift::with_seed(959);
+        let mut forked1 = rng.fork();
+        let mut forked2 = rng.fork();
+        let xs: Vec<u64> = (0..100).map(|_| rng.next()).collect();
+        let ys: Vec<u64> = (0..100).map(|_| forked1.next()).collect();
+        let zs: Vec<u64> = (0..100).map(|_| forked2.next()).collect();
+        assert!(xs.iter().all(|x| !ys.contains(x) && !zs.contains(x)));
+        assert!(ys.iter().all(|y| !zs.contains(y)));
+
+        let mut same = Xorshift::with_seed(959);
+        assert_eq!(same.fork().next::<u64>(), ys[0]);
+    }
+
+    #[test]
+    fn test_other_rngs() {
+        let mut rng = StepRng::new(std::u64::MAX, 1);
+        assert_eq!(rng.next_u64(), std::u64::MAX);
+        assert_eq!(rng.next_u64(), 0);
+        // The highest bits of 1 and 2^63 + 1 give the bottom and the middle of the range.
+        let mut rng = StepRng::new(1, 1 << 63);
+        assert_eq!(rng.gen_range(0..10), 0);
+        assert_eq!(rng.gen_range(0..10), 5);
+        let mut v = vec![1, 2, 3];
+        StepRng::new(1 << 63 | 1, 0).shuffle(&mut v);
+        assert_eq!(v, vec![1, 3, 2]);
+
+        let mut rng1 = SplitMix64::with_seed(962);
+        let mut rng2 = SplitMix64::with_seed(962);
+        let xs: Vec<u64> = (0..100).map(|_| rng1.gen_range(0..1000)).collect();
+        let ys: Vec<u64> = (0..100).map(|_| rng2.gen_range(0..1000)).collect();
+        assert_eq!(xs, ys);
+        let mut counts = [0; 4];
+        for _ in 0..40_000 {
+            counts[rng1.gen_range(0..4) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| 9_500 < c && c < 10_500), "{:?}", counts);
+    }
+}
@@ -24,8 +24,17 @@
 //! </pre>
 //!
 //! So the algorithm find 2 occurrences of `P` in `T`.
+//!
+//! In fact, [`z_match_indices`](trait.ZString.html#tymethod.z_match_indices)
+//! computes the Z-array of `P` first, and then the match lengths for `T` using it,
+//! which is equivalent to the above but needs neither the concatenation nor `$`.
+//!
+//! This module also offers KMP (Knuth-Morris-Pratt) algorithm,
+//! [`kmp_match_indices`](fn.kmp_match_indices.html).
+
+use crate::collections::sparse_table::SparseTable;
 
-// BEGIN SNIPPET z
+// BEGIN SNIPPET z DEPENDS ON sparse_table
 
 mod z_internal {
     use std::ops::Range;
@@ -105,48 +114,58 @@ impl<'a, T: Eq> Iterator for LongestPrefixLengths<'a, T> {
 }
 
 /// An iterator generated by [`z_match_indices`](trait.ZString.html#tymethod.z_match_indices).
-pub struct ZMatchIndices<T: Eq + Clone> {
-    concat: Vec<T>,
-    pattern_len: usize,
+pub struct ZMatchIndices<'a, T: Eq> {
+    text: &'a [T],
+    pattern: &'a [T],
+    // Z-array of `pattern` whose 1st value is `pattern.len()`.
+    pattern_z_table: Vec<usize>,
+    // `text[z_box]` matches a prefix of `pattern`.
     z_box: std::ops::Range<usize>,
-    z_table: Vec<usize>
+    index: usize
 }
 
-impl<T: Eq + Clone> Iterator for ZMatchIndices<T> {
+impl<'a, T: Eq> Iterator for ZMatchIndices<'a, T> {
     type Item = usize;
 
     fn next(&mut self) -> Option<usize> {
         use self::z_internal::*;
 
-        while update_state(&self.concat, &mut self.z_box, &mut self.z_table) {
-            if *self.z_table.last().unwrap() == self.pattern_len {
-                return Some(self.z_table.len() - 1 - (self.pattern_len + 1))
+        while self.index < self.text.len() {
+            let index = self.index;
+            self.index += 1;
+
+            // Same as `update_state`, except that prefixes of `pattern` are compared
+            // instead of prefixes of `text`.
+            let len = if index < self.z_box.end
+                && self.pattern_z_table[index - self.z_box.start] < self.z_box.end - index
+            {
+                self.pattern_z_table[index - self.z_box.start]
+            } else {
+                let known_len = self.z_box.end.saturating_sub(index);
+                let len = known_len + exact_match_len(
+                    &self.pattern[known_len..],
+                    &self.text[index + known_len..]
+                );
+                self.z_box = index..index+len;
+                len
+            };
+
+            if len == self.pattern.len() {
+                return Some(index);
             }
         }
         None
     }
 }
 
-impl<T: Eq + Clone> ZMatchIndices<T> {
-    fn new(pattern: Vec<T>, sep: T, text: impl Iterator<Item=T>) -> ZMatchIndices<T> {
-        use self::z_internal::*;
-
-        let len = pattern.len();
-        let mut concat = pattern;
-        concat.push(sep);
-        concat.extend(text);
-        let mut z_box = 0..0;
-        let mut z_table = vec![0];
-
-        for _ in 0..len {
-            update_state(&concat, &mut z_box, &mut z_table);
-        }
-
+impl<'a, T: Eq> ZMatchIndices<'a, T> {
+    fn new(text: &'a [T], pattern: &'a [T]) -> ZMatchIndices<'a, T> {
         ZMatchIndices {
-            concat,
-            pattern_len: len,
-            z_box,
-            z_table,
+            text,
+            pattern,
+            pattern_z_table: z_array(pattern),
+            z_box: 0..0,
+            index: 0
         }
     }
 }
@@ -222,11 +241,12 @@ pub trait ZString<T: Eq + Clone> {
     /// assert_eq!(length("samplease", "in"), 0);
     /// assert_eq!(length("sampleasein", "out"), 0);
     /// ```
-    fn longest_prefix_lengths(&self) -> LongestPrefixLengths<T>;
+    fn longest_prefix_lengths(&self) -> LongestPrefixLengths<'_, T>;
 
     /// Gets an iterator yielding `self`'s indices matching `pattern`.
     ///
     /// Exahausting the iterator takes Θ(`self.len() + pattern.len()`) time.
+    /// Neither `self` nor `pattern` is copied.
     ///
     /// # Example
     ///
@@ -244,21 +264,837 @@ pub trait ZString<T: Eq + Clone> {
     /// assert_eq!(indices.next(), Some(16));
     /// assert_eq!(indices.next(), None);
     /// ```
-    fn z_match_indices(&self, pattern: &Self) -> ZMatchIndices<Option<T>>;
+    fn z_match_indices<'a>(&'a self, pattern: &'a Self) -> ZMatchIndices<'a, T>;
+
+    /// Gets the whole Z-array as a vector.
+    ///
+    /// Same as [`z_array`](fn.z_array.html).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use atcoder_snippets::z::*;
+    /// let text: Vec<char> = "aabcaab".chars().collect();
+    /// assert_eq!(text.z_array(), vec![7, 1, 0, 0, 3, 1, 0]);
+    /// ```
+    fn z_array(&self) -> Vec<usize>;
 }
 
 impl<T: Eq + Clone> ZString<T> for [T] {
-    fn longest_prefix_lengths(&self) -> LongestPrefixLengths<T> {
+    fn longest_prefix_lengths(&self) -> LongestPrefixLengths<'_, T> {
         LongestPrefixLengths::new(self)
     }
 
-    fn z_match_indices(&self, pattern: &[T]) -> ZMatchIndices<Option<T>> {
-        let mut copied = Vec::with_capacity(self.len() + 1 + pattern.len());
-        copied.extend(pattern.iter().map(|x| Some(x.clone())));
-        ZMatchIndices::new(copied, None, self.iter().map(|x| Some(x.clone())))
+    fn z_match_indices<'a>(&'a self, pattern: &'a [T]) -> ZMatchIndices<'a, T> {
+        ZMatchIndices::new(self, pattern)
+    }
+
+    fn z_array(&self) -> Vec<usize> {
+        z_array(self)
+    }
+}
+
+/// `str` is treated as a sequence of bytes.
+///
+/// All the indices and lengths are byte-based,
+/// so they differ from character-based ones if the string contains multibyte characters.
+/// It is not a problem in most contests, whose inputs are ASCII.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let indices: Vec<usize> = "xababxabababxabxabab".z_match_indices("abab").collect();
+/// assert_eq!(indices, vec![1, 6, 8, 16]);
+/// assert_eq!("aabcaab".z_array(), vec![7, 1, 0, 0, 3, 1, 0]);
+/// ```
+impl ZString<u8> for str {
+    fn longest_prefix_lengths(&self) -> LongestPrefixLengths<'_, u8> {
+        self.as_bytes().longest_prefix_lengths()
+    }
+
+    fn z_match_indices<'a>(&'a self, pattern: &'a str) -> ZMatchIndices<'a, u8> {
+        self.as_bytes().z_match_indices(pattern.as_bytes())
+    }
+
+    fn z_array(&self) -> Vec<usize> {
+        z_array(self.as_bytes())
+    }
+}
+
+/// Gets an iterator yielding `text`'s byte indices matching `pattern`.
+///
+/// Same as `text.z_match_indices(pattern)`.
+/// See [`ZString` implementation for `str`](trait.ZString.html#impl-ZString%3Cu8%3E-for-str).
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let indices: Vec<usize> = z_match_indices_str("aaaa", "aa").collect();
+/// assert_eq!(indices, vec![0, 1, 2]);
+/// ```
+pub fn z_match_indices_str<'a>(text: &'a str, pattern: &'a str) -> ZMatchIndices<'a, u8> {
+    text.z_match_indices(pattern)
+}
+
+/// Gets the whole Z-array of `text` as a vector with the same length as `text`.
+///
+/// Unlike the definition in the [module documentation](index.html),
+/// the 1st value is `text.len()`, following many references.
+/// Use [`longest_prefix_lengths`](trait.ZString.html#tymethod.longest_prefix_lengths)
+/// to get the values one by one.
+///
+/// It takes Θ(`text.len()`) time.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let text: Vec<char> = "abababaab".chars().collect();
+/// assert_eq!(z_array(&text), vec![9, 0, 5, 0, 3, 0, 1, 2, 0]);
+/// ```
+pub fn z_array<T: Eq>(text: &[T]) -> Vec<usize> {
+    use self::z_internal::*;
+
+    if text.is_empty() {
+        return Vec::new();
+    }
+
+    let mut z_box = 0..0;
+    let mut z_table = Vec::with_capacity(text.len());
+    z_table.push(0);
+    while update_state(text, &mut z_box, &mut z_table) {}
+    z_table[0] = text.len();
+    z_table
+}
+
+/// Computes the failure function of KMP algorithm.
+///
+/// The `i`-th value is the length of the longest proper prefix of `pattern[..=i]`
+/// which is also a suffix of it.
+///
+/// It takes Θ(`pattern.len()`) time.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let pattern: Vec<char> = "abacabab".chars().collect();
+/// assert_eq!(kmp_failure(&pattern), vec![0, 0, 1, 0, 1, 2, 3, 2]);
+/// ```
+pub fn kmp_failure<T: Eq>(pattern: &[T]) -> Vec<usize> {
+    let mut failure = vec![0; pattern.len()];
+    let mut len = 0;
+    for i in 1..pattern.len() {
+        while len > 0 && pattern[i] != pattern[len] {
+            len = failure[len-1];
+        }
+        if pattern[i] == pattern[len] {
+            len += 1;
+        }
+        failure[i] = len;
+    }
+    failure
+}
+
+/// Smallest period of `pattern`, that is,
+/// the smallest `p > 0` such that `pattern[i] == pattern[i+p]` for all valid `i`.
+///
+/// If `pattern` is empty, returns 0.
+/// Note that `pattern` is a repetition of `pattern[..p]`
+/// only if `pattern.len()` is divisible by `p`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let pattern: Vec<char> = "abcabcab".chars().collect();
+/// assert_eq!(period(&pattern), 3);
+/// let pattern: Vec<char> = "abcd".chars().collect();
+/// assert_eq!(period(&pattern), 4);
+/// ```
+pub fn period<T: Eq>(pattern: &[T]) -> usize {
+    kmp_failure(pattern).last().map_or(0, |&border| pattern.len() - border)
+}
+
+/// An iterator created by [`kmp_match_indices`](fn.kmp_match_indices.html).
+pub struct KmpMatchIndices<'a, T> {
+    text: &'a [T],
+    pattern: &'a [T],
+    failure: Vec<usize>,
+    text_index: usize,
+    matched_len: usize
+}
+
+impl<'a, T: Eq> Iterator for KmpMatchIndices<'a, T> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.pattern.is_empty() {
+            let index = self.text_index;
+            self.text_index += 1;
+            return if index < self.text.len() { Some(index) } else { None };
+        }
+
+        while self.text_index < self.text.len() {
+            let c = &self.text[self.text_index];
+            self.text_index += 1;
+            while self.matched_len > 0 && *c != self.pattern[self.matched_len] {
+                self.matched_len = self.failure[self.matched_len - 1];
+            }
+            if *c == self.pattern[self.matched_len] {
+                self.matched_len += 1;
+            }
+            if self.matched_len == self.pattern.len() {
+                self.matched_len = self.failure[self.matched_len - 1];
+                return Some(self.text_index - self.pattern.len());
+            }
+        }
+        None
+    }
+}
+
+/// Gets an iterator yielding `text`'s indices matching `pattern` by KMP algorithm.
+///
+/// Yields the same indices as [`z_match_indices`](trait.ZString.html#tymethod.z_match_indices).
+/// Overlapping occurrences are all yielded.
+/// An empty pattern matches at every index of `text`.
+///
+/// Exahausting the iterator takes Θ(`text.len() + pattern.len()`) time.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let text: Vec<char> = "xababxabababxabxabab".chars().collect();
+/// let pattern: Vec<char> = "abab".chars().collect();
+/// let indices: Vec<usize> = kmp_match_indices(&text, &pattern).collect();
+/// assert_eq!(indices, vec![1, 6, 8, 16]);
+/// ```
+pub fn kmp_match_indices<'a, T: Eq>(text: &'a [T], pattern: &'a [T]) -> KmpMatchIndices<'a, T> {
+    KmpMatchIndices {
+        text,
+        pattern,
+        failure: kmp_failure(pattern),
+        text_index: 0,
+        matched_len: 0
+    }
+}
+
+/// Computes radii of palindromes in `text` by Manacher's algorithm.
+///
+/// Returns a vector `radii` of length `2 * text.len() + 1`.
+/// Each index `c` of `radii` represents a center of palindromes:
+/// an even `c` is the boundary before `text[c/2]`, and an odd `c` is `text[c/2]` itself.
+/// `radii[c]` is the length of the longest palindrome centered at `c`,
+/// that is, `text[(c - radii[c])/2 .. (c + radii[c])/2]`.
+///
+/// Thanks to this representation, a substring `text[l..r]` is a palindrome
+/// if and only if `radii[l + r] >= r - l`.
+/// See [`is_palindrome_range`](fn.is_palindrome_range.html).
+///
+/// It takes Θ(`text.len()`) time.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let text: Vec<char> = "abaa".chars().collect();
+/// assert_eq!(manacher(&text), vec![0, 1, 0, 3, 0, 1, 2, 1, 0]);
+/// ```
+pub fn manacher<T: Eq>(text: &[T]) -> Vec<usize> {
+    // Text with separators `None` inserted at both ends and between all the items.
+    let at = |i: usize| if i % 2 == 1 { Some(&text[i/2]) } else { None };
+    let len = 2 * text.len() + 1;
+
+    let mut radii = vec![0; len];
+    // The palindrome reaching the rightmost is centered at `center` and ends at `right`.
+    let (mut center, mut right) = (0, 0);
+    for i in 0..len {
+        let mut radius = if i < right {
+            std::cmp::min(radii[2*center - i], right - i)
+        } else {
+            0
+        };
+        while radius < i && i + radius + 1 < len && at(i - radius - 1) == at(i + radius + 1) {
+            radius += 1;
+        }
+        radii[i] = radius;
+        if i + radius > right {
+            center = i;
+            right = i + radius;
+        }
+    }
+    radii
+}
+
+/// Checks if `text[range]` is a palindrome in O(1) time,
+/// where `radii` is the result of [`manacher`](fn.manacher.html) for `text`.
+///
+/// An empty range is always a palindrome.
+///
+/// # Panics
+///
+/// Panics if `range` is out of `text`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let text: Vec<char> = "abcbd".chars().collect();
+/// let radii = manacher(&text);
+/// assert!(is_palindrome_range(&radii, 1..4));
+/// assert!(!is_palindrome_range(&radii, 0..4));
+/// ```
+pub fn is_palindrome_range(radii: &[usize], range: std::ops::Range<usize>) -> bool {
+    assert!(range.start <= range.end && 2 * range.end < radii.len(), "range is out of the text");
+    radii[range.start + range.end] >= range.end - range.start
+}
+
+/// Finds the longest palindrome in `text`.
+///
+/// If there are two or more longest ones, returns the leftmost one.
+/// If `text` is empty, returns `0..0`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let text: Vec<char> = "abacdcabd".chars().collect();
+/// assert_eq!(longest_palindromic_substring(&text), 1..8);
+/// ```
+pub fn longest_palindromic_substring<T: Eq>(text: &[T]) -> std::ops::Range<usize> {
+    let radii = manacher(text);
+    let mut best = 0;
+    for c in 0..radii.len() {
+        if radii[c] > radii[best] {
+            best = c;
+        }
+    }
+    (best - radii[best]) / 2 .. (best + radii[best]) / 2
+}
+
+/// Splits `text` into the Lyndon factorization by Duval's algorithm.
+///
+/// A Lyndon word is a nonempty string strictly smaller than all its proper suffixes.
+/// Any string is uniquely written as the concatenation of Lyndon words
+/// `w_1 w_2 ... w_k` with `w_1 >= w_2 >= ... >= w_k`.
+/// Returns the ranges of `w_1`, ..., `w_k` in `text`.
+///
+/// It takes Θ(`text.len()`) time.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let text: Vec<char> = "abbabaab".chars().collect();
+/// // abb, ab, aab
+/// assert_eq!(lyndon_factorization(&text), vec![0..3, 3..5, 5..8]);
+/// ```
+pub fn lyndon_factorization<T: Ord>(text: &[T]) -> Vec<std::ops::Range<usize>> {
+    let n = text.len();
+    let mut factors = Vec::new();
+    let mut i = 0;
+    while i < n {
+        // `text[i..j]` is a power of a Lyndon word of length `j - k`
+        // followed by its proper prefix.
+        let mut j = i + 1;
+        let mut k = i;
+        while j < n && text[k] <= text[j] {
+            if text[k] < text[j] {
+                k = i;
+            } else {
+                k += 1;
+            }
+            j += 1;
+        }
+        while i <= k {
+            factors.push(i..i+j-k);
+            i += j - k;
+        }
+    }
+    factors
+}
+
+/// Returns the starting index of the lexicographically smallest rotation of `text`
+/// by Booth's algorithm.
+///
+/// If there are two or more smallest rotations, returns the smallest index.
+/// Comparing `min_rotation`-rotated strings decides if two strings are
+/// equal as necklaces.
+/// If `text` is empty, returns 0.
+///
+/// It takes Θ(`text.len()`) time.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let text: Vec<char> = "cabcab".chars().collect();
+/// assert_eq!(min_rotation(&text), 1);
+/// ```
+pub fn min_rotation<T: Ord>(text: &[T]) -> usize {
+    let n = text.len();
+    if n == 0 {
+        return 0;
+    }
+    let at = |i: usize| &text[i % n];
+
+    // Failure function of the rotation starting at `k`, with `-1` for none.
+    let mut failure: Vec<isize> = vec![-1; 2*n];
+    let mut k = 0;
+    for j in 1..2*n {
+        let mut i = failure[j-k-1];
+        while i != -1 && at(j) != at(k + i as usize + 1) {
+            if at(j) < at(k + i as usize + 1) {
+                k = j - i as usize - 1;
+            }
+            i = failure[i as usize];
+        }
+        if i == -1 && at(j) != at(k) {
+            if at(j) < at(k) {
+                k = j;
+            }
+            failure[j-k] = -1;
+        } else {
+            failure[j-k] = i + 1;
+        }
+    }
+    k
+}
+
+#[derive(Clone, Debug)]
+struct PalindromicTreeNode<T: Eq + std::hash::Hash> {
+    // -1 for the odd root.
+    len: isize,
+    // Index in the text next to the first occurrence.
+    end: usize,
+    suffix_link: usize,
+    children: std::collections::HashMap<T, usize>,
+    // Number of palindromic suffixes of the prefixes of the text equal to this node,
+    // before propagated through suffix links.
+    count: u64
+}
+
+/// Palindromic tree (eertree), which holds all the distinct palindromic substrings
+/// of a string built by pushing items one by one.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let mut tree = PalindromicTree::new();
+/// for c in "abaab".chars() {
+///     tree.push(c);
+/// }
+/// // a, b, aba, aa, baab
+/// assert_eq!(tree.distinct_palindromes(), 5);
+///
+/// let mut counts: Vec<(std::ops::Range<usize>, u64)> = tree.occurrence_counts();
+/// counts.sort_by_key(|&(ref range, _)| (range.start, range.end));
+/// assert_eq!(counts, vec![(0..1, 3), (0..3, 1), (1..2, 2), (1..5, 1), (2..4, 1)]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PalindromicTree<T: Eq + std::hash::Hash> {
+    text: Vec<T>,
+    // 0th is the odd root of length -1, 1st is the even root of length 0.
+    nodes: Vec<PalindromicTreeNode<T>>,
+    // Node of the longest palindromic suffix of `text`.
+    last: usize
+}
+
+impl<T: Eq + std::hash::Hash + Clone> PalindromicTree<T> {
+    /// Creates a tree for the empty string.
+    pub fn new() -> PalindromicTree<T> {
+        let root = |len| PalindromicTreeNode {
+            len,
+            end: 0,
+            suffix_link: 0,
+            children: std::collections::HashMap::new(),
+            count: 0
+        };
+        PalindromicTree {
+            text: Vec::new(),
+            nodes: vec![root(-1), root(0)],
+            last: 1
+        }
+    }
+
+    // Finds the longest palindromic suffix `s` reachable by suffix links from `node`
+    // such that `s` is preceded by the last item of the text.
+    fn extendable_suffix(&self, mut node: usize) -> usize {
+        let last = self.text.len() - 1;
+        loop {
+            let start = last as isize - self.nodes[node].len - 1;
+            if start >= 0 && self.text[start as usize] == self.text[last] {
+                return node;
+            }
+            node = self.nodes[node].suffix_link;
+        }
+    }
+
+    /// Appends `item` to the string.
+    ///
+    /// It takes amortized O(1) time, as long as hashing `item` takes O(1) time.
+    pub fn push(&mut self, item: T) {
+        self.text.push(item.clone());
+        let parent = self.extendable_suffix(self.last);
+        if let Some(&node) = self.nodes[parent].children.get(&item) {
+            self.nodes[node].count += 1;
+            self.last = node;
+            return;
+        }
+
+        let len = self.nodes[parent].len + 2;
+        let suffix_link = if len == 1 {
+            1
+        } else {
+            let link_parent = self.extendable_suffix(self.nodes[parent].suffix_link);
+            self.nodes[link_parent].children[&item]
+        };
+        let node = self.nodes.len();
+        self.nodes.push(PalindromicTreeNode {
+            len,
+            end: self.text.len(),
+            suffix_link,
+            children: std::collections::HashMap::new(),
+            count: 1
+        });
+        self.nodes[parent].children.insert(item, node);
+        self.last = node;
+    }
+
+    /// Returns the number of distinct nonempty palindromic substrings.
+    pub fn distinct_palindromes(&self) -> usize {
+        self.nodes.len() - 2
+    }
+
+    /// Returns all the distinct nonempty palindromic substrings,
+    /// each represented by the range of its first occurrence,
+    /// together with the number of its occurrences.
+    ///
+    /// Palindromes are ordered by the end of the first occurrences.
+    ///
+    /// It takes Θ(`self.distinct_palindromes()`) time.
+    pub fn occurrence_counts(&self) -> Vec<(std::ops::Range<usize>, u64)> {
+        let mut counts: Vec<u64> = self.nodes.iter().map(|node| node.count).collect();
+        // A suffix link always points to an earlier node.
+        for node in (2..self.nodes.len()).rev() {
+            counts[self.nodes[node].suffix_link] += counts[node];
+        }
+        (2..self.nodes.len()).map(|i| {
+            let node = &self.nodes[i];
+            (node.end - node.len as usize .. node.end, counts[i])
+        }).collect()
+    }
+}
+
+impl<T: Eq + std::hash::Hash + Clone> Default for PalindromicTree<T> {
+    fn default() -> PalindromicTree<T> {
+        PalindromicTree::new()
+    }
+}
+
+/// Returns the suffix array of `text`,
+/// the starting indices of all the suffixes sorted lexicographically.
+///
+/// It takes O(*n* log(*n*)^2) time, as *n* is `text.len()`.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let text: Vec<char> = "abracadabra".chars().collect();
+/// assert_eq!(suffix_array(&text), vec![10, 7, 0, 3, 5, 8, 1, 4, 6, 9, 2]);
+/// ```
+pub fn suffix_array<T: Ord>(text: &[T]) -> Vec<usize> {
+    let n = text.len();
+    let mut sa: Vec<usize> = (0..n).collect();
+    sa.sort_by(|&i, &j| text[i].cmp(&text[j]));
+    let mut rank = vec![0; n];
+    for k in 1..n {
+        rank[sa[k]] = rank[sa[k-1]] + (text[sa[k-1]] != text[sa[k]]) as usize;
+    }
+
+    // Sorts by the first `2 * width` items, using ranks by the first `width` items.
+    let mut width = 1;
+    let mut next_rank = vec![0; n];
+    while width < n && rank[sa[n-1]] < n - 1 {
+        let key = |i: usize| (rank[i], if i + width < n { Some(rank[i + width]) } else { None });
+        sa.sort_by_key(|&i| key(i));
+        next_rank[sa[0]] = 0;
+        for k in 1..n {
+            next_rank[sa[k]] = next_rank[sa[k-1]] + (key(sa[k-1]) != key(sa[k])) as usize;
+        }
+        std::mem::swap(&mut rank, &mut next_rank);
+        width *= 2;
+    }
+    sa
+}
+
+/// Returns the LCP array of `text` by Kasai's algorithm,
+/// where `sa` is the result of [`suffix_array`](fn.suffix_array.html) for `text`.
+///
+/// The `k`-th item of the result is the length of the longest common prefix of
+/// the suffixes starting at `sa[k-1]` and `sa[k]`, with an exception that the 0th is 0.
+///
+/// It takes Θ(`text.len()`) time.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let text: Vec<char> = "abab".chars().collect();
+/// let sa = suffix_array(&text);
+/// assert_eq!(sa, vec![2, 0, 3, 1]);
+/// assert_eq!(lcp_array(&text, &sa), vec![0, 2, 0, 1]);
+/// ```
+pub fn lcp_array<T: Eq>(text: &[T], sa: &[usize]) -> Vec<usize> {
+    let n = text.len();
+    let mut rank = vec![0; n];
+    for (k, &i) in sa.iter().enumerate() {
+        rank[i] = k;
+    }
+
+    let mut lcp = vec![0; n];
+    let mut len: usize = 0;
+    for i in 0..n {
+        if rank[i] == 0 {
+            len = 0;
+            continue;
+        }
+        let j = sa[rank[i] - 1];
+        while i + len < n && j + len < n && text[i + len] == text[j + len] {
+            len += 1;
+        }
+        lcp[rank[i]] = len;
+        len = len.saturating_sub(1);
+    }
+    lcp
+}
+
+/// Answers longest common prefixes of suffixes and comparisons of substrings
+/// of a fixed text in constant time.
+///
+/// Construction takes O(*n* log(*n*)^2) time, as *n* is the length of the text.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// use std::cmp::Ordering;
+///
+/// let text: Vec<char> = "abracadabra".chars().collect();
+/// let queries = LcpQueries::new(&text);
+/// assert_eq!(queries.lcp(0, 7), 4);
+/// assert_eq!(queries.lcp(3, 3), 8);
+/// assert_eq!(queries.compare_substrings(0..4, 7..11), Ordering::Equal);
+/// assert_eq!(queries.compare_substrings(0..4, 7..10), Ordering::Greater);
+/// assert_eq!(queries.compare_substrings(0..2, 3..5), Ordering::Less);
+/// ```
+pub struct LcpQueries {
+    // `rank[i]` is the position of the suffix starting at `i` in the suffix array.
+    rank: Vec<usize>,
+    lcp: SparseTable<usize, fn(&usize, &usize) -> usize>
+}
+
+impl LcpQueries {
+    /// Preprocesses `text`.
+    pub fn new<T: Ord>(text: &[T]) -> LcpQueries {
+        let sa = suffix_array(text);
+        let lcp = lcp_array(text, &sa);
+        let mut rank = vec![0; text.len()];
+        for (k, &i) in sa.iter().enumerate() {
+            rank[i] = k;
+        }
+        let min: fn(&usize, &usize) -> usize = |&x, &y| std::cmp::min(x, y);
+        LcpQueries {
+            rank,
+            lcp: SparseTable::from_vec(lcp, min)
+        }
+    }
+
+    /// Returns the length of the longest common prefix
+    /// of the suffixes starting at `i` and `j`.
+    ///
+    /// `i` and `j` may be the length of the text, representing the empty suffix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is greater than the length of the text.
+    pub fn lcp(&self, i: usize, j: usize) -> usize {
+        let n = self.rank.len();
+        assert!(i <= n && j <= n, "index is out of the text");
+        if i == j {
+            n - i
+        } else if i == n || j == n {
+            0
+        } else {
+            let (ri, rj) = (self.rank[i], self.rank[j]);
+            let (lo, hi) = if ri < rj { (ri, rj) } else { (rj, ri) };
+            self.lcp.query(lo+1..hi+1).unwrap()
+        }
+    }
+
+    /// Compares two substrings `text[r1]` and `text[r2]` lexicographically.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r1` or `r2` is out of the text.
+    pub fn compare_substrings(
+        &self, r1: std::ops::Range<usize>, r2: std::ops::Range<usize>
+    ) -> std::cmp::Ordering {
+        let n = self.rank.len();
+        assert!(r1.start <= r1.end && r1.end <= n, "range is out of the text");
+        assert!(r2.start <= r2.end && r2.end <= n, "range is out of the text");
+        let (len1, len2) = (r1.end - r1.start, r2.end - r2.start);
+        let min_len = std::cmp::min(len1, len2);
+        if self.lcp(r1.start, r2.start) >= min_len {
+            len1.cmp(&len2)
+        } else {
+            self.rank[r1.start].cmp(&self.rank[r2.start])
+        }
+    }
+}
+
+/// Checks if `pattern` is a (not necessarily contiguous) subsequence of `text`.
+///
+/// It takes O(`text.len()`) time.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// assert!(is_subsequence(b"ace", b"abcde"));
+/// assert!(!is_subsequence(b"aec", b"abcde"));
+/// assert!(is_subsequence(b"", b""));
+/// ```
+pub fn is_subsequence<T: Eq>(pattern: &[T], text: &[T]) -> bool {
+    let mut pattern_iter = pattern.iter().peekable();
+    for x in text {
+        if pattern_iter.peek() == Some(&x) {
+            pattern_iter.next();
+        }
+    }
+    pattern_iter.peek().is_none()
+}
+
+/// A table of the next occurrences of each item in a text,
+/// generated by [`subsequence_next_table`](fn.subsequence_next_table.html).
+#[derive(Clone, Debug)]
+pub struct SubsequenceNextTable {
+    text_len: usize,
+    alphabet_size: usize,
+    // `next[i * alphabet_size + c]` is the minimum `j >= i` that `text[j] == c`,
+    // or the length of the text if there is no such `j`.
+    next: Vec<usize>
+}
+
+/// Builds a table of the next occurrences of each item in `text`,
+/// where every item of `text` is less than `alphabet_size`.
+///
+/// It takes Θ(`text.len() * alphabet_size`) time and space.
+///
+/// # Panics
+///
+/// Panics if an item of `text` is `alphabet_size` or more.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// let text: Vec<usize> = "abcab".bytes().map(|b| (b - b'a') as usize).collect();
+/// let table = subsequence_next_table(&text, 26);
+/// assert_eq!(table.next(1, 0), Some(3));
+/// assert_eq!(table.next(4, 2), None);
+///
+/// let pattern: Vec<usize> = "cb".bytes().map(|b| (b - b'a') as usize).collect();
+/// assert!(table.is_subsequence(&pattern));
+/// // "abcab"[2..5] is the shortest substring starting at 2 containing "cb".
+/// assert_eq!(table.match_end(2, &pattern), Some(5));
+/// ```
+pub fn subsequence_next_table(text: &[usize], alphabet_size: usize) -> SubsequenceNextTable {
+    let n = text.len();
+    let mut next = vec![n; (n + 1) * alphabet_size];
+    for i in (0..n).rev() {
+        assert!(text[i] < alphabet_size, "item {} is out of the alphabet", text[i]);
+        for c in 0..alphabet_size {
+            next[i * alphabet_size + c] = next[(i + 1) * alphabet_size + c];
+        }
+        next[i * alphabet_size + text[i]] = i;
+    }
+    SubsequenceNextTable { text_len: n, alphabet_size, next }
+}
+
+impl SubsequenceNextTable {
+    /// Returns the minimum index `j` such that `i <= j` and `text[j] == c`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is greater than the length of the text,
+    /// or `c` is `alphabet_size` or more.
+    pub fn next(&self, i: usize, c: usize) -> Option<usize> {
+        assert!(c < self.alphabet_size, "item {} is out of the alphabet", c);
+        let j = self.next[i * self.alphabet_size + c];
+        if j < self.text_len { Some(j) } else { None }
+    }
+
+    /// Returns the minimum `end` such that `pattern` is a subsequence of `text[start..end]`.
+    ///
+    /// This is the end of the shortest substring starting at `start`
+    /// that contains `pattern` as a subsequence.
+    ///
+    /// It takes O(`pattern.len()`) time.
+    pub fn match_end(&self, start: usize, pattern: &[usize]) -> Option<usize> {
+        let mut end = start;
+        for &c in pattern {
+            end = self.next(end, c)? + 1;
+        }
+        Some(end)
+    }
+
+    /// Checks if `pattern` is a subsequence of the text.
+    ///
+    /// It takes O(`pattern.len()`) time.
+    pub fn is_subsequence(&self, pattern: &[usize]) -> bool {
+        self.match_end(0, pattern).is_some()
     }
 }
 
+/// Counts the distinct subsequences of `text`, including the empty one, modulo `modulus`.
+///
+/// It takes O(`text.len()`) time.
+///
+/// # Panics
+///
+/// Panics if `modulus` is 0 or greater than 2^63.
+///
+/// # Example
+///
+/// ```
+/// # use atcoder_snippets::z::*;
+/// // "", "a", "b", "aa", "ab", "ba", "aba"
+/// assert_eq!(count_distinct_subsequences(b"aba", 1_000_000_007), 7);
+/// assert_eq!(count_distinct_subsequences(b"aba", 5), 2);
+/// ```
+pub fn count_distinct_subsequences<T: Eq + std::hash::Hash>(text: &[T], modulus: u64) -> u64 {
+    assert!(0 < modulus && modulus <= 1 << 63, "modulus must be in 1..=2^63");
+    // `count` is the answer for the processed prefix, and `last_count[x]` is
+    // the answer just before the last occurrence of `x`.
+    let mut count = 1 % modulus;
+    let mut last_count: std::collections::HashMap<&T, u64> = std::collections::HashMap::new();
+    for x in text {
+        let prev = count;
+        // The subsequences ending with `x` which are counted twice are those
+        // counted at the last occurrence of `x`.
+        let dup = last_count.get(x).cloned().unwrap_or(0);
+        count = ((count + modulus - dup) % modulus + count) % modulus;
+        last_count.insert(x, prev);
+    }
+    count
+}
+
 // END SNIPPET
 
 #[cfg(test)]
@@ -311,4 +1147,406 @@ mod tests {
         let indices: Vec<usize> = text.z_match_indices(&pattern).collect();
         assert_eq!(indices, vec![0]);
     }
+
+    #[test]
+    fn test_kmp_failure() {
+        assert_eq!(kmp_failure::<char>(&[]), vec![]);
+        assert_eq!(kmp_failure(&['a']), vec![0]);
+        assert_eq!(kmp_failure(&['a', 'a', 'a', 'a']), vec![0, 1, 2, 3]);
+        let pattern: Vec<char> = "aabaaab".chars().collect();
+        assert_eq!(kmp_failure(&pattern), vec![0, 1, 0, 1, 2, 2, 3]);
+    }
+
+    #[test]
+    fn test_kmp_match_indices() {
+        fn indices(text: &str, pattern: &str) -> Vec<usize> {
+            let text: Vec<char> = text.chars().collect();
+            let pattern: Vec<char> = pattern.chars().collect();
+            kmp_match_indices(&text, &pattern).collect()
+        }
+
+        assert_eq!(indices("", ""), vec![]);
+        assert_eq!(indices("", "a"), vec![]);
+        assert_eq!(indices("a", ""), vec![0]);
+        assert_eq!(indices("aaaaaaaaaa", ""), vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(indices("a", "a"), vec![0]);
+        assert_eq!(indices("a", "aa"), vec![]);
+
+        // Overlapping matches
+        assert_eq!(indices("aaaa", "aa"), vec![0, 1, 2]);
+        assert_eq!(indices("abababa", "aba"), vec![0, 2, 4]);
+        assert_eq!(indices("aabaabaab", "aabaab"), vec![0, 3]);
+    }
+
+    #[test]
+    fn test_kmp_match_indices_same_as_z() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(936);
+
+        for _ in 0..500 {
+            let text: Vec<u8> = (0..rng.gen_range(0, 30)).map(|_| rng.gen_range(b'a', b'c')).collect();
+            let pattern: Vec<u8> = (0..rng.gen_range(0, 5)).map(|_| rng.gen_range(b'a', b'c')).collect();
+            let kmp: Vec<usize> = kmp_match_indices(&text, &pattern).collect();
+            let z: Vec<usize> = text.z_match_indices(&pattern).collect();
+            assert_eq!(kmp, z);
+        }
+    }
+
+    #[test]
+    fn test_period() {
+        assert_eq!(period::<char>(&[]), 0);
+        assert_eq!(period(&['a']), 1);
+        assert_eq!(period(&['a', 'a', 'a']), 1);
+        assert_eq!(period(&['a', 'b', 'a']), 2);
+        assert_eq!(period(&['a', 'b', 'a', 'b']), 2);
+        assert_eq!(period(&['a', 'a', 'b']), 3);
+
+        let pattern: Vec<char> = "abaababaab".chars().collect();
+        let brute_force = (1..=pattern.len())
+            .find(|&p| (0..pattern.len()-p).all(|i| pattern[i] == pattern[i+p]))
+            .unwrap();
+        assert_eq!(period(&pattern), brute_force);
+    }
+
+    #[test]
+    fn test_manacher_small() {
+        assert_eq!(manacher::<char>(&[]), vec![0]);
+        assert_eq!(manacher(&['a']), vec![0, 1, 0]);
+        assert_eq!(manacher(&['a', 'a']), vec![0, 1, 2, 1, 0]);
+        assert_eq!(manacher(&['a', 'b']), vec![0, 1, 0, 1, 0]);
+
+        assert_eq!(longest_palindromic_substring::<char>(&[]), 0..0);
+        assert_eq!(longest_palindromic_substring(&['a']), 0..1);
+        assert_eq!(longest_palindromic_substring(&['a', 'b']), 0..1);
+        assert_eq!(longest_palindromic_substring(&['a', 'b', 'b']), 1..3);
+
+        assert!(is_palindrome_range(&manacher::<char>(&[]), 0..0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_is_palindrome_range_out_of_text() {
+        let radii = manacher(&['a', 'a']);
+        is_palindrome_range(&radii, 1..3);
+    }
+
+    #[test]
+    fn test_manacher_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(937);
+
+        for _ in 0..300 {
+            let text: Vec<u8> = (0..rng.gen_range(0, 15)).map(|_| rng.gen_range(b'a', b'c')).collect();
+            let n = text.len();
+            let is_palindrome = |l: usize, r: usize| (l..r).all(|i| text[i] == text[l + r - 1 - i]);
+
+            let radii = manacher(&text);
+            assert_eq!(radii.len(), 2*n + 1);
+            for c in 0..=2*n {
+                let expected = (0..=std::cmp::min(c, 2*n - c))
+                    .filter(|&len| (c + len) % 2 == 0 && is_palindrome((c - len) / 2, (c + len) / 2))
+                    .max()
+                    .unwrap();
+                assert_eq!(radii[c], expected);
+            }
+
+            let mut longest = 0..0;
+            for l in 0..=n {
+                for r in l..=n {
+                    assert_eq!(is_palindrome_range(&radii, l..r), is_palindrome(l, r));
+                    if is_palindrome(l, r) && r - l > longest.end - longest.start {
+                        longest = l..r;
+                    }
+                }
+            }
+            assert_eq!(longest_palindromic_substring(&text), longest);
+        }
+    }
+
+    #[test]
+    fn test_z_array() {
+        assert_eq!(z_array::<char>(&[]), vec![]);
+        assert_eq!(z_array(&['a']), vec![1]);
+        assert_eq!(['a', 'a'].z_array(), vec![2, 1]);
+        assert_eq!(['a', 'b'].z_array(), vec![2, 0]);
+
+        let texts = ["abababaab", "aabcabaabcac", "pleasesample", "aaaaa", "abcde"];
+        for text in texts.iter() {
+            let text: Vec<char> = text.chars().collect();
+            let z = z_array(&text);
+            assert_eq!(z.len(), text.len());
+            assert_eq!(z[0], text.len());
+            let lengths: Vec<usize> = text.longest_prefix_lengths().collect();
+            assert_eq!(&z[1..], &lengths[..]);
+        }
+    }
+
+    #[test]
+    fn test_str() {
+        let lengths: Vec<usize> = "".longest_prefix_lengths().collect();
+        assert_eq!(lengths, vec![]);
+        let lengths: Vec<usize> = "aa".longest_prefix_lengths().collect();
+        assert_eq!(lengths, vec![1]);
+        let lengths: Vec<usize> = "aabcabaabcac".longest_prefix_lengths().collect();
+        assert_eq!(lengths, vec![1, 0, 0, 1, 0, 5, 1, 0, 0, 1, 0]);
+
+        let cases = [("", ""), ("", "a"), ("a", ""), ("aaaaaaaaaa", ""), ("a", "a"),
+                     ("xababxabababxabxabab", "abab"), ("aaaa", "aa"), ("abcabc", "d")];
+        for &(text, pattern) in cases.iter() {
+            let text_chars: Vec<char> = text.chars().collect();
+            let pattern_chars: Vec<char> = pattern.chars().collect();
+            let expected: Vec<usize> = text_chars.z_match_indices(&pattern_chars).collect();
+
+            let indices: Vec<usize> = text.z_match_indices(pattern).collect();
+            assert_eq!(indices, expected);
+            let indices: Vec<usize> = z_match_indices_str(text, pattern).collect();
+            assert_eq!(indices, expected);
+            let indices: Vec<usize> = text.to_string().z_match_indices(pattern).collect();
+            assert_eq!(indices, expected);
+
+            assert_eq!(text.z_array(), text_chars.z_array());
+        }
+
+        // Indices are byte-based.
+        let indices: Vec<usize> = "あいあ".z_match_indices("あ").collect();
+        assert_eq!(indices, vec![0, 6]);
+    }
+
+    #[test]
+    fn test_z_match_indices_random() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(941);
+
+        for _ in 0..500 {
+            let text: Vec<u8> = (0..rng.gen_range(0, 30)).map(|_| rng.gen_range(b'a', b'c')).collect();
+            let pattern: Vec<u8> = (0..rng.gen_range(0, 5)).map(|_| rng.gen_range(b'a', b'c')).collect();
+            let indices: Vec<usize> = text.z_match_indices(&pattern).collect();
+            let expected: Vec<usize> = (0..text.len())
+                .filter(|&i| text[i..].starts_with(&pattern))
+                .collect();
+            assert_eq!(indices, expected);
+        }
+    }
+
+    #[test]
+    fn test_z_match_indices_long() {
+        let text = vec![0u8; 1_000_000];
+        let pattern = vec![0u8; 1_000];
+        assert_eq!(text.z_match_indices(&pattern).count(), 999_001);
+
+        let mut text = vec![0u8; 1_000_000];
+        text[500_000] = 1;
+        let mut indices = text.z_match_indices(&pattern);
+        assert_eq!(indices.nth(499_000), Some(499_000));
+        assert_eq!(indices.next(), Some(500_001));
+        assert_eq!(indices.count(), 498_999);
+    }
+
+    fn is_lyndon(word: &[u8]) -> bool {
+        !word.is_empty() && (1..word.len()).all(|i| word < &word[i..])
+    }
+
+    #[test]
+    fn test_lyndon_factorization() {
+        assert_eq!(lyndon_factorization::<u8>(&[]), vec![]);
+        assert_eq!(lyndon_factorization(b"a"), vec![0..1]);
+        assert_eq!(lyndon_factorization(b"aaa"), vec![0..1, 1..2, 2..3]);
+        assert_eq!(lyndon_factorization(b"cba"), vec![0..1, 1..2, 2..3]);
+        assert_eq!(lyndon_factorization(b"abc"), vec![0..3]);
+
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(943);
+        for _ in 0..500 {
+            let text: Vec<u8> = (0..rng.gen_range(1, 30)).map(|_| rng.gen_range(b'a', b'd')).collect();
+            let factors = lyndon_factorization(&text);
+            assert_eq!(factors[0].start, 0);
+            assert_eq!(factors.last().unwrap().end, text.len());
+            for i in 0..factors.len() {
+                assert!(is_lyndon(&text[factors[i].clone()]));
+                if i > 0 {
+                    assert_eq!(factors[i-1].end, factors[i].start);
+                    assert!(text[factors[i-1].clone()] >= text[factors[i].clone()]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_min_rotation() {
+        assert_eq!(min_rotation::<u8>(&[]), 0);
+        assert_eq!(min_rotation(b"a"), 0);
+        assert_eq!(min_rotation(b"aaaa"), 0);
+        assert_eq!(min_rotation(b"baaa"), 1);
+
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(943);
+        for _ in 0..500 {
+            let text: Vec<u8> = (0..rng.gen_range(1, 20)).map(|_| rng.gen_range(b'a', b'c')).collect();
+            let rotated = |i: usize| [&text[i..], &text[..i]].concat();
+            let expected = (0..text.len()).min_by_key(|&i| rotated(i)).unwrap();
+            assert_eq!(min_rotation(&text), expected, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn test_palindromic_tree_small() {
+        let mut tree = PalindromicTree::new();
+        assert_eq!(tree.distinct_palindromes(), 0);
+        assert_eq!(tree.occurrence_counts(), vec![]);
+        tree.push('a');
+        assert_eq!(tree.distinct_palindromes(), 1);
+        assert_eq!(tree.occurrence_counts(), vec![(0..1, 1)]);
+        tree.push('a');
+        tree.push('a');
+        assert_eq!(tree.occurrence_counts(), vec![(0..1, 3), (0..2, 2), (0..3, 1)]);
+    }
+
+    #[test]
+    fn test_palindromic_tree_brute_force() {
+        use std::collections::HashMap;
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(944);
+
+        for _ in 0..100 {
+            let len = rng.gen_range(1, 201);
+            let alphabet = rng.gen_range(1, 4);
+            let text: Vec<u8> = (0..len).map(|_| rng.gen_range(0, alphabet)).collect();
+
+            let mut tree = PalindromicTree::new();
+            for &x in &text {
+                tree.push(x);
+            }
+
+            let mut expected: HashMap<&[u8], u64> = HashMap::new();
+            for l in 0..len {
+                for r in l+1..len+1 {
+                    let sub = &text[l..r];
+                    if sub.iter().eq(sub.iter().rev()) {
+                        *expected.entry(sub).or_insert(0) += 1;
+                    }
+                }
+            }
+            assert_eq!(tree.distinct_palindromes(), expected.len());
+
+            let counts = tree.occurrence_counts();
+            assert_eq!(counts.len(), expected.len());
+            for (range, count) in counts {
+                assert_eq!(count, expected[&text[range]]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_suffix_array_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(945);
+
+        assert_eq!(suffix_array::<u8>(&[]), vec![]);
+        for _ in 0..300 {
+            let text: Vec<u8> = (0..rng.gen_range(1, 40)).map(|_| rng.gen_range(0, 3)).collect();
+            let mut expected: Vec<usize> = (0..text.len()).collect();
+            expected.sort_by_key(|&i| &text[i..]);
+            assert_eq!(suffix_array(&text), expected);
+        }
+    }
+
+    #[test]
+    fn test_lcp_queries_brute_force() {
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(945);
+
+        let common_prefix = |s: &[u8], t: &[u8]| s.iter().zip(t).take_while(|(x, y)| x == y).count();
+        for _ in 0..100 {
+            let n = rng.gen_range(0, 30);
+            let alphabet = rng.gen_range(1, 4);
+            let text: Vec<u8> = (0..n).map(|_| rng.gen_range(0, alphabet)).collect();
+            let queries = LcpQueries::new(&text);
+
+            for i in 0..n+1 {
+                for j in 0..n+1 {
+                    assert_eq!(queries.lcp(i, j), common_prefix(&text[i..], &text[j..]));
+                }
+            }
+            for _ in 0..300 {
+                let l1 = rng.gen_range(0, n+1);
+                let r1 = rng.gen_range(l1, n+1);
+                let l2 = rng.gen_range(0, n+1);
+                let r2 = rng.gen_range(l2, n+1);
+                assert_eq!(
+                    queries.compare_substrings(l1..r1, l2..r2),
+                    text[l1..r1].cmp(&text[l2..r2])
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_compare_equal_substrings_of_different_lengths() {
+        use std::cmp::Ordering::*;
+        let queries = LcpQueries::new(b"aaaa");
+        assert_eq!(queries.compare_substrings(0..2, 1..4), Less);
+        assert_eq!(queries.compare_substrings(1..4, 0..2), Greater);
+        assert_eq!(queries.compare_substrings(0..2, 2..4), Equal);
+        assert_eq!(queries.compare_substrings(4..4, 0..0), Equal);
+    }
+
+    #[test]
+    fn test_is_subsequence() {
+        assert!(is_subsequence::<u8>(b"", b"abc"));
+        assert!(!is_subsequence(b"abcd", b"abc"));
+        assert!(!is_subsequence(b"aa", b"a"));
+
+        let table = subsequence_next_table(&[0, 1, 2], 3);
+        assert!(table.is_subsequence(&[]));
+        assert!(!table.is_subsequence(&[0, 1, 2, 0]));
+        assert_eq!(table.match_end(3, &[]), Some(3));
+
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(946);
+        for _ in 0..1000 {
+            let text: Vec<usize> = (0..rng.gen_range(0, 10)).map(|_| rng.gen_range(0, 3)).collect();
+            let pattern: Vec<usize> = (0..rng.gen_range(0, 12)).map(|_| rng.gen_range(0, 3)).collect();
+            let table = subsequence_next_table(&text, 3);
+            // Brute force over the subsets of `text`.
+            let expected = (0..1 << text.len()).any(|mask: usize| {
+                let sub: Vec<usize> = (0..text.len()).filter(|&i| mask >> i & 1 == 1).map(|i| text[i]).collect();
+                sub == pattern
+            });
+            assert_eq!(is_subsequence(&pattern, &text), expected);
+            assert_eq!(table.is_subsequence(&pattern), expected);
+
+            for start in 0..text.len()+1 {
+                let expected_end = (start..text.len()+1)
+                    .find(|&end| is_subsequence(&pattern, &text[start..end]));
+                assert_eq!(table.match_end(start, &pattern), expected_end);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_distinct_subsequences() {
+        use std::collections::HashSet;
+        use rand::prelude::*;
+        let mut rng = StdRng::seed_from_u64(946);
+
+        assert_eq!(count_distinct_subsequences::<u8>(&[], 1_000_000_007), 1);
+        assert_eq!(count_distinct_subsequences(b"a", 1_000_000_007), 2);
+        assert_eq!(count_distinct_subsequences(b"aa", 1_000_000_007), 3);
+        assert_eq!(count_distinct_subsequences(b"ab", 1_000_000_007), 4);
+        assert_eq!(count_distinct_subsequences(b"aba", 1_000_000_007), 7);
+        assert_eq!(count_distinct_subsequences(b"abab", 1_000_000_007), 12);
+        assert_eq!(count_distinct_subsequences(b"aaa", 1), 0);
+        assert_eq!(count_distinct_subsequences(b"abcdefghijklmnopqrstuvwxyz", 1_000_000_007), 1 << 26);
+        assert_eq!(count_distinct_subsequences(b"abcdefghijklmnopqrstuvwxyz", 1 << 63), 1 << 26);
+
+        for _ in 0..100 {
+            let text: Vec<u8> = (0..rng.gen_range(0, 16)).map(|_| rng.gen_range(0, 3)).collect();
+            let subsequences: HashSet<Vec<u8>> = (0..1 << text.len()).map(|mask: usize| {
+                (0..text.len()).filter(|&i| mask >> i & 1 == 1).map(|i| text[i]).collect()
+            }).collect();
+            let expected = subsequences.len() as u64;
+            assert_eq!(count_distinct_subsequences(&text, 1_000_000_007), expected);
+            assert_eq!(count_distinct_subsequences(&text, 7), expected % 7);
+        }
+    }
 }